@@ -0,0 +1,36 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AccountBalanceEntity {
+    pub account_id: i32,
+    pub balance: i64,
+    pub last_activity_id: i32,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl AccountBalanceEntity {
+    pub fn new(
+        account_id: i32,
+        balance: i64,
+        last_activity_id: i32,
+        updated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            account_id,
+            balance,
+            last_activity_id,
+            updated_at,
+        }
+    }
+}
+
+/// A kept balance next to the sum of the activities of its account, either side missing if
+/// the account has no row there.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BalanceDriftEntity {
+    pub account_id: i32,
+    pub kept_balance: i64,
+    pub kept_last_activity_id: Option<i32>,
+    pub booked_balance: i64,
+    pub last_activity_id: Option<i32>,
+}
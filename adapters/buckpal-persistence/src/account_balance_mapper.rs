@@ -0,0 +1,43 @@
+use crate::account_balance_entity::{AccountBalanceEntity, BalanceDriftEntity};
+use crate::activity_entity::ActivityEntity;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::account_balance::{AccountBalance, BalanceDrift};
+use buckpal_application::domain::activity::ActivityId;
+use rusty_money::{money, Money};
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct AccountBalanceMapper {}
+
+impl AccountBalanceMapper {
+    pub fn map_to_domain_entity(&self, entity: AccountBalanceEntity) -> AccountBalance {
+        AccountBalance::new(
+            AccountId(entity.account_id),
+            money!(entity.balance, "AUD"),
+            ActivityId(entity.last_activity_id),
+        )
+    }
+
+    pub fn map_to_balance_drift(&self, entity: BalanceDriftEntity) -> BalanceDrift {
+        BalanceDrift {
+            account_id: AccountId(entity.account_id),
+            kept_balance: money!(entity.kept_balance, "AUD"),
+            kept_last_activity_id: entity.kept_last_activity_id.map(ActivityId),
+            booked_balance: money!(entity.booked_balance, "AUD"),
+            last_activity_id: entity.last_activity_id.map(ActivityId),
+        }
+    }
+
+    /// What the activity adds to the balance of its owner, nothing if the owner transferred
+    /// money to itself.
+    pub fn map_to_balance_change(&self, activity: &ActivityEntity) -> i64 {
+        let mut change = 0;
+        if activity.target_account_id == activity.owner_account_id {
+            change += activity.amount;
+        }
+        if activity.source_account_id == activity.owner_account_id {
+            change -= activity.amount;
+        }
+
+        change
+    }
+}
@@ -0,0 +1,123 @@
+use crate::account_balance_entity::{AccountBalanceEntity, BalanceDriftEntity};
+use anyhow::Result;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct AccountBalanceRepository {
+    pool: PgPool,
+}
+
+impl AccountBalanceRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Adds the change an activity makes to the kept balance of its owner, as part of the
+    /// transaction inserting the activity.
+    pub async fn apply_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: i32,
+        change: i64,
+        activity_id: i32,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO 
+                            account_balance (account_id, balance, last_activity_id, updated_at)
+                VALUES 
+                            ($1, $2, $3, now())
+                ON CONFLICT (account_id) DO UPDATE SET 
+                            balance = account_balance.balance + EXCLUDED.balance,
+                            last_activity_id = GREATEST(account_balance.last_activity_id, EXCLUDED.last_activity_id),
+                            updated_at = EXCLUDED.updated_at
+            "#,
+            account_id,
+            change,
+            activity_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_account_id(
+        &self,
+        account_id: i32,
+    ) -> Result<Option<AccountBalanceEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        balance,
+                        last_activity_id,
+                        updated_at
+                FROM 
+                        account_balance
+                WHERE 
+                        account_id = $1
+            "#,
+            account_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| {
+            AccountBalanceEntity::new(
+                entity.account_id,
+                entity.balance,
+                entity.last_activity_id,
+                entity.updated_at,
+            )
+        }))
+    }
+
+    /// Finds the accounts whose kept balance or last activity differs from what their
+    /// activities add up to.
+    pub async fn find_drifts(&self) -> Result<Vec<BalanceDriftEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                WITH booked AS (
+                    SELECT 
+                            owner_account_id AS account_id,
+                            (SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE 0 END)
+                                - SUM(CASE WHEN source_account_id = owner_account_id THEN amount ELSE 0 END))::BIGINT AS balance,
+                            MAX(id) AS last_activity_id
+                    FROM 
+                            activity
+                    GROUP BY 
+                            owner_account_id
+                )
+                SELECT 
+                        COALESCE(kept.account_id, booked.account_id) AS "account_id!",
+                        COALESCE(kept.balance, 0) AS "kept_balance!",
+                        kept.last_activity_id AS "kept_last_activity_id?",
+                        COALESCE(booked.balance, 0) AS "booked_balance!",
+                        booked.last_activity_id AS "last_activity_id?"
+                FROM 
+                        account_balance kept
+                        FULL OUTER JOIN booked ON booked.account_id = kept.account_id
+                WHERE 
+                        kept.balance IS DISTINCT FROM booked.balance
+                        OR kept.last_activity_id IS DISTINCT FROM booked.last_activity_id
+                ORDER BY 
+                        1
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| BalanceDriftEntity {
+                account_id: entity.account_id,
+                kept_balance: entity.kept_balance,
+                kept_last_activity_id: entity.kept_last_activity_id,
+                booked_balance: entity.booked_balance,
+                last_activity_id: entity.last_activity_id,
+            })
+            .collect())
+    }
+}
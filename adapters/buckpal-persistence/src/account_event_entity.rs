@@ -0,0 +1,43 @@
+use crate::domain_event_entity::DomainEventEntity;
+use chrono::{DateTime, Utc};
+
+/// An event at a version of the stream of the account it belongs to.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AccountEventEntity {
+    pub version: i32,
+    pub event: DomainEventEntity,
+    /// The activity the projection wrote for the event.
+    pub activity_id: Option<i32>,
+}
+
+impl AccountEventEntity {
+    pub fn new(version: i32, event: DomainEventEntity, activity_id: Option<i32>) -> Self {
+        Self {
+            version,
+            event,
+            activity_id,
+        }
+    }
+}
+
+/// The balance of an account after the events up to and including a version of its stream.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AccountSnapshotEntity {
+    pub account_id: i32,
+    pub version: i32,
+    pub balance: i64,
+    /// The latest timestamp of the events up to the version. Clocks may disagree, so it needn't
+    /// be the timestamp of the event at the version.
+    pub last_event_at: DateTime<Utc>,
+}
+
+impl AccountSnapshotEntity {
+    pub fn new(account_id: i32, version: i32, balance: i64, last_event_at: DateTime<Utc>) -> Self {
+        Self {
+            account_id,
+            version,
+            balance,
+            last_event_at,
+        }
+    }
+}
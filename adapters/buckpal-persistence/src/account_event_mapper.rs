@@ -0,0 +1,39 @@
+use crate::account_event_entity::AccountEventEntity;
+use crate::activity_entity::ActivityEntity;
+use crate::domain_event_entity::DomainEventEntity;
+
+/// Maps the events of account streams to what they did to the account.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct AccountEventMapper {}
+
+impl AccountEventMapper {
+    /// The activity the event stands for, owned by the account of the stream.
+    pub fn map_to_activity_entity(
+        &self,
+        event: &DomainEventEntity,
+        activity_id: Option<i32>,
+    ) -> ActivityEntity {
+        ActivityEntity::new(
+            activity_id,
+            event.timestamp,
+            event.account_id,
+            event.source_account_id,
+            event.target_account_id,
+            event.amount,
+            Some(event.transfer_id),
+        )
+    }
+
+    pub fn map_stored_to_activity_entity(&self, entity: &AccountEventEntity) -> ActivityEntity {
+        self.map_to_activity_entity(&entity.event, entity.activity_id)
+    }
+
+    /// What the event adds to the balance of the account of the stream.
+    pub fn map_to_balance_change(&self, event: &DomainEventEntity) -> i64 {
+        if event.event_type == "MoneyDeposited" {
+            event.amount
+        } else {
+            -event.amount
+        }
+    }
+}
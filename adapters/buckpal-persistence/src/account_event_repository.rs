@@ -0,0 +1,229 @@
+use crate::account_event_entity::{AccountEventEntity, AccountSnapshotEntity};
+use crate::domain_event_entity::DomainEventEntity;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+
+/// The event store of the event-sourced ledger, together with its snapshots and the balance
+/// projection.
+#[derive(Debug, Clone)]
+pub struct AccountEventRepository {
+    pool: PgPool,
+}
+
+impl AccountEventRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>> {
+        Ok(self.pool.begin().await?)
+    }
+
+    /// Appends the event at its version of the stream, unless the version is taken already.
+    /// Returns whether it was appended.
+    pub async fn append_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity: &AccountEventEntity,
+    ) -> Result<bool> {
+        let appended = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            account_event (account_id, version, event_type, transfer_id, source_account_id, target_account_id, amount, timestamp, activity_id)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                ON CONFLICT (account_id, version) DO NOTHING
+                RETURNING 
+                            version 
+            "#,
+            entity.event.account_id,
+            entity.version,
+            entity.event.event_type,
+            entity.event.transfer_id,
+            entity.event.source_account_id,
+            entity.event.target_account_id,
+            entity.event.amount,
+            entity.event.timestamp,
+            entity.activity_id
+        )
+        .fetch_optional(&mut *tx)
+        .await?;
+
+        Ok(appended.is_some())
+    }
+
+    /// Finds the events of the stream after the given version, in the order they were appended.
+    pub async fn find_after_version(
+        &self,
+        account_id: i32,
+        version: i32,
+    ) -> Result<Vec<AccountEventEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        version,
+                        event_type,
+                        transfer_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        timestamp,
+                        activity_id
+                FROM 
+                        account_event
+                WHERE 
+                        account_id = $1 AND version > $2
+                ORDER BY 
+                        version
+            "#,
+            account_id,
+            version
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                AccountEventEntity::new(
+                    entity.version,
+                    DomainEventEntity {
+                        account_id: entity.account_id,
+                        event_type: entity.event_type,
+                        transfer_id: entity.transfer_id,
+                        source_account_id: entity.source_account_id,
+                        target_account_id: entity.target_account_id,
+                        amount: entity.amount,
+                        timestamp: entity.timestamp,
+                    },
+                    entity.activity_id,
+                )
+            })
+            .collect())
+    }
+
+    /// Finds the latest snapshot covering only events before the given point in time.
+    pub async fn find_latest_snapshot_before(
+        &self,
+        account_id: i32,
+        instant: &DateTime<Utc>,
+    ) -> Result<Option<AccountSnapshotEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        version,
+                        balance,
+                        last_event_at
+                FROM 
+                        account_snapshot
+                WHERE 
+                        account_id = $1 AND last_event_at < $2
+                ORDER BY 
+                        version DESC
+                LIMIT 
+                        1
+            "#,
+            account_id,
+            *instant
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| {
+            AccountSnapshotEntity::new(
+                entity.account_id,
+                entity.version,
+                entity.balance,
+                entity.last_event_at,
+            )
+        }))
+    }
+
+    pub async fn save_snapshot_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity: &AccountSnapshotEntity,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO 
+                            account_snapshot (account_id, version, balance, last_event_at)
+                VALUES 
+                            ($1, $2, $3, $4)
+                ON CONFLICT (account_id, version) DO NOTHING
+            "#,
+            entity.account_id,
+            entity.version,
+            entity.balance,
+            entity.last_event_at
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Finds the projected balance at the head of the stream, a snapshot of its latest version.
+    pub async fn find_balance(&self, account_id: i32) -> Result<Option<AccountSnapshotEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        version,
+                        balance,
+                        last_event_at
+                FROM 
+                        account_ledger_balance
+                WHERE 
+                        account_id = $1
+            "#,
+            account_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| {
+            AccountSnapshotEntity::new(
+                entity.account_id,
+                entity.version,
+                entity.balance,
+                entity.last_event_at,
+            )
+        }))
+    }
+
+    /// Projects the balance of the account as of the version of its stream. Keeps the later of
+    /// the projected and the given `last_event_at` and returns it.
+    pub async fn save_balance_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        entity: &AccountSnapshotEntity,
+    ) -> Result<DateTime<Utc>> {
+        let row = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            account_ledger_balance (account_id, version, balance, last_event_at)
+                VALUES 
+                            ($1, $2, $3, $4)
+                ON CONFLICT (account_id) DO UPDATE SET 
+                            version = EXCLUDED.version,
+                            balance = EXCLUDED.balance,
+                            last_event_at = GREATEST(account_ledger_balance.last_event_at, EXCLUDED.last_event_at)
+                RETURNING 
+                            last_event_at
+            "#,
+            entity.account_id,
+            entity.version,
+            entity.balance,
+            entity.last_event_at
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(row.last_event_at)
+    }
+}
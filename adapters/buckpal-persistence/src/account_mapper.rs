@@ -3,6 +3,8 @@ use crate::activity_entity::ActivityEntity;
 use buckpal_application::domain::account::{Account, AccountId};
 use buckpal_application::domain::activity::{Activity, ActivityId};
 use buckpal_application::domain::activity_window::ActivityWindow;
+use buckpal_application::domain::principal::Principal;
+use buckpal_application::domain::transfer::TransferId;
 use rusty_money::{money, Money};
 
 #[derive(Debug, Eq, PartialEq, Clone, Default)]
@@ -35,7 +37,12 @@ impl AccountMapper {
             AccountId(activity.target_account_id),
             activity.timestamp,
             money!(activity.amount, "AUD"),
+            activity.transfer_id.map(TransferId),
         )
+        .with_initiator(activity.initiator.as_deref().map(|initiator| {
+            // only ever written from a principal, no way to recover
+            Principal::parse(initiator).unwrap()
+        }))
     }
 
     pub fn map_to_activity_window(&self, activities: Vec<ActivityEntity>) -> ActivityWindow {
@@ -58,6 +65,8 @@ impl AccountMapper {
             activity.target_account_id.0,
             // here we want to explode, no way to recover
             activity.money.amount().to_i64().unwrap(),
+            activity.transfer_id.map(|id| id.0),
         )
+        .with_initiator(activity.initiator.map(|initiator| initiator.to_string()))
     }
 }
@@ -1,31 +1,141 @@
+use crate::account_balance_mapper::AccountBalanceMapper;
+use crate::account_balance_repository::AccountBalanceRepository;
 use crate::account_mapper::AccountMapper;
 use crate::account_repository::AccountRepository;
-use crate::activity_repository::ActivityRepository;
-use anyhow::Result;
+use crate::activity_chain_repository::ActivityChainRepository;
+use crate::activity_repository::{ActivityCriteria, ActivityRepository};
+use crate::domain_event_mapper::DomainEventMapper;
+use crate::hold_mapper::HoldMapper;
+use crate::hold_repository::HoldRepository;
+use crate::outbox_repository::OutboxRepository;
+use crate::transfer_mapper::TransferMapper;
+use crate::transfer_repository::TransferRepository;
+use anyhow::{anyhow, Result};
 use async_trait::async_trait;
+use buckpal_application::application::port::incoming::list_activities_query::{
+    ActivityCursor, ActivityFilter,
+};
 use buckpal_application::application::port::outgoing::{
-    load_account_port::LoadAccountPort, update_account_state_port::UpdateAccountStatePort,
+    account_access_port::AccountAccessPort, account_balance_port::AccountBalancePort,
+    load_account_port::LoadAccountPort, load_activities_port::LoadActivitiesPort,
+    load_hold_port::LoadHoldPort, lookup_account_identifier_port::LookupAccountIdentifierPort,
+    record_transfer_port::RecordTransferPort, update_account_state_port::UpdateAccountStatePort,
 };
-use buckpal_application::domain::account::{Account, AccountId};
-use buckpal_application::domain::activity::Activity;
+use buckpal_application::domain::account::{Account, AccountError, AccountId};
+use buckpal_application::domain::account_balance::{AccountBalance, BalanceDrift};
+use buckpal_application::domain::account_identifier::AccountIdentifier;
+use buckpal_application::domain::activity::{Activity, ActivityKind};
+use buckpal_application::domain::activity_chain::hash_activity;
+use buckpal_application::domain::hold::{Hold, HoldId, HoldStatus};
+use buckpal_application::domain::principal::Principal;
+use buckpal_application::domain::transfer::{Transfer, TransferId};
 use chrono::{DateTime, Utc};
+use rusty_money::{money, Money};
 use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
 
 #[derive(Debug, Clone)]
 pub struct AccountPersistenceAdapter {
     account_repository: AccountRepository,
+    account_balance_repository: AccountBalanceRepository,
+    activity_chain_repository: ActivityChainRepository,
     activity_repository: ActivityRepository,
+    hold_repository: HoldRepository,
+    outbox_repository: OutboxRepository,
+    transfer_repository: TransferRepository,
     account_mapper: AccountMapper,
+    account_balance_mapper: AccountBalanceMapper,
+    hold_mapper: HoldMapper,
+    domain_event_mapper: DomainEventMapper,
+    transfer_mapper: TransferMapper,
 }
 
 impl AccountPersistenceAdapter {
     pub fn new(pool: PgPool) -> Self {
         Self {
             account_repository: AccountRepository::new(pool.clone()),
-            activity_repository: ActivityRepository::new(pool),
+            account_balance_repository: AccountBalanceRepository::new(pool.clone()),
+            activity_chain_repository: ActivityChainRepository::new(pool.clone()),
+            activity_repository: ActivityRepository::new(pool.clone()),
+            hold_repository: HoldRepository::new(pool.clone()),
+            outbox_repository: OutboxRepository::new(pool.clone()),
+            transfer_repository: TransferRepository::new(pool),
             account_mapper: AccountMapper::default(),
+            account_balance_mapper: AccountBalanceMapper::default(),
+            hold_mapper: HoldMapper::default(),
+            domain_event_mapper: DomainEventMapper::default(),
+            transfer_mapper: TransferMapper::default(),
         }
     }
+
+    /// Chains the new activities of the account, applies them to its kept balance and writes its
+    /// events to the outbox, all as part of the given transaction.
+    async fn save_activities_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account: &Account,
+    ) -> Result<Vec<Activity>> {
+        let mut activities: Vec<Activity> = vec![];
+        for activity in account.clone().activity_window.activities {
+            if activity.id.is_none() {
+                let previous_hash = self
+                    .activity_chain_repository
+                    .lock_head_in(tx, activity.owner_account_id.0)
+                    .await?;
+                let activity_entity = self
+                    .activity_repository
+                    .save_in(tx, &self.account_mapper.map_to_entity(activity))
+                    .await?;
+                let saved_activity = self.account_mapper.map_to_activity(&activity_entity);
+                self.activity_chain_repository
+                    .link_in(
+                        tx,
+                        &activity_entity,
+                        &previous_hash,
+                        &hash_activity(&previous_hash, &saved_activity),
+                    )
+                    .await?;
+                self.account_balance_repository
+                    .apply_in(
+                        tx,
+                        activity_entity.owner_account_id,
+                        self.account_balance_mapper
+                            .map_to_balance_change(&activity_entity),
+                        activity_entity
+                            .id
+                            .expect("expected saved activity to have an ID"),
+                    )
+                    .await?;
+                activities.push(saved_activity);
+            }
+        }
+
+        for event in &account.events {
+            self.outbox_repository
+                .save_in(tx, &self.domain_event_mapper.map_to_entity(event))
+                .await?;
+        }
+
+        Ok(activities)
+    }
+
+    /// Moves the settled holds of the account out of their active status as part of the given
+    /// transaction, failing if any of them was settled in the meantime.
+    async fn claim_settled_holds_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account: &Account,
+    ) -> Result<()> {
+        for hold in &account.holds {
+            if hold.id.is_some() && hold.status != HoldStatus::Active {
+                self.hold_repository
+                    .update_status_in(tx, &self.hold_mapper.map_to_entity(hold))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -35,7 +145,16 @@ impl LoadAccountPort for AccountPersistenceAdapter {
         account_id: &AccountId,
         baseline_date: &DateTime<Utc>,
     ) -> Result<Account> {
-        let account_entity = self.account_repository.find_by_id(account_id.0).await?;
+        let account_entity = self
+            .account_repository
+            .find_by_id(account_id.0)
+            .await
+            .map_err(|err| match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => {
+                    anyhow!(AccountError::AccountNotFound(account_id.0))
+                }
+                _ => err,
+            })?;
 
         let activities = self
             .activity_repository
@@ -54,32 +173,219 @@ impl LoadAccountPort for AccountPersistenceAdapter {
             .await
             .unwrap_or(0);
 
-        let account = self.account_mapper.map_to_domain_entity(
+        let holds = self
+            .hold_repository
+            .find_active_by_account(account_id.0)
+            .await?;
+
+        let mut account = self.account_mapper.map_to_domain_entity(
             account_entity,
             activities,
             withdrawal_balance,
             deposit_balance,
         );
+        account.holds = holds
+            .into_iter()
+            .map(|hold| self.hold_mapper.map_to_domain_entity(hold))
+            .collect::<Result<Vec<Hold>>>()?;
 
         Ok(account)
     }
 }
 
+#[async_trait]
+impl LoadActivitiesPort for AccountPersistenceAdapter {
+    async fn load_activities(
+        &self,
+        account_id: &AccountId,
+        filter: &ActivityFilter,
+        cursor: Option<&ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Activity>> {
+        use rust_decimal::prelude::*;
+
+        let criteria = ActivityCriteria {
+            from: filter.from,
+            to: filter.to,
+            counterparty_account_id: filter.counterparty_account_id.clone().map(|id| id.0),
+            kind: filter.kind.map(|kind| {
+                String::from(match kind {
+                    ActivityKind::Deposit => "DEPOSIT",
+                    ActivityKind::Withdrawal => "WITHDRAWAL",
+                })
+            }),
+            min_amount: filter
+                .min_amount
+                .as_ref()
+                .and_then(|money| money.amount().to_i64()),
+            max_amount: filter
+                .max_amount
+                .as_ref()
+                .and_then(|money| money.amount().to_i64()),
+            cursor_timestamp: cursor.map(|cursor| cursor.timestamp),
+            cursor_id: cursor.map(|cursor| cursor.activity_id.0),
+        };
+
+        let activities = self
+            .activity_repository
+            .find_page_by_owner(account_id.0, &criteria, limit)
+            .await?;
+
+        Ok(activities
+            .iter()
+            .map(|activity| self.account_mapper.map_to_activity(activity))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl LoadHoldPort for AccountPersistenceAdapter {
+    async fn load_hold(&self, hold_id: &HoldId) -> Result<Hold> {
+        let hold_entity = self.hold_repository.find_by_id(hold_id.0).await?;
+
+        self.hold_mapper.map_to_domain_entity(hold_entity)
+    }
+}
+
 #[async_trait]
 impl UpdateAccountStatePort for AccountPersistenceAdapter {
     async fn update_activities(&self, account: &Account) -> Result<Vec<Activity>> {
-        let mut activities: Vec<Activity> = vec![];
-        for activity in account.clone().activity_window.activities {
-            if activity.id.is_none() {
-                let activity_entity = self
-                    .activity_repository
-                    .save(&self.account_mapper.map_to_entity(activity))
-                    .await?;
-                activities.push(self.account_mapper.map_to_activity(&activity_entity));
+        // the events describing the new activities and the kept balance are only written if the
+        // activities are
+        let mut tx = self.outbox_repository.begin().await?;
+
+        let activities = self.save_activities_in(&mut tx, account).await?;
+
+        tx.commit().await?;
+
+        Ok(activities)
+    }
+
+    async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>> {
+        let mut holds: Vec<Hold> = vec![];
+        for hold in &account.holds {
+            let hold_entity = self.hold_mapper.map_to_entity(hold);
+
+            if hold.id.is_none() {
+                let hold_entity = self.hold_repository.save(&hold_entity).await?;
+                holds.push(self.hold_mapper.map_to_domain_entity(hold_entity)?);
+            } else if hold.status != HoldStatus::Active {
+                self.hold_repository.update_status(&hold_entity).await?;
             }
         }
 
-        Ok(activities)
+        Ok(holds)
+    }
+}
+
+#[async_trait]
+impl RecordTransferPort for AccountPersistenceAdapter {
+    async fn reserve_transfer_id(&self) -> Result<TransferId> {
+        Ok(TransferId(self.transfer_repository.reserve_id().await?))
+    }
+
+    async fn record_transfer(
+        &self,
+        transfer: &Transfer,
+        source_account: &Account,
+        target_account: &Account,
+    ) -> Result<Transfer> {
+        let mut tx = self.outbox_repository.begin().await?;
+
+        // the legs refer to the transfer, it has to be there first
+        let transfer_entity = self
+            .transfer_repository
+            .save_reserved_in(&mut tx, &self.transfer_mapper.map_to_entity(transfer))
+            .await?;
+
+        // the chain heads of both accounts are locked in the order of their ids, so that
+        // transfers between the same accounts in opposite directions don't deadlock
+        let mut accounts = [source_account, target_account];
+        accounts.sort_by_key(|account| account.id.as_ref().map(|id| id.0));
+
+        // the holds settled by the transfer are claimed before any money moves, a hold settled
+        // concurrently is no longer active and rolls the transfer back
+        for account in accounts.iter() {
+            self.claim_settled_holds_in(&mut tx, account).await?;
+        }
+        for account in accounts.iter() {
+            self.save_activities_in(&mut tx, account).await?;
+        }
+
+        for event in &transfer.events {
+            self.outbox_repository
+                .save_in(&mut tx, &self.domain_event_mapper.map_to_entity(event))
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.transfer_mapper.map_to_domain_entity(transfer_entity)
+    }
+}
+
+#[async_trait]
+impl AccountBalancePort for AccountPersistenceAdapter {
+    async fn load_account_balance(&self, account_id: &AccountId) -> Result<Option<AccountBalance>> {
+        let account_balance = self
+            .account_balance_repository
+            .find_by_account_id(account_id.0)
+            .await?;
+
+        Ok(account_balance.map(|entity| self.account_balance_mapper.map_to_domain_entity(entity)))
+    }
+
+    async fn load_balance_as_of(
+        &self,
+        account_id: &AccountId,
+        instant: &DateTime<Utc>,
+    ) -> Result<Money> {
+        self.account_repository
+            .find_by_id(account_id.0)
+            .await
+            .map_err(|err| match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => {
+                    anyhow!(AccountError::AccountNotFound(account_id.0))
+                }
+                _ => err,
+            })?;
+
+        let balance = self
+            .activity_repository
+            .get_balance_as_of(account_id.0, instant)
+            .await?;
+
+        Ok(money!(balance, "AUD"))
+    }
+
+    async fn load_balance_drifts(&self) -> Result<Vec<BalanceDrift>> {
+        let drifts = self.account_balance_repository.find_drifts().await?;
+
+        Ok(drifts
+            .into_iter()
+            .map(|entity| self.account_balance_mapper.map_to_balance_drift(entity))
+            .collect())
+    }
+}
+
+#[async_trait]
+impl LookupAccountIdentifierPort for AccountPersistenceAdapter {
+    async fn lookup_account_id(&self, identifier: &AccountIdentifier) -> Result<Option<AccountId>> {
+        let account_id = self
+            .account_repository
+            .find_id_by_identifier(identifier.scheme(), &identifier.value())
+            .await?;
+
+        Ok(account_id.map(AccountId))
+    }
+}
+
+#[async_trait]
+impl AccountAccessPort for AccountPersistenceAdapter {
+    async fn has_access(&self, account_id: &AccountId, principal: &Principal) -> Result<bool> {
+        self.account_repository
+            .has_access(account_id.0, &principal.to_string())
+            .await
     }
 }
 
@@ -89,8 +395,11 @@ mod tests {
     use crate::activity_entity::ActivityEntity;
     use crate::activity_repository::ActivityRepository;
     use anyhow::Result;
-    use buckpal_application::application::port::outgoing::load_account_port::LoadAccountPort;
+    use buckpal_application::application::port::outgoing::{
+        account_access_port::AccountAccessPort, load_account_port::LoadAccountPort,
+    };
     use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::principal::Principal;
     use chrono::{DateTime, NaiveDate, Utc};
     use rusty_money::{money, Money};
     use sqlx::postgres::{PgPool, PgPoolOptions};
@@ -143,6 +452,198 @@ mod tests {
         Ok(())
     }
 
+    #[async_std::test]
+    async fn loads_balance_as_of_instant() {
+        use buckpal_application::application::port::outgoing::account_balance_port::AccountBalancePort;
+        use buckpal_application::domain::account::AccountError;
+
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        // setup db
+        let first_account_id = given_an_account(&pool).await.unwrap();
+        let second_account_id = given_an_account(&pool).await.unwrap();
+
+        let activity_ids =
+            given_some_activites_for_account_ids(first_account_id, second_account_id, &pool)
+                .await
+                .unwrap();
+        // end setup db
+
+        let account_id = AccountId(first_account_id);
+        let adapter = AccountPersistenceAdapter::new(pool.clone());
+
+        // activities at the instant count
+        let at_deposit = adapter
+            .load_balance_as_of(
+                &account_id,
+                &DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2018, 8, 9).and_hms(10, 0, 0), Utc),
+            )
+            .await
+            .unwrap();
+        let before_last_deposit = adapter
+            .load_balance_as_of(
+                &account_id,
+                &DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2019, 8, 9).and_hms(9, 59, 59), Utc),
+            )
+            .await
+            .unwrap();
+        let of_unknown_account = adapter
+            .load_balance_as_of(&AccountId(i32::MAX), &Utc::now())
+            .await
+            .unwrap_err();
+
+        // cleanup db
+        delete_account_with_id(first_account_id, &pool)
+            .await
+            .unwrap();
+        delete_account_with_id(second_account_id, &pool)
+            .await
+            .unwrap();
+
+        delete_activites_for_ids(activity_ids, &pool).await.unwrap();
+        // end cleanup db
+
+        assert_eq!(at_deposit, money!(500, "AUD"));
+        assert_eq!(before_last_deposit, money!(-500, "AUD"));
+        assert!(matches!(
+            of_unknown_account.downcast_ref::<AccountError>(),
+            Some(AccountError::AccountNotFound(_))
+        ));
+    }
+
+    #[async_std::test]
+    async fn loads_activity_pages_newest_first() {
+        use buckpal_application::application::port::incoming::list_activities_query::{
+            ActivityCursor, ActivityFilter,
+        };
+        use buckpal_application::application::port::outgoing::load_activities_port::LoadActivitiesPort;
+        use buckpal_application::domain::activity::{ActivityId, ActivityKind};
+
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        // setup db
+        let first_account_id = given_an_account(&pool).await.unwrap();
+        let second_account_id = given_an_account(&pool).await.unwrap();
+
+        let activity_ids =
+            given_some_activites_for_account_ids(first_account_id, second_account_id, &pool)
+                .await
+                .unwrap();
+        // end setup db
+
+        let account_id = AccountId(first_account_id);
+        let adapter = AccountPersistenceAdapter::new(pool.clone());
+        let ids_of = |activities: Vec<buckpal_application::domain::activity::Activity>| {
+            activities
+                .into_iter()
+                .map(|activity| activity.id.unwrap().0)
+                .collect::<Vec<i32>>()
+        };
+
+        let first_page = adapter
+            .load_activities(&account_id, &ActivityFilter::default(), None, 2)
+            .await
+            .unwrap();
+        let cursor = ActivityCursor {
+            timestamp: first_page[1].timestamp,
+            activity_id: first_page[1].id.clone().unwrap(),
+        };
+        let second_page = adapter
+            .load_activities(&account_id, &ActivityFilter::default(), Some(&cursor), 2)
+            .await
+            .unwrap();
+        let after_last_page = adapter
+            .load_activities(
+                &account_id,
+                &ActivityFilter::default(),
+                Some(&ActivityCursor {
+                    timestamp: second_page[1].timestamp,
+                    activity_id: ActivityId(activity_ids[0]),
+                }),
+                2,
+            )
+            .await
+            .unwrap();
+        let deposits = adapter
+            .load_activities(
+                &account_id,
+                &ActivityFilter {
+                    kind: Some(ActivityKind::Deposit),
+                    ..ActivityFilter::default()
+                },
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        let large_ones_of_2019 = adapter
+            .load_activities(
+                &account_id,
+                &ActivityFilter {
+                    from: Some(DateTime::<Utc>::from_utc(
+                        NaiveDate::from_ymd(2019, 1, 1).and_hms(0, 0, 0),
+                        Utc,
+                    )),
+                    min_amount: Some(money!(1000, "AUD")),
+                    ..ActivityFilter::default()
+                },
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+        let with_itself_as_counterparty = adapter
+            .load_activities(
+                &account_id,
+                &ActivityFilter {
+                    counterparty_account_id: Some(account_id.clone()),
+                    ..ActivityFilter::default()
+                },
+                None,
+                10,
+            )
+            .await
+            .unwrap();
+
+        // cleanup db
+        delete_account_with_id(first_account_id, &pool)
+            .await
+            .unwrap();
+        delete_account_with_id(second_account_id, &pool)
+            .await
+            .unwrap();
+
+        delete_activites_for_ids(activity_ids.clone(), &pool)
+            .await
+            .unwrap();
+        // end cleanup db
+
+        // the first account owns every other activity, the latest ones being the 7th and 5th
+        assert_eq!(ids_of(first_page), vec![activity_ids[6], activity_ids[4]]);
+        assert_eq!(ids_of(second_page), vec![activity_ids[2], activity_ids[0]]);
+        assert!(after_last_page.is_empty());
+        assert_eq!(ids_of(deposits), vec![activity_ids[6], activity_ids[2]]);
+        assert_eq!(
+            ids_of(large_ones_of_2019),
+            vec![activity_ids[6], activity_ids[4]]
+        );
+        assert!(with_itself_as_counterparty.is_empty());
+    }
+
     #[async_std::test]
     async fn updates_activities() {
         use super::AccountPersistenceAdapter;
@@ -150,10 +651,13 @@ mod tests {
         use buckpal_application::domain::account::account_test_data::AccountBuilder;
         use buckpal_application::domain::activity::activity_test_data::ActivityBuilder;
         use buckpal_application::domain::activity_window::ActivityWindow;
+        use buckpal_application::domain::principal::Principal;
 
+        let initiator = Principal::User(String::from("alice"));
         let activity_window = ActivityWindow::new(vec![ActivityBuilder::default_activity()
             .with_money(&money!(1, "AUD"))
-            .build()]);
+            .build()
+            .with_initiator(Some(initiator.clone()))]);
         let account = AccountBuilder::default_account()
             .with_baseline_balance(&money!(555, "AUD"))
             .with_activity_window(&activity_window)
@@ -184,9 +688,314 @@ mod tests {
         let saved_activity = find_activity(*first_id, &pool).await.unwrap();
 
         delete_activites_for_ids(activity_ids, &pool).await.unwrap();
+        delete_account_balance(42, &pool).await.unwrap();
 
         assert_eq!(updated_activities.len(), 1);
         assert_eq!(saved_activity.amount, 1);
+        assert_eq!(saved_activity.initiator.as_deref(), Some("user:alice"));
+        assert_eq!(updated_activities[0].initiator, Some(initiator));
+    }
+
+    #[async_std::test]
+    async fn keeps_balance_with_activities() {
+        use buckpal_application::application::port::outgoing::{
+            account_balance_port::AccountBalancePort,
+            update_account_state_port::UpdateAccountStatePort,
+        };
+        use buckpal_application::domain::account::account_test_data::AccountBuilder;
+        use buckpal_application::domain::activity::{Activity, ActivityId};
+        use buckpal_application::domain::activity_window::ActivityWindow;
+
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let other_account_id = AccountId(given_an_account(&pool).await.unwrap());
+
+        let activity_window = ActivityWindow::new(vec![
+            Activity::new(
+                account_id.clone(),
+                other_account_id.clone(),
+                account_id.clone(),
+                Utc::now(),
+                money!(500, "AUD"),
+                None,
+            ),
+            Activity::new(
+                account_id.clone(),
+                account_id.clone(),
+                other_account_id.clone(),
+                Utc::now(),
+                money!(200, "AUD"),
+                None,
+            ),
+        ]);
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_activity_window(&activity_window)
+            .build();
+
+        let adapter = AccountPersistenceAdapter::new(pool.clone());
+        let activity_ids: Vec<i32> = adapter
+            .update_activities(&account)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|activity| activity.id.unwrap().0)
+            .collect();
+
+        let account_balance = adapter
+            .load_account_balance(&account_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let drifts_before = adapter.load_balance_drifts().await.unwrap();
+
+        // an activity going missing behind the adapter's back makes the kept balance drift
+        delete_activites_for_ids(vec![activity_ids[1]], &pool)
+            .await
+            .unwrap();
+        let drifts_after = adapter.load_balance_drifts().await.unwrap();
+
+        // cleanup db
+        delete_activites_for_ids(vec![activity_ids[0]], &pool)
+            .await
+            .unwrap();
+        delete_account_balance(account_id.0, &pool).await.unwrap();
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+        delete_account_with_id(other_account_id.0, &pool)
+            .await
+            .unwrap();
+        // end cleanup db
+
+        assert_eq!(account_balance.balance, money!(300, "AUD"));
+        assert_eq!(
+            account_balance.last_activity_id,
+            ActivityId(activity_ids[1])
+        );
+        assert!(drifts_before
+            .iter()
+            .all(|drift| drift.account_id != account_id));
+
+        let drift = drifts_after
+            .iter()
+            .find(|drift| drift.account_id == account_id)
+            .unwrap();
+        assert_eq!(drift.kept_balance, money!(300, "AUD"));
+        assert_eq!(drift.booked_balance, money!(500, "AUD"));
+        assert_eq!(drift.last_activity_id, Some(ActivityId(activity_ids[0])));
+    }
+
+    #[async_std::test]
+    async fn only_one_of_two_concurrent_captures_of_a_hold_is_recorded() {
+        use buckpal_application::application::port::outgoing::{
+            load_hold_port::LoadHoldPort, record_transfer_port::RecordTransferPort,
+            update_account_state_port::UpdateAccountStatePort,
+        };
+        use buckpal_application::domain::account::account_test_data::AccountBuilder;
+        use buckpal_application::domain::activity::Activity;
+        use buckpal_application::domain::activity_window::ActivityWindow;
+        use buckpal_application::domain::hold::HoldStatus;
+        use buckpal_application::domain::transfer::{Transfer, TransferStatus};
+        use chrono::Duration;
+
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let source_account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let target_account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let adapter = AccountPersistenceAdapter::new(pool.clone());
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        adapter
+            .update_activities(
+                &AccountBuilder::default_account()
+                    .with_account_id(&source_account_id)
+                    .with_activity_window(&ActivityWindow::new(vec![Activity::new(
+                        source_account_id.clone(),
+                        target_account_id.clone(),
+                        source_account_id.clone(),
+                        Utc::now(),
+                        money!(500, "AUD"),
+                        None,
+                    )]))
+                    .build(),
+            )
+            .await
+            .unwrap();
+
+        let mut source_account = adapter
+            .load_account(&source_account_id, &baseline_date)
+            .await
+            .unwrap();
+        source_account
+            .place_hold(
+                &money!(300, "AUD"),
+                &target_account_id,
+                Utc::now(),
+                Utc::now() + Duration::hours(1),
+            )
+            .unwrap();
+        let hold_id = adapter.update_holds(&source_account).await.unwrap()[0]
+            .id
+            .clone()
+            .unwrap();
+
+        // both captures are prepared from the same view of the hold before either is recorded
+        let mut captures = vec![];
+        for _ in 0..2 {
+            let mut source_account = adapter
+                .load_account(&source_account_id, &baseline_date)
+                .await
+                .unwrap();
+            let mut target_account = adapter
+                .load_account(&target_account_id, &baseline_date)
+                .await
+                .unwrap();
+            let transfer_id = adapter.reserve_transfer_id().await.unwrap();
+            source_account
+                .capture_hold(&hold_id, &money!(300, "AUD"), &transfer_id, None)
+                .unwrap();
+            target_account
+                .deposit(&money!(300, "AUD"), &source_account_id, &transfer_id, None)
+                .unwrap();
+            let mut transfer = Transfer::new_with_id(
+                transfer_id,
+                source_account_id.clone(),
+                target_account_id.clone(),
+                Utc::now(),
+                money!(300, "AUD"),
+                TransferStatus::Pending,
+                None,
+                None,
+            );
+            transfer.complete();
+            captures.push((transfer, source_account, target_account));
+        }
+
+        let tasks: Vec<_> = captures
+            .into_iter()
+            .map(|(transfer, source_account, target_account)| {
+                let adapter = adapter.clone();
+                async_std::task::spawn(async move {
+                    adapter
+                        .record_transfer(&transfer, &source_account, &target_account)
+                        .await
+                        .is_ok()
+                })
+            })
+            .collect();
+        let mut recorded = vec![];
+        for task in tasks {
+            recorded.push(task.await);
+        }
+
+        let source_balance = adapter
+            .load_account(&source_account_id, &baseline_date)
+            .await
+            .unwrap()
+            .calculate_balance();
+        let target_balance = adapter
+            .load_account(&target_account_id, &baseline_date)
+            .await
+            .unwrap()
+            .calculate_balance();
+        let hold_status = adapter.load_hold(&hold_id).await.unwrap().status;
+
+        // cleanup db
+        delete_capture_of_accounts(source_account_id.0, target_account_id.0, &pool)
+            .await
+            .unwrap();
+        // end cleanup db
+
+        assert_eq!(recorded.iter().filter(|recorded| **recorded).count(), 1);
+        assert_eq!(source_balance, money!(200, "AUD"));
+        assert_eq!(target_balance, money!(300, "AUD"));
+        assert_eq!(hold_status, HoldStatus::Captured);
+    }
+
+    #[async_std::test]
+    async fn grants_access_to_listed_principals_only() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        // setup db
+        let account_id = given_an_account(&pool).await.unwrap();
+        grant_access(account_id, "user:alice", &pool).await.unwrap();
+        // end setup db
+
+        let adapter = AccountPersistenceAdapter::new(pool.clone());
+
+        let alice = adapter
+            .has_access(
+                &AccountId(account_id),
+                &Principal::User(String::from("alice")),
+            )
+            .await
+            .unwrap();
+        let mallory = adapter
+            .has_access(
+                &AccountId(account_id),
+                &Principal::User(String::from("mallory")),
+            )
+            .await
+            .unwrap();
+        let service_alice = adapter
+            .has_access(
+                &AccountId(account_id),
+                &Principal::Service(String::from("alice")),
+            )
+            .await
+            .unwrap();
+
+        // cleanup db
+        sqlx::query!(
+            r#"
+                DELETE FROM account_access WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        delete_account_with_id(account_id, &pool).await.unwrap();
+        // end cleanup db
+
+        assert!(alice);
+        assert!(!mallory);
+        assert!(!service_alice);
+    }
+
+    async fn grant_access(account_id: i32, principal: &str, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO account_access (account_id, principal) VALUES ($1, $2)
+            "#,
+            account_id,
+            principal,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
     }
 
     async fn find_activity(activity_id: i32, pool: &PgPool) -> Result<ActivityEntity> {
@@ -198,7 +1007,9 @@ mod tests {
                         owner_account_id,
                         source_account_id,
                         target_account_id,
-                        amount
+                        amount,
+                        transfer_id,
+                        initiator
                 FROM 
                         activity
                 WHERE 
@@ -216,7 +1027,9 @@ mod tests {
             entity.source_account_id,
             entity.target_account_id,
             entity.amount,
-        );
+            entity.transfer_id,
+        )
+        .with_initiator(entity.initiator);
 
         Ok(entity)
     }
@@ -261,6 +1074,7 @@ mod tests {
                 first_account_id,
                 second_account_id,
                 500,
+                None,
             ))
             .await?;
 
@@ -272,6 +1086,7 @@ mod tests {
                 first_account_id,
                 second_account_id,
                 500,
+                None,
             ))
             .await?;
 
@@ -283,6 +1098,7 @@ mod tests {
                 second_account_id,
                 first_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -294,6 +1110,7 @@ mod tests {
                 second_account_id,
                 first_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -305,6 +1122,7 @@ mod tests {
                 first_account_id,
                 second_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -316,6 +1134,7 @@ mod tests {
                 first_account_id,
                 second_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -327,6 +1146,7 @@ mod tests {
                 second_account_id,
                 first_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -338,6 +1158,7 @@ mod tests {
                 second_account_id,
                 first_account_id,
                 1000,
+                None,
             ))
             .await?;
 
@@ -353,6 +1174,88 @@ mod tests {
         ])
     }
 
+    /// Deletes whatever a capture between the two accounts left behind, the accounts included.
+    async fn delete_capture_of_accounts(
+        source_account_id: i32,
+        target_account_id: i32,
+        pool: &PgPool,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM activity_chain_head WHERE account_id IN ($1, $2)
+            "#,
+            source_account_id,
+            target_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account_balance WHERE account_id IN ($1, $2)
+            "#,
+            source_account_id,
+            target_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM activity WHERE owner_account_id IN ($1, $2)
+            "#,
+            source_account_id,
+            target_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM hold WHERE account_id = $1
+            "#,
+            source_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM outbox WHERE source_account_id = $1 AND target_account_id = $2
+            "#,
+            source_account_id,
+            target_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM transfer WHERE source_account_id = $1 AND target_account_id = $2
+            "#,
+            source_account_id,
+            target_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        delete_account_with_id(source_account_id, pool).await?;
+        delete_account_with_id(target_account_id, pool).await
+    }
+
+    async fn delete_account_balance(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account_balance WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
     async fn delete_activites_for_ids(activity_ids: Vec<i32>, pool: &PgPool) -> Result<()> {
         for activity_id in activity_ids {
             sqlx::query!(
@@ -30,4 +30,51 @@ impl AccountRepository {
 
         Ok(entity)
     }
+
+    /// Finds the account registered for the identifier, given in its normalised form.
+    pub async fn find_id_by_identifier(&self, scheme: &str, value: &str) -> Result<Option<i32>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        account_id 
+                FROM 
+                        account_identifier
+                WHERE 
+                        scheme = $1
+                AND
+                        value = $2
+            "#,
+            scheme,
+            value
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| entity.account_id))
+    }
+
+    /// Whether the principal, in the form `Principal` displays, was granted access to the account.
+    pub async fn has_access(&self, account_id: i32, principal: &str) -> Result<bool> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        EXISTS (
+                            SELECT
+                                    1
+                            FROM
+                                    account_access
+                            WHERE
+                                    account_id = $1
+                            AND
+                                    principal = $2
+                        ) AS "granted!"
+            "#,
+            account_id,
+            principal
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entity.granted)
+    }
 }
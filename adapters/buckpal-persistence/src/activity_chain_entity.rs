@@ -0,0 +1,25 @@
+use crate::activity_entity::ActivityEntity;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainedActivityEntity {
+    pub activity: ActivityEntity,
+    pub previous_hash: Option<String>,
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainHeadEntity {
+    pub account_id: i32,
+    pub activity_id: i32,
+    pub hash: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainCheckpointEntity {
+    pub id: Option<i32>,
+    pub heads: Vec<ChainHeadEntity>,
+    pub digest: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
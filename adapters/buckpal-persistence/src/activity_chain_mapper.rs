@@ -0,0 +1,62 @@
+use crate::account_mapper::AccountMapper;
+use crate::activity_chain_entity::{ChainCheckpointEntity, ChainHeadEntity, ChainedActivityEntity};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::ActivityId;
+use buckpal_application::domain::activity_chain::{
+    ChainCheckpoint, ChainCheckpointId, ChainHead, ChainedActivity,
+};
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ActivityChainMapper {
+    account_mapper: AccountMapper,
+}
+
+impl ActivityChainMapper {
+    pub fn map_to_chained_activity(&self, entity: &ChainedActivityEntity) -> ChainedActivity {
+        ChainedActivity::new(
+            self.account_mapper.map_to_activity(&entity.activity),
+            entity.previous_hash.clone(),
+            entity.hash.clone(),
+        )
+    }
+
+    pub fn map_to_head(&self, entity: &ChainHeadEntity) -> ChainHead {
+        ChainHead::new(
+            AccountId(entity.account_id),
+            ActivityId(entity.activity_id),
+            &entity.hash,
+        )
+    }
+
+    pub fn map_to_checkpoint(&self, entity: ChainCheckpointEntity) -> ChainCheckpoint {
+        ChainCheckpoint {
+            id: entity.id.map(ChainCheckpointId),
+            heads: entity
+                .heads
+                .iter()
+                .map(|head| self.map_to_head(head))
+                .collect(),
+            digest: entity.digest,
+            signature: entity.signature,
+            created_at: entity.created_at,
+        }
+    }
+
+    pub fn map_to_checkpoint_entity(&self, checkpoint: &ChainCheckpoint) -> ChainCheckpointEntity {
+        ChainCheckpointEntity {
+            id: checkpoint.id.clone().map(|id| id.0),
+            heads: checkpoint
+                .heads
+                .iter()
+                .map(|head| ChainHeadEntity {
+                    account_id: head.account_id.0,
+                    activity_id: head.activity_id.0,
+                    hash: head.hash.clone(),
+                })
+                .collect(),
+            digest: checkpoint.digest.clone(),
+            signature: checkpoint.signature.clone(),
+            created_at: checkpoint.created_at,
+        }
+    }
+}
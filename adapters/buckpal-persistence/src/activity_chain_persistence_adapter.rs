@@ -0,0 +1,242 @@
+use crate::activity_chain_mapper::ActivityChainMapper;
+use crate::activity_chain_repository::ActivityChainRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::activity_chain_port::ActivityChainPort;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity_chain::{ChainCheckpoint, ChainHead, ChainedActivity};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct ActivityChainPersistenceAdapter {
+    activity_chain_repository: ActivityChainRepository,
+    activity_chain_mapper: ActivityChainMapper,
+}
+
+impl ActivityChainPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            activity_chain_repository: ActivityChainRepository::new(pool),
+            activity_chain_mapper: ActivityChainMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ActivityChainPort for ActivityChainPersistenceAdapter {
+    async fn load_chained_activities(
+        &self,
+        account_id: &AccountId,
+    ) -> Result<Vec<ChainedActivity>> {
+        let activities = self
+            .activity_chain_repository
+            .find_by_owner(account_id.0)
+            .await?;
+
+        Ok(activities
+            .iter()
+            .map(|activity| self.activity_chain_mapper.map_to_chained_activity(activity))
+            .collect())
+    }
+
+    async fn load_chain_head(&self, account_id: &AccountId) -> Result<Option<ChainHead>> {
+        let head = self
+            .activity_chain_repository
+            .find_head(account_id.0)
+            .await?;
+
+        Ok(head.map(|head| self.activity_chain_mapper.map_to_head(&head)))
+    }
+
+    async fn load_chain_heads(&self) -> Result<Vec<ChainHead>> {
+        let heads = self.activity_chain_repository.find_heads().await?;
+
+        Ok(heads
+            .iter()
+            .map(|head| self.activity_chain_mapper.map_to_head(head))
+            .collect())
+    }
+
+    async fn load_latest_checkpoint(&self) -> Result<Option<ChainCheckpoint>> {
+        let checkpoint = self
+            .activity_chain_repository
+            .find_latest_checkpoint()
+            .await?;
+
+        Ok(checkpoint.map(|checkpoint| self.activity_chain_mapper.map_to_checkpoint(checkpoint)))
+    }
+
+    async fn save_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<ChainCheckpoint> {
+        let entity = self
+            .activity_chain_repository
+            .save_checkpoint(
+                &self
+                    .activity_chain_mapper
+                    .map_to_checkpoint_entity(checkpoint),
+            )
+            .await?;
+
+        Ok(self.activity_chain_mapper.map_to_checkpoint(entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ActivityChainPersistenceAdapter;
+    use crate::account_persistence_adapter::AccountPersistenceAdapter;
+    use anyhow::Result;
+    use buckpal_application::application::port::outgoing::{
+        activity_chain_port::ActivityChainPort, update_account_state_port::UpdateAccountStatePort,
+    };
+    use buckpal_application::domain::account::account_test_data::AccountBuilder;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::activity::Activity;
+    use buckpal_application::domain::activity_chain::{verify_chain, ChainBreak};
+    use buckpal_application::domain::activity_window::ActivityWindow;
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+
+    #[async_std::test]
+    async fn chains_saved_activities() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let other_account_id = AccountId(given_an_account(&pool).await.unwrap());
+
+        let activity_window = ActivityWindow::new(
+            [500, 300, 200]
+                .iter()
+                .map(|amount| {
+                    Activity::new(
+                        account_id.clone(),
+                        other_account_id.clone(),
+                        account_id.clone(),
+                        Utc::now(),
+                        money!(*amount, "AUD"),
+                        None,
+                    )
+                })
+                .collect(),
+        );
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_activity_window(&activity_window)
+            .build();
+        AccountPersistenceAdapter::new(pool.clone())
+            .update_activities(&account)
+            .await
+            .unwrap();
+
+        let adapter = ActivityChainPersistenceAdapter::new(pool.clone());
+        let chain = adapter.load_chained_activities(&account_id).await.unwrap();
+        let head = adapter.load_chain_head(&account_id).await.unwrap();
+
+        // an edit behind the adapter's back breaks the chain at the edited activity
+        let edited_activity_id = chain[1].activity.id.clone().unwrap();
+        edit_activity_amount(edited_activity_id.0, &pool)
+            .await
+            .unwrap();
+        let edited_chain = adapter.load_chained_activities(&account_id).await.unwrap();
+
+        // cleanup db
+        delete_chain_of(account_id.0, &pool).await.unwrap();
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+        delete_account_with_id(other_account_id.0, &pool)
+            .await
+            .unwrap();
+        // end cleanup db
+
+        assert_eq!(chain.len(), 3);
+        assert_eq!(
+            head.as_ref().unwrap().activity_id,
+            chain[2].activity.id.clone().unwrap()
+        );
+        assert!(verify_chain(&account_id, &chain, head.as_ref(), None).is_intact());
+
+        let broken_link = verify_chain(&account_id, &edited_chain, head.as_ref(), None)
+            .broken_link
+            .unwrap();
+        assert_eq!(broken_link.activity_id, Some(edited_activity_id));
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::HashMismatch { .. }
+        ));
+    }
+
+    async fn given_an_account(pool: &PgPool) -> Result<i32> {
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO account DEFAULT VALUES RETURNING id 
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entity.id)
+    }
+
+    async fn edit_activity_amount(activity_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE activity SET amount = amount + 1 WHERE id = $1
+            "#,
+            activity_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_account_with_id(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account WHERE id = $1 
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_chain_of(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM activity WHERE owner_account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM activity_chain_head WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account_balance WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,297 @@
+use crate::activity_chain_entity::{ChainCheckpointEntity, ChainHeadEntity, ChainedActivityEntity};
+use crate::activity_entity::ActivityEntity;
+use anyhow::Result;
+use buckpal_application::domain::activity_chain::GENESIS_HASH;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct ActivityChainRepository {
+    pool: PgPool,
+}
+
+impl ActivityChainRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Locks the head of the chain of the account until the transaction ends, so that activities
+    /// are chained in the order they are inserted. Returns the hash to chain the next activity
+    /// onto.
+    pub async fn lock_head_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account_id: i32,
+    ) -> Result<String> {
+        let head = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            activity_chain_head (account_id, activity_id, hash, updated_at)
+                VALUES 
+                            ($1, NULL, $2, now())
+                ON CONFLICT (account_id) DO UPDATE SET 
+                            hash = activity_chain_head.hash
+                RETURNING 
+                            hash
+            "#,
+            account_id,
+            GENESIS_HASH
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        Ok(head.hash)
+    }
+
+    /// Stores the link of the inserted activity and moves the locked head on to it.
+    pub async fn link_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        activity_entity: &ActivityEntity,
+        previous_hash: &str,
+        hash: &str,
+    ) -> Result<()> {
+        let activity_id = activity_entity
+            .id
+            .expect("expected saved activity to have an ID");
+
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        activity
+                SET 
+                        previous_hash = $2,
+                        hash = $3
+                WHERE 
+                        id = $1
+            "#,
+            activity_id,
+            previous_hash,
+            hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        activity_chain_head
+                SET 
+                        activity_id = $2,
+                        hash = $3,
+                        updated_at = now()
+                WHERE 
+                        account_id = $1
+            "#,
+            activity_entity.owner_account_id,
+            activity_id,
+            hash
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_owner(&self, account_id: i32) -> Result<Vec<ChainedActivityEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        timestamp,
+                        owner_account_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        transfer_id,
+                        initiator,
+                        previous_hash,
+                        hash
+                FROM 
+                        activity
+                WHERE 
+                        owner_account_id = $1
+                ORDER BY 
+                        id
+            "#,
+            account_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| ChainedActivityEntity {
+                activity: ActivityEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.owner_account_id,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.transfer_id,
+                )
+                .with_initiator(entity.initiator),
+                previous_hash: entity.previous_hash,
+                hash: entity.hash,
+            })
+            .collect())
+    }
+
+    pub async fn find_head(&self, account_id: i32) -> Result<Option<ChainHeadEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        activity_id AS "activity_id!",
+                        hash
+                FROM 
+                        activity_chain_head
+                WHERE 
+                        account_id = $1 AND activity_id IS NOT NULL
+            "#,
+            account_id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| ChainHeadEntity {
+            account_id: entity.account_id,
+            activity_id: entity.activity_id,
+            hash: entity.hash,
+        }))
+    }
+
+    pub async fn find_heads(&self) -> Result<Vec<ChainHeadEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        activity_id AS "activity_id!",
+                        hash
+                FROM 
+                        activity_chain_head
+                WHERE 
+                        activity_id IS NOT NULL
+                ORDER BY 
+                        account_id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| ChainHeadEntity {
+                account_id: entity.account_id,
+                activity_id: entity.activity_id,
+                hash: entity.hash,
+            })
+            .collect())
+    }
+
+    pub async fn find_latest_checkpoint(&self) -> Result<Option<ChainCheckpointEntity>> {
+        let checkpoint = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        digest,
+                        signature,
+                        created_at
+                FROM 
+                        activity_chain_checkpoint
+                ORDER BY 
+                        id DESC
+                LIMIT 
+                        1
+            "#
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        let checkpoint = match checkpoint {
+            Some(checkpoint) => checkpoint,
+            None => return Ok(None),
+        };
+
+        let heads = sqlx::query!(
+            r#"
+                SELECT 
+                        account_id,
+                        activity_id,
+                        hash
+                FROM 
+                        activity_chain_checkpoint_head
+                WHERE 
+                        checkpoint_id = $1
+                ORDER BY 
+                        account_id
+            "#,
+            checkpoint.id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(Some(ChainCheckpointEntity {
+            id: Some(checkpoint.id),
+            heads: heads
+                .into_iter()
+                .map(|head| ChainHeadEntity {
+                    account_id: head.account_id,
+                    activity_id: head.activity_id,
+                    hash: head.hash,
+                })
+                .collect(),
+            digest: checkpoint.digest,
+            signature: checkpoint.signature,
+            created_at: checkpoint.created_at,
+        }))
+    }
+
+    pub async fn save_checkpoint(
+        &self,
+        entity: &ChainCheckpointEntity,
+    ) -> Result<ChainCheckpointEntity> {
+        let mut tx = self.pool.begin().await?;
+
+        let checkpoint = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            activity_chain_checkpoint (digest, signature, created_at)
+                VALUES 
+                            ($1, $2, $3)
+                RETURNING 
+                            id
+            "#,
+            entity.digest,
+            entity.signature,
+            entity.created_at
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        for head in &entity.heads {
+            sqlx::query!(
+                r#"
+                    INSERT INTO 
+                                activity_chain_checkpoint_head (checkpoint_id, account_id, activity_id, hash)
+                    VALUES 
+                                ($1, $2, $3, $4)
+                "#,
+                checkpoint.id,
+                head.account_id,
+                head.activity_id,
+                head.hash
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        let mut entity = entity.clone();
+        entity.id = Some(checkpoint.id);
+
+        Ok(entity)
+    }
+}
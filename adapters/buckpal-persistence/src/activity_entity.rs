@@ -8,6 +8,9 @@ pub struct ActivityEntity {
     pub source_account_id: i32,
     pub target_account_id: i32,
     pub amount: i64,
+    pub transfer_id: Option<i32>,
+    /// The principal that initiated the activity, e.g. `user:alice`.
+    pub initiator: Option<String>,
 }
 
 impl ActivityEntity {
@@ -18,6 +21,7 @@ impl ActivityEntity {
         source_account_id: i32,
         target_account_id: i32,
         amount: i64,
+        transfer_id: Option<i32>,
     ) -> Self {
         Self {
             id,
@@ -26,6 +30,13 @@ impl ActivityEntity {
             source_account_id,
             target_account_id,
             amount,
+            transfer_id,
+            initiator: None,
         }
     }
+
+    pub fn with_initiator(mut self, initiator: Option<String>) -> Self {
+        self.initiator = initiator;
+        self
+    }
 }
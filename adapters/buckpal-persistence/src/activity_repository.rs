@@ -2,6 +2,7 @@ use crate::activity_entity::ActivityEntity;
 use anyhow::{anyhow, Result};
 use chrono::{DateTime, Utc};
 use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -12,6 +13,21 @@ pub enum ActivityRepositoryError {
     AlreadyHasAnIdException(i32),
 }
 
+/// Optional criteria for listing the activities of an owner account. A cursor lists the
+/// activities before the given timestamp and ID.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ActivityCriteria {
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub counterparty_account_id: Option<i32>,
+    /// Either `DEPOSIT` or `WITHDRAWAL`.
+    pub kind: Option<String>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub cursor_timestamp: Option<DateTime<Utc>>,
+    pub cursor_id: Option<i32>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ActivityRepository {
     pool: PgPool,
@@ -23,6 +39,19 @@ impl ActivityRepository {
     }
 
     pub async fn save(&self, activity_entity: &ActivityEntity) -> Result<ActivityEntity> {
+        let mut tx = self.pool.begin().await?;
+        let entity = self.save_in(&mut tx, activity_entity).await?;
+        tx.commit().await?;
+
+        Ok(entity)
+    }
+
+    /// Inserts the activity as part of a larger transaction.
+    pub async fn save_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        activity_entity: &ActivityEntity,
+    ) -> Result<ActivityEntity> {
         match activity_entity.id {
             Some(activity_id) => Err(anyhow!(ActivityRepositoryError::AlreadyHasAnIdException(
                 activity_id
@@ -31,19 +60,21 @@ impl ActivityRepository {
                 let entity = sqlx::query!(
                     r#"
                         INSERT INTO 
-                                    activity (timestamp, owner_account_id, source_account_id, target_account_id, amount)
+                                    activity (timestamp, owner_account_id, source_account_id, target_account_id, amount, transfer_id, initiator)
                         VALUES 
-                                    ($1, $2, $3, $4, $5)
+                                    ($1, $2, $3, $4, $5, $6, $7)
                         RETURNING 
-                                    id, timestamp, owner_account_id, source_account_id, target_account_id, amount 
+                                    id, timestamp, owner_account_id, source_account_id, target_account_id, amount, transfer_id, initiator 
                     "#,
                     activity_entity.timestamp,
                     activity_entity.owner_account_id,
                     activity_entity.source_account_id,
                     activity_entity.target_account_id,
-                    activity_entity.amount
+                    activity_entity.amount,
+                    activity_entity.transfer_id,
+                    activity_entity.initiator
                 )
-                .fetch_one(&self.pool)
+                .fetch_one(&mut *tx)
                 .await?;
 
                 let entity = ActivityEntity::new(
@@ -53,7 +84,9 @@ impl ActivityRepository {
                     entity.source_account_id,
                     entity.target_account_id,
                     entity.amount,
-                );
+                    entity.transfer_id,
+                )
+                .with_initiator(entity.initiator);
 
                 Ok(entity)
             }
@@ -73,7 +106,9 @@ impl ActivityRepository {
                         owner_account_id,
                         source_account_id,
                         target_account_id,
-                        amount
+                        amount,
+                        transfer_id,
+                        initiator
                 FROM 
                         activity
                 WHERE 
@@ -97,13 +132,158 @@ impl ActivityRepository {
                     entity.source_account_id,
                     entity.target_account_id,
                     entity.amount,
+                    entity.transfer_id,
                 )
+                .with_initiator(entity.initiator)
             })
             .collect();
 
         Ok(entitites)
     }
 
+    pub async fn find_page_by_owner(
+        &self,
+        owner_account_id: i32,
+        criteria: &ActivityCriteria,
+        limit: i64,
+    ) -> Result<Vec<ActivityEntity>> {
+        let entitites = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        timestamp,
+                        owner_account_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        transfer_id,
+                        initiator
+                FROM 
+                        activity
+                WHERE 
+                        owner_account_id = $1
+                AND
+                        ($2::TIMESTAMPTZ IS NULL OR timestamp >= $2)
+                AND
+                        ($3::TIMESTAMPTZ IS NULL OR timestamp < $3)
+                AND
+                        ($4::INT IS NULL OR $4 = CASE 
+                                                    WHEN target_account_id = owner_account_id THEN source_account_id 
+                                                    ELSE target_account_id 
+                                                 END)
+                AND
+                        ($5::TEXT IS NULL 
+                            OR ($5 = 'DEPOSIT' AND target_account_id = owner_account_id) 
+                            OR ($5 = 'WITHDRAWAL' AND source_account_id = owner_account_id))
+                AND
+                        ($6::BIGINT IS NULL OR amount >= $6)
+                AND
+                        ($7::BIGINT IS NULL OR amount <= $7)
+                AND
+                        ($8::TIMESTAMPTZ IS NULL OR (timestamp, id) < ($8, $9::INT))
+                ORDER BY
+                        timestamp DESC, id DESC
+                LIMIT 
+                        $10
+            "#,
+            owner_account_id,
+            criteria.from,
+            criteria.to,
+            criteria.counterparty_account_id,
+            criteria.kind,
+            criteria.min_amount,
+            criteria.max_amount,
+            criteria.cursor_timestamp,
+            criteria.cursor_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entitites = entitites
+            .into_iter()
+            .map(|entity| {
+                ActivityEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.owner_account_id,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.transfer_id,
+                )
+                .with_initiator(entity.initiator)
+            })
+            .collect();
+
+        Ok(entitites)
+    }
+
+    pub async fn find_by_transfer_id(&self, transfer_id: i32) -> Result<Vec<ActivityEntity>> {
+        let entitites = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        timestamp,
+                        owner_account_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        transfer_id,
+                        initiator
+                FROM 
+                        activity
+                WHERE 
+                        transfer_id = $1
+                ORDER BY
+                        id
+            "#,
+            transfer_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entitites = entitites
+            .into_iter()
+            .map(|entity| {
+                ActivityEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.owner_account_id,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.transfer_id,
+                )
+                .with_initiator(entity.initiator)
+            })
+            .collect();
+
+        Ok(entitites)
+    }
+
+    /// Sums the deposits minus the withdrawals of the account up to and including the instant.
+    pub async fn get_balance_as_of(&self, account_id: i32, instant: &DateTime<Utc>) -> Result<i64> {
+        let sum = sqlx::query!(
+            r#"
+                SELECT
+                        COALESCE(SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE -amount END), 0)::BIGINT AS "total!"
+                FROM
+                        activity
+                WHERE
+                        owner_account_id = $1
+                AND     
+                        timestamp <= $2
+           "#,
+            account_id,
+            *instant,
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(sum.total)
+    }
+
     pub async fn get_deposit_balance_until(
         &self,
         account_id: i32,
@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AuditLogEntity {
+    pub id: Option<i32>,
+    pub occurred_at: DateTime<Utc>,
+    pub action: String,
+    pub details: String,
+    pub actor: Option<String>,
+    pub source_ip: Option<String>,
+    pub request_id: Option<String>,
+    pub outcome: String,
+    pub error_category: Option<String>,
+}
@@ -0,0 +1,90 @@
+use crate::audit_log_entity::AuditLogEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::audit::{
+    AuditContext, AuditEntry, AuditEntryId, AuditOutcome, ErrorCategory,
+};
+use buckpal_application::domain::principal::Principal;
+use thiserror::Error;
+
+const SUCCEEDED: &str = "SUCCEEDED";
+const FAILED: &str = "FAILED";
+
+#[derive(Error, Debug)]
+pub enum AuditLogMapperError {
+    #[error("Unknown audit outcome `{0}`")]
+    UnknownOutcome(String),
+    #[error("Unknown error category `{0:?}`")]
+    UnknownErrorCategory(Option<String>),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct AuditLogMapper {}
+
+impl AuditLogMapper {
+    pub fn map_to_entry(&self, entity: AuditLogEntity) -> Result<AuditEntry> {
+        let outcome = match entity.outcome.as_str() {
+            SUCCEEDED => AuditOutcome::Succeeded,
+            FAILED => AuditOutcome::Failed(self.map_to_error_category(entity.error_category)?),
+            _ => return Err(anyhow!(AuditLogMapperError::UnknownOutcome(entity.outcome))),
+        };
+
+        let actor = entity
+            .actor
+            .map(|actor| Principal::parse(&actor))
+            .transpose()?;
+
+        Ok(AuditEntry {
+            id: entity.id.map(AuditEntryId),
+            occurred_at: entity.occurred_at,
+            action: entity.action,
+            details: entity.details,
+            context: AuditContext::new(actor, entity.source_ip, entity.request_id),
+            outcome,
+        })
+    }
+
+    pub fn map_to_entity(&self, entry: &AuditEntry) -> AuditLogEntity {
+        let (outcome, error_category) = match entry.outcome {
+            AuditOutcome::Succeeded => (SUCCEEDED, None),
+            AuditOutcome::Failed(error_category) => (
+                FAILED,
+                Some(self.map_to_error_category_name(error_category)),
+            ),
+        };
+
+        AuditLogEntity {
+            id: entry.id.clone().map(|id| id.0),
+            occurred_at: entry.occurred_at,
+            action: entry.action.clone(),
+            details: entry.details.clone(),
+            actor: entry.context.actor.as_ref().map(ToString::to_string),
+            source_ip: entry.context.source_ip.clone(),
+            request_id: entry.context.request_id.clone(),
+            outcome: String::from(outcome),
+            error_category: error_category.map(String::from),
+        }
+    }
+
+    fn map_to_error_category(&self, error_category: Option<String>) -> Result<ErrorCategory> {
+        match error_category.as_deref() {
+            Some("REJECTED") => Ok(ErrorCategory::Rejected),
+            Some("CONFLICT") => Ok(ErrorCategory::Conflict),
+            Some("NOT_FOUND") => Ok(ErrorCategory::NotFound),
+            Some("FORBIDDEN") => Ok(ErrorCategory::Forbidden),
+            Some("INTERNAL") => Ok(ErrorCategory::Internal),
+            _ => Err(anyhow!(AuditLogMapperError::UnknownErrorCategory(
+                error_category
+            ))),
+        }
+    }
+
+    fn map_to_error_category_name(&self, error_category: ErrorCategory) -> &'static str {
+        match error_category {
+            ErrorCategory::Rejected => "REJECTED",
+            ErrorCategory::Conflict => "CONFLICT",
+            ErrorCategory::NotFound => "NOT_FOUND",
+            ErrorCategory::Forbidden => "FORBIDDEN",
+            ErrorCategory::Internal => "INTERNAL",
+        }
+    }
+}
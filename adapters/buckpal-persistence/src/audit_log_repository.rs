@@ -0,0 +1,107 @@
+use crate::audit_log_entity::AuditLogEntity;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct AuditLogRepository {
+    pool: PgPool,
+}
+
+impl AuditLogRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn insert(&self, entity: &AuditLogEntity) -> Result<AuditLogEntity> {
+        let inserted = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            audit_log (occurred_at, action, details, actor, source_ip, request_id, outcome, error_category)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING 
+                            id
+            "#,
+            entity.occurred_at,
+            entity.action,
+            entity.details,
+            entity.actor,
+            entity.source_ip,
+            entity.request_id,
+            entity.outcome,
+            entity.error_category
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let mut entity = entity.clone();
+        entity.id = Some(inserted.id);
+
+        Ok(entity)
+    }
+
+    pub async fn find(
+        &self,
+        actor: Option<&str>,
+        action: Option<&str>,
+        from: Option<DateTime<Utc>>,
+        to: Option<DateTime<Utc>>,
+        before_id: Option<i32>,
+        limit: i64,
+    ) -> Result<Vec<AuditLogEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        occurred_at,
+                        action,
+                        details,
+                        actor,
+                        source_ip,
+                        request_id,
+                        outcome,
+                        error_category
+                FROM 
+                        audit_log
+                WHERE 
+                        ($1::TEXT IS NULL OR actor = $1)
+                AND
+                        ($2::TEXT IS NULL OR action = $2)
+                AND
+                        ($3::TIMESTAMPTZ IS NULL OR occurred_at >= $3)
+                AND
+                        ($4::TIMESTAMPTZ IS NULL OR occurred_at < $4)
+                AND
+                        ($5::INT IS NULL OR id < $5)
+                ORDER BY
+                        id DESC
+                LIMIT 
+                        $6
+            "#,
+            actor,
+            action,
+            from,
+            to,
+            before_id,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| AuditLogEntity {
+                id: Some(entity.id),
+                occurred_at: entity.occurred_at,
+                action: entity.action,
+                details: entity.details,
+                actor: entity.actor,
+                source_ip: entity.source_ip,
+                request_id: entity.request_id,
+                outcome: entity.outcome,
+                error_category: entity.error_category,
+            })
+            .collect())
+    }
+}
@@ -0,0 +1,142 @@
+use crate::audit_log_mapper::AuditLogMapper;
+use crate::audit_log_repository::AuditLogRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::incoming::list_audit_log_query::AuditLogFilter;
+use buckpal_application::application::port::outgoing::audit_port::AuditPort;
+use buckpal_application::domain::audit::{AuditEntry, AuditEntryId};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct AuditPersistenceAdapter {
+    audit_log_repository: AuditLogRepository,
+    audit_log_mapper: AuditLogMapper,
+}
+
+impl AuditPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            audit_log_repository: AuditLogRepository::new(pool),
+            audit_log_mapper: AuditLogMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditPort for AuditPersistenceAdapter {
+    async fn record(&self, entry: &AuditEntry) -> Result<AuditEntry> {
+        let entity = self
+            .audit_log_repository
+            .insert(&self.audit_log_mapper.map_to_entity(entry))
+            .await?;
+
+        self.audit_log_mapper.map_to_entry(entity)
+    }
+
+    async fn load_entries(
+        &self,
+        filter: &AuditLogFilter,
+        before: Option<&AuditEntryId>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>> {
+        let actor = filter.actor.as_ref().map(ToString::to_string);
+        let entities = self
+            .audit_log_repository
+            .find(
+                actor.as_deref(),
+                filter.action.as_deref(),
+                filter.from,
+                filter.to,
+                before.map(|id| id.0),
+                limit,
+            )
+            .await?;
+
+        entities
+            .into_iter()
+            .map(|entity| self.audit_log_mapper.map_to_entry(entity))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditPersistenceAdapter;
+    use buckpal_application::application::port::incoming::list_audit_log_query::AuditLogFilter;
+    use buckpal_application::application::port::outgoing::audit_port::AuditPort;
+    use buckpal_application::domain::audit::{
+        AuditContext, AuditEntry, AuditOutcome, ErrorCategory,
+    };
+    use buckpal_application::domain::principal::Principal;
+    use chrono::{SubsecRound, Utc};
+    use sqlx::postgres::PgPoolOptions;
+
+    #[async_std::test]
+    async fn records_and_loads_entries() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = AuditPersistenceAdapter::new(pool.clone());
+
+        // entries can't be deleted afterwards, an action of its own keeps the test apart from
+        // earlier runs
+        let action = format!("test_{}", Utc::now().timestamp_nanos());
+        // timestamps are persisted to the microsecond
+        let occurred_at = Utc::now().trunc_subsecs(6);
+        let context = AuditContext::new(
+            Some(Principal::User(String::from("auditor"))),
+            Some(String::from("127.0.0.1")),
+            Some(String::from("req-1")),
+        );
+
+        let succeeded = adapter
+            .record(&AuditEntry::new(
+                occurred_at,
+                &action,
+                "account=42",
+                &context,
+                AuditOutcome::Succeeded,
+            ))
+            .await
+            .unwrap();
+        let failed = adapter
+            .record(&AuditEntry::new(
+                occurred_at,
+                &action,
+                "account=41",
+                &context,
+                AuditOutcome::Failed(ErrorCategory::NotFound),
+            ))
+            .await
+            .unwrap();
+
+        let filter = AuditLogFilter {
+            action: Some(action.clone()),
+            ..AuditLogFilter::default()
+        };
+        let entries = adapter.load_entries(&filter, None, 10).await.unwrap();
+        let older_entries = adapter
+            .load_entries(&filter, failed.id.as_ref(), 10)
+            .await
+            .unwrap();
+
+        let rejected_update = sqlx::query!(
+            r#"
+                UPDATE audit_log SET details = '' WHERE id = $1
+            "#,
+            failed.id.clone().unwrap().0,
+        )
+        .execute(&pool)
+        .await;
+
+        assert_eq!(entries, vec![failed, succeeded.clone()]);
+        assert_eq!(older_entries, vec![succeeded]);
+        assert!(rejected_update.is_err());
+    }
+}
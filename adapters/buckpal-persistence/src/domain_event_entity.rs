@@ -0,0 +1,14 @@
+use chrono::{DateTime, Utc};
+
+/// The columns a domain event is stored in wherever it is kept around.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct DomainEventEntity {
+    /// The account the event belongs to, the source account for events of a transfer as a whole.
+    pub account_id: i32,
+    pub event_type: String,
+    pub transfer_id: i32,
+    pub source_account_id: i32,
+    pub target_account_id: i32,
+    pub amount: i64,
+    pub timestamp: DateTime<Utc>,
+}
@@ -0,0 +1,115 @@
+use crate::domain_event_entity::DomainEventEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::domain_event::DomainEvent;
+use buckpal_application::domain::transfer::TransferId;
+use rusty_money::{money, Money};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DomainEventMapperError {
+    #[error("Unknown event type `{0}`")]
+    UnknownEventType(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct DomainEventMapper {}
+
+impl DomainEventMapper {
+    pub fn map_to_domain_event(&self, entity: &DomainEventEntity) -> Result<DomainEvent> {
+        let transfer_id = TransferId(entity.transfer_id);
+        let source_account_id = AccountId(entity.source_account_id);
+        let target_account_id = AccountId(entity.target_account_id);
+        let money = money!(entity.amount, "AUD");
+        let timestamp = entity.timestamp;
+
+        match entity.event_type.as_str() {
+            "MoneyWithdrawn" => Ok(DomainEvent::MoneyWithdrawn {
+                account_id: source_account_id,
+                target_account_id,
+                transfer_id,
+                money,
+                timestamp,
+            }),
+            "MoneyDeposited" => Ok(DomainEvent::MoneyDeposited {
+                account_id: target_account_id,
+                source_account_id,
+                transfer_id,
+                money,
+                timestamp,
+            }),
+            "TransferCompleted" => Ok(DomainEvent::TransferCompleted {
+                transfer_id,
+                source_account_id,
+                target_account_id,
+                money,
+                timestamp,
+            }),
+            event_type => Err(anyhow!(DomainEventMapperError::UnknownEventType(
+                String::from(event_type)
+            ))),
+        }
+    }
+
+    /// Maps the event to the columns it is stored in. Events of a transfer as a whole belong to
+    /// its source account.
+    pub fn map_to_entity(&self, event: &DomainEvent) -> DomainEventEntity {
+        use rust_decimal::prelude::*;
+
+        let (account_id, transfer_id, source_account_id, target_account_id, money, timestamp) =
+            match event {
+                DomainEvent::MoneyWithdrawn {
+                    account_id,
+                    target_account_id,
+                    transfer_id,
+                    money,
+                    timestamp,
+                } => (
+                    account_id,
+                    transfer_id,
+                    account_id,
+                    target_account_id,
+                    money,
+                    timestamp,
+                ),
+                DomainEvent::MoneyDeposited {
+                    account_id,
+                    source_account_id,
+                    transfer_id,
+                    money,
+                    timestamp,
+                } => (
+                    account_id,
+                    transfer_id,
+                    source_account_id,
+                    account_id,
+                    money,
+                    timestamp,
+                ),
+                DomainEvent::TransferCompleted {
+                    transfer_id,
+                    source_account_id,
+                    target_account_id,
+                    money,
+                    timestamp,
+                } => (
+                    source_account_id,
+                    transfer_id,
+                    source_account_id,
+                    target_account_id,
+                    money,
+                    timestamp,
+                ),
+            };
+
+        DomainEventEntity {
+            account_id: account_id.0,
+            event_type: String::from(event.name()),
+            transfer_id: transfer_id.0,
+            source_account_id: source_account_id.0,
+            target_account_id: target_account_id.0,
+            amount: money.amount().to_i64().unwrap(),
+            timestamp: *timestamp,
+        }
+    }
+}
@@ -0,0 +1,812 @@
+use crate::account_balance_mapper::AccountBalanceMapper;
+use crate::account_balance_repository::AccountBalanceRepository;
+use crate::account_event_entity::{AccountEventEntity, AccountSnapshotEntity};
+use crate::account_event_mapper::AccountEventMapper;
+use crate::account_event_repository::AccountEventRepository;
+use crate::account_mapper::AccountMapper;
+use crate::account_repository::AccountRepository;
+use crate::activity_chain_repository::ActivityChainRepository;
+use crate::activity_repository::ActivityRepository;
+use crate::domain_event_mapper::DomainEventMapper;
+use crate::hold_mapper::HoldMapper;
+use crate::hold_repository::HoldRepository;
+use crate::outbox_repository::OutboxRepository;
+use crate::transfer_mapper::TransferMapper;
+use crate::transfer_repository::TransferRepository;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::{
+    load_account_port::LoadAccountPort, record_transfer_port::RecordTransferPort,
+    update_account_state_port::UpdateAccountStatePort,
+};
+use buckpal_application::domain::account::{Account, AccountError, AccountId};
+use buckpal_application::domain::activity::Activity;
+use buckpal_application::domain::activity_chain::hash_activity;
+use buckpal_application::domain::domain_event::DomainEvent;
+use buckpal_application::domain::hold::{Hold, HoldStatus};
+use buckpal_application::domain::transfer::{Transfer, TransferId};
+use chrono::{DateTime, Utc};
+use rusty_money::{money, Money};
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+use thiserror::Error;
+
+/// The number of events after which a snapshot of the balance is taken by default.
+const DEFAULT_SNAPSHOT_INTERVAL: i32 = 100;
+
+#[derive(Error, Debug)]
+pub enum EventSourcedAccountError {
+    #[error("Account id is missing, can't append to its stream")]
+    MissingAccountId,
+    #[error("Account `{account_id}` was modified concurrently, its stream is past version `{expected_version}`")]
+    ConcurrentModification {
+        account_id: i32,
+        expected_version: i32,
+    },
+}
+
+/// Keeps accounts as streams of their withdrawals and deposits, which accounts are rebuilt from.
+/// Appending is optimistic, it fails if the stream moved on since the account was loaded.
+/// The activity list and the balance are projected from the events in the same transaction, so
+/// reading activities and statements works the same as with `AccountPersistenceAdapter`.
+#[derive(Debug, Clone)]
+pub struct EventSourcedAccountPersistenceAdapter {
+    account_repository: AccountRepository,
+    account_balance_repository: AccountBalanceRepository,
+    activity_chain_repository: ActivityChainRepository,
+    account_event_repository: AccountEventRepository,
+    activity_repository: ActivityRepository,
+    hold_repository: HoldRepository,
+    outbox_repository: OutboxRepository,
+    transfer_repository: TransferRepository,
+    account_mapper: AccountMapper,
+    account_balance_mapper: AccountBalanceMapper,
+    account_event_mapper: AccountEventMapper,
+    hold_mapper: HoldMapper,
+    domain_event_mapper: DomainEventMapper,
+    transfer_mapper: TransferMapper,
+    snapshot_interval: i32,
+}
+
+impl EventSourcedAccountPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            account_repository: AccountRepository::new(pool.clone()),
+            account_balance_repository: AccountBalanceRepository::new(pool.clone()),
+            activity_chain_repository: ActivityChainRepository::new(pool.clone()),
+            account_event_repository: AccountEventRepository::new(pool.clone()),
+            activity_repository: ActivityRepository::new(pool.clone()),
+            hold_repository: HoldRepository::new(pool.clone()),
+            outbox_repository: OutboxRepository::new(pool.clone()),
+            transfer_repository: TransferRepository::new(pool),
+            account_mapper: AccountMapper::default(),
+            account_balance_mapper: AccountBalanceMapper::default(),
+            account_event_mapper: AccountEventMapper::default(),
+            hold_mapper: HoldMapper::default(),
+            domain_event_mapper: DomainEventMapper::default(),
+            transfer_mapper: TransferMapper::default(),
+            snapshot_interval: DEFAULT_SNAPSHOT_INTERVAL,
+        }
+    }
+
+    /// Takes a snapshot every time the stream of an account grows by the given number of events.
+    pub fn with_snapshot_interval(mut self, snapshot_interval: i32) -> Self {
+        self.snapshot_interval = snapshot_interval.max(1);
+        self
+    }
+
+    /// Finds the balance of the account before the given point in time that the events after it
+    /// are replayed onto: the projected balance if there is no later event, otherwise the
+    /// latest snapshot before it. Timestamps needn't grow with the version, which is why both
+    /// keep the latest timestamp of the events they include rather than that of the last one.
+    async fn find_starting_point(
+        &self,
+        account_id: i32,
+        baseline_date: &DateTime<Utc>,
+    ) -> Result<Option<AccountSnapshotEntity>> {
+        if let Some(balance) = self
+            .account_event_repository
+            .find_balance(account_id)
+            .await?
+        {
+            if balance.last_event_at < *baseline_date {
+                return Ok(Some(balance));
+            }
+        }
+
+        self.account_event_repository
+            .find_latest_snapshot_before(account_id, baseline_date)
+            .await
+    }
+
+    /// Appends the new events of the account to its stream and projects them onto its
+    /// activities, chain and balance, all as part of the given transaction.
+    async fn save_activities_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account: &Account,
+    ) -> Result<Vec<Activity>> {
+        use rust_decimal::prelude::*;
+
+        let account_id = account
+            .id
+            .clone()
+            .ok_or_else(|| anyhow!(EventSourcedAccountError::MissingAccountId))?;
+
+        let mut version = account.version;
+        let mut last_event_at = None;
+        let mut activities: Vec<Activity> = vec![];
+        for event in &account.events {
+            let event_entity = self.domain_event_mapper.map_to_entity(event);
+
+            match event {
+                DomainEvent::MoneyWithdrawn { .. } | DomainEvent::MoneyDeposited { .. }
+                    if event_entity.account_id == account_id.0 =>
+                {
+                    let previous_hash = self
+                        .activity_chain_repository
+                        .lock_head_in(tx, event_entity.account_id)
+                        .await?;
+                    let activity_entity = self
+                        .activity_repository
+                        .save_in(
+                            tx,
+                            &self
+                                .account_event_mapper
+                                .map_to_activity_entity(&event_entity, None)
+                                .with_initiator(initiator_of(account, event_entity.transfer_id)),
+                        )
+                        .await?;
+                    let saved_activity = self.account_mapper.map_to_activity(&activity_entity);
+                    self.activity_chain_repository
+                        .link_in(
+                            tx,
+                            &activity_entity,
+                            &previous_hash,
+                            &hash_activity(&previous_hash, &saved_activity),
+                        )
+                        .await?;
+                    self.account_balance_repository
+                        .apply_in(
+                            tx,
+                            activity_entity.owner_account_id,
+                            self.account_balance_mapper
+                                .map_to_balance_change(&activity_entity),
+                            activity_entity
+                                .id
+                                .expect("expected saved activity to have an ID"),
+                        )
+                        .await?;
+
+                    version += 1;
+                    let appended = self
+                        .account_event_repository
+                        .append_in(
+                            tx,
+                            &AccountEventEntity::new(
+                                version,
+                                event_entity.clone(),
+                                activity_entity.id,
+                            ),
+                        )
+                        .await?;
+                    if !appended {
+                        return Err(anyhow!(EventSourcedAccountError::ConcurrentModification {
+                            account_id: account_id.0,
+                            expected_version: account.version,
+                        }));
+                    }
+
+                    last_event_at =
+                        Some(last_event_at.map_or(
+                            event_entity.timestamp,
+                            |last_event_at: DateTime<Utc>| {
+                                last_event_at.max(event_entity.timestamp)
+                            },
+                        ));
+                    activities.push(saved_activity);
+                }
+                _ => {}
+            }
+
+            self.outbox_repository.save_in(tx, &event_entity).await?;
+        }
+
+        if let Some(last_event_at) = last_event_at {
+            let mut balance = AccountSnapshotEntity::new(
+                account_id.0,
+                version,
+                // here we want to explode, no way to recover
+                account.calculate_balance().amount().to_i64().unwrap(),
+                last_event_at,
+            );
+
+            balance.last_event_at = self
+                .account_event_repository
+                .save_balance_in(tx, &balance)
+                .await?;
+
+            if version / self.snapshot_interval > account.version / self.snapshot_interval {
+                self.account_event_repository
+                    .save_snapshot_in(tx, &balance)
+                    .await?;
+            }
+        }
+
+        Ok(activities)
+    }
+
+    /// Moves the settled holds of the account out of their active status as part of the given
+    /// transaction, failing if any of them was settled in the meantime.
+    async fn claim_settled_holds_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        account: &Account,
+    ) -> Result<()> {
+        for hold in &account.holds {
+            if hold.id.is_some() && hold.status != HoldStatus::Active {
+                self.hold_repository
+                    .update_status_in(tx, &self.hold_mapper.map_to_entity(hold))
+                    .await?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LoadAccountPort for EventSourcedAccountPersistenceAdapter {
+    async fn load_account(
+        &self,
+        account_id: &AccountId,
+        baseline_date: &DateTime<Utc>,
+    ) -> Result<Account> {
+        let account_entity = self
+            .account_repository
+            .find_by_id(account_id.0)
+            .await
+            .map_err(|err| match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => {
+                    anyhow!(AccountError::AccountNotFound(account_id.0))
+                }
+                _ => err,
+            })?;
+
+        let starting_point = self
+            .find_starting_point(account_entity.id, baseline_date)
+            .await?;
+        let (mut version, mut baseline_balance) = starting_point.map_or((0, 0), |starting_point| {
+            (starting_point.version, starting_point.balance)
+        });
+
+        let events = self
+            .account_event_repository
+            .find_after_version(account_entity.id, version)
+            .await?;
+
+        let mut activities = vec![];
+        for entity in events {
+            version = entity.version;
+
+            if entity.event.timestamp < *baseline_date {
+                baseline_balance += self
+                    .account_event_mapper
+                    .map_to_balance_change(&entity.event);
+            } else {
+                activities.push(
+                    self.account_event_mapper
+                        .map_stored_to_activity_entity(&entity),
+                );
+            }
+        }
+
+        let holds = self
+            .hold_repository
+            .find_active_by_account(account_entity.id)
+            .await?;
+
+        let mut account = Account::new_with_id(
+            AccountId(account_entity.id),
+            money!(baseline_balance, "AUD"),
+            self.account_mapper.map_to_activity_window(activities),
+        );
+        account.version = version;
+        account.holds = holds
+            .into_iter()
+            .map(|hold| self.hold_mapper.map_to_domain_entity(hold))
+            .collect::<Result<Vec<Hold>>>()?;
+
+        Ok(account)
+    }
+}
+
+/// Events don't say who initiated them, the activity of the account for the same transfer that
+/// is yet to be saved does.
+fn initiator_of(account: &Account, transfer_id: i32) -> Option<String> {
+    account
+        .activity_window
+        .activities
+        .iter()
+        .find(|activity| {
+            activity.id.is_none()
+                && activity.transfer_id.as_ref().map(|id| id.0) == Some(transfer_id)
+        })
+        .and_then(|activity| activity.initiator.as_ref())
+        .map(|initiator| initiator.to_string())
+}
+
+#[async_trait]
+impl UpdateAccountStatePort for EventSourcedAccountPersistenceAdapter {
+    async fn update_activities(&self, account: &Account) -> Result<Vec<Activity>> {
+        // the transaction is rolled back when dropped, should any append fail
+        let mut tx = self.account_event_repository.begin().await?;
+
+        let activities = self.save_activities_in(&mut tx, account).await?;
+
+        tx.commit().await?;
+
+        Ok(activities)
+    }
+
+    async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>> {
+        // holds reserve money without moving it, so they are kept outside of the stream
+        let mut holds: Vec<Hold> = vec![];
+        for hold in &account.holds {
+            let hold_entity = self.hold_mapper.map_to_entity(hold);
+
+            if hold.id.is_none() {
+                let hold_entity = self.hold_repository.save(&hold_entity).await?;
+                holds.push(self.hold_mapper.map_to_domain_entity(hold_entity)?);
+            } else if hold.status != HoldStatus::Active {
+                self.hold_repository.update_status(&hold_entity).await?;
+            }
+        }
+
+        Ok(holds)
+    }
+}
+
+#[async_trait]
+impl RecordTransferPort for EventSourcedAccountPersistenceAdapter {
+    async fn reserve_transfer_id(&self) -> Result<TransferId> {
+        Ok(TransferId(self.transfer_repository.reserve_id().await?))
+    }
+
+    async fn record_transfer(
+        &self,
+        transfer: &Transfer,
+        source_account: &Account,
+        target_account: &Account,
+    ) -> Result<Transfer> {
+        // a stale stream of either account rolls back the transfer along with the other leg
+        let mut tx = self.account_event_repository.begin().await?;
+
+        // the legs refer to the transfer, it has to be there first
+        let transfer_entity = self
+            .transfer_repository
+            .save_reserved_in(&mut tx, &self.transfer_mapper.map_to_entity(transfer))
+            .await?;
+
+        // the chain heads of both accounts are locked in the order of their ids, so that
+        // transfers between the same accounts in opposite directions don't deadlock
+        let mut accounts = [source_account, target_account];
+        accounts.sort_by_key(|account| account.id.as_ref().map(|id| id.0));
+
+        // the holds settled by the transfer are claimed before any money moves, a hold settled
+        // concurrently is no longer active and rolls the transfer back
+        for account in accounts.iter() {
+            self.claim_settled_holds_in(&mut tx, account).await?;
+        }
+        for account in accounts.iter() {
+            self.save_activities_in(&mut tx, account).await?;
+        }
+
+        for event in &transfer.events {
+            self.outbox_repository
+                .save_in(&mut tx, &self.domain_event_mapper.map_to_entity(event))
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.transfer_mapper.map_to_domain_entity(transfer_entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EventSourcedAccountError, EventSourcedAccountPersistenceAdapter};
+    use crate::account_event_repository::AccountEventRepository;
+    use anyhow::Result;
+    use buckpal_application::application::port::outgoing::{
+        load_account_port::LoadAccountPort, record_transfer_port::RecordTransferPort,
+        update_account_state_port::UpdateAccountStatePort,
+    };
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::transfer::{Transfer, TransferId, TransferStatus};
+    use chrono::{DateTime, Duration, Utc};
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+
+    #[async_std::test]
+    async fn rebuilds_account_from_its_stream_and_rejects_stale_appends() {
+        let pool = given_a_pool().await;
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let adapter =
+            EventSourcedAccountPersistenceAdapter::new(pool.clone()).with_snapshot_interval(2);
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        let mut account = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+        account
+            .deposit(&money!(500, "AUD"), &AccountId(1), &TransferId(1), None)
+            .unwrap();
+        account
+            .deposit(&money!(300, "AUD"), &AccountId(1), &TransferId(2), None)
+            .unwrap();
+        let stale_account = account.clone();
+        adapter.update_activities(&account).await.unwrap();
+
+        let mut account = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+        account
+            .withdraw(&money!(200, "AUD"), &AccountId(1), &TransferId(3), None)
+            .unwrap();
+        adapter.update_activities(&account).await.unwrap();
+
+        let stale_append = adapter.update_activities(&stale_account).await;
+        let rebuilt = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+        let from_balance = adapter
+            .load_account(&account_id, &(Utc::now() + Duration::days(1)))
+            .await
+            .unwrap();
+
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+
+        assert!(matches!(
+            stale_append
+                .unwrap_err()
+                .downcast_ref::<EventSourcedAccountError>(),
+            Some(EventSourcedAccountError::ConcurrentModification { .. })
+        ));
+        assert_eq!(rebuilt.version, 3);
+        assert_eq!(rebuilt.activity_window.activities.len(), 3);
+        assert!(rebuilt
+            .activity_window
+            .activities
+            .iter()
+            .all(|activity| activity.id.is_some()));
+        assert_eq!(rebuilt.calculate_balance(), money!(600, "AUD"));
+        assert_eq!(from_balance.version, 3);
+        assert_eq!(from_balance.activity_window.activities.len(), 0);
+        assert_eq!(from_balance.calculate_balance(), money!(600, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn rebuilding_from_a_snapshot_equals_a_full_replay() {
+        let pool = given_a_pool().await;
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let adapter =
+            EventSourcedAccountPersistenceAdapter::new(pool.clone()).with_snapshot_interval(2);
+
+        let mut account = adapter
+            .load_account(&account_id, &Utc::now())
+            .await
+            .unwrap();
+        account
+            .deposit(&money!(500, "AUD"), &AccountId(1), &TransferId(1), None)
+            .unwrap();
+        account
+            .deposit(&money!(300, "AUD"), &AccountId(1), &TransferId(2), None)
+            .unwrap();
+        adapter.update_activities(&account).await.unwrap();
+
+        let baseline_date = a_moment_later().await;
+
+        let mut account = adapter
+            .load_account(&account_id, &Utc::now())
+            .await
+            .unwrap();
+        account
+            .withdraw(&money!(200, "AUD"), &AccountId(1), &TransferId(3), None)
+            .unwrap();
+        adapter.update_activities(&account).await.unwrap();
+
+        let snapshot = AccountEventRepository::new(pool.clone())
+            .find_latest_snapshot_before(account_id.0, &baseline_date)
+            .await
+            .unwrap();
+        let from_snapshot = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+        delete_snapshots_of_account_with_id(account_id.0, &pool)
+            .await
+            .unwrap();
+        let from_start = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+
+        assert_eq!(snapshot.map(|snapshot| snapshot.version), Some(2));
+        assert_eq!(from_snapshot.version, from_start.version);
+        assert_eq!(from_snapshot.baseline_balance, money!(800, "AUD"));
+        assert_eq!(from_snapshot.baseline_balance, from_start.baseline_balance);
+        assert_eq!(
+            from_snapshot.activity_window.activities,
+            from_start.activity_window.activities
+        );
+        assert_eq!(from_snapshot.activity_window.activities.len(), 1);
+        assert_eq!(from_snapshot.calculate_balance(), money!(600, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn baseline_before_the_projected_balance_replays_the_later_events() {
+        let pool = given_a_pool().await;
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let adapter = EventSourcedAccountPersistenceAdapter::new(pool.clone());
+
+        let mut account = adapter
+            .load_account(&account_id, &Utc::now())
+            .await
+            .unwrap();
+        account
+            .deposit(&money!(500, "AUD"), &AccountId(1), &TransferId(1), None)
+            .unwrap();
+        adapter.update_activities(&account).await.unwrap();
+
+        let baseline_date = a_moment_later().await;
+
+        let mut account = adapter
+            .load_account(&account_id, &Utc::now())
+            .await
+            .unwrap();
+        account
+            .withdraw(&money!(200, "AUD"), &AccountId(1), &TransferId(2), None)
+            .unwrap();
+        adapter.update_activities(&account).await.unwrap();
+
+        let loaded = adapter
+            .load_account(&account_id, &baseline_date)
+            .await
+            .unwrap();
+
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+
+        assert_eq!(loaded.version, 2);
+        assert_eq!(loaded.baseline_balance, money!(500, "AUD"));
+        assert_eq!(loaded.activity_window.activities.len(), 1);
+        assert_eq!(
+            loaded.activity_window.activities[0].money,
+            money!(200, "AUD")
+        );
+        assert_eq!(loaded.calculate_balance(), money!(300, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn records_both_legs_of_a_transfer_or_neither() {
+        let pool = given_a_pool().await;
+        let source_account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let target_account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let adapter = EventSourcedAccountPersistenceAdapter::new(pool.clone());
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        let mut source_account = adapter
+            .load_account(&source_account_id, &baseline_date)
+            .await
+            .unwrap();
+        source_account
+            .deposit(&money!(500, "AUD"), &AccountId(1), &TransferId(1), None)
+            .unwrap();
+        adapter.update_activities(&source_account).await.unwrap();
+
+        let mut source_account = adapter
+            .load_account(&source_account_id, &baseline_date)
+            .await
+            .unwrap();
+        let mut target_account = adapter
+            .load_account(&target_account_id, &baseline_date)
+            .await
+            .unwrap();
+
+        // the stream of the target account moves on behind the transfer's back
+        let mut moved_on = target_account.clone();
+        moved_on
+            .deposit(&money!(100, "AUD"), &AccountId(1), &TransferId(1), None)
+            .unwrap();
+        adapter.update_activities(&moved_on).await.unwrap();
+
+        let transfer_id = adapter.reserve_transfer_id().await.unwrap();
+        let mut transfer = Transfer::new_with_id(
+            transfer_id.clone(),
+            source_account_id.clone(),
+            target_account_id.clone(),
+            Utc::now(),
+            money!(200, "AUD"),
+            TransferStatus::Pending,
+            None,
+            None,
+        );
+        source_account
+            .withdraw(&money!(200, "AUD"), &target_account_id, &transfer_id, None)
+            .unwrap();
+        target_account
+            .deposit(&money!(200, "AUD"), &source_account_id, &transfer_id, None)
+            .unwrap();
+        transfer.complete();
+
+        let stale_transfer = adapter
+            .record_transfer(&transfer, &source_account, &target_account)
+            .await;
+        let source_balance = adapter
+            .load_account(&source_account_id, &baseline_date)
+            .await
+            .unwrap()
+            .calculate_balance();
+        let recorded = count_transfer_rows(transfer_id.0, &pool).await.unwrap();
+
+        delete_account_with_id(source_account_id.0, &pool)
+            .await
+            .unwrap();
+        delete_account_with_id(target_account_id.0, &pool)
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            stale_transfer
+                .unwrap_err()
+                .downcast_ref::<EventSourcedAccountError>(),
+            Some(EventSourcedAccountError::ConcurrentModification { .. })
+        ));
+        assert_eq!(source_balance, money!(500, "AUD"));
+        assert_eq!(recorded, 0);
+    }
+
+    async fn given_a_pool() -> PgPool {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap()
+    }
+
+    /// A point in time after the events appended so far and before the next ones, which the
+    /// database stores with a precision of microseconds.
+    async fn a_moment_later() -> DateTime<Utc> {
+        async_std::task::sleep(std::time::Duration::from_millis(5)).await;
+        let moment = Utc::now();
+        async_std::task::sleep(std::time::Duration::from_millis(5)).await;
+
+        moment
+    }
+
+    /// Counts the transfer and its legs.
+    async fn count_transfer_rows(transfer_id: i32, pool: &PgPool) -> Result<i64> {
+        let counted = sqlx::query!(
+            r#"
+                SELECT 
+                        (SELECT COUNT(*) FROM transfer WHERE id = $1)
+                        + (SELECT COUNT(*) FROM activity WHERE transfer_id = $1) AS "count!"
+            "#,
+            transfer_id,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(counted.count)
+    }
+
+    async fn delete_snapshots_of_account_with_id(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account_snapshot WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn given_an_account(pool: &PgPool) -> Result<i32> {
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO account DEFAULT VALUES RETURNING id
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entity.id)
+    }
+
+    async fn delete_account_with_id(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account_event WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account_snapshot WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account_ledger_balance WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account_balance WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM activity_chain_head WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM activity WHERE owner_account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM outbox WHERE account_id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        sqlx::query!(
+            r#"
+                DELETE FROM account WHERE id = $1
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
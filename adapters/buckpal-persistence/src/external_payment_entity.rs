@@ -0,0 +1,48 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// A transfer to an external account joined with the details of that account.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalPaymentEntity {
+    pub transfer_id: i32,
+    pub source_account_id: i32,
+    pub amount: i64,
+    pub reference: Option<String>,
+    pub timestamp: DateTime<Utc>,
+    pub account_id: i32,
+    pub bsb: String,
+    pub account_number: String,
+    pub account_name: String,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalPaymentBatchEntity {
+    pub id: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub processing_date: NaiveDate,
+    pub record_count: i64,
+    pub credit_total: i64,
+    pub debit_total: i64,
+    pub contents: String,
+}
+
+impl ExternalPaymentBatchEntity {
+    pub fn new(
+        id: Option<i32>,
+        created_at: DateTime<Utc>,
+        processing_date: NaiveDate,
+        record_count: i64,
+        credit_total: i64,
+        debit_total: i64,
+        contents: String,
+    ) -> Self {
+        Self {
+            id,
+            created_at,
+            processing_date,
+            record_count,
+            credit_total,
+            debit_total,
+            contents,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use crate::external_payment_entity::{ExternalPaymentBatchEntity, ExternalPaymentEntity};
+use anyhow::Result;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::external_account::ExternalAccount;
+use buckpal_application::domain::external_payment::{
+    ExternalPayment, ExternalPaymentBatch, ExternalPaymentBatchId,
+};
+use buckpal_application::domain::transfer::TransferId;
+use rusty_money::{money, Money};
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ExternalPaymentMapper {}
+
+impl ExternalPaymentMapper {
+    pub fn map_to_domain_entity(&self, payment: ExternalPaymentEntity) -> Result<ExternalPayment> {
+        Ok(ExternalPayment {
+            transfer_id: TransferId(payment.transfer_id),
+            source_account_id: AccountId(payment.source_account_id),
+            external_account: ExternalAccount::new(
+                AccountId(payment.account_id),
+                &payment.bsb,
+                &payment.account_number,
+                &payment.account_name,
+            )?,
+            money: money!(payment.amount, "AUD"),
+            reference: payment.reference,
+            timestamp: payment.timestamp,
+        })
+    }
+
+    pub fn map_to_batch_entity(&self, batch: &ExternalPaymentBatch) -> ExternalPaymentBatchEntity {
+        ExternalPaymentBatchEntity::new(
+            batch.id.clone().map(|id| id.0),
+            batch.created_at,
+            batch.processing_date,
+            batch.record_count,
+            batch.credit_total,
+            batch.debit_total,
+            batch.contents.clone(),
+        )
+    }
+
+    pub fn map_to_transfer_ids(&self, batch: &ExternalPaymentBatch) -> Vec<i32> {
+        batch.transfer_ids.iter().map(|id| id.0).collect()
+    }
+
+    pub fn map_to_batch(
+        &self,
+        batch: ExternalPaymentBatchEntity,
+        transfer_ids: Vec<i32>,
+    ) -> ExternalPaymentBatch {
+        ExternalPaymentBatch {
+            id: batch.id.map(ExternalPaymentBatchId),
+            created_at: batch.created_at,
+            processing_date: batch.processing_date,
+            transfer_ids: transfer_ids.into_iter().map(TransferId).collect(),
+            record_count: batch.record_count,
+            credit_total: batch.credit_total,
+            debit_total: batch.debit_total,
+            contents: batch.contents,
+        }
+    }
+}
@@ -0,0 +1,50 @@
+use crate::external_payment_mapper::ExternalPaymentMapper;
+use crate::external_payment_repository::ExternalPaymentRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::external_payment_port::ExternalPaymentPort;
+use buckpal_application::domain::external_payment::{ExternalPayment, ExternalPaymentBatch};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct ExternalPaymentPersistenceAdapter {
+    external_payment_repository: ExternalPaymentRepository,
+    external_payment_mapper: ExternalPaymentMapper,
+}
+
+impl ExternalPaymentPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            external_payment_repository: ExternalPaymentRepository::new(pool),
+            external_payment_mapper: ExternalPaymentMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ExternalPaymentPort for ExternalPaymentPersistenceAdapter {
+    async fn load_unbatched_payments(&self) -> Result<Vec<ExternalPayment>> {
+        self.external_payment_repository
+            .find_unbatched()
+            .await?
+            .into_iter()
+            .map(|payment| self.external_payment_mapper.map_to_domain_entity(payment))
+            .collect()
+    }
+
+    async fn save_batch(&self, batch: &ExternalPaymentBatch) -> Result<ExternalPaymentBatch> {
+        let transfer_ids = self.external_payment_mapper.map_to_transfer_ids(batch);
+
+        let saved = self
+            .external_payment_repository
+            .save_batch(
+                &self.external_payment_mapper.map_to_batch_entity(batch),
+                &transfer_ids,
+            )
+            .await?;
+
+        Ok(self
+            .external_payment_mapper
+            .map_to_batch(saved, transfer_ids))
+    }
+}
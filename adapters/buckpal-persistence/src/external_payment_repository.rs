@@ -0,0 +1,130 @@
+use crate::external_payment_entity::{ExternalPaymentBatchEntity, ExternalPaymentEntity};
+use anyhow::{anyhow, Result};
+use sqlx::postgres::PgPool;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum ExternalPaymentRepositoryError {
+    #[error("External payment batch already has an id `{0}`, skipping insert")]
+    AlreadyHasAnIdException(i32),
+}
+
+#[derive(Debug, Clone)]
+pub struct ExternalPaymentRepository {
+    pool: PgPool,
+}
+
+impl ExternalPaymentRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Finds the completed transfers to external accounts that are not part of any batch yet.
+    pub async fn find_unbatched(&self) -> Result<Vec<ExternalPaymentEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT
+                        transfer.id,
+                        transfer.source_account_id,
+                        transfer.amount,
+                        transfer.reference,
+                        transfer.timestamp,
+                        external_account.account_id,
+                        external_account.bsb,
+                        external_account.account_number,
+                        external_account.account_name
+                FROM
+                        transfer
+                JOIN
+                        external_account ON external_account.account_id = transfer.target_account_id
+                LEFT JOIN
+                        external_payment ON external_payment.transfer_id = transfer.id
+                WHERE
+                        transfer.status = 'COMPLETED'
+                AND
+                        external_payment.transfer_id IS NULL
+                ORDER BY
+                        transfer.timestamp, transfer.id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| ExternalPaymentEntity {
+                transfer_id: entity.id,
+                source_account_id: entity.source_account_id,
+                amount: entity.amount,
+                reference: entity.reference,
+                timestamp: entity.timestamp,
+                account_id: entity.account_id,
+                bsb: entity.bsb,
+                account_number: entity.account_number,
+                account_name: entity.account_name,
+            })
+            .collect())
+    }
+
+    /// Inserts the batch together with the transfers it pays out in one transaction, which
+    /// fails as a whole if any of them is part of another batch already.
+    pub async fn save_batch(
+        &self,
+        batch_entity: &ExternalPaymentBatchEntity,
+        transfer_ids: &[i32],
+    ) -> Result<ExternalPaymentBatchEntity> {
+        if let Some(batch_id) = batch_entity.id {
+            return Err(anyhow!(
+                ExternalPaymentRepositoryError::AlreadyHasAnIdException(batch_id)
+            ));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            external_payment_batch (created_at, processing_date, record_count, credit_total, debit_total, contents)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6)
+                RETURNING 
+                            id, created_at, processing_date, record_count, credit_total, debit_total, contents 
+            "#,
+            batch_entity.created_at,
+            batch_entity.processing_date,
+            batch_entity.record_count,
+            batch_entity.credit_total,
+            batch_entity.debit_total,
+            batch_entity.contents
+        )
+        .fetch_one(&mut tx)
+        .await?;
+
+        for transfer_id in transfer_ids {
+            sqlx::query!(
+                r#"
+                    INSERT INTO 
+                                external_payment (transfer_id, batch_id)
+                    VALUES 
+                                ($1, $2)
+                "#,
+                *transfer_id,
+                entity.id
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(ExternalPaymentBatchEntity::new(
+            Some(entity.id),
+            entity.created_at,
+            entity.processing_date,
+            entity.record_count,
+            entity.credit_total,
+            entity.debit_total,
+            entity.contents,
+        ))
+    }
+}
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HoldEntity {
+    pub id: Option<i32>,
+    pub account_id: i32,
+    pub target_account_id: i32,
+    pub amount: i64,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub status: String,
+    pub transfer_id: Option<i32>,
+}
+
+impl HoldEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<i32>,
+        account_id: i32,
+        target_account_id: i32,
+        amount: i64,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        status: String,
+        transfer_id: Option<i32>,
+    ) -> Self {
+        Self {
+            id,
+            account_id,
+            target_account_id,
+            amount,
+            created_at,
+            expires_at,
+            status,
+            transfer_id,
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use crate::hold_entity::HoldEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::hold::{Hold, HoldId, HoldStatus};
+use buckpal_application::domain::transfer::TransferId;
+use rusty_money::{money, Money};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HoldMapperError {
+    #[error("Unknown hold status `{0}`")]
+    UnknownStatus(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct HoldMapper {}
+
+impl HoldMapper {
+    pub fn map_to_domain_entity(&self, hold: HoldEntity) -> Result<Hold> {
+        let status = self.map_to_status(&hold.status)?;
+
+        Ok(Hold::new_with_id(
+            HoldId(hold.id.expect("expected persisted hold to have an ID")),
+            AccountId(hold.account_id),
+            AccountId(hold.target_account_id),
+            money!(hold.amount, "AUD"),
+            hold.created_at,
+            hold.expires_at,
+            status,
+            hold.transfer_id.map(TransferId),
+        ))
+    }
+
+    pub fn map_to_entity(&self, hold: &Hold) -> HoldEntity {
+        use rust_decimal::prelude::*;
+
+        HoldEntity::new(
+            hold.id.clone().map(|id| id.0),
+            hold.account_id.0,
+            hold.target_account_id.0,
+            // here we want to explode, no way to recover
+            hold.money.amount().to_i64().unwrap(),
+            hold.created_at,
+            hold.expires_at,
+            String::from(self.map_from_status(hold.status)),
+            hold.transfer_id.clone().map(|id| id.0),
+        )
+    }
+
+    fn map_to_status(&self, status: &str) -> Result<HoldStatus> {
+        match status {
+            "ACTIVE" => Ok(HoldStatus::Active),
+            "CAPTURED" => Ok(HoldStatus::Captured),
+            "VOIDED" => Ok(HoldStatus::Voided),
+            "EXPIRED" => Ok(HoldStatus::Expired),
+            other => Err(anyhow!(HoldMapperError::UnknownStatus(String::from(other)))),
+        }
+    }
+
+    fn map_from_status(&self, status: HoldStatus) -> &'static str {
+        match status {
+            HoldStatus::Active => "ACTIVE",
+            HoldStatus::Captured => "CAPTURED",
+            HoldStatus::Voided => "VOIDED",
+            HoldStatus::Expired => "EXPIRED",
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use crate::hold_entity::HoldEntity;
+use anyhow::{anyhow, Result};
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum HoldRepositoryError {
+    #[error("Hold already has an id `{0}`, skipping insert")]
+    AlreadyHasAnIdException(i32),
+    #[error("Hold has no id, skipping update")]
+    MissingIdException,
+    #[error("Hold `{0}` is no longer active")]
+    NotActiveException(i32),
+}
+
+#[derive(Debug, Clone)]
+pub struct HoldRepository {
+    pool: PgPool,
+}
+
+impl HoldRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn save(&self, hold_entity: &HoldEntity) -> Result<HoldEntity> {
+        match hold_entity.id {
+            Some(hold_id) => Err(anyhow!(HoldRepositoryError::AlreadyHasAnIdException(
+                hold_id
+            ))),
+            None => {
+                let entity = sqlx::query!(
+                    r#"
+                        INSERT INTO 
+                                    hold (account_id, target_account_id, amount, created_at, expires_at, status, transfer_id)
+                        VALUES 
+                                    ($1, $2, $3, $4, $5, $6, $7)
+                        RETURNING 
+                                    id, account_id, target_account_id, amount, created_at, expires_at, status, transfer_id 
+                    "#,
+                    hold_entity.account_id,
+                    hold_entity.target_account_id,
+                    hold_entity.amount,
+                    hold_entity.created_at,
+                    hold_entity.expires_at,
+                    hold_entity.status,
+                    hold_entity.transfer_id
+                )
+                .fetch_one(&self.pool)
+                .await?;
+
+                let entity = HoldEntity::new(
+                    Some(entity.id),
+                    entity.account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.created_at,
+                    entity.expires_at,
+                    entity.status,
+                    entity.transfer_id,
+                );
+
+                Ok(entity)
+            }
+        }
+    }
+
+    pub async fn update_status(&self, hold_entity: &HoldEntity) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        self.update_status_in(&mut tx, hold_entity).await?;
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    /// Updates the status as part of a larger transaction. The hold stays locked until the
+    /// transaction ends, a concurrent update of it waits and then finds it no longer active.
+    pub async fn update_status_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        hold_entity: &HoldEntity,
+    ) -> Result<()> {
+        let hold_id = hold_entity
+            .id
+            .ok_or_else(|| anyhow!(HoldRepositoryError::MissingIdException))?;
+
+        // only an active hold may change, so that a hold captured or voided concurrently isn't
+        // settled a second time
+        let updated = sqlx::query!(
+            r#"
+                UPDATE 
+                        hold
+                SET 
+                        status = $2,
+                        transfer_id = $3
+                WHERE 
+                        id = $1
+                AND
+                        status = 'ACTIVE'
+            "#,
+            hold_id,
+            hold_entity.status,
+            hold_entity.transfer_id
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            return Err(anyhow!(HoldRepositoryError::NotActiveException(hold_id)));
+        }
+
+        Ok(())
+    }
+
+    pub async fn find_by_id(&self, hold_id: i32) -> Result<HoldEntity> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        id,
+                        account_id,
+                        target_account_id,
+                        amount,
+                        created_at,
+                        expires_at,
+                        status,
+                        transfer_id
+                FROM 
+                        hold
+                WHERE 
+                        id = $1
+            "#,
+            hold_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let entity = HoldEntity::new(
+            Some(entity.id),
+            entity.account_id,
+            entity.target_account_id,
+            entity.amount,
+            entity.created_at,
+            entity.expires_at,
+            entity.status,
+            entity.transfer_id,
+        );
+
+        Ok(entity)
+    }
+
+    pub async fn find_active_by_account(&self, account_id: i32) -> Result<Vec<HoldEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT
+                        id,
+                        account_id,
+                        target_account_id,
+                        amount,
+                        created_at,
+                        expires_at,
+                        status,
+                        transfer_id
+                FROM 
+                        hold
+                WHERE 
+                        account_id = $1
+                AND
+                        status = 'ACTIVE'
+            "#,
+            account_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                HoldEntity::new(
+                    Some(entity.id),
+                    entity.account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.created_at,
+                    entity.expires_at,
+                    entity.status,
+                    entity.transfer_id,
+                )
+            })
+            .collect();
+
+        Ok(entities)
+    }
+}
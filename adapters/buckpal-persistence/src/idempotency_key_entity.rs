@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct IdempotencyKeyEntity {
+    pub key: String,
+    pub source_account_id: i32,
+    pub fingerprint: String,
+    pub status: String,
+    pub transfer_id: Option<i32>,
+    pub error_message: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyKeyEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        key: String,
+        source_account_id: i32,
+        fingerprint: String,
+        status: String,
+        transfer_id: Option<i32>,
+        error_message: Option<String>,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            key,
+            source_account_id,
+            fingerprint,
+            status,
+            transfer_id,
+            error_message,
+            created_at,
+            expires_at,
+        }
+    }
+}
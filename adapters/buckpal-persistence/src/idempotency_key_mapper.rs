@@ -0,0 +1,66 @@
+use crate::idempotency_key_entity::IdempotencyKeyEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::idempotency_record::{
+    IdempotencyKey, IdempotencyRecord, IdempotencyStatus,
+};
+use buckpal_application::domain::transfer::TransferId;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum IdempotencyKeyMapperError {
+    #[error("Unknown idempotency key status `{0}`")]
+    UnknownStatus(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct IdempotencyKeyMapper {}
+
+impl IdempotencyKeyMapper {
+    pub fn map_to_domain_entity(&self, entity: IdempotencyKeyEntity) -> Result<IdempotencyRecord> {
+        let status = self.map_to_status(&entity.status)?;
+
+        Ok(IdempotencyRecord::new_with_outcome(
+            IdempotencyKey(entity.key),
+            AccountId(entity.source_account_id),
+            entity.fingerprint,
+            status,
+            entity.transfer_id.map(TransferId),
+            entity.error_message,
+            entity.created_at,
+            entity.expires_at,
+        ))
+    }
+
+    pub fn map_to_entity(&self, record: &IdempotencyRecord) -> IdempotencyKeyEntity {
+        IdempotencyKeyEntity::new(
+            record.key.0.clone(),
+            record.source_account_id.0,
+            record.fingerprint.clone(),
+            String::from(self.map_from_status(record.status)),
+            record.transfer_id.clone().map(|id| id.0),
+            record.error_message.clone(),
+            record.created_at,
+            record.expires_at,
+        )
+    }
+
+    fn map_to_status(&self, status: &str) -> Result<IdempotencyStatus> {
+        match status {
+            "IN_PROGRESS" => Ok(IdempotencyStatus::InProgress),
+            "SUCCEEDED" => Ok(IdempotencyStatus::Succeeded),
+            "FAILED" => Ok(IdempotencyStatus::Failed),
+            other => Err(anyhow!(IdempotencyKeyMapperError::UnknownStatus(
+                String::from(other)
+            ))),
+        }
+    }
+
+    fn map_from_status(&self, status: IdempotencyStatus) -> &'static str {
+        match status {
+            IdempotencyStatus::InProgress => "IN_PROGRESS",
+            IdempotencyStatus::Succeeded => "SUCCEEDED",
+            IdempotencyStatus::Failed => "FAILED",
+        }
+    }
+}
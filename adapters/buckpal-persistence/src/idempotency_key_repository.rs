@@ -0,0 +1,128 @@
+use crate::idempotency_key_entity::IdempotencyKeyEntity;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyKeyRepository {
+    pool: PgPool,
+}
+
+impl IdempotencyKeyRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts the entity unless its key is already taken for its source account, returns whether
+    /// it was inserted.
+    pub async fn insert_if_absent(&self, entity: &IdempotencyKeyEntity) -> Result<bool> {
+        let inserted = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            idempotency_key (key, source_account_id, fingerprint, status, transfer_id, error_message, created_at, expires_at)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (source_account_id, key) DO NOTHING
+                RETURNING 
+                            key 
+            "#,
+            entity.key,
+            entity.source_account_id,
+            entity.fingerprint,
+            entity.status,
+            entity.transfer_id,
+            entity.error_message,
+            entity.created_at,
+            entity.expires_at
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(inserted.is_some())
+    }
+
+    pub async fn update_outcome(&self, entity: &IdempotencyKeyEntity) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        idempotency_key
+                SET 
+                        status = $3,
+                        transfer_id = $4,
+                        error_message = $5
+                WHERE 
+                        key = $1
+                AND
+                        source_account_id = $2
+            "#,
+            entity.key,
+            entity.source_account_id,
+            entity.status,
+            entity.transfer_id,
+            entity.error_message
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn find_by_key(
+        &self,
+        source_account_id: i32,
+        key: &str,
+    ) -> Result<IdempotencyKeyEntity> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        key,
+                        source_account_id,
+                        fingerprint,
+                        status,
+                        transfer_id,
+                        error_message,
+                        created_at,
+                        expires_at
+                FROM 
+                        idempotency_key
+                WHERE 
+                        key = $1
+                AND
+                        source_account_id = $2
+            "#,
+            key,
+            source_account_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let entity = IdempotencyKeyEntity::new(
+            entity.key,
+            entity.source_account_id,
+            entity.fingerprint,
+            entity.status,
+            entity.transfer_id,
+            entity.error_message,
+            entity.created_at,
+            entity.expires_at,
+        );
+
+        Ok(entity)
+    }
+
+    pub async fn delete_expired(&self, now: &DateTime<Utc>) -> Result<u64> {
+        let deleted = sqlx::query!(
+            r#"
+                DELETE FROM 
+                            idempotency_key
+                WHERE 
+                            expires_at <= $1
+            "#,
+            now
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(deleted.rows_affected())
+    }
+}
@@ -0,0 +1,172 @@
+use crate::idempotency_key_mapper::IdempotencyKeyMapper;
+use crate::idempotency_key_repository::IdempotencyKeyRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::idempotency_port::IdempotencyPort;
+use buckpal_application::domain::idempotency_record::IdempotencyRecord;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyPersistenceAdapter {
+    idempotency_key_repository: IdempotencyKeyRepository,
+    idempotency_key_mapper: IdempotencyKeyMapper,
+}
+
+impl IdempotencyPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            idempotency_key_repository: IdempotencyKeyRepository::new(pool),
+            idempotency_key_mapper: IdempotencyKeyMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl IdempotencyPort for IdempotencyPersistenceAdapter {
+    async fn claim_key(&self, record: &IdempotencyRecord) -> Result<Option<IdempotencyRecord>> {
+        let entity = self.idempotency_key_mapper.map_to_entity(record);
+
+        if self
+            .idempotency_key_repository
+            .insert_if_absent(&entity)
+            .await?
+        {
+            return Ok(None);
+        }
+
+        let existing = self
+            .idempotency_key_repository
+            .find_by_key(entity.source_account_id, &entity.key)
+            .await?;
+
+        Ok(Some(
+            self.idempotency_key_mapper.map_to_domain_entity(existing)?,
+        ))
+    }
+
+    async fn complete_key(&self, record: &IdempotencyRecord) -> Result<IdempotencyRecord> {
+        let entity = self.idempotency_key_mapper.map_to_entity(record);
+
+        self.idempotency_key_repository
+            .update_outcome(&entity)
+            .await?;
+
+        Ok(record.clone())
+    }
+
+    async fn remove_expired_keys(&self, now: &DateTime<Utc>) -> Result<u64> {
+        self.idempotency_key_repository.delete_expired(now).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdempotencyPersistenceAdapter;
+    use buckpal_application::application::port::outgoing::idempotency_port::IdempotencyPort;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::idempotency_record::{
+        IdempotencyKey, IdempotencyRecord, IdempotencyStatus,
+    };
+    use chrono::{Duration, Utc};
+    use sqlx::postgres::PgPoolOptions;
+
+    #[async_std::test]
+    async fn claims_key_only_once_until_it_expires() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = IdempotencyPersistenceAdapter::new(pool);
+
+        let now = Utc::now();
+        let key = IdempotencyKey(format!("test-{}", now.timestamp_nanos()));
+        let mut record = IdempotencyRecord::new_in_progress(
+            key.clone(),
+            AccountId(41),
+            String::from("send_money:41:42:500"),
+            now,
+            now + Duration::minutes(1),
+        );
+
+        let first_claim = adapter.claim_key(&record).await.unwrap();
+
+        record.fail(String::from("Insufficient funds"));
+        adapter.complete_key(&record).await.unwrap();
+
+        let second_claim = adapter.claim_key(&record).await.unwrap().unwrap();
+
+        adapter
+            .remove_expired_keys(&(now + Duration::minutes(2)))
+            .await
+            .unwrap();
+
+        let third_claim = adapter.claim_key(&record).await.unwrap();
+
+        adapter
+            .remove_expired_keys(&(now + Duration::minutes(2)))
+            .await
+            .unwrap();
+
+        assert_eq!(first_claim, None);
+        assert_eq!(second_claim.key, key);
+        assert_eq!(second_claim.status, IdempotencyStatus::Failed);
+        assert_eq!(
+            second_claim.error_message,
+            Some(String::from("Insufficient funds"))
+        );
+        assert_eq!(third_claim, None);
+    }
+
+    #[async_std::test]
+    async fn same_key_can_be_claimed_for_each_source_account() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = IdempotencyPersistenceAdapter::new(pool);
+
+        let now = Utc::now();
+        let key = IdempotencyKey(format!("test-{}", now.timestamp_nanos()));
+        let record = IdempotencyRecord::new_in_progress(
+            key.clone(),
+            AccountId(41),
+            String::from("send_money:41:42:500"),
+            now,
+            now + Duration::minutes(1),
+        );
+        let record_of_other_account = IdempotencyRecord::new_in_progress(
+            key.clone(),
+            AccountId(43),
+            String::from("send_money:43:42:500"),
+            now,
+            now + Duration::minutes(1),
+        );
+
+        let first_claim = adapter.claim_key(&record).await.unwrap();
+        let claim_of_other_account = adapter.claim_key(&record_of_other_account).await.unwrap();
+        let second_claim = adapter.claim_key(&record).await.unwrap();
+
+        adapter
+            .remove_expired_keys(&(now + Duration::minutes(2)))
+            .await
+            .unwrap();
+
+        assert_eq!(first_claim, None);
+        assert_eq!(claim_of_other_account, None);
+        assert_eq!(
+            second_claim.map(|existing| existing.source_account_id),
+            Some(AccountId(41))
+        );
+    }
+}
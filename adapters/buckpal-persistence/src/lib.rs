@@ -1,6 +1,54 @@
+mod account_balance_entity;
+mod account_balance_mapper;
+mod account_balance_repository;
 mod account_entity;
+mod account_event_entity;
+mod account_event_mapper;
+mod account_event_repository;
 mod account_mapper;
 pub mod account_persistence_adapter;
 mod account_repository;
+mod activity_chain_entity;
+mod activity_chain_mapper;
+pub mod activity_chain_persistence_adapter;
+mod activity_chain_repository;
 mod activity_entity;
 mod activity_repository;
+mod audit_log_entity;
+mod audit_log_mapper;
+mod audit_log_repository;
+pub mod audit_persistence_adapter;
+mod domain_event_entity;
+mod domain_event_mapper;
+pub mod event_sourced_account_persistence_adapter;
+mod external_payment_entity;
+mod external_payment_mapper;
+pub mod external_payment_persistence_adapter;
+mod external_payment_repository;
+mod hold_entity;
+mod hold_mapper;
+mod hold_repository;
+mod idempotency_key_entity;
+mod idempotency_key_mapper;
+mod idempotency_key_repository;
+pub mod idempotency_persistence_adapter;
+mod outbox_entity;
+pub mod outbox_relay;
+mod outbox_repository;
+pub mod payment_initiation_persistence_adapter;
+mod payment_initiation_repository;
+mod reconciliation_entity;
+pub mod reconciliation_persistence_adapter;
+mod reconciliation_repository;
+mod statement_entity;
+mod statement_mapper;
+pub mod statement_persistence_adapter;
+mod statement_repository;
+mod transfer_entity;
+mod transfer_mapper;
+pub mod transfer_persistence_adapter;
+mod transfer_repository;
+mod webhook_entity;
+mod webhook_mapper;
+pub mod webhook_persistence_adapter;
+mod webhook_repository;
@@ -0,0 +1,18 @@
+use crate::domain_event_entity::DomainEventEntity;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct OutboxEntity {
+    pub id: Option<i64>,
+    pub event: DomainEventEntity,
+    pub attempts: i32,
+}
+
+impl OutboxEntity {
+    pub fn new(id: Option<i64>, event: DomainEventEntity, attempts: i32) -> Self {
+        Self {
+            id,
+            event,
+            attempts,
+        }
+    }
+}
@@ -0,0 +1,412 @@
+use crate::domain_event_mapper::DomainEventMapper;
+use crate::outbox_repository::OutboxRepository;
+use anyhow::Result;
+use async_std::task;
+use buckpal_application::application::port::outgoing::domain_event_publisher::DomainEventPublisher;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::webhook::RetryPolicy;
+use chrono::Utc;
+use sqlx::postgres::PgPool;
+use std::collections::HashSet;
+use std::time::Duration;
+
+const DEFAULT_BATCH_SIZE: i64 = 100;
+
+/// Delivers the events written to the outbox to a sink. Every event is delivered at least once,
+/// and the events of an account in the order they were written in: an event that can't be
+/// delivered holds back the later events of its account until it is, or until the retry policy
+/// gives up on it and dead-letters it.
+pub struct OutboxRelay {
+    outbox_repository: OutboxRepository,
+    domain_event_mapper: DomainEventMapper,
+    sink: Box<dyn DomainEventPublisher + Send + Sync>,
+    batch_size: i64,
+    retry_policy: RetryPolicy,
+    account_ids: Vec<i32>,
+}
+
+impl OutboxRelay {
+    pub fn new(pool: PgPool, sink: Box<dyn DomainEventPublisher + Send + Sync>) -> Self {
+        Self {
+            outbox_repository: OutboxRepository::new(pool),
+            domain_event_mapper: DomainEventMapper::default(),
+            sink,
+            batch_size: DEFAULT_BATCH_SIZE,
+            retry_policy: RetryPolicy::default(),
+            account_ids: vec![],
+        }
+    }
+
+    /// Sets the maximum number of entries handled by a single pass.
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Only relays the events of the given accounts instead of those of all of them.
+    pub fn with_account_ids(mut self, account_ids: &[AccountId]) -> Self {
+        self.account_ids = account_ids.iter().map(|account_id| account_id.0).collect();
+        self
+    }
+
+    /// Delivers a batch of pending events and returns how many were delivered. Does nothing if
+    /// another relay is busy with the outbox.
+    pub async fn relay_pending(&self) -> Result<usize> {
+        let mut conn = self.outbox_repository.acquire().await?;
+
+        if !self.outbox_repository.try_lock_relay(&mut conn).await? {
+            return Ok(0);
+        }
+
+        let result = self.relay_batch().await;
+
+        // the lock belongs to the session, which outlives this pass in the pool
+        self.outbox_repository.unlock_relay(&mut conn).await?;
+
+        result
+    }
+
+    async fn relay_batch(&self) -> Result<usize> {
+        let pending = self
+            .outbox_repository
+            .find_pending(&self.account_ids, self.batch_size)
+            .await?;
+
+        let mut held_back = HashSet::new();
+        let mut delivered = 0;
+
+        for entity in pending {
+            if held_back.contains(&entity.event.account_id) {
+                continue;
+            }
+
+            let id = entity.id.expect("expected outbox entry to have an ID");
+            let result = match self.domain_event_mapper.map_to_domain_event(&entity.event) {
+                Ok(event) => self.sink.publish(&[event]).await,
+                Err(err) => Err(err),
+            };
+
+            // an entry is only marked once it was delivered, so stopping in between delivers it
+            // again rather than not at all
+            match result {
+                Ok(()) => {
+                    self.outbox_repository.mark_processed(id).await?;
+                    delivered += 1;
+                }
+                Err(err) if entity.attempts + 1 >= self.retry_policy.max_attempts => {
+                    log::error!(
+                        "Dead-lettering outbox entry {} after {} attempts: {}",
+                        id,
+                        entity.attempts + 1,
+                        err
+                    );
+                    self.outbox_repository
+                        .mark_dead_lettered(id, &err.to_string())
+                        .await?;
+                }
+                Err(err) => {
+                    let next_attempt_at =
+                        Utc::now() + self.retry_policy.backoff(entity.attempts + 1);
+                    self.outbox_repository
+                        .mark_failed(id, &err.to_string(), &next_attempt_at)
+                        .await?;
+                    held_back.insert(entity.event.account_id);
+                }
+            }
+        }
+
+        Ok(delivered)
+    }
+
+    /// Keeps relaying pending events, waiting for the interval whenever there was nothing left
+    /// to deliver.
+    pub async fn run(&self, interval: Duration) {
+        loop {
+            match self.relay_pending().await {
+                Ok(delivered) if delivered > 0 => continue,
+                Ok(_) => {}
+                Err(err) => log::error!("Relaying the outbox failed: {}", err),
+            }
+
+            task::sleep(interval).await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::OutboxRelay;
+    use crate::domain_event_mapper::DomainEventMapper;
+    use crate::outbox_repository::OutboxRepository;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use buckpal_application::application::port::outgoing::domain_event_publisher::DomainEventPublisher;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::domain_event::DomainEvent;
+    use buckpal_application::domain::transfer::TransferId;
+    use buckpal_application::domain::webhook::RetryPolicy;
+    use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn undeliverable_event_holds_back_later_events_of_its_account_only() {
+        let pool = given_a_pool().await;
+        let (withdrawn, deposited, completed) = given_a_transfer_in_the_outbox(&pool).await;
+        let account_ids = ordering_account_ids(&withdrawn, &deposited);
+        let retry_policy = RetryPolicy {
+            initial_backoff: Duration::zero(),
+            ..RetryPolicy::default()
+        };
+
+        let sink = RecordingSink::failing_for(&withdrawn);
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .with_retry_policy(retry_policy.clone())
+            .relay_pending()
+            .await
+            .unwrap();
+        let first_pass = sink.received();
+
+        let sink = RecordingSink::default();
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .with_retry_policy(retry_policy)
+            .relay_pending()
+            .await
+            .unwrap();
+        let second_pass = sink.received();
+
+        delete_accounts_and_their_outbox_entries(&account_ids, &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(first_pass, vec![deposited]);
+        assert_eq!(second_pass, vec![withdrawn, completed]);
+    }
+
+    #[async_std::test]
+    async fn failed_event_waits_for_its_backoff() {
+        let pool = given_a_pool().await;
+        let (withdrawn, deposited, _) = given_a_transfer_in_the_outbox(&pool).await;
+        let account_ids = ordering_account_ids(&withdrawn, &deposited);
+
+        let sink = RecordingSink::failing_for(&withdrawn);
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .relay_pending()
+            .await
+            .unwrap();
+
+        let sink = RecordingSink::default();
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .relay_pending()
+            .await
+            .unwrap();
+        let second_pass = sink.received();
+
+        delete_accounts_and_their_outbox_entries(&account_ids, &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(second_pass, vec![]);
+    }
+
+    #[async_std::test]
+    async fn dead_lettered_event_no_longer_holds_back_its_account() {
+        let pool = given_a_pool().await;
+        let (withdrawn, deposited, completed) = given_a_transfer_in_the_outbox(&pool).await;
+        let account_ids = ordering_account_ids(&withdrawn, &deposited);
+        let retry_policy = RetryPolicy {
+            max_attempts: 1,
+            ..RetryPolicy::default()
+        };
+
+        let sink = RecordingSink::failing_for(&withdrawn);
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .with_retry_policy(retry_policy.clone())
+            .relay_pending()
+            .await
+            .unwrap();
+        let first_pass = sink.received();
+
+        let sink = RecordingSink::default();
+        OutboxRelay::new(pool.clone(), Box::new(sink.clone()))
+            .with_account_ids(&account_ids)
+            .with_retry_policy(retry_policy)
+            .relay_pending()
+            .await
+            .unwrap();
+        let second_pass = sink.received();
+
+        delete_accounts_and_their_outbox_entries(&account_ids, &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(first_pass, vec![deposited, completed]);
+        assert_eq!(second_pass, vec![]);
+    }
+
+    async fn given_a_pool() -> PgPool {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap()
+    }
+
+    /// Writes the withdrawal, the deposit and the completion of a transfer between two new
+    /// accounts to the outbox, in that order.
+    async fn given_a_transfer_in_the_outbox(
+        pool: &PgPool,
+    ) -> (DomainEvent, DomainEvent, DomainEvent) {
+        let source_account_id = AccountId(given_an_account(pool).await.unwrap());
+        let target_account_id = AccountId(given_an_account(pool).await.unwrap());
+
+        // whole seconds survive the round trip through the database unchanged
+        let timestamp =
+            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 4, 11).and_hms(9, 0, 0), Utc);
+        let withdrawn = DomainEvent::MoneyWithdrawn {
+            account_id: source_account_id.clone(),
+            target_account_id: target_account_id.clone(),
+            transfer_id: TransferId(7),
+            money: money!(500, "AUD"),
+            timestamp,
+        };
+        let deposited = DomainEvent::MoneyDeposited {
+            account_id: target_account_id.clone(),
+            source_account_id: source_account_id.clone(),
+            transfer_id: TransferId(7),
+            money: money!(500, "AUD"),
+            timestamp,
+        };
+        let completed = DomainEvent::TransferCompleted {
+            transfer_id: TransferId(7),
+            source_account_id,
+            target_account_id,
+            money: money!(500, "AUD"),
+            timestamp,
+        };
+        given_outbox_entries(&[&withdrawn, &deposited, &completed], pool)
+            .await
+            .unwrap();
+
+        (withdrawn, deposited, completed)
+    }
+
+    async fn given_outbox_entries(events: &[&DomainEvent], pool: &PgPool) -> Result<()> {
+        let repository = OutboxRepository::new(pool.clone());
+        let mapper = DomainEventMapper::default();
+
+        let mut tx = repository.begin().await?;
+        for event in events {
+            repository
+                .save_in(&mut tx, &mapper.map_to_entity(event))
+                .await?;
+        }
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    async fn given_an_account(pool: &PgPool) -> Result<i32> {
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO account DEFAULT VALUES RETURNING id 
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entity.id)
+    }
+
+    async fn delete_accounts_and_their_outbox_entries(
+        account_ids: &[AccountId],
+        pool: &PgPool,
+    ) -> Result<()> {
+        for account_id in account_ids {
+            sqlx::query!(
+                r#"
+                    DELETE FROM outbox WHERE account_id = $1
+                "#,
+                account_id.0,
+            )
+            .execute(pool)
+            .await?;
+
+            sqlx::query!(
+                r#"
+                    DELETE FROM account WHERE id = $1 
+                "#,
+                account_id.0,
+            )
+            .execute(pool)
+            .await?;
+        }
+
+        Ok(())
+    }
+
+    fn ordering_account_ids(withdrawn: &DomainEvent, deposited: &DomainEvent) -> Vec<AccountId> {
+        vec![
+            ordering_account_id(withdrawn).clone(),
+            ordering_account_id(deposited).clone(),
+        ]
+    }
+
+    /// Records every event it is given, failing to deliver the given one.
+    #[derive(Debug, Default, Clone)]
+    struct RecordingSink {
+        failing_event: Option<DomainEvent>,
+        events: Arc<Mutex<Vec<DomainEvent>>>,
+    }
+
+    impl RecordingSink {
+        fn failing_for(event: &DomainEvent) -> Self {
+            Self {
+                failing_event: Some(event.clone()),
+                events: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        fn received(&self) -> Vec<DomainEvent> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl DomainEventPublisher for RecordingSink {
+        async fn publish(&self, events: &[DomainEvent]) -> Result<()> {
+            for event in events {
+                if self.failing_event.as_ref() == Some(event) {
+                    return Err(anyhow!("unavailable"));
+                }
+                self.events.lock().unwrap().push(event.clone());
+            }
+
+            Ok(())
+        }
+    }
+
+    fn ordering_account_id(event: &DomainEvent) -> &AccountId {
+        match event {
+            DomainEvent::MoneyWithdrawn { account_id, .. }
+            | DomainEvent::MoneyDeposited { account_id, .. } => account_id,
+            DomainEvent::TransferCompleted {
+                source_account_id, ..
+            } => source_account_id,
+        }
+    }
+}
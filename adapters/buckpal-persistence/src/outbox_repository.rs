@@ -0,0 +1,221 @@
+use crate::domain_event_entity::DomainEventEntity;
+use crate::outbox_entity::OutboxEntity;
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::pool::PoolConnection;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+
+/// The key of the advisory lock held by the relay delivering the outbox, see
+/// [`OutboxRepository::try_lock_relay`].
+const RELAY_LOCK_KEY: i64 = 0x6f75_7462_6f78;
+
+#[derive(Debug, Clone)]
+pub struct OutboxRepository {
+    pool: PgPool,
+}
+
+impl OutboxRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn begin(&self) -> Result<Transaction<'static, Postgres>> {
+        Ok(self.pool.begin().await?)
+    }
+
+    /// Writes a pending entry as part of the transaction of the change the event describes.
+    pub async fn save_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        event_entity: &DomainEventEntity,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                INSERT INTO 
+                            outbox (account_id, event_type, transfer_id, source_account_id, target_account_id, amount, timestamp)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+            event_entity.account_id,
+            event_entity.event_type,
+            event_entity.transfer_id,
+            event_entity.source_account_id,
+            event_entity.target_account_id,
+            event_entity.amount,
+            event_entity.timestamp
+        )
+        .execute(&mut *tx)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn acquire(&self) -> Result<PoolConnection<Postgres>> {
+        Ok(self.pool.acquire().await?)
+    }
+
+    /// Makes the session of the connection the only one relaying the outbox until
+    /// [`OutboxRepository::unlock_relay`], which is what keeps the events of an account in order.
+    /// No transaction stays open meanwhile. Returns `false` if another session already is.
+    pub async fn try_lock_relay(&self, conn: &mut PoolConnection<Postgres>) -> Result<bool> {
+        let row = sqlx::query!(
+            r#"
+                SELECT pg_try_advisory_lock($1) AS "locked!"
+            "#,
+            RELAY_LOCK_KEY
+        )
+        .fetch_one(&mut *conn)
+        .await?;
+
+        Ok(row.locked)
+    }
+
+    pub async fn unlock_relay(&self, conn: &mut PoolConnection<Postgres>) -> Result<()> {
+        sqlx::query!(
+            r#"
+                SELECT pg_advisory_unlock($1)
+            "#,
+            RELAY_LOCK_KEY
+        )
+        .execute(&mut *conn)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Finds the oldest entries that are due for delivery, in the order they were written,
+    /// restricted to the given accounts unless there are none. An entry waiting for its next
+    /// attempt keeps the later entries of its account from being found.
+    pub async fn find_pending(&self, account_ids: &[i32], limit: i64) -> Result<Vec<OutboxEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        account_id,
+                        event_type,
+                        transfer_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        timestamp,
+                        attempts
+                FROM 
+                        outbox
+                WHERE 
+                        processed_at IS NULL 
+                        AND dead_lettered_at IS NULL 
+                        AND (cardinality($1::INT[]) = 0 OR account_id = ANY($1)) 
+                        AND NOT EXISTS (
+                            SELECT 
+                                    1
+                            FROM 
+                                    outbox AS waiting
+                            WHERE 
+                                    waiting.account_id = outbox.account_id 
+                                    AND waiting.id <= outbox.id 
+                                    AND waiting.processed_at IS NULL 
+                                    AND waiting.dead_lettered_at IS NULL 
+                                    AND waiting.next_attempt_at > now()
+                        )
+                ORDER BY 
+                        id
+                LIMIT 
+                        $2
+            "#,
+            account_ids,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                OutboxEntity::new(
+                    Some(entity.id),
+                    DomainEventEntity {
+                        account_id: entity.account_id,
+                        event_type: entity.event_type,
+                        transfer_id: entity.transfer_id,
+                        source_account_id: entity.source_account_id,
+                        target_account_id: entity.target_account_id,
+                        amount: entity.amount,
+                        timestamp: entity.timestamp,
+                    },
+                    entity.attempts,
+                )
+            })
+            .collect())
+    }
+
+    pub async fn mark_processed(&self, id: i64) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        outbox
+                SET 
+                        processed_at = now(),
+                        attempts = attempts + 1,
+                        last_error = NULL
+                WHERE 
+                        id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records a failed attempt and when to make the next one.
+    pub async fn mark_failed(
+        &self,
+        id: i64,
+        error: &str,
+        next_attempt_at: &DateTime<Utc>,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        outbox
+                SET 
+                        attempts = attempts + 1,
+                        last_error = $2,
+                        next_attempt_at = $3
+                WHERE 
+                        id = $1
+            "#,
+            id,
+            error,
+            next_attempt_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Records the last failed attempt, after which the entry is no longer delivered.
+    pub async fn mark_dead_lettered(&self, id: i64, error: &str) -> Result<()> {
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        outbox
+                SET 
+                        attempts = attempts + 1,
+                        last_error = $2,
+                        dead_lettered_at = now()
+                WHERE 
+                        id = $1
+            "#,
+            id,
+            error
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+use crate::payment_initiation_repository::PaymentInitiationRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::payment_initiation_port::PaymentInitiationPort;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct PaymentInitiationPersistenceAdapter {
+    payment_initiation_repository: PaymentInitiationRepository,
+}
+
+impl PaymentInitiationPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            payment_initiation_repository: PaymentInitiationRepository::new(pool),
+        }
+    }
+}
+
+#[async_trait]
+impl PaymentInitiationPort for PaymentInitiationPersistenceAdapter {
+    async fn claim_message_id(
+        &self,
+        message_id: &str,
+        received_at: &DateTime<Utc>,
+    ) -> Result<bool> {
+        self.payment_initiation_repository
+            .insert_if_absent(message_id, received_at)
+            .await
+    }
+}
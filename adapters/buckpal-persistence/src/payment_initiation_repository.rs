@@ -0,0 +1,39 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct PaymentInitiationRepository {
+    pool: PgPool,
+}
+
+impl PaymentInitiationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts the message id unless it is already taken, returns whether it was inserted.
+    pub async fn insert_if_absent(
+        &self,
+        message_id: &str,
+        received_at: &DateTime<Utc>,
+    ) -> Result<bool> {
+        let inserted = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            payment_initiation (message_id, received_at)
+                VALUES 
+                            ($1, $2)
+                ON CONFLICT (message_id) DO NOTHING
+                RETURNING 
+                            message_id 
+            "#,
+            message_id,
+            *received_at
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(inserted.is_some())
+    }
+}
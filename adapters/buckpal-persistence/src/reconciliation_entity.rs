@@ -0,0 +1,11 @@
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LedgerTotalsEntity {
+    pub deposited: i64,
+    pub withdrawn: i64,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct NegativeBalanceEntity {
+    pub account_id: i32,
+    pub balance: i64,
+}
@@ -0,0 +1,190 @@
+use crate::account_mapper::AccountMapper;
+use crate::reconciliation_repository::ReconciliationRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::reconciliation_port::ReconciliationPort;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::Activity;
+use buckpal_application::domain::reconciliation::LedgerTotals;
+use rusty_money::{money, Money};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct ReconciliationPersistenceAdapter {
+    reconciliation_repository: ReconciliationRepository,
+    account_mapper: AccountMapper,
+}
+
+impl ReconciliationPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            reconciliation_repository: ReconciliationRepository::new(pool),
+            account_mapper: AccountMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl ReconciliationPort for ReconciliationPersistenceAdapter {
+    async fn load_unmatched_activities(&self) -> Result<Vec<Activity>> {
+        let activities = self
+            .reconciliation_repository
+            .find_unmatched_activities()
+            .await?;
+
+        Ok(activities
+            .iter()
+            .map(|activity| self.account_mapper.map_to_activity(activity))
+            .collect())
+    }
+
+    async fn load_ledger_totals(&self) -> Result<LedgerTotals> {
+        let totals = self.reconciliation_repository.sum_totals().await?;
+
+        Ok(LedgerTotals::new(
+            money!(totals.deposited, "AUD"),
+            money!(totals.withdrawn, "AUD"),
+        ))
+    }
+
+    async fn load_negative_balances(&self) -> Result<Vec<(AccountId, Money)>> {
+        let balances = self
+            .reconciliation_repository
+            .find_negative_balances()
+            .await?;
+
+        Ok(balances
+            .into_iter()
+            .map(|balance| {
+                (
+                    AccountId(balance.account_id),
+                    money!(balance.balance, "AUD"),
+                )
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReconciliationPersistenceAdapter;
+    use crate::activity_entity::ActivityEntity;
+    use crate::activity_repository::ActivityRepository;
+    use anyhow::Result;
+    use buckpal_application::application::port::outgoing::reconciliation_port::ReconciliationPort;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::activity::ActivityId;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+
+    #[async_std::test]
+    async fn finds_unmatched_withdrawal_and_negative_balance() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let source_account_id = given_an_account(&pool).await.unwrap();
+        let target_account_id = given_an_account(&pool).await.unwrap();
+        let timestamp =
+            DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 5, 9).and_hms(8, 0, 0), Utc);
+
+        // a transfer of which both legs were written, and one of which only the withdrawal was
+        let activity_repository = ActivityRepository::new(pool.clone());
+        let mut activity_ids = vec![];
+        for (owner_account_id, amount) in &[
+            (source_account_id, 300),
+            (target_account_id, 300),
+            (source_account_id, 700),
+        ] {
+            let activity = activity_repository
+                .save(&ActivityEntity::new(
+                    None,
+                    timestamp,
+                    *owner_account_id,
+                    source_account_id,
+                    target_account_id,
+                    *amount,
+                    None,
+                ))
+                .await
+                .unwrap();
+            activity_ids.push(activity.id.unwrap());
+        }
+
+        let adapter = ReconciliationPersistenceAdapter::new(pool.clone());
+        let unmatched_activities = adapter.load_unmatched_activities().await.unwrap();
+        let negative_balances = adapter.load_negative_balances().await.unwrap();
+
+        // cleanup db
+        delete_activities_of(source_account_id, target_account_id, &pool)
+            .await
+            .unwrap();
+        delete_account_with_id(source_account_id, &pool)
+            .await
+            .unwrap();
+        delete_account_with_id(target_account_id, &pool)
+            .await
+            .unwrap();
+        // end cleanup db
+
+        let unmatched_ids: Vec<ActivityId> = unmatched_activities
+            .into_iter()
+            .filter(|activity| activity.owner_account_id.0 == source_account_id)
+            .map(|activity| activity.id.unwrap())
+            .collect();
+        assert_eq!(unmatched_ids, vec![ActivityId(activity_ids[2])]);
+        assert!(negative_balances.contains(&(AccountId(source_account_id), money!(-1000, "AUD"))));
+        assert!(negative_balances
+            .iter()
+            .all(|(account_id, _)| account_id.0 != target_account_id));
+    }
+
+    async fn given_an_account(pool: &PgPool) -> Result<i32> {
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO account DEFAULT VALUES RETURNING id 
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entity.id)
+    }
+
+    async fn delete_account_with_id(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account WHERE id = $1 
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+
+    async fn delete_activities_of(
+        first_account_id: i32,
+        second_account_id: i32,
+        pool: &PgPool,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM activity WHERE owner_account_id IN ($1, $2)
+            "#,
+            first_account_id,
+            second_account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use crate::activity_entity::ActivityEntity;
+use crate::reconciliation_entity::{LedgerTotalsEntity, NegativeBalanceEntity};
+use anyhow::Result;
+use sqlx::postgres::PgPool;
+
+/// Scans the activities of all accounts for what doesn't add up.
+#[derive(Debug, Clone)]
+pub struct ReconciliationRepository {
+    pool: PgPool,
+}
+
+impl ReconciliationRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Pairs every withdrawal with a deposit of the same accounts, amount, timestamp and
+    /// transfer, numbering duplicates so that each activity is paired at most once, and finds
+    /// the activities left over on either side.
+    pub async fn find_unmatched_activities(&self) -> Result<Vec<ActivityEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                WITH withdrawal AS (
+                    SELECT 
+                            id,
+                            source_account_id,
+                            target_account_id,
+                            amount,
+                            timestamp,
+                            COALESCE(transfer_id, 0) AS transfer_key,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY source_account_id, target_account_id, amount, timestamp, COALESCE(transfer_id, 0)
+                                ORDER BY id
+                            ) AS n
+                    FROM 
+                            activity
+                    WHERE 
+                            owner_account_id = source_account_id
+                ), deposit AS (
+                    SELECT 
+                            id,
+                            source_account_id,
+                            target_account_id,
+                            amount,
+                            timestamp,
+                            COALESCE(transfer_id, 0) AS transfer_key,
+                            ROW_NUMBER() OVER (
+                                PARTITION BY source_account_id, target_account_id, amount, timestamp, COALESCE(transfer_id, 0)
+                                ORDER BY id
+                            ) AS n
+                    FROM 
+                            activity
+                    WHERE 
+                            owner_account_id = target_account_id
+                )
+                SELECT 
+                        id,
+                        timestamp,
+                        owner_account_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        transfer_id
+                FROM 
+                        activity
+                WHERE 
+                        id IN (
+                            SELECT 
+                                    COALESCE(withdrawal.id, deposit.id)
+                            FROM 
+                                    withdrawal
+                                    FULL OUTER JOIN deposit ON deposit.source_account_id = withdrawal.source_account_id
+                                        AND deposit.target_account_id = withdrawal.target_account_id
+                                        AND deposit.amount = withdrawal.amount
+                                        AND deposit.timestamp = withdrawal.timestamp
+                                        AND deposit.transfer_key = withdrawal.transfer_key
+                                        AND deposit.n = withdrawal.n
+                            WHERE 
+                                    withdrawal.id IS NULL OR deposit.id IS NULL
+                        )
+                ORDER BY 
+                        id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                ActivityEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.owner_account_id,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.transfer_id,
+                )
+            })
+            .collect())
+    }
+
+    pub async fn sum_totals(&self) -> Result<LedgerTotalsEntity> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        COALESCE(SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE 0 END), 0)::BIGINT AS "deposited!",
+                        COALESCE(SUM(CASE WHEN source_account_id = owner_account_id THEN amount ELSE 0 END), 0)::BIGINT AS "withdrawn!"
+                FROM 
+                        activity
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(LedgerTotalsEntity {
+            deposited: entity.deposited,
+            withdrawn: entity.withdrawn,
+        })
+    }
+
+    pub async fn find_negative_balances(&self) -> Result<Vec<NegativeBalanceEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        owner_account_id AS account_id,
+                        (SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE 0 END)
+                            - SUM(CASE WHEN source_account_id = owner_account_id THEN amount ELSE 0 END))::BIGINT AS "balance!"
+                FROM 
+                        activity
+                GROUP BY 
+                        owner_account_id
+                HAVING 
+                        SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE 0 END)
+                            < SUM(CASE WHEN source_account_id = owner_account_id THEN amount ELSE 0 END)
+                ORDER BY 
+                        owner_account_id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| NegativeBalanceEntity {
+                account_id: entity.account_id,
+                balance: entity.balance,
+            })
+            .collect())
+    }
+}
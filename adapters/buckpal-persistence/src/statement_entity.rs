@@ -0,0 +1,81 @@
+use chrono::{DateTime, NaiveDate, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct StatementEntity {
+    pub id: Option<i32>,
+    pub account_id: i32,
+    pub period_from: NaiveDate,
+    pub period_to: NaiveDate,
+    pub opening_balance: i64,
+    pub total_in: i64,
+    pub total_out: i64,
+    pub closing_balance: i64,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl StatementEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<i32>,
+        account_id: i32,
+        period_from: NaiveDate,
+        period_to: NaiveDate,
+        opening_balance: i64,
+        total_in: i64,
+        total_out: i64,
+        closing_balance: i64,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            account_id,
+            period_from,
+            period_to,
+            opening_balance,
+            total_in,
+            total_out,
+            closing_balance,
+            generated_at,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct StatementLineEntity {
+    pub position: i32,
+    pub activity_id: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub kind: String,
+    pub counterparty_account_id: i32,
+    pub amount: i64,
+    pub running_balance: i64,
+    pub transfer_id: Option<i32>,
+    pub reference: Option<String>,
+}
+
+impl StatementLineEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        position: i32,
+        activity_id: Option<i32>,
+        timestamp: DateTime<Utc>,
+        kind: String,
+        counterparty_account_id: i32,
+        amount: i64,
+        running_balance: i64,
+        transfer_id: Option<i32>,
+        reference: Option<String>,
+    ) -> Self {
+        Self {
+            position,
+            activity_id,
+            timestamp,
+            kind,
+            counterparty_account_id,
+            amount,
+            running_balance,
+            transfer_id,
+            reference,
+        }
+    }
+}
@@ -0,0 +1,115 @@
+use crate::statement_entity::{StatementEntity, StatementLineEntity};
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::{ActivityId, ActivityKind};
+use buckpal_application::domain::statement::{Statement, StatementId, StatementLine};
+use buckpal_application::domain::transfer::TransferId;
+use rusty_money::{money, Money};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StatementMapperError {
+    #[error("Unknown statement line kind `{0}`")]
+    UnknownKind(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct StatementMapper {}
+
+impl StatementMapper {
+    pub fn map_to_domain_entity(
+        &self,
+        statement: StatementEntity,
+        lines: Vec<StatementLineEntity>,
+    ) -> Result<Statement> {
+        let lines = lines
+            .into_iter()
+            .map(|line| self.map_to_line(line))
+            .collect::<Result<Vec<StatementLine>>>()?;
+
+        Ok(Statement {
+            id: statement.id.map(StatementId),
+            account_id: AccountId(statement.account_id),
+            from: statement.period_from,
+            to: statement.period_to,
+            opening_balance: money!(statement.opening_balance, "AUD"),
+            total_in: money!(statement.total_in, "AUD"),
+            total_out: money!(statement.total_out, "AUD"),
+            closing_balance: money!(statement.closing_balance, "AUD"),
+            lines,
+            generated_at: statement.generated_at,
+        })
+    }
+
+    pub fn map_to_entity(&self, statement: &Statement) -> StatementEntity {
+        StatementEntity::new(
+            statement.id.clone().map(|id| id.0),
+            statement.account_id.0,
+            statement.from,
+            statement.to,
+            to_amount(&statement.opening_balance),
+            to_amount(&statement.total_in),
+            to_amount(&statement.total_out),
+            to_amount(&statement.closing_balance),
+            statement.generated_at,
+        )
+    }
+
+    pub fn map_to_line_entities(&self, statement: &Statement) -> Vec<StatementLineEntity> {
+        statement
+            .lines
+            .iter()
+            .enumerate()
+            .map(|(position, line)| {
+                StatementLineEntity::new(
+                    position as i32,
+                    line.activity_id.clone().map(|id| id.0),
+                    line.timestamp,
+                    String::from(self.map_from_kind(line.kind)),
+                    line.counterparty_account_id.0,
+                    to_amount(&line.money),
+                    to_amount(&line.running_balance),
+                    line.transfer_id.clone().map(|id| id.0),
+                    line.reference.clone(),
+                )
+            })
+            .collect()
+    }
+
+    fn map_to_line(&self, line: StatementLineEntity) -> Result<StatementLine> {
+        Ok(StatementLine {
+            activity_id: line.activity_id.map(ActivityId),
+            timestamp: line.timestamp,
+            kind: self.map_to_kind(&line.kind)?,
+            counterparty_account_id: AccountId(line.counterparty_account_id),
+            money: money!(line.amount, "AUD"),
+            running_balance: money!(line.running_balance, "AUD"),
+            transfer_id: line.transfer_id.map(TransferId),
+            reference: line.reference,
+        })
+    }
+
+    fn map_to_kind(&self, kind: &str) -> Result<ActivityKind> {
+        match kind {
+            "DEPOSIT" => Ok(ActivityKind::Deposit),
+            "WITHDRAWAL" => Ok(ActivityKind::Withdrawal),
+            other => Err(anyhow!(StatementMapperError::UnknownKind(String::from(
+                other
+            )))),
+        }
+    }
+
+    fn map_from_kind(&self, kind: ActivityKind) -> &'static str {
+        match kind {
+            ActivityKind::Deposit => "DEPOSIT",
+            ActivityKind::Withdrawal => "WITHDRAWAL",
+        }
+    }
+}
+
+fn to_amount(money: &Money) -> i64 {
+    use rust_decimal::prelude::*;
+
+    // here we want to explode, no way to recover
+    money.amount().to_i64().unwrap()
+}
@@ -0,0 +1,173 @@
+use crate::statement_mapper::StatementMapper;
+use crate::statement_repository::StatementRepository;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::{
+    load_statement_port::LoadStatementPort, save_statement_port::SaveStatementPort,
+};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::statement::{Statement, StatementId};
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct StatementPersistenceAdapter {
+    statement_repository: StatementRepository,
+    statement_mapper: StatementMapper,
+}
+
+impl StatementPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            statement_repository: StatementRepository::new(pool),
+            statement_mapper: StatementMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl LoadStatementPort for StatementPersistenceAdapter {
+    async fn load_statement(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+    ) -> Result<Option<Statement>> {
+        let statement = self
+            .statement_repository
+            .find_by_period(account_id.0, from, to)
+            .await?;
+
+        let statement = match statement {
+            Some(statement) => statement,
+            None => return Ok(None),
+        };
+
+        let lines = self
+            .statement_repository
+            .find_lines(
+                statement
+                    .id
+                    .expect("expected persisted statement to have an ID"),
+            )
+            .await?;
+
+        Ok(Some(
+            self.statement_mapper
+                .map_to_domain_entity(statement, lines)?,
+        ))
+    }
+}
+
+#[async_trait]
+impl SaveStatementPort for StatementPersistenceAdapter {
+    async fn save_statement(&self, statement: &Statement) -> Result<Statement> {
+        let statement_entity = self.statement_mapper.map_to_entity(statement);
+        let line_entities = self.statement_mapper.map_to_line_entities(statement);
+
+        let saved = self
+            .statement_repository
+            .save(&statement_entity, &line_entities)
+            .await?;
+
+        if let Some(saved) = saved {
+            let mut statement = statement.clone();
+            statement.id = saved.id.map(StatementId);
+            return Ok(statement);
+        }
+
+        // someone else issued the statement first, theirs is the one that counts
+        self.load_statement(&statement.account_id, &statement.from, &statement.to)
+            .await?
+            .ok_or_else(|| anyhow!("expected concurrently issued statement to exist"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatementPersistenceAdapter;
+    use anyhow::Result;
+    use buckpal_application::application::port::outgoing::{
+        load_statement_port::LoadStatementPort, save_statement_port::SaveStatementPort,
+    };
+    use buckpal_application::domain::account::account_test_data::AccountBuilder;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::activity::activity_test_data::ActivityBuilder;
+    use buckpal_application::domain::activity_window::ActivityWindow;
+    use buckpal_application::domain::statement::Statement;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+
+    #[async_std::test]
+    async fn issues_statement_only_once() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = StatementPersistenceAdapter::new(pool.clone());
+
+        // statements can't be deleted, so every run uses an account of its own
+        let account_id = AccountId(given_an_account(&pool).await.unwrap());
+        let from = NaiveDate::from_ymd(2021, 1, 1);
+        let activity = ActivityBuilder::default_activity()
+            .with_source_account(&AccountId(2))
+            .with_target_account(&account_id)
+            .with_timestamp(&DateTime::<Utc>::from_utc(from.and_hms(12, 0, 0), Utc))
+            .with_money(&money!(50, "AUD"))
+            .build();
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(100, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![activity]))
+            .build();
+
+        let first = Statement::generate(&account_id, &account, from, from, Utc::now());
+        let second = Statement::generate(&account_id, &account, from, from, Utc::now());
+
+        let first = adapter.save_statement(&first).await.unwrap();
+        let second = adapter.save_statement(&second).await.unwrap();
+        let loaded = adapter
+            .load_statement(&account_id, &from, &from)
+            .await
+            .unwrap()
+            .unwrap();
+
+        delete_account_with_id(account_id.0, &pool).await.unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.id, loaded.id);
+        assert_eq!(loaded.lines.len(), 1);
+        assert_eq!(loaded.closing_balance, money!(150, "AUD"));
+    }
+
+    async fn given_an_account(pool: &PgPool) -> Result<i32> {
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO account DEFAULT VALUES RETURNING id 
+            "#,
+        )
+        .fetch_one(pool)
+        .await?;
+
+        Ok(entity.id)
+    }
+
+    async fn delete_account_with_id(account_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM account WHERE id = $1 
+            "#,
+            account_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::statement_entity::{StatementEntity, StatementLineEntity};
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use sqlx::postgres::PgPool;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum StatementRepositoryError {
+    #[error("Statement already has an id `{0}`, skipping insert")]
+    AlreadyHasAnIdException(i32),
+}
+
+#[derive(Debug, Clone)]
+pub struct StatementRepository {
+    pool: PgPool,
+}
+
+impl StatementRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Inserts the statement together with its lines in one transaction. Returns nothing if a
+    /// statement for the same account and period exists already.
+    pub async fn save(
+        &self,
+        statement_entity: &StatementEntity,
+        line_entities: &[StatementLineEntity],
+    ) -> Result<Option<StatementEntity>> {
+        if let Some(statement_id) = statement_entity.id {
+            return Err(anyhow!(StatementRepositoryError::AlreadyHasAnIdException(
+                statement_id
+            )));
+        }
+
+        let mut tx = self.pool.begin().await?;
+
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            statement (account_id, period_from, period_to, opening_balance, total_in, total_out, closing_balance, generated_at)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7, $8)
+                ON CONFLICT (account_id, period_from, period_to) DO NOTHING
+                RETURNING 
+                            id, account_id, period_from, period_to, opening_balance, total_in, total_out, closing_balance, generated_at 
+            "#,
+            statement_entity.account_id,
+            statement_entity.period_from,
+            statement_entity.period_to,
+            statement_entity.opening_balance,
+            statement_entity.total_in,
+            statement_entity.total_out,
+            statement_entity.closing_balance,
+            statement_entity.generated_at
+        )
+        .fetch_optional(&mut tx)
+        .await?;
+
+        let entity = match entity {
+            Some(entity) => entity,
+            None => {
+                tx.rollback().await?;
+                return Ok(None);
+            }
+        };
+
+        for line_entity in line_entities {
+            sqlx::query!(
+                r#"
+                    INSERT INTO 
+                                statement_line (statement_id, position, activity_id, timestamp, kind, counterparty_account_id, amount, running_balance, transfer_id, reference)
+                    VALUES 
+                                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+                entity.id,
+                line_entity.position,
+                line_entity.activity_id,
+                line_entity.timestamp,
+                line_entity.kind,
+                line_entity.counterparty_account_id,
+                line_entity.amount,
+                line_entity.running_balance,
+                line_entity.transfer_id,
+                line_entity.reference
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(Some(StatementEntity::new(
+            Some(entity.id),
+            entity.account_id,
+            entity.period_from,
+            entity.period_to,
+            entity.opening_balance,
+            entity.total_in,
+            entity.total_out,
+            entity.closing_balance,
+            entity.generated_at,
+        )))
+    }
+
+    pub async fn find_by_period(
+        &self,
+        account_id: i32,
+        period_from: &NaiveDate,
+        period_to: &NaiveDate,
+    ) -> Result<Option<StatementEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        id,
+                        account_id,
+                        period_from,
+                        period_to,
+                        opening_balance,
+                        total_in,
+                        total_out,
+                        closing_balance,
+                        generated_at
+                FROM 
+                        statement
+                WHERE 
+                        account_id = $1
+                AND
+                        period_from = $2
+                AND
+                        period_to = $3
+            "#,
+            account_id,
+            *period_from,
+            *period_to
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| {
+            StatementEntity::new(
+                Some(entity.id),
+                entity.account_id,
+                entity.period_from,
+                entity.period_to,
+                entity.opening_balance,
+                entity.total_in,
+                entity.total_out,
+                entity.closing_balance,
+                entity.generated_at,
+            )
+        }))
+    }
+
+    pub async fn find_lines(&self, statement_id: i32) -> Result<Vec<StatementLineEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT
+                        position,
+                        activity_id,
+                        timestamp,
+                        kind,
+                        counterparty_account_id,
+                        amount,
+                        running_balance,
+                        transfer_id,
+                        reference
+                FROM 
+                        statement_line
+                WHERE 
+                        statement_id = $1
+                ORDER BY
+                        position
+            "#,
+            statement_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                StatementLineEntity::new(
+                    entity.position,
+                    entity.activity_id,
+                    entity.timestamp,
+                    entity.kind,
+                    entity.counterparty_account_id,
+                    entity.amount,
+                    entity.running_balance,
+                    entity.transfer_id,
+                    entity.reference,
+                )
+            })
+            .collect();
+
+        Ok(entities)
+    }
+}
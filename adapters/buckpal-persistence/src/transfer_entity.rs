@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TransferEntity {
+    pub id: Option<i32>,
+    pub timestamp: DateTime<Utc>,
+    pub source_account_id: i32,
+    pub target_account_id: i32,
+    pub amount: i64,
+    pub status: String,
+    pub reversed_transfer_id: Option<i32>,
+    pub reference: Option<String>,
+}
+
+impl TransferEntity {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        id: Option<i32>,
+        timestamp: DateTime<Utc>,
+        source_account_id: i32,
+        target_account_id: i32,
+        amount: i64,
+        status: String,
+        reversed_transfer_id: Option<i32>,
+        reference: Option<String>,
+    ) -> Self {
+        Self {
+            id,
+            timestamp,
+            source_account_id,
+            target_account_id,
+            amount,
+            status,
+            reversed_transfer_id,
+            reference,
+        }
+    }
+}
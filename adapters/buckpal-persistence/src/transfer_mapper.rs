@@ -0,0 +1,75 @@
+use crate::transfer_entity::TransferEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::transfer::{Transfer, TransferId, TransferStatus};
+use rusty_money::{money, Money};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransferMapperError {
+    #[error("Unknown transfer status `{0}`")]
+    UnknownStatus(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct TransferMapper {}
+
+impl TransferMapper {
+    pub fn map_to_domain_entity(&self, transfer: TransferEntity) -> Result<Transfer> {
+        let status = self.map_to_status(&transfer.status)?;
+
+        Ok(Transfer::new_with_id(
+            TransferId(
+                transfer
+                    .id
+                    .expect("expected persisted transfer to have an ID"),
+            ),
+            AccountId(transfer.source_account_id),
+            AccountId(transfer.target_account_id),
+            transfer.timestamp,
+            money!(transfer.amount, "AUD"),
+            status,
+            transfer.reversed_transfer_id.map(TransferId),
+            transfer.reference,
+        ))
+    }
+
+    pub fn map_to_entity(&self, transfer: &Transfer) -> TransferEntity {
+        use rust_decimal::prelude::*;
+
+        TransferEntity::new(
+            transfer.id.clone().map(|id| id.0),
+            transfer.timestamp,
+            transfer.source_account_id.0,
+            transfer.target_account_id.0,
+            // here we want to explode, no way to recover
+            transfer.money.amount().to_i64().unwrap(),
+            String::from(self.map_from_status(transfer.status)),
+            transfer.reversed_transfer_id.clone().map(|id| id.0),
+            transfer.reference.clone(),
+        )
+    }
+
+    fn map_to_status(&self, status: &str) -> Result<TransferStatus> {
+        match status {
+            "PENDING" => Ok(TransferStatus::Pending),
+            "COMPLETED" => Ok(TransferStatus::Completed),
+            "FAILED" => Ok(TransferStatus::Failed),
+            "PARTIALLY_REVERSED" => Ok(TransferStatus::PartiallyReversed),
+            "REVERSED" => Ok(TransferStatus::Reversed),
+            other => Err(anyhow!(TransferMapperError::UnknownStatus(String::from(
+                other
+            )))),
+        }
+    }
+
+    fn map_from_status(&self, status: TransferStatus) -> &'static str {
+        match status {
+            TransferStatus::Pending => "PENDING",
+            TransferStatus::Completed => "COMPLETED",
+            TransferStatus::Failed => "FAILED",
+            TransferStatus::PartiallyReversed => "PARTIALLY_REVERSED",
+            TransferStatus::Reversed => "REVERSED",
+        }
+    }
+}
@@ -0,0 +1,330 @@
+use crate::account_mapper::AccountMapper;
+use crate::activity_repository::ActivityRepository;
+use crate::domain_event_mapper::DomainEventMapper;
+use crate::outbox_repository::OutboxRepository;
+use crate::transfer_mapper::TransferMapper;
+use crate::transfer_repository::TransferRepository;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::{
+    load_transfer_port::LoadTransferPort, update_transfer_state_port::UpdateTransferStatePort,
+};
+use buckpal_application::domain::activity::Activity;
+use buckpal_application::domain::transfer::{Transfer, TransferError, TransferId};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct TransferPersistenceAdapter {
+    transfer_repository: TransferRepository,
+    activity_repository: ActivityRepository,
+    outbox_repository: OutboxRepository,
+    transfer_mapper: TransferMapper,
+    account_mapper: AccountMapper,
+    domain_event_mapper: DomainEventMapper,
+}
+
+impl TransferPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            transfer_repository: TransferRepository::new(pool.clone()),
+            activity_repository: ActivityRepository::new(pool.clone()),
+            outbox_repository: OutboxRepository::new(pool),
+            transfer_mapper: TransferMapper::default(),
+            account_mapper: AccountMapper::default(),
+            domain_event_mapper: DomainEventMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl LoadTransferPort for TransferPersistenceAdapter {
+    async fn load_transfer(&self, transfer_id: &TransferId) -> Result<Transfer> {
+        let transfer_entity = self
+            .transfer_repository
+            .find_by_id(transfer_id.0)
+            .await
+            .map_err(|err| match err.downcast_ref::<sqlx::Error>() {
+                Some(sqlx::Error::RowNotFound) => {
+                    anyhow!(TransferError::TransferNotFound(transfer_id.0))
+                }
+                _ => err,
+            })?;
+
+        self.transfer_mapper.map_to_domain_entity(transfer_entity)
+    }
+
+    async fn load_transfer_activities(&self, transfer_id: &TransferId) -> Result<Vec<Activity>> {
+        let activities = self
+            .activity_repository
+            .find_by_transfer_id(transfer_id.0)
+            .await?;
+
+        Ok(activities
+            .iter()
+            .map(|activity| self.account_mapper.map_to_activity(activity))
+            .collect())
+    }
+
+    async fn load_reversals(&self, transfer_id: &TransferId) -> Result<Vec<Transfer>> {
+        let transfers = self
+            .transfer_repository
+            .find_by_reversed_transfer_id(transfer_id.0)
+            .await?;
+
+        transfers
+            .into_iter()
+            .map(|transfer| self.transfer_mapper.map_to_domain_entity(transfer))
+            .collect()
+    }
+}
+
+#[async_trait]
+impl UpdateTransferStatePort for TransferPersistenceAdapter {
+    async fn create_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+        let transfer_entity = self.transfer_mapper.map_to_entity(transfer);
+        // reversals are checked against the reversed transfer once more, other reversals of it
+        // may have been created since it was loaded
+        let transfer_entity = match transfer.reversed_transfer_id {
+            Some(_) => {
+                self.transfer_repository
+                    .save_reversal(&transfer_entity)
+                    .await?
+            }
+            None => self.transfer_repository.save(&transfer_entity).await?,
+        };
+
+        self.transfer_mapper.map_to_domain_entity(transfer_entity)
+    }
+
+    async fn update_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+        let mut tx = self.outbox_repository.begin().await?;
+
+        let transfer_entity = self
+            .transfer_repository
+            .update_status(&mut tx, &self.transfer_mapper.map_to_entity(transfer))
+            .await?;
+
+        // the reversed transfer is brought up to date from what is committed, not from the
+        // reversals the caller happened to load
+        if let Some(reversed_transfer_id) = &transfer.reversed_transfer_id {
+            self.transfer_repository
+                .update_reversed_status_in(&mut tx, reversed_transfer_id.0)
+                .await?;
+        }
+
+        for event in &transfer.events {
+            self.outbox_repository
+                .save_in(&mut tx, &self.domain_event_mapper.map_to_entity(event))
+                .await?;
+        }
+
+        tx.commit().await?;
+
+        self.transfer_mapper.map_to_domain_entity(transfer_entity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransferPersistenceAdapter;
+    use anyhow::Result;
+    use buckpal_application::application::port::outgoing::{
+        load_transfer_port::LoadTransferPort, update_transfer_state_port::UpdateTransferStatePort,
+    };
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::transfer::{Transfer, TransferStatus};
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+    use sqlx::postgres::{PgPool, PgPoolOptions};
+
+    #[async_std::test]
+    async fn creates_and_completes_transfer() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = TransferPersistenceAdapter::new(pool.clone());
+
+        let mut transfer = adapter
+            .create_transfer(&Transfer::new_without_id(
+                AccountId(41),
+                AccountId(42),
+                Utc::now(),
+                money!(500, "AUD"),
+            ))
+            .await
+            .unwrap();
+        let transfer_id = transfer.id.clone().unwrap();
+
+        transfer.complete();
+        adapter.update_transfer(&transfer).await.unwrap();
+
+        let loaded_transfer = adapter.load_transfer(&transfer_id).await.unwrap();
+        let legs = adapter
+            .load_transfer_activities(&transfer_id)
+            .await
+            .unwrap();
+        let outbox_entries = delete_outbox_entries_for_transfer_id(transfer_id.0, &pool)
+            .await
+            .unwrap();
+
+        delete_transfer_with_id(transfer_id.0, &pool).await.unwrap();
+
+        assert_eq!(loaded_transfer.status, TransferStatus::Completed);
+        assert_eq!(loaded_transfer.source_account_id, AccountId(41));
+        assert_eq!(loaded_transfer.target_account_id, AccountId(42));
+        assert_eq!(loaded_transfer.money, money!(500, "AUD"));
+        assert_eq!(legs.len(), 0);
+        assert_eq!(outbox_entries, 1);
+    }
+
+    #[async_std::test]
+    async fn rejects_reversal_exceeding_what_is_left() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = TransferPersistenceAdapter::new(pool.clone());
+
+        let mut original = adapter
+            .create_transfer(&Transfer::new_without_id(
+                AccountId(41),
+                AccountId(42),
+                Utc::now(),
+                money!(500, "AUD"),
+            ))
+            .await
+            .unwrap();
+        original.status = TransferStatus::Completed;
+
+        // both reversals are checked against the same, stale view of the original transfer, as
+        // two concurrent reversals would be
+        let first = adapter
+            .create_transfer(
+                &original
+                    .reverse(&money!(300, "AUD"), &[], Utc::now())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let second = adapter
+            .create_transfer(
+                &original
+                    .reverse(&money!(300, "AUD"), &[], Utc::now())
+                    .unwrap(),
+            )
+            .await;
+
+        delete_transfer_with_id(first.id.unwrap().0, &pool)
+            .await
+            .unwrap();
+        delete_transfer_with_id(original.id.unwrap().0, &pool)
+            .await
+            .unwrap();
+
+        assert_eq!(
+            second.unwrap_err().to_string(),
+            "Reversal of `300` exceeds the remaining reversible amount of `200`"
+        );
+    }
+
+    #[async_std::test]
+    async fn completing_reversals_updates_the_reversed_transfer() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = TransferPersistenceAdapter::new(pool.clone());
+
+        let mut original = adapter
+            .create_transfer(&Transfer::new_without_id(
+                AccountId(41),
+                AccountId(42),
+                Utc::now(),
+                money!(500, "AUD"),
+            ))
+            .await
+            .unwrap();
+        original.status = TransferStatus::Completed;
+        adapter.update_transfer(&original).await.unwrap();
+        let original_id = original.id.clone().unwrap();
+
+        // both reversals are created from the same view of the original transfer, as two
+        // concurrent reversals would be
+        let mut first = adapter
+            .create_transfer(
+                &original
+                    .reverse(&money!(300, "AUD"), &[], Utc::now())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let mut second = adapter
+            .create_transfer(
+                &original
+                    .reverse(&money!(200, "AUD"), &[], Utc::now())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+
+        first.complete();
+        adapter.update_transfer(&first).await.unwrap();
+        let after_first = adapter.load_transfer(&original_id).await.unwrap();
+
+        second.complete();
+        adapter.update_transfer(&second).await.unwrap();
+        let after_second = adapter.load_transfer(&original_id).await.unwrap();
+
+        for transfer_id in &[first.id.unwrap(), second.id.unwrap(), original_id] {
+            delete_outbox_entries_for_transfer_id(transfer_id.0, &pool)
+                .await
+                .unwrap();
+            delete_transfer_with_id(transfer_id.0, &pool).await.unwrap();
+        }
+
+        assert_eq!(after_first.status, TransferStatus::PartiallyReversed);
+        assert_eq!(after_second.status, TransferStatus::Reversed);
+    }
+
+    async fn delete_outbox_entries_for_transfer_id(transfer_id: i32, pool: &PgPool) -> Result<u64> {
+        let deleted = sqlx::query!(
+            r#"
+                DELETE FROM outbox WHERE transfer_id = $1
+            "#,
+            transfer_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(deleted.rows_affected())
+    }
+
+    async fn delete_transfer_with_id(transfer_id: i32, pool: &PgPool) -> Result<()> {
+        sqlx::query!(
+            r#"
+                DELETE FROM transfer WHERE id = $1
+            "#,
+            transfer_id,
+        )
+        .execute(pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,379 @@
+use crate::transfer_entity::TransferEntity;
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::transfer::TransferError;
+use sqlx::postgres::PgPool;
+use sqlx::{Postgres, Transaction};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransferRepositoryError {
+    #[error("Transfer already has an id `{0}`, skipping insert")]
+    AlreadyHasAnIdException(i32),
+    #[error("Transfer has no id, skipping update")]
+    MissingIdException,
+    #[error("Transfer is no reversal, skipping insert")]
+    NotAReversalException,
+}
+
+#[derive(Debug, Clone)]
+pub struct TransferRepository {
+    pool: PgPool,
+}
+
+impl TransferRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn save(&self, transfer_entity: &TransferEntity) -> Result<TransferEntity> {
+        let mut tx = self.pool.begin().await?;
+        let entity = self.save_in(&mut tx, transfer_entity).await?;
+        tx.commit().await?;
+
+        Ok(entity)
+    }
+
+    /// Inserts a reversal unless it exceeds what is left of the reversed transfer. The reversed
+    /// transfer stays locked until the reversal is committed, so that concurrent reversals of it
+    /// are checked one after the other.
+    pub async fn save_reversal(&self, transfer_entity: &TransferEntity) -> Result<TransferEntity> {
+        let reversed_transfer_id = transfer_entity
+            .reversed_transfer_id
+            .ok_or_else(|| anyhow!(TransferRepositoryError::NotAReversalException))?;
+
+        let mut tx = self.pool.begin().await?;
+
+        let remaining = sqlx::query!(
+            r#"
+                SELECT 
+                        (amount - COALESCE((
+                            SELECT 
+                                    SUM(reversal.amount) 
+                            FROM 
+                                    transfer reversal 
+                            WHERE 
+                                    reversal.reversed_transfer_id = transfer.id 
+                            AND 
+                                    reversal.status <> 'FAILED'
+                        ), 0))::BIGINT AS "remaining!"
+                FROM 
+                        transfer
+                WHERE 
+                        id = $1
+                FOR UPDATE
+            "#,
+            reversed_transfer_id
+        )
+        .fetch_one(&mut tx)
+        .await?
+        .remaining;
+
+        if transfer_entity.amount > remaining {
+            return Err(anyhow!(TransferError::ReversalExceedsRemaining {
+                requested: transfer_entity.amount,
+                remaining,
+            }));
+        }
+
+        let entity = self.save_in(&mut tx, transfer_entity).await?;
+        tx.commit().await?;
+
+        Ok(entity)
+    }
+
+    /// Takes the next transfer id without inserting a transfer, so that activities can refer to
+    /// a transfer that is inserted together with them.
+    pub async fn reserve_id(&self) -> Result<i32> {
+        let reserved = sqlx::query!(
+            r#"
+                SELECT nextval('transfer_id_seq')::INT AS "id!"
+            "#
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(reserved.id)
+    }
+
+    /// Inserts the transfer under the id reserved for it as part of a larger transaction.
+    pub async fn save_reserved_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        transfer_entity: &TransferEntity,
+    ) -> Result<TransferEntity> {
+        let transfer_id = transfer_entity
+            .id
+            .ok_or_else(|| anyhow!(TransferRepositoryError::MissingIdException))?;
+
+        let entity = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            transfer (id, timestamp, source_account_id, target_account_id, amount, status, reversed_transfer_id, reference)
+                VALUES 
+                            ($1, $2, $3, $4, $5, $6, $7, $8)
+                RETURNING 
+                            id, timestamp, source_account_id, target_account_id, amount, status, reversed_transfer_id, reference 
+            "#,
+            transfer_id,
+            transfer_entity.timestamp,
+            transfer_entity.source_account_id,
+            transfer_entity.target_account_id,
+            transfer_entity.amount,
+            transfer_entity.status,
+            transfer_entity.reversed_transfer_id,
+            transfer_entity.reference
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entity = TransferEntity::new(
+            Some(entity.id),
+            entity.timestamp,
+            entity.source_account_id,
+            entity.target_account_id,
+            entity.amount,
+            entity.status,
+            entity.reversed_transfer_id,
+            entity.reference,
+        );
+
+        Ok(entity)
+    }
+
+    /// Inserts the transfer as part of a larger transaction.
+    pub async fn save_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        transfer_entity: &TransferEntity,
+    ) -> Result<TransferEntity> {
+        match transfer_entity.id {
+            Some(transfer_id) => Err(anyhow!(TransferRepositoryError::AlreadyHasAnIdException(
+                transfer_id
+            ))),
+            None => {
+                let entity = sqlx::query!(
+                    r#"
+                        INSERT INTO 
+                                    transfer (timestamp, source_account_id, target_account_id, amount, status, reversed_transfer_id, reference)
+                        VALUES 
+                                    ($1, $2, $3, $4, $5, $6, $7)
+                        RETURNING 
+                                    id, timestamp, source_account_id, target_account_id, amount, status, reversed_transfer_id, reference 
+                    "#,
+                    transfer_entity.timestamp,
+                    transfer_entity.source_account_id,
+                    transfer_entity.target_account_id,
+                    transfer_entity.amount,
+                    transfer_entity.status,
+                    transfer_entity.reversed_transfer_id,
+                    transfer_entity.reference
+                )
+                .fetch_one(&mut *tx)
+                .await?;
+
+                let entity = TransferEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.status,
+                    entity.reversed_transfer_id,
+                    entity.reference,
+                );
+
+                Ok(entity)
+            }
+        }
+    }
+
+    /// Updates the status as part of a larger transaction.
+    pub async fn update_status(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        transfer_entity: &TransferEntity,
+    ) -> Result<TransferEntity> {
+        let transfer_id = transfer_entity
+            .id
+            .ok_or_else(|| anyhow!(TransferRepositoryError::MissingIdException))?;
+
+        let entity = sqlx::query!(
+            r#"
+                UPDATE 
+                        transfer
+                SET 
+                        status = $2
+                WHERE 
+                        id = $1
+                RETURNING 
+                        id, timestamp, source_account_id, target_account_id, amount, status, reversed_transfer_id, reference 
+            "#,
+            transfer_id,
+            transfer_entity.status
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entity = TransferEntity::new(
+            Some(entity.id),
+            entity.timestamp,
+            entity.source_account_id,
+            entity.target_account_id,
+            entity.amount,
+            entity.status,
+            entity.reversed_transfer_id,
+            entity.reference,
+        );
+
+        Ok(entity)
+    }
+
+    /// Brings the status of a reversed transfer up to date with its completed reversals as part
+    /// of a larger transaction. The reversed transfer is locked first, as it is while a reversal
+    /// of it is saved, so that the reversals are summed only once concurrent ones are committed.
+    pub async fn update_reversed_status_in(
+        &self,
+        tx: &mut Transaction<'_, Postgres>,
+        reversed_transfer_id: i32,
+    ) -> Result<TransferEntity> {
+        sqlx::query!(
+            r#"
+                SELECT 
+                        id
+                FROM 
+                        transfer
+                WHERE 
+                        id = $1
+                FOR UPDATE
+            "#,
+            reversed_transfer_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entity = sqlx::query!(
+            r#"
+                UPDATE 
+                        transfer
+                SET 
+                        status = CASE
+                            WHEN reversed.amount = 0 THEN transfer.status
+                            WHEN reversed.amount < transfer.amount THEN 'PARTIALLY_REVERSED'
+                            ELSE 'REVERSED'
+                        END
+                FROM (
+                        SELECT 
+                                COALESCE(SUM(reversal.amount), 0) AS amount
+                        FROM 
+                                transfer reversal 
+                        WHERE 
+                                reversal.reversed_transfer_id = $1 
+                        AND 
+                                reversal.status = 'COMPLETED'
+                ) reversed
+                WHERE 
+                        transfer.id = $1
+                RETURNING 
+                        transfer.id, transfer.timestamp, transfer.source_account_id, transfer.target_account_id, transfer.amount, transfer.status, transfer.reversed_transfer_id, transfer.reference 
+            "#,
+            reversed_transfer_id
+        )
+        .fetch_one(&mut *tx)
+        .await?;
+
+        let entity = TransferEntity::new(
+            Some(entity.id),
+            entity.timestamp,
+            entity.source_account_id,
+            entity.target_account_id,
+            entity.amount,
+            entity.status,
+            entity.reversed_transfer_id,
+            entity.reference,
+        );
+
+        Ok(entity)
+    }
+
+    pub async fn find_by_id(&self, transfer_id: i32) -> Result<TransferEntity> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT
+                        id,
+                        timestamp,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        status,
+                        reversed_transfer_id,
+                        reference
+                FROM 
+                        transfer
+                WHERE 
+                        id = $1
+            "#,
+            transfer_id
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let entity = TransferEntity::new(
+            Some(entity.id),
+            entity.timestamp,
+            entity.source_account_id,
+            entity.target_account_id,
+            entity.amount,
+            entity.status,
+            entity.reversed_transfer_id,
+            entity.reference,
+        );
+
+        Ok(entity)
+    }
+
+    pub async fn find_by_reversed_transfer_id(
+        &self,
+        reversed_transfer_id: i32,
+    ) -> Result<Vec<TransferEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT
+                        id,
+                        timestamp,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        status,
+                        reversed_transfer_id,
+                        reference
+                FROM 
+                        transfer
+                WHERE 
+                        reversed_transfer_id = $1
+                ORDER BY
+                        id
+            "#,
+            reversed_transfer_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let entities = entities
+            .into_iter()
+            .map(|entity| {
+                TransferEntity::new(
+                    Some(entity.id),
+                    entity.timestamp,
+                    entity.source_account_id,
+                    entity.target_account_id,
+                    entity.amount,
+                    entity.status,
+                    entity.reversed_transfer_id,
+                    entity.reference,
+                )
+            })
+            .collect();
+
+        Ok(entities)
+    }
+}
@@ -0,0 +1,44 @@
+use crate::domain_event_entity::DomainEventEntity;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookSubscriptionEntity {
+    pub id: Option<i32>,
+    pub url: String,
+    pub secret: String,
+    /// The names of the subscribed events, separated by commas.
+    pub event_types: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscriptionEntity {
+    pub fn new(
+        id: Option<i32>,
+        url: String,
+        secret: String,
+        event_types: String,
+        created_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id,
+            url,
+            secret,
+            event_types,
+            created_at,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookDeliveryEntity {
+    pub id: Option<i32>,
+    pub subscription_id: i32,
+    pub event_id: String,
+    pub event: DomainEventEntity,
+    pub status: String,
+    pub attempts: i32,
+    pub next_attempt_at: DateTime<Utc>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
@@ -0,0 +1,99 @@
+use crate::domain_event_mapper::DomainEventMapper;
+use crate::webhook_entity::{WebhookDeliveryEntity, WebhookSubscriptionEntity};
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::webhook::{
+    WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum WebhookMapperError {
+    #[error("Unknown webhook delivery status `{0}`")]
+    UnknownStatus(String),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct WebhookMapper {
+    domain_event_mapper: DomainEventMapper,
+}
+
+impl WebhookMapper {
+    pub fn map_to_subscription(&self, entity: WebhookSubscriptionEntity) -> WebhookSubscription {
+        WebhookSubscription {
+            id: entity.id.map(WebhookSubscriptionId),
+            url: entity.url,
+            secret: entity.secret,
+            event_types: entity
+                .event_types
+                .split(',')
+                .filter(|event_type| !event_type.is_empty())
+                .map(String::from)
+                .collect(),
+            created_at: entity.created_at,
+        }
+    }
+
+    pub fn map_to_subscription_entity(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> WebhookSubscriptionEntity {
+        WebhookSubscriptionEntity::new(
+            subscription.id.clone().map(|id| id.0),
+            subscription.url.clone(),
+            subscription.secret.clone(),
+            subscription.event_types.join(","),
+            subscription.created_at,
+        )
+    }
+
+    pub fn map_to_delivery(&self, entity: WebhookDeliveryEntity) -> Result<WebhookDelivery> {
+        Ok(WebhookDelivery {
+            id: entity.id.map(WebhookDeliveryId),
+            subscription_id: WebhookSubscriptionId(entity.subscription_id),
+            event: self
+                .domain_event_mapper
+                .map_to_domain_event(&entity.event)?,
+            status: self.map_to_status(&entity.status)?,
+            attempts: entity.attempts,
+            next_attempt_at: entity.next_attempt_at,
+            last_error: entity.last_error,
+            created_at: entity.created_at,
+            delivered_at: entity.delivered_at,
+        })
+    }
+
+    pub fn map_to_delivery_entity(&self, delivery: &WebhookDelivery) -> WebhookDeliveryEntity {
+        WebhookDeliveryEntity {
+            id: delivery.id.clone().map(|id| id.0),
+            subscription_id: delivery.subscription_id.0,
+            event_id: delivery.event.id(),
+            event: self.domain_event_mapper.map_to_entity(&delivery.event),
+            status: String::from(self.map_from_status(delivery.status)),
+            attempts: delivery.attempts,
+            next_attempt_at: delivery.next_attempt_at,
+            last_error: delivery.last_error.clone(),
+            created_at: delivery.created_at,
+            delivered_at: delivery.delivered_at,
+        }
+    }
+
+    fn map_to_status(&self, status: &str) -> Result<WebhookDeliveryStatus> {
+        match status {
+            "PENDING" => Ok(WebhookDeliveryStatus::Pending),
+            "DELIVERED" => Ok(WebhookDeliveryStatus::Delivered),
+            "DEAD_LETTERED" => Ok(WebhookDeliveryStatus::DeadLettered),
+            other => Err(anyhow!(WebhookMapperError::UnknownStatus(String::from(
+                other
+            )))),
+        }
+    }
+
+    fn map_from_status(&self, status: WebhookDeliveryStatus) -> &'static str {
+        match status {
+            WebhookDeliveryStatus::Pending => "PENDING",
+            WebhookDeliveryStatus::Delivered => "DELIVERED",
+            WebhookDeliveryStatus::DeadLettered => "DEAD_LETTERED",
+        }
+    }
+}
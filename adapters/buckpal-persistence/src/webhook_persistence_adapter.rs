@@ -0,0 +1,212 @@
+use crate::webhook_mapper::WebhookMapper;
+use crate::webhook_repository::WebhookRepository;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::webhook_port::WebhookPort;
+use buckpal_application::domain::domain_event::DomainEvent;
+use buckpal_application::domain::webhook::{
+    WebhookDelivery, WebhookDeliveryId, WebhookSubscription, WebhookSubscriptionId,
+};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+
+#[derive(Debug, Clone)]
+pub struct WebhookPersistenceAdapter {
+    webhook_repository: WebhookRepository,
+    webhook_mapper: WebhookMapper,
+}
+
+impl WebhookPersistenceAdapter {
+    pub fn new(pool: PgPool) -> Self {
+        Self {
+            webhook_repository: WebhookRepository::new(pool),
+            webhook_mapper: WebhookMapper::default(),
+        }
+    }
+}
+
+#[async_trait]
+impl WebhookPort for WebhookPersistenceAdapter {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription> {
+        let entity = self
+            .webhook_repository
+            .save_subscription(&self.webhook_mapper.map_to_subscription_entity(subscription))
+            .await?;
+
+        Ok(self.webhook_mapper.map_to_subscription(entity))
+    }
+
+    async fn load_subscriptions(&self) -> Result<Vec<WebhookSubscription>> {
+        let entities = self.webhook_repository.find_subscriptions().await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| self.webhook_mapper.map_to_subscription(entity))
+            .collect())
+    }
+
+    async fn load_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Option<WebhookSubscription>> {
+        let entity = self
+            .webhook_repository
+            .find_subscription_by_id(subscription_id.0)
+            .await?;
+
+        Ok(entity.map(|entity| self.webhook_mapper.map_to_subscription(entity)))
+    }
+
+    async fn delete_subscription(&self, subscription_id: &WebhookSubscriptionId) -> Result<bool> {
+        self.webhook_repository
+            .delete_subscription(subscription_id.0)
+            .await
+    }
+
+    async fn load_subscriptions_for(
+        &self,
+        event: &DomainEvent,
+    ) -> Result<Vec<WebhookSubscription>> {
+        let entities = self
+            .webhook_repository
+            .find_subscriptions_by_event_type(event.name())
+            .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| self.webhook_mapper.map_to_subscription(entity))
+            .collect())
+    }
+
+    async fn save_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<()> {
+        let entities: Vec<_> = deliveries
+            .iter()
+            .map(|delivery| self.webhook_mapper.map_to_delivery_entity(delivery))
+            .collect();
+
+        self.webhook_repository.save_deliveries(&entities).await
+    }
+
+    async fn load_delivery(
+        &self,
+        delivery_id: &WebhookDeliveryId,
+    ) -> Result<Option<WebhookDelivery>> {
+        self.webhook_repository
+            .find_delivery_by_id(delivery_id.0)
+            .await?
+            .map(|entity| self.webhook_mapper.map_to_delivery(entity))
+            .transpose()
+    }
+
+    async fn load_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Vec<WebhookDelivery>> {
+        self.webhook_repository
+            .find_deliveries_by_subscription_id(subscription_id.0)
+            .await?
+            .into_iter()
+            .map(|entity| self.webhook_mapper.map_to_delivery(entity))
+            .collect()
+    }
+
+    async fn load_due_deliveries(
+        &self,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>> {
+        self.webhook_repository
+            .find_due_deliveries(now, limit)
+            .await?
+            .into_iter()
+            .map(|entity| self.webhook_mapper.map_to_delivery(entity))
+            .collect()
+    }
+
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+        self.webhook_repository
+            .update_delivery(&self.webhook_mapper.map_to_delivery_entity(delivery))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::WebhookPersistenceAdapter;
+    use buckpal_application::application::port::outgoing::webhook_port::WebhookPort;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::domain_event::DomainEvent;
+    use buckpal_application::domain::transfer::TransferId;
+    use buckpal_application::domain::webhook::{
+        WebhookDelivery, WebhookDeliveryStatus, WebhookSubscription,
+    };
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+    use sqlx::postgres::PgPoolOptions;
+
+    #[async_std::test]
+    async fn queues_each_event_once_per_subscription() {
+        let database_url = std::env::var("DATABASE_URL")
+            .unwrap_or(String::from("postgres://localhost/buckpal_test"));
+
+        let pool = PgPoolOptions::new()
+            .max_connections(5)
+            .connect(&database_url)
+            .await
+            .unwrap();
+
+        let adapter = WebhookPersistenceAdapter::new(pool);
+
+        let subscription = adapter
+            .save_subscription(
+                &WebhookSubscription::new(
+                    "http://localhost:9/hooks",
+                    "0123456789abcdef",
+                    &[String::from("MoneyDeposited")],
+                    Utc::now(),
+                )
+                .unwrap(),
+            )
+            .await
+            .unwrap();
+        let subscription_id = subscription.id.clone().unwrap();
+
+        let event = DomainEvent::MoneyDeposited {
+            account_id: AccountId(952),
+            source_account_id: AccountId(951),
+            transfer_id: TransferId(95),
+            money: money!(500, "AUD"),
+            timestamp: DateTime::<Utc>::from_utc(
+                NaiveDate::from_ymd(2021, 4, 18).and_hms(9, 0, 0),
+                Utc,
+            ),
+        };
+        let delivery = WebhookDelivery::new(subscription_id.clone(), event.clone(), Utc::now());
+
+        let subscribed = adapter.load_subscriptions_for(&event).await.unwrap();
+        adapter.save_deliveries(&[delivery.clone()]).await.unwrap();
+        adapter.save_deliveries(&[delivery]).await.unwrap();
+
+        let mut deliveries = adapter.load_deliveries(&subscription_id).await.unwrap();
+        deliveries[0].succeed(Utc::now());
+        adapter.update_delivery(&deliveries[0]).await.unwrap();
+        let delivered = adapter
+            .load_delivery(deliveries[0].id.as_ref().unwrap())
+            .await
+            .unwrap()
+            .unwrap();
+
+        adapter.delete_subscription(&subscription_id).await.unwrap();
+
+        assert!(subscribed
+            .iter()
+            .any(|subscribed| subscribed.id == subscription.id));
+        assert_eq!(deliveries.len(), 1);
+        assert_eq!(delivered.event, event);
+        assert_eq!(delivered.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivered.attempts, 1);
+    }
+}
@@ -0,0 +1,407 @@
+use crate::domain_event_entity::DomainEventEntity;
+use crate::webhook_entity::{WebhookDeliveryEntity, WebhookSubscriptionEntity};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use sqlx::postgres::PgPool;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum WebhookRepositoryError {
+    #[error("Webhook subscription already has an id `{0}`, skipping insert")]
+    AlreadyHasAnIdException(i32),
+    #[error("Webhook delivery has no id, can't update it")]
+    MissingIdException,
+}
+
+/// A row of the `webhook_delivery` table, with the event in its own columns.
+struct WebhookDeliveryRow {
+    id: i32,
+    subscription_id: i32,
+    event_id: String,
+    account_id: i32,
+    event_type: String,
+    transfer_id: i32,
+    source_account_id: i32,
+    target_account_id: i32,
+    amount: i64,
+    timestamp: DateTime<Utc>,
+    status: String,
+    attempts: i32,
+    next_attempt_at: DateTime<Utc>,
+    last_error: Option<String>,
+    created_at: DateTime<Utc>,
+    delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<WebhookDeliveryRow> for WebhookDeliveryEntity {
+    fn from(row: WebhookDeliveryRow) -> Self {
+        Self {
+            id: Some(row.id),
+            subscription_id: row.subscription_id,
+            event_id: row.event_id,
+            event: DomainEventEntity {
+                account_id: row.account_id,
+                event_type: row.event_type,
+                transfer_id: row.transfer_id,
+                source_account_id: row.source_account_id,
+                target_account_id: row.target_account_id,
+                amount: row.amount,
+                timestamp: row.timestamp,
+            },
+            status: row.status,
+            attempts: row.attempts,
+            next_attempt_at: row.next_attempt_at,
+            last_error: row.last_error,
+            created_at: row.created_at,
+            delivered_at: row.delivered_at,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookRepository {
+    pool: PgPool,
+}
+
+impl WebhookRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn save_subscription(
+        &self,
+        entity: &WebhookSubscriptionEntity,
+    ) -> Result<WebhookSubscriptionEntity> {
+        if let Some(id) = entity.id {
+            return Err(anyhow!(WebhookRepositoryError::AlreadyHasAnIdException(id)));
+        }
+
+        let row = sqlx::query!(
+            r#"
+                INSERT INTO 
+                            webhook_subscription (url, secret, event_types, created_at)
+                VALUES 
+                            ($1, $2, $3, $4)
+                RETURNING 
+                            id 
+            "#,
+            entity.url,
+            entity.secret,
+            entity.event_types,
+            entity.created_at
+        )
+        .fetch_one(&self.pool)
+        .await?;
+
+        let mut saved = entity.clone();
+        saved.id = Some(row.id);
+
+        Ok(saved)
+    }
+
+    pub async fn find_subscriptions(&self) -> Result<Vec<WebhookSubscriptionEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        url,
+                        secret,
+                        event_types,
+                        created_at
+                FROM 
+                        webhook_subscription
+                ORDER BY 
+                        id
+            "#
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                WebhookSubscriptionEntity::new(
+                    Some(entity.id),
+                    entity.url,
+                    entity.secret,
+                    entity.event_types,
+                    entity.created_at,
+                )
+            })
+            .collect())
+    }
+
+    pub async fn find_subscription_by_id(
+        &self,
+        id: i32,
+    ) -> Result<Option<WebhookSubscriptionEntity>> {
+        let entity = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        url,
+                        secret,
+                        event_types,
+                        created_at
+                FROM 
+                        webhook_subscription
+                WHERE 
+                        id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(entity.map(|entity| {
+            WebhookSubscriptionEntity::new(
+                Some(entity.id),
+                entity.url,
+                entity.secret,
+                entity.event_types,
+                entity.created_at,
+            )
+        }))
+    }
+
+    /// Finds the subscriptions listing the event type among theirs.
+    pub async fn find_subscriptions_by_event_type(
+        &self,
+        event_type: &str,
+    ) -> Result<Vec<WebhookSubscriptionEntity>> {
+        let entities = sqlx::query!(
+            r#"
+                SELECT 
+                        id,
+                        url,
+                        secret,
+                        event_types,
+                        created_at
+                FROM 
+                        webhook_subscription
+                WHERE 
+                        $1 = ANY(string_to_array(event_types, ','))
+                ORDER BY 
+                        id
+            "#,
+            event_type
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entities
+            .into_iter()
+            .map(|entity| {
+                WebhookSubscriptionEntity::new(
+                    Some(entity.id),
+                    entity.url,
+                    entity.secret,
+                    entity.event_types,
+                    entity.created_at,
+                )
+            })
+            .collect())
+    }
+
+    /// Deletes the subscription, its deliveries go with it. Returns whether there was one.
+    pub async fn delete_subscription(&self, id: i32) -> Result<bool> {
+        let deleted = sqlx::query!(
+            r#"
+                DELETE FROM webhook_subscription WHERE id = $1
+            "#,
+            id
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(deleted.rows_affected() > 0)
+    }
+
+    /// Inserts the deliveries in one transaction, skipping those whose event is already queued
+    /// for their subscription.
+    pub async fn save_deliveries(&self, entities: &[WebhookDeliveryEntity]) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+
+        for entity in entities {
+            sqlx::query!(
+                r#"
+                    INSERT INTO 
+                                webhook_delivery (subscription_id, event_id, account_id, event_type, transfer_id, source_account_id, target_account_id, amount, timestamp, status, attempts, next_attempt_at, last_error, created_at, delivered_at)
+                    VALUES 
+                                ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15)
+                    ON CONFLICT (subscription_id, event_id) DO NOTHING
+                "#,
+                entity.subscription_id,
+                entity.event_id,
+                entity.event.account_id,
+                entity.event.event_type,
+                entity.event.transfer_id,
+                entity.event.source_account_id,
+                entity.event.target_account_id,
+                entity.event.amount,
+                entity.event.timestamp,
+                entity.status,
+                entity.attempts,
+                entity.next_attempt_at,
+                entity.last_error,
+                entity.created_at,
+                entity.delivered_at
+            )
+            .execute(&mut tx)
+            .await?;
+        }
+
+        tx.commit().await?;
+
+        Ok(())
+    }
+
+    pub async fn find_delivery_by_id(&self, id: i32) -> Result<Option<WebhookDeliveryEntity>> {
+        let row = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"
+                SELECT 
+                        id,
+                        subscription_id,
+                        event_id,
+                        account_id,
+                        event_type,
+                        transfer_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        timestamp,
+                        status,
+                        attempts,
+                        next_attempt_at,
+                        last_error,
+                        created_at,
+                        delivered_at
+                FROM 
+                        webhook_delivery
+                WHERE 
+                        id = $1
+            "#,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(row.map(WebhookDeliveryEntity::from))
+    }
+
+    /// Finds the deliveries of a subscription, the most recent first.
+    pub async fn find_deliveries_by_subscription_id(
+        &self,
+        subscription_id: i32,
+    ) -> Result<Vec<WebhookDeliveryEntity>> {
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"
+                SELECT 
+                        id,
+                        subscription_id,
+                        event_id,
+                        account_id,
+                        event_type,
+                        transfer_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        timestamp,
+                        status,
+                        attempts,
+                        next_attempt_at,
+                        last_error,
+                        created_at,
+                        delivered_at
+                FROM 
+                        webhook_delivery
+                WHERE 
+                        subscription_id = $1
+                ORDER BY 
+                        id DESC
+            "#,
+            subscription_id
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(WebhookDeliveryEntity::from).collect())
+    }
+
+    /// Finds the oldest pending deliveries due at the given point in time.
+    pub async fn find_due_deliveries(
+        &self,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDeliveryEntity>> {
+        let rows = sqlx::query_as!(
+            WebhookDeliveryRow,
+            r#"
+                SELECT 
+                        id,
+                        subscription_id,
+                        event_id,
+                        account_id,
+                        event_type,
+                        transfer_id,
+                        source_account_id,
+                        target_account_id,
+                        amount,
+                        timestamp,
+                        status,
+                        attempts,
+                        next_attempt_at,
+                        last_error,
+                        created_at,
+                        delivered_at
+                FROM 
+                        webhook_delivery
+                WHERE 
+                        status = 'PENDING' AND next_attempt_at <= $1
+                ORDER BY 
+                        next_attempt_at, id
+                LIMIT 
+                        $2
+            "#,
+            *now,
+            limit
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows.into_iter().map(WebhookDeliveryEntity::from).collect())
+    }
+
+    pub async fn update_delivery(&self, entity: &WebhookDeliveryEntity) -> Result<()> {
+        let id = entity
+            .id
+            .ok_or_else(|| anyhow!(WebhookRepositoryError::MissingIdException))?;
+
+        sqlx::query!(
+            r#"
+                UPDATE 
+                        webhook_delivery
+                SET 
+                        status = $2,
+                        attempts = $3,
+                        next_attempt_at = $4,
+                        last_error = $5,
+                        delivered_at = $6
+                WHERE 
+                        id = $1
+            "#,
+            id,
+            entity.status,
+            entity.attempts,
+            entity.next_attempt_at,
+            entity.last_error,
+            entity.delivered_at
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+}
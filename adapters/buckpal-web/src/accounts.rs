@@ -0,0 +1,309 @@
+use crate::audit::audit_context;
+use crate::authentication::principal;
+use crate::transfers::{error_status, to_activity_response, to_amount, ActivityResponse};
+use crate::utils::{json_to_res, wants_format};
+use crate::AppState;
+use buckpal_application::application::port::incoming::list_activities_query::{
+    ActivityCursor, ActivityFilter,
+};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::{ActivityId, ActivityKind};
+use chrono::{DateTime, NaiveDate, TimeZone, Utc};
+use rusty_money::{money, Money};
+use serde::{Deserialize, Serialize};
+use tide::http::mime;
+use tide::{Error, ParamError, Request, Response, StatusCode};
+
+/// The page size used when the client doesn't ask for one.
+const DEFAULT_PAGE_SIZE: i64 = 20;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ListActivitiesParams {
+    from: Option<String>,
+    to: Option<String>,
+    counterparty: Option<i32>,
+    kind: Option<String>,
+    min_amount: Option<i64>,
+    max_amount: Option<i64>,
+    cursor: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ActivityPageResponse {
+    activities: Vec<ActivityResponse>,
+    next_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct GetBalanceParams {
+    as_of: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BalanceResponse {
+    account_id: i32,
+    balance: i64,
+    currency: &'static str,
+    as_of: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BalanceHistoryParams {
+    from: String,
+    to: String,
+    format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct DailyBalanceResponse {
+    date: String,
+    closing_balance: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BalanceHistoryResponse {
+    account_id: i32,
+    currency: &'static str,
+    balances: Vec<DailyBalanceResponse>,
+}
+
+pub fn validate_account_id_param(req: &Request<AppState>) -> tide::Result<i32> {
+    req.param("accountId")
+        .map_err(|err: ParamError<std::num::ParseIntError>| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid accountId: {}", err.to_string()),
+            )
+        })
+}
+
+pub fn parse_timestamp(name: &str, value: &str) -> tide::Result<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|timestamp| timestamp.with_timezone(&Utc))
+        .map_err(|err| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid {}: {}", name, err.to_string()),
+            )
+        })
+}
+
+pub fn parse_date(name: &str, value: &str) -> tide::Result<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|err| {
+        Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!("Invalid {}: {}", name, err.to_string()),
+        )
+    })
+}
+
+fn parse_kind(value: &str) -> tide::Result<ActivityKind> {
+    match value {
+        "deposit" => Ok(ActivityKind::Deposit),
+        "withdrawal" => Ok(ActivityKind::Withdrawal),
+        other => Err(Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!("Invalid kind: `{}`", other),
+        )),
+    }
+}
+
+/// Cursors are opaque to clients, they encode the timestamp in nanoseconds and the ID of the
+/// last activity of a page.
+fn encode_cursor(cursor: &ActivityCursor) -> String {
+    format!(
+        "{}_{}",
+        cursor.timestamp.timestamp_nanos(),
+        cursor.activity_id.0
+    )
+}
+
+fn decode_cursor(value: &str) -> tide::Result<ActivityCursor> {
+    let invalid_cursor = || {
+        Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!("Invalid cursor: `{}`", value),
+        )
+    };
+
+    let mut parts = value.splitn(2, '_');
+    let nanos: i64 = parts
+        .next()
+        .and_then(|part| part.parse().ok())
+        .ok_or_else(invalid_cursor)?;
+    let activity_id: i32 = parts
+        .next()
+        .and_then(|part| part.parse().ok())
+        .ok_or_else(invalid_cursor)?;
+
+    Ok(ActivityCursor {
+        timestamp: Utc.timestamp_nanos(nanos),
+        activity_id: ActivityId(activity_id),
+    })
+}
+
+fn to_filter(params: &ListActivitiesParams) -> tide::Result<ActivityFilter> {
+    Ok(ActivityFilter {
+        from: params
+            .from
+            .as_deref()
+            .map(|from| parse_timestamp("from", from))
+            .transpose()?,
+        to: params
+            .to
+            .as_deref()
+            .map(|to| parse_timestamp("to", to))
+            .transpose()?,
+        counterparty_account_id: params.counterparty.map(AccountId),
+        kind: params.kind.as_deref().map(parse_kind).transpose()?,
+        min_amount: params.min_amount.map(|amount| money!(amount, "AUD")),
+        max_amount: params.max_amount.map(|amount| money!(amount, "AUD")),
+    })
+}
+
+pub async fn handle_get_balance(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+    let params: GetBalanceParams = req.query()?;
+
+    let as_of = params
+        .as_of
+        .as_deref()
+        .map(|as_of| parse_timestamp("asOf", as_of))
+        .transpose()?
+        .unwrap_or_else(Utc::now);
+
+    let get_account_balance_query = req.state().get_account_balance_query.clone();
+
+    let balance = get_account_balance_query
+        .get_balance_as_of(
+            &AccountId(account_id),
+            &as_of,
+            &principal(&req)?,
+            &audit_context(&req),
+        )
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let balance_response = BalanceResponse {
+        account_id,
+        balance: to_amount(&balance),
+        currency: "AUD",
+        as_of: as_of.to_rfc3339(),
+    };
+
+    json_to_res(StatusCode::Ok, &balance_response)
+}
+
+pub async fn handle_get_balance_history(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+    let params: BalanceHistoryParams = req.query()?;
+
+    let from = parse_date("from", &params.from)?;
+    let to = parse_date("to", &params.to)?;
+
+    let balance_history_query = req.state().balance_history_query.clone();
+
+    let balances = balance_history_query
+        .get_balance_history(&AccountId(account_id), &from, &to, &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let balances: Vec<DailyBalanceResponse> = balances
+        .iter()
+        .map(|balance| DailyBalanceResponse {
+            date: balance.date.format("%Y-%m-%d").to_string(),
+            closing_balance: to_amount(&balance.closing_balance),
+        })
+        .collect();
+
+    if wants_format(&req, params.format.as_deref(), "csv", "text/csv") {
+        let mut csv = String::from("date,closing_balance\n");
+        for balance in balances {
+            csv.push_str(&format!("{},{}\n", balance.date, balance.closing_balance));
+        }
+
+        let mut res = Response::new(StatusCode::Ok);
+        res.set_body(csv);
+        res.set_content_type(mime::CSV);
+
+        return Ok(res);
+    }
+
+    let balance_history_response = BalanceHistoryResponse {
+        account_id,
+        currency: "AUD",
+        balances,
+    };
+
+    json_to_res(StatusCode::Ok, &balance_history_response)
+}
+
+pub async fn handle_list_activities(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+    let params: ListActivitiesParams = req.query()?;
+
+    let filter = to_filter(&params)?;
+    let cursor = params.cursor.as_deref().map(decode_cursor).transpose()?;
+
+    let list_activities_query = req.state().list_activities_query.clone();
+
+    let page = list_activities_query
+        .list_activities(
+            &AccountId(account_id),
+            &filter,
+            cursor.as_ref(),
+            params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            &principal(&req)?,
+        )
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let activity_page_response = ActivityPageResponse {
+        activities: page.activities.iter().map(to_activity_response).collect(),
+        next_cursor: page.next_cursor.as_ref().map(encode_cursor),
+    };
+
+    json_to_res(StatusCode::Ok, &activity_page_response)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_cursor, encode_cursor};
+    use buckpal_application::application::port::incoming::list_activities_query::ActivityCursor;
+    use buckpal_application::domain::activity::ActivityId;
+    use chrono::{TimeZone, Utc};
+
+    #[test]
+    fn decodes_encoded_cursors() {
+        let cursor = ActivityCursor {
+            timestamp: Utc.ymd(2021, 5, 23).and_hms_nano(9, 2, 14, 123_456_789),
+            activity_id: ActivityId(42),
+        };
+
+        let encoded = encode_cursor(&cursor);
+
+        assert_eq!(encoded, "1621760534123456789_42");
+        assert_eq!(decode_cursor(&encoded).unwrap(), cursor);
+    }
+
+    #[test]
+    fn rejects_malformed_cursors() {
+        for value in &[
+            "",
+            "1621760534123456789",
+            "_42",
+            "abc_42",
+            "1621760534123456789_x",
+        ] {
+            assert!(decode_cursor(value).is_err());
+        }
+    }
+}
@@ -0,0 +1,90 @@
+use crate::accounts::validate_account_id_param;
+use crate::authentication::principal;
+use crate::transfers::error_status;
+use crate::v1::data_to_res;
+use crate::AppState;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity_chain::{BrokenLink, ChainBreak, ChainVerification};
+use serde::Serialize;
+use tide::{Error, Request, Response, StatusCode};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ChainVerificationResponse {
+    account_id: i32,
+    intact: bool,
+    verified_activities: usize,
+    checkpoint_id: Option<i32>,
+    broken_link: Option<BrokenLinkResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BrokenLinkResponse {
+    activity_id: Option<i32>,
+    reason: &'static str,
+    expected_hash: Option<String>,
+    actual_hash: Option<String>,
+}
+
+fn to_broken_link_response(broken_link: &BrokenLink) -> BrokenLinkResponse {
+    let (reason, hashes) = match &broken_link.reason {
+        ChainBreak::Unchained => ("unchained", None),
+        ChainBreak::PreviousHashMismatch { expected, actual } => {
+            ("previous_hash_mismatch", Some((expected, actual)))
+        }
+        ChainBreak::HashMismatch { expected, actual } => {
+            ("hash_mismatch", Some((expected, actual)))
+        }
+        ChainBreak::HeadMismatch { expected, actual } => {
+            ("head_mismatch", Some((expected, actual)))
+        }
+        ChainBreak::CheckpointMismatch { expected, actual } => {
+            ("checkpoint_mismatch", Some((expected, actual)))
+        }
+        ChainBreak::CheckpointSignatureInvalid => ("checkpoint_signature_invalid", None),
+    };
+
+    BrokenLinkResponse {
+        activity_id: broken_link.activity_id.as_ref().map(|id| id.0),
+        reason,
+        expected_hash: hashes.map(|(expected, _)| expected.clone()),
+        actual_hash: hashes.map(|(_, actual)| actual.clone()),
+    }
+}
+
+fn to_chain_verification_response(verification: &ChainVerification) -> ChainVerificationResponse {
+    ChainVerificationResponse {
+        account_id: verification.account_id.0,
+        intact: verification.is_intact(),
+        verified_activities: verification.verified_activities,
+        checkpoint_id: verification.checkpoint_id.as_ref().map(|id| id.0),
+        broken_link: verification
+            .broken_link
+            .as_ref()
+            .map(to_broken_link_response),
+    }
+}
+
+/// Walks the hash chain of the activities of the account. A broken chain is a finding, not a
+/// failure of the request, so it is answered with `200 OK` too.
+pub async fn handle_verify_activity_chain(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+
+    let verify_activity_chain_query = req
+        .state()
+        .verify_activity_chain_query
+        .clone()
+        .expect("expected the route to only be served with a checkpoint key");
+
+    let verification = verify_activity_chain_query
+        .verify_activity_chain(&AccountId(account_id), &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    data_to_res(
+        &req,
+        StatusCode::Ok,
+        to_chain_verification_response(&verification),
+    )
+}
@@ -0,0 +1,230 @@
+use crate::accounts::parse_timestamp;
+use crate::authentication::principal;
+use crate::transfers::error_status;
+use crate::v1::{data_to_res, request_id};
+use crate::AppState;
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::port::incoming::list_audit_log_query::AuditLogFilter;
+use buckpal_application::domain::audit::{
+    AuditContext, AuditEntry, AuditEntryId, AuditOutcome, ErrorCategory,
+};
+use buckpal_application::domain::principal::Principal;
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+use std::sync::Arc;
+use tide::{Error, Middleware, Next, Request, Response, StatusCode};
+
+const DEFAULT_PAGE_SIZE: i64 = 100;
+
+#[derive(Debug, Deserialize)]
+struct ListAuditLogParams {
+    actor: Option<String>,
+    action: Option<String>,
+    from: Option<String>,
+    to: Option<String>,
+    /// The id of the last entry of the previous page.
+    cursor: Option<i32>,
+    limit: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditEntryResponse {
+    id: i32,
+    occurred_at: String,
+    action: String,
+    details: String,
+    actor: Option<String>,
+    source_ip: Option<String>,
+    request_id: Option<String>,
+    outcome: &'static str,
+    error_category: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditLogPageResponse {
+    entries: Vec<AuditEntryResponse>,
+    next_cursor: Option<i32>,
+}
+
+/// The IP address a request came from, as resolved by [`SourceIpResolver`].
+#[derive(Debug, Clone)]
+pub struct SourceIp(pub String);
+
+/// Resolves the IP address requests came from. That is the peer address of the connection,
+/// unless the peer is one of the trusted proxies, whose `Forwarded` or `X-Forwarded-For` header
+/// is believed instead.
+#[derive(Clone, Default)]
+pub struct SourceIpResolver {
+    trusted_proxies: Arc<Vec<IpAddr>>,
+}
+
+impl SourceIpResolver {
+    /// Takes comma separated IP addresses of the trusted proxies, e.g. `10.0.0.1,10.0.0.2`.
+    pub fn new(trusted_proxies: &str) -> Result<Self> {
+        let trusted_proxies = trusted_proxies
+            .split(',')
+            .map(str::trim)
+            .filter(|proxy| !proxy.is_empty())
+            .map(str::parse)
+            .collect::<std::result::Result<Vec<IpAddr>, _>>()?;
+
+        Ok(Self {
+            trusted_proxies: Arc::new(trusted_proxies),
+        })
+    }
+
+    /// The source IP given the peer address of the connection and the client address the
+    /// forwarding headers claim.
+    pub fn resolve(&self, peer_addr: Option<&str>, forwarded_for: Option<&str>) -> Option<String> {
+        let peer_ip = peer_addr.map(|peer_addr| match peer_addr.parse::<SocketAddr>() {
+            Ok(socket_addr) => socket_addr.ip().to_string(),
+            Err(_) => String::from(peer_addr),
+        });
+
+        let peer_is_trusted = peer_ip
+            .as_deref()
+            .and_then(|peer_ip| peer_ip.parse::<IpAddr>().ok())
+            .map_or(false, |peer_ip| self.trusted_proxies.contains(&peer_ip));
+
+        match forwarded_for {
+            Some(forwarded_for) if peer_is_trusted => Some(String::from(forwarded_for)),
+            _ => peer_ip,
+        }
+    }
+}
+
+#[async_trait]
+impl<State: Clone + Send + Sync + 'static> Middleware<State> for SourceIpResolver {
+    async fn handle(&self, mut req: Request<State>, next: Next<'_, State>) -> tide::Result {
+        // `remote` falls back to the peer address when no forwarding header is present
+        if let Some(source_ip) = self.resolve(req.peer_addr(), req.remote()) {
+            req.set_ext(SourceIp(source_ip));
+        }
+
+        Ok(next.run(req).await)
+    }
+}
+
+/// Who sent the request and where it came from.
+pub fn audit_context(req: &Request<AppState>) -> AuditContext {
+    AuditContext::new(
+        req.ext::<Principal>().cloned(),
+        req.ext::<SourceIp>().map(|source_ip| source_ip.0.clone()),
+        request_id(req).or_else(|| {
+            req.header("X-Request-Id")
+                .map(|values| String::from(values.last().as_str()))
+        }),
+    )
+}
+
+fn to_error_category(error_category: ErrorCategory) -> &'static str {
+    match error_category {
+        ErrorCategory::Rejected => "rejected",
+        ErrorCategory::Conflict => "conflict",
+        ErrorCategory::NotFound => "not_found",
+        ErrorCategory::Forbidden => "forbidden",
+        ErrorCategory::Internal => "internal",
+    }
+}
+
+fn to_audit_entry_response(entry: &AuditEntry) -> AuditEntryResponse {
+    let (outcome, error_category) = match entry.outcome {
+        AuditOutcome::Succeeded => ("succeeded", None),
+        AuditOutcome::Failed(error_category) => ("failed", Some(to_error_category(error_category))),
+    };
+
+    AuditEntryResponse {
+        id: entry
+            .id
+            .clone()
+            .expect("expected recorded audit entry to have an ID")
+            .0,
+        occurred_at: entry.occurred_at.to_rfc3339(),
+        action: entry.action.clone(),
+        details: entry.details.clone(),
+        actor: entry.context.actor.as_ref().map(ToString::to_string),
+        source_ip: entry.context.source_ip.clone(),
+        request_id: entry.context.request_id.clone(),
+        outcome,
+        error_category,
+    }
+}
+
+pub async fn handle_list_audit_log(req: Request<AppState>) -> tide::Result<Response> {
+    let params: ListAuditLogParams = req.query()?;
+
+    let filter = AuditLogFilter {
+        actor: params
+            .actor
+            .as_deref()
+            .map(Principal::parse)
+            .transpose()
+            .map_err(|err| Error::from_str(StatusCode::UnprocessableEntity, err.to_string()))?,
+        action: params.action,
+        from: params
+            .from
+            .as_deref()
+            .map(|from| parse_timestamp("from", from))
+            .transpose()?,
+        to: params
+            .to
+            .as_deref()
+            .map(|to| parse_timestamp("to", to))
+            .transpose()?,
+    };
+
+    let list_audit_log_query = req.state().list_audit_log_query.clone();
+
+    let page = list_audit_log_query
+        .list_audit_log(
+            &filter,
+            params.cursor.map(AuditEntryId).as_ref(),
+            params.limit.unwrap_or(DEFAULT_PAGE_SIZE),
+            &principal(&req)?,
+        )
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    data_to_res(
+        &req,
+        StatusCode::Ok,
+        AuditLogPageResponse {
+            entries: page.entries.iter().map(to_audit_entry_response).collect(),
+            next_cursor: page.next_cursor.map(|id| id.0),
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SourceIpResolver;
+
+    #[test]
+    fn ignores_forwarding_headers_of_untrusted_peers() {
+        let resolver = SourceIpResolver::new("").unwrap();
+
+        assert_eq!(
+            resolver.resolve(Some("192.0.2.7:51234"), Some("203.0.113.9")),
+            Some(String::from("192.0.2.7"))
+        );
+        assert_eq!(resolver.resolve(None, Some("203.0.113.9")), None);
+    }
+
+    #[test]
+    fn believes_forwarding_headers_of_trusted_proxies() {
+        let resolver = SourceIpResolver::new("10.0.0.1, 10.0.0.2").unwrap();
+
+        assert_eq!(
+            resolver.resolve(Some("10.0.0.2:443"), Some("203.0.113.9")),
+            Some(String::from("203.0.113.9"))
+        );
+        assert_eq!(
+            resolver.resolve(Some("192.0.2.7:51234"), Some("203.0.113.9")),
+            Some(String::from("192.0.2.7"))
+        );
+        assert!(SourceIpResolver::new("proxy.internal").is_err());
+    }
+}
@@ -0,0 +1,147 @@
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use buckpal_application::domain::principal::Principal;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tide::{Error, Middleware, Next, Request, Response, StatusCode};
+
+/// Authenticates requests by the API key in their `Authorization: Bearer` header and attaches
+/// the principal the key was issued to, rejecting requests without a known key.
+#[derive(Clone)]
+pub struct ApiKeyAuthentication {
+    /// Principals by the hex encoded SHA-256 digest of their key, so that looking a key up
+    /// doesn't compare it with the configured ones byte by byte.
+    principals: Arc<HashMap<String, Principal>>,
+}
+
+impl ApiKeyAuthentication {
+    /// Takes comma separated `key=principal` pairs, e.g. `k1=user:alice,k2=service:payroll`.
+    pub fn new(api_keys: &str) -> Result<Self> {
+        let mut principals = HashMap::new();
+        for pair in api_keys
+            .split(',')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+        {
+            let (key, principal) = match pair.find('=') {
+                Some(index) => (&pair[..index], &pair[index + 1..]),
+                None => return Err(anyhow!("API key entry isn't `key=principal`")),
+            };
+            if key.is_empty() {
+                return Err(anyhow!("API key of `{}` is empty", principal));
+            }
+
+            principals.insert(digest(key), Principal::parse(principal)?);
+        }
+
+        if principals.is_empty() {
+            return Err(anyhow!("No API keys configured"));
+        }
+
+        Ok(Self {
+            principals: Arc::new(principals),
+        })
+    }
+
+    pub fn authenticate(&self, api_key: &str) -> Option<&Principal> {
+        self.principals.get(&digest(api_key))
+    }
+}
+
+fn digest(api_key: &str) -> String {
+    hex::encode(Sha256::digest(api_key.as_bytes()))
+}
+
+fn bearer_token<State>(req: &Request<State>) -> Option<String> {
+    let authorization = req.header("Authorization")?.last().as_str();
+
+    match (authorization.get(..7), authorization.get(7..)) {
+        (Some(scheme), Some(token)) if scheme.eq_ignore_ascii_case("bearer ") => {
+            Some(String::from(token.trim()))
+        }
+        _ => None,
+    }
+}
+
+#[async_trait]
+impl<State: Clone + Send + Sync + 'static> Middleware<State> for ApiKeyAuthentication {
+    async fn handle(&self, mut req: Request<State>, next: Next<'_, State>) -> tide::Result {
+        let principal = bearer_token(&req).and_then(|api_key| self.authenticate(&api_key).cloned());
+
+        match principal {
+            Some(principal) => {
+                req.set_ext(principal);
+
+                Ok(next.run(req).await)
+            }
+            None => {
+                let mut res = Response::new(StatusCode::Unauthorized);
+                res.insert_header("WWW-Authenticate", "Bearer");
+
+                Ok(res)
+            }
+        }
+    }
+}
+
+/// The principal the request was authenticated as.
+pub fn principal<State>(req: &Request<State>) -> tide::Result<Principal> {
+    req.ext::<Principal>()
+        .cloned()
+        .ok_or_else(|| Error::from_str(StatusCode::Unauthorized, "Request isn't authenticated"))
+}
+
+/// Parses comma separated principals, e.g. `user:alice,service:compliance`.
+pub fn parse_principals(principals: &str) -> Result<Vec<Principal>> {
+    let principals = principals
+        .split(',')
+        .map(str::trim)
+        .filter(|principal| !principal.is_empty())
+        .map(Principal::parse)
+        .collect::<std::result::Result<Vec<Principal>, _>>()?;
+
+    Ok(principals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_principals, ApiKeyAuthentication};
+    use buckpal_application::domain::principal::Principal;
+
+    #[test]
+    fn authenticates_configured_keys() {
+        let authentication =
+            ApiKeyAuthentication::new("k1=user:alice, k2=service:payroll").unwrap();
+
+        assert_eq!(
+            authentication.authenticate("k1"),
+            Some(&Principal::User(String::from("alice")))
+        );
+        assert_eq!(
+            authentication.authenticate("k2"),
+            Some(&Principal::Service(String::from("payroll")))
+        );
+        assert_eq!(authentication.authenticate("k3"), None);
+    }
+
+    #[test]
+    fn rejects_malformed_keys() {
+        for api_keys in &["", "k1", "=user:alice", "k1=robot:r2d2"] {
+            assert!(ApiKeyAuthentication::new(api_keys).is_err());
+        }
+    }
+
+    #[test]
+    fn parses_listed_principals() {
+        assert_eq!(
+            parse_principals("user:alice, service:compliance").unwrap(),
+            vec![
+                Principal::User(String::from("alice")),
+                Principal::Service(String::from("compliance"))
+            ]
+        );
+        assert!(parse_principals("").unwrap().is_empty());
+        assert!(parse_principals("robot:r2d2").is_err());
+    }
+}
@@ -0,0 +1,16 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use buckpal_application::application::service::in_memory_domain_event_publisher::DomainEventSubscriber;
+use buckpal_application::domain::domain_event::DomainEvent;
+
+/// Writes every domain event to the log.
+pub struct LogDomainEventSubscriber {}
+
+#[async_trait]
+impl DomainEventSubscriber for LogDomainEventSubscriber {
+    async fn handle(&self, event: &DomainEvent) -> Result<()> {
+        info!("{}: {:?}", event.name(), event);
+
+        Ok(())
+    }
+}
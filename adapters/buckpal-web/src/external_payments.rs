@@ -0,0 +1,54 @@
+use crate::accounts::parse_date;
+use crate::AppState;
+use chrono::Utc;
+use serde::Deserialize;
+use tide::{Error, Request, Response, StatusCode};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ExportExternalPaymentsParams {
+    /// The day the bank is asked to process the file on, today (UTC) by default.
+    processing_date: Option<String>,
+}
+
+/// Pays out everything sent to external accounts since the last export as an ABA direct entry
+/// file, ready to be lodged with the bank. Answers with no content if there is nothing to pay.
+pub async fn handle_export_external_payments(req: Request<AppState>) -> tide::Result<Response> {
+    let params: ExportExternalPaymentsParams = req.query()?;
+    let processing_date = match &params.processing_date {
+        Some(processing_date) => parse_date("processingDate", processing_date)?,
+        None => Utc::now().date().naive_utc(),
+    };
+
+    let export_external_payments_use_case = req
+        .state()
+        .export_external_payments_use_case
+        .clone()
+        .expect("expected the route to only be served with ABA configured");
+
+    let batch = export_external_payments_use_case
+        .export_external_payments(&processing_date)
+        .await
+        .map_err(|err| Error::from_str(StatusCode::InternalServerError, err.to_string()))?;
+
+    let batch = match batch {
+        Some(batch) => batch,
+        None => return Ok(Response::new(StatusCode::NoContent)),
+    };
+
+    let batch_id = batch.id.expect("expected persisted batch to have an ID").0;
+
+    let mut res = Response::new(StatusCode::Created);
+    res.set_body(batch.contents);
+    res.set_content_type("text/plain");
+    res.insert_header(
+        "Content-Disposition",
+        format!(
+            "attachment; filename=\"payments-{}-{}.aba\"",
+            batch_id,
+            batch.processing_date.format("%Y%m%d")
+        ),
+    );
+
+    Ok(res)
+}
@@ -0,0 +1,169 @@
+use super::{to_decimal, StatementFormatter};
+use crate::iso20022::escape;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::ActivityKind;
+use buckpal_application::domain::statement::{Statement, StatementLine};
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use rusty_money::Money;
+
+/// The longest text ISO 20022 allows for identifiers such as `EndToEndId`.
+const MAX_ID_LENGTH: usize = 35;
+
+/// The longest text ISO 20022 allows for unstructured remittance information.
+const MAX_REMITTANCE_LENGTH: usize = 140;
+
+/// An ISO 20022 `camt.053.001.02` bank-to-customer statement with booked opening and closing
+/// balances and one booked entry per activity.
+#[derive(Debug, Default)]
+pub struct Camt053Formatter {}
+
+impl StatementFormatter for Camt053Formatter {
+    fn name(&self) -> &'static str {
+        "camt053"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/xml"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "xml"
+    }
+
+    fn format(&self, statement: &Statement) -> String {
+        let statement_id = format!(
+            "{}-{}-{}",
+            statement.account_id.0,
+            statement.from.format("%Y%m%d"),
+            statement.to.format("%Y%m%d")
+        );
+        let created_at = to_date_time(&statement.generated_at);
+
+        let credits = count_lines(statement, ActivityKind::Deposit);
+        let debits = count_lines(statement, ActivityKind::Withdrawal);
+
+        let mut entries = String::new();
+        for line in &statement.lines {
+            entries.push_str(&to_entry(line));
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+<BkToCstmrStmt>
+<GrpHdr><MsgId>STMT-{statement_id}</MsgId><CreDtTm>{created_at}</CreDtTm></GrpHdr>
+<Stmt>
+<Id>{statement_id}</Id>
+<CreDtTm>{created_at}</CreDtTm>
+<FrToDt><FrDtTm>{from}</FrDtTm><ToDtTm>{to}</ToDtTm></FrToDt>
+<Acct>{account}<Ccy>AUD</Ccy></Acct>
+{opening_balance}
+{closing_balance}
+<TxsSummry><TtlNtries><NbOfNtries>{entry_count}</NbOfNtries></TtlNtries><TtlCdtNtries><NbOfNtries>{credits}</NbOfNtries><Sum>{total_in}</Sum></TtlCdtNtries><TtlDbtNtries><NbOfNtries>{debits}</NbOfNtries><Sum>{total_out}</Sum></TtlDbtNtries></TxsSummry>
+{entries}</Stmt>
+</BkToCstmrStmt>
+</Document>
+"#,
+            statement_id = statement_id,
+            created_at = created_at,
+            from = to_date_time(&Statement::start_of_day(&statement.from)),
+            to = to_date_time(
+                &(Statement::start_of_day(&statement.to.succ()) - Duration::seconds(1))
+            ),
+            account = to_account(&statement.account_id),
+            opening_balance = to_balance("OPBD", &statement.opening_balance, &statement.from),
+            closing_balance = to_balance("CLBD", &statement.closing_balance, &statement.to),
+            entry_count = statement.lines.len(),
+            credits = credits,
+            total_in = to_decimal(&statement.total_in),
+            debits = debits,
+            total_out = to_decimal(&statement.total_out),
+            entries = entries
+        )
+    }
+}
+
+fn count_lines(statement: &Statement, kind: ActivityKind) -> usize {
+    statement
+        .lines
+        .iter()
+        .filter(|line| line.kind == kind)
+        .count()
+}
+
+fn to_date_time(timestamp: &DateTime<Utc>) -> String {
+    timestamp.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+fn to_account(account_id: &AccountId) -> String {
+    format!("<Id><Othr><Id>{}</Id></Othr></Id>", account_id.0)
+}
+
+/// Balances are written as a positive amount with an indicator of which side they are on.
+fn to_balance(code: &str, balance: &Money, date: &NaiveDate) -> String {
+    let amount = to_decimal(balance);
+    let (amount, indicator) = match amount.strip_prefix('-') {
+        Some(amount) => (amount, "DBIT"),
+        None => (amount.as_str(), "CRDT"),
+    };
+
+    format!(
+        "<Bal><Tp><CdOrPrtry><Cd>{}</Cd></CdOrPrtry></Tp><Amt Ccy=\"AUD\">{}</Amt><CdtDbtInd>{}</CdtDbtInd><Dt><Dt>{}</Dt></Dt></Bal>",
+        code,
+        amount,
+        indicator,
+        date.format("%Y-%m-%d")
+    )
+}
+
+fn to_entry(line: &StatementLine) -> String {
+    // received and issued credit transfers booked within the ledger
+    let (indicator, family, related_party) = match line.kind {
+        ActivityKind::Deposit => ("CRDT", "RCDT", "DbtrAcct"),
+        ActivityKind::Withdrawal => ("DBIT", "ICDT", "CdtrAcct"),
+    };
+
+    let entry_reference = line
+        .activity_id
+        .clone()
+        .map(|id| format!("<NtryRef>{}</NtryRef>", id.0))
+        .unwrap_or_default();
+
+    let mut references = String::new();
+    if let Some(reference) = line
+        .reference
+        .as_deref()
+        .filter(|reference| reference.chars().count() <= MAX_ID_LENGTH)
+    {
+        references.push_str(&format!("<EndToEndId>{}</EndToEndId>", escape(reference)));
+    }
+    if let Some(transfer_id) = &line.transfer_id {
+        references.push_str(&format!("<TxId>{}</TxId>", transfer_id.0));
+    }
+    if !references.is_empty() {
+        references = format!("<Refs>{}</Refs>", references);
+    }
+
+    let remittance_information = line
+        .reference
+        .as_deref()
+        .filter(|reference| !reference.is_empty())
+        .map(|reference| {
+            let reference: String = reference.chars().take(MAX_REMITTANCE_LENGTH).collect();
+            format!("<RmtInf><Ustrd>{}</Ustrd></RmtInf>", escape(&reference))
+        })
+        .unwrap_or_default();
+
+    format!(
+        "<Ntry>{entry_reference}<Amt Ccy=\"AUD\">{amount}</Amt><CdtDbtInd>{indicator}</CdtDbtInd><Sts>BOOK</Sts><BookgDt><DtTm>{booked_at}</DtTm></BookgDt><BkTxCd><Domn><Cd>PMNT</Cd><Fmly><Cd>{family}</Cd><SubFmlyCd>BOOK</SubFmlyCd></Fmly></Domn></BkTxCd><NtryDtls><TxDtls>{references}<RltdPties><{related_party}>{counterparty}</{related_party}></RltdPties>{remittance_information}</TxDtls></NtryDtls></Ntry>\n",
+        entry_reference = entry_reference,
+        amount = to_decimal(&line.money),
+        indicator = indicator,
+        booked_at = to_date_time(&line.timestamp),
+        family = family,
+        references = references,
+        related_party = related_party,
+        counterparty = to_account(&line.counterparty_account_id),
+        remittance_information = remittance_information
+    )
+}
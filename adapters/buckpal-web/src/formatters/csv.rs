@@ -0,0 +1,49 @@
+use super::{to_decimal, StatementFormatter};
+use buckpal_application::domain::activity::ActivityKind;
+use buckpal_application::domain::statement::Statement;
+
+/// One row per activity with signed amounts, withdrawals are negative.
+#[derive(Debug, Default)]
+pub struct CsvFormatter {}
+
+impl StatementFormatter for CsvFormatter {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "text/csv"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "csv"
+    }
+
+    fn format(&self, statement: &Statement) -> String {
+        let mut csv =
+            String::from("date,activity_id,kind,counterparty_account_id,amount,running_balance\n");
+
+        for line in &statement.lines {
+            let (kind, sign) = match line.kind {
+                ActivityKind::Deposit => ("deposit", ""),
+                ActivityKind::Withdrawal => ("withdrawal", "-"),
+            };
+
+            csv.push_str(&format!(
+                "{},{},{},{},{}{},{}\n",
+                line.timestamp.to_rfc3339(),
+                line.activity_id
+                    .clone()
+                    .map(|id| id.0.to_string())
+                    .unwrap_or_default(),
+                kind,
+                line.counterparty_account_id.0,
+                sign,
+                to_decimal(&line.money),
+                to_decimal(&line.running_balance)
+            ));
+        }
+
+        csv
+    }
+}
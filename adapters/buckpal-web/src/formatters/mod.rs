@@ -0,0 +1,238 @@
+mod camt053;
+mod csv;
+mod ofx;
+mod qif;
+
+pub use self::camt053::Camt053Formatter;
+pub use self::csv::CsvFormatter;
+pub use self::ofx::OfxFormatter;
+pub use self::qif::QifFormatter;
+
+use buckpal_application::domain::statement::Statement;
+use rusty_money::Money;
+
+/// Writes the activities of a statement in a format that accounting tools can import.
+pub trait StatementFormatter {
+    /// The name clients select the format by, e.g. with `?format=ofx`.
+    fn name(&self) -> &'static str;
+
+    /// The media type of the output, also used to select the format by `Accept` header.
+    fn content_type(&self) -> &'static str;
+
+    /// The extension of the downloaded file.
+    fn file_extension(&self) -> &'static str;
+
+    fn format(&self, statement: &Statement) -> String;
+}
+
+/// The formatters statements can be downloaded with.
+pub struct StatementFormatters {
+    formatters: Vec<Box<dyn StatementFormatter + Send + Sync>>,
+}
+
+impl Default for StatementFormatters {
+    fn default() -> Self {
+        Self::new()
+            .with(Box::new(CsvFormatter::default()))
+            .with(Box::new(OfxFormatter::default()))
+            .with(Box::new(QifFormatter::default()))
+            .with(Box::new(Camt053Formatter::default()))
+    }
+}
+
+impl StatementFormatters {
+    pub fn new() -> Self {
+        Self { formatters: vec![] }
+    }
+
+    /// Registers another formatter, formatters registered first win when several match.
+    pub fn with(mut self, formatter: Box<dyn StatementFormatter + Send + Sync>) -> Self {
+        self.formatters.push(formatter);
+        self
+    }
+
+    pub fn find_by_name(&self, name: &str) -> Option<&(dyn StatementFormatter + Send + Sync)> {
+        self.formatters
+            .iter()
+            .find(|formatter| formatter.name().eq_ignore_ascii_case(name))
+            .map(|formatter| formatter.as_ref())
+    }
+
+    /// Picks the first formatter whose media type is listed in the `Accept` header. Quality
+    /// values are ignored.
+    pub fn find_by_accept(&self, accept: &str) -> Option<&(dyn StatementFormatter + Send + Sync)> {
+        let accepted: Vec<&str> = accept
+            .split(',')
+            .map(|media_type| media_type.split(';').next().unwrap_or("").trim())
+            .collect();
+
+        self.formatters
+            .iter()
+            .find(|formatter| accepted.contains(&formatter.content_type()))
+            .map(|formatter| formatter.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.formatters
+            .iter()
+            .map(|formatter| formatter.name())
+            .collect()
+    }
+}
+
+/// Formats whole dollars with the two decimals most formats expect.
+fn to_decimal(money: &Money) -> String {
+    use rust_decimal::prelude::*;
+
+    format!("{:.2}", money.amount().to_f64().unwrap_or_default())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StatementFormatters;
+    use crate::iso20022::assert_valid_against_schema;
+    use buckpal_application::domain::account::account_test_data::AccountBuilder;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::activity::activity_test_data::ActivityBuilder;
+    use buckpal_application::domain::activity::ActivityId;
+    use buckpal_application::domain::activity_window::ActivityWindow;
+    use buckpal_application::domain::statement::Statement;
+    use buckpal_application::domain::transfer::TransferId;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+
+    fn given_a_statement() -> Statement {
+        let account_id = AccountId(42);
+        let activities = vec![
+            ActivityBuilder::default_activity()
+                .with_activity_id(&ActivityId(7))
+                .with_source_account(&AccountId(41))
+                .with_target_account(&account_id)
+                .with_timestamp(&DateTime::<Utc>::from_utc(
+                    NaiveDate::from_ymd(2021, 2, 2).and_hms(9, 0, 0),
+                    Utc,
+                ))
+                .with_money(&money!(50, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_activity_id(&ActivityId(8))
+                .with_source_account(&account_id)
+                .with_target_account(&AccountId(43))
+                .with_timestamp(&DateTime::<Utc>::from_utc(
+                    NaiveDate::from_ymd(2021, 2, 10).and_hms(12, 30, 0),
+                    Utc,
+                ))
+                .with_money(&money!(30, "AUD"))
+                .build(),
+        ];
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(100, "AUD"))
+            .with_activity_window(&ActivityWindow::new(activities))
+            .build();
+
+        Statement::generate(
+            &account_id,
+            &account,
+            NaiveDate::from_ymd(2021, 2, 1),
+            NaiveDate::from_ymd(2021, 2, 28),
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn selects_formatter_by_name_and_accept_header() {
+        let formatters = StatementFormatters::default();
+
+        assert_eq!(
+            formatters.find_by_name("OFX").map(|f| f.name()),
+            Some("ofx")
+        );
+        assert_eq!(
+            formatters
+                .find_by_accept("text/html;q=0.9, application/qif")
+                .map(|f| f.name()),
+            Some("qif")
+        );
+        assert!(formatters.find_by_accept("application/pdf").is_none());
+    }
+
+    #[test]
+    fn writes_csv() {
+        let csv = StatementFormatters::default()
+            .find_by_name("csv")
+            .unwrap()
+            .format(&given_a_statement());
+
+        assert_eq!(
+            csv,
+            "date,activity_id,kind,counterparty_account_id,amount,running_balance\n\
+             2021-02-02T09:00:00+00:00,7,deposit,41,50.00,150.00\n\
+             2021-02-10T12:30:00+00:00,8,withdrawal,43,-30.00,120.00\n"
+        );
+    }
+
+    #[test]
+    fn writes_ofx() {
+        let ofx = StatementFormatters::default()
+            .find_by_name("ofx")
+            .unwrap()
+            .format(&given_a_statement());
+
+        assert!(ofx.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\""));
+        assert!(ofx.contains("<?OFX OFXHEADER=\"200\" VERSION=\"220\""));
+        assert!(ofx.contains(
+            "<STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20210210123000</DTPOSTED><TRNAMT>-30.00</TRNAMT><FITID>8</FITID><NAME>Account 43</NAME></STMTTRN>"
+        ));
+        assert!(ofx.contains("<LEDGERBAL><BALAMT>120.00</BALAMT>"));
+    }
+
+    fn given_a_statement_with_a_reference() -> Statement {
+        let mut statement = given_a_statement();
+        statement.lines[0].transfer_id = Some(TransferId(3));
+        statement.lines[0].reference = Some(String::from("Invoice <2021-001> & co"));
+        statement
+    }
+
+    #[test]
+    fn writes_camt053() {
+        let camt053 = StatementFormatters::default()
+            .find_by_name("camt053")
+            .unwrap()
+            .format(&given_a_statement_with_a_reference());
+
+        assert!(camt053.contains(
+            "<Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy=\"AUD\">100.00</Amt><CdtDbtInd>CRDT</CdtDbtInd>"
+        ));
+        assert!(camt053.contains(
+            "<Refs><EndToEndId>Invoice &lt;2021-001&gt; &amp; co</EndToEndId><TxId>3</TxId></Refs>"
+        ));
+        assert!(camt053.contains("<Amt Ccy=\"AUD\">30.00</Amt><CdtDbtInd>DBIT</CdtDbtInd>"));
+    }
+
+    #[test]
+    #[ignore = "needs xmllint and the published camt.053.001.02 schema in schemas/"]
+    fn writes_camt053_valid_against_schema() {
+        let camt053 = StatementFormatters::default()
+            .find_by_name("camt053")
+            .unwrap()
+            .format(&given_a_statement_with_a_reference());
+
+        assert_valid_against_schema(&camt053, "camt.053.001.02.xsd");
+    }
+
+    #[test]
+    fn writes_qif() {
+        let qif = StatementFormatters::default()
+            .find_by_name("qif")
+            .unwrap()
+            .format(&given_a_statement());
+
+        assert_eq!(
+            qif,
+            "!Type:Bank\n\
+             D02/02/2021\nT50.00\nPAccount 41\nN7\n^\n\
+             D10/02/2021\nT-30.00\nPAccount 43\nN8\n^\n"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::{to_decimal, StatementFormatter};
+use buckpal_application::domain::activity::ActivityKind;
+use buckpal_application::domain::statement::Statement;
+
+/// An OFX 2.2 bank statement response. Transactions are identified by their activity, which
+/// lets accounting tools skip activities they imported before.
+#[derive(Debug, Default)]
+pub struct OfxFormatter {}
+
+impl StatementFormatter for OfxFormatter {
+    fn name(&self) -> &'static str {
+        "ofx"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/x-ofx"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "ofx"
+    }
+
+    fn format(&self, statement: &Statement) -> String {
+        let mut transactions = String::new();
+
+        for (position, line) in statement.lines.iter().enumerate() {
+            let (transaction_type, sign) = match line.kind {
+                ActivityKind::Deposit => ("CREDIT", ""),
+                ActivityKind::Withdrawal => ("DEBIT", "-"),
+            };
+            let fit_id = line
+                .activity_id
+                .clone()
+                .map(|id| id.0.to_string())
+                .unwrap_or_else(|| format!("{}-{}", statement.from.format("%Y%m%d"), position));
+
+            transactions.push_str(&format!(
+                "<STMTTRN><TRNTYPE>{}</TRNTYPE><DTPOSTED>{}</DTPOSTED><TRNAMT>{}{}</TRNAMT><FITID>{}</FITID><NAME>Account {}</NAME></STMTTRN>\n",
+                transaction_type,
+                line.timestamp.format("%Y%m%d%H%M%S"),
+                sign,
+                to_decimal(&line.money),
+                fit_id,
+                line.counterparty_account_id.0
+            ));
+        }
+
+        format!(
+            r#"<?xml version="1.0" encoding="UTF-8" standalone="no"?>
+<?OFX OFXHEADER="200" VERSION="220" SECURITY="NONE" OLDFILEUID="NONE" NEWFILEUID="NONE"?>
+<OFX>
+<SIGNONMSGSRSV1><SONRS><STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS><DTSERVER>{generated_at}</DTSERVER><LANGUAGE>ENG</LANGUAGE></SONRS></SIGNONMSGSRSV1>
+<BANKMSGSRSV1><STMTTRNRS><TRNUID>0</TRNUID><STATUS><CODE>0</CODE><SEVERITY>INFO</SEVERITY></STATUS>
+<STMTRS><CURDEF>AUD</CURDEF>
+<BANKACCTFROM><BANKID>BUCKPAL</BANKID><ACCTID>{account_id}</ACCTID><ACCTTYPE>CHECKING</ACCTTYPE></BANKACCTFROM>
+<BANKTRANLIST><DTSTART>{from}</DTSTART><DTEND>{to}</DTEND>
+{transactions}</BANKTRANLIST>
+<LEDGERBAL><BALAMT>{closing_balance}</BALAMT><DTASOF>{to}</DTASOF></LEDGERBAL>
+</STMTRS></STMTTRNRS></BANKMSGSRSV1>
+</OFX>
+"#,
+            generated_at = statement.generated_at.format("%Y%m%d%H%M%S"),
+            account_id = statement.account_id.0,
+            from = statement.from.format("%Y%m%d"),
+            // the end of the range is exclusive in OFX
+            to = statement.to.succ().format("%Y%m%d"),
+            transactions = transactions,
+            closing_balance = to_decimal(&statement.closing_balance)
+        )
+    }
+}
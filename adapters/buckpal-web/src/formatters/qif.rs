@@ -0,0 +1,46 @@
+use super::{to_decimal, StatementFormatter};
+use buckpal_application::domain::activity::ActivityKind;
+use buckpal_application::domain::statement::Statement;
+
+/// A QIF bank register. Dates are written day first, as Australian accounting tools expect.
+#[derive(Debug, Default)]
+pub struct QifFormatter {}
+
+impl StatementFormatter for QifFormatter {
+    fn name(&self) -> &'static str {
+        "qif"
+    }
+
+    fn content_type(&self) -> &'static str {
+        "application/qif"
+    }
+
+    fn file_extension(&self) -> &'static str {
+        "qif"
+    }
+
+    fn format(&self, statement: &Statement) -> String {
+        let mut qif = String::from("!Type:Bank\n");
+
+        for line in &statement.lines {
+            let sign = match line.kind {
+                ActivityKind::Deposit => "",
+                ActivityKind::Withdrawal => "-",
+            };
+
+            qif.push_str(&format!(
+                "D{}\nT{}{}\nPAccount {}\n",
+                line.timestamp.format("%d/%m/%Y"),
+                sign,
+                to_decimal(&line.money),
+                line.counterparty_account_id.0
+            ));
+            if let Some(activity_id) = &line.activity_id {
+                qif.push_str(&format!("N{}\n", activity_id.0));
+            }
+            qif.push_str("^\n");
+        }
+
+        qif
+    }
+}
@@ -0,0 +1,134 @@
+use crate::audit::audit_context;
+use crate::authentication::principal;
+use crate::transfers::{error_status, SendMoneyResponse};
+use crate::utils::{json_to_res, success_to_res};
+use crate::AppState;
+use buckpal_application::application::port::incoming::{
+    authorize_transfer_use_case::AuthorizeTransferCommand,
+    capture_transfer_use_case::CaptureTransferCommand, void_hold_use_case::VoidHoldCommand,
+};
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::hold::HoldId;
+use rusty_money::{money, Money};
+use serde::{Deserialize, Serialize};
+use tide::{Error, ParamError, Request, Response, StatusCode};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorizeTransferRequest {
+    source_account_id: i32,
+    target_account_id: i32,
+    amount: i64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CaptureTransferRequest {
+    amount: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuthorizeTransferResponse {
+    message: String,
+    hold_id: i32,
+}
+
+fn validate_hold_id_param(req: &Request<AppState>) -> tide::Result<i32> {
+    req.param("holdId")
+        .map_err(|err: ParamError<std::num::ParseIntError>| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid holdId: {}", err.to_string()),
+            )
+        })
+}
+
+pub async fn handle_authorize_transfer(mut req: Request<AppState>) -> tide::Result<Response> {
+    let authorize_transfer_request: AuthorizeTransferRequest =
+        req.body_json().await.map_err(|err| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid body: {}", err.to_string()),
+            )
+        })?;
+
+    let command = AuthorizeTransferCommand::new(
+        AccountId(authorize_transfer_request.source_account_id),
+        AccountId(authorize_transfer_request.target_account_id),
+        money!(authorize_transfer_request.amount, "AUD"),
+    )
+    .with_audit_context(audit_context(&req))
+    .with_initiator(principal(&req)?);
+
+    let authorize_transfer_use_case = req.state().authorize_transfer_use_case.clone();
+
+    let hold_id = authorize_transfer_use_case
+        .authorize_transfer(&command)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let authorize_transfer_response = AuthorizeTransferResponse {
+        message: String::from("Transfer Authorized!"),
+        hold_id: hold_id.0,
+    };
+
+    json_to_res(StatusCode::Ok, &authorize_transfer_response)
+}
+
+pub async fn handle_capture_transfer(mut req: Request<AppState>) -> tide::Result<Response> {
+    let hold_id = validate_hold_id_param(&req)?;
+
+    // the body is optional, without an amount everything that was held gets captured
+    let body = req.body_string().await?;
+    let capture_transfer_request = if body.trim().is_empty() {
+        CaptureTransferRequest { amount: None }
+    } else {
+        serde_json::from_str(&body).map_err(|err| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid body: {}", err.to_string()),
+            )
+        })?
+    };
+
+    let command = CaptureTransferCommand::new(
+        HoldId(hold_id),
+        capture_transfer_request
+            .amount
+            .map(|amount| money!(amount, "AUD")),
+    )
+    .with_audit_context(audit_context(&req))
+    .with_initiator(principal(&req)?);
+
+    let capture_transfer_use_case = req.state().capture_transfer_use_case.clone();
+
+    let transfer_id = capture_transfer_use_case
+        .capture_transfer(&command)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let send_money_response = SendMoneyResponse {
+        message: String::from("Transfer Captured!"),
+        transfer_id: transfer_id.0,
+    };
+
+    json_to_res(StatusCode::Ok, &send_money_response)
+}
+
+pub async fn handle_void_hold(req: Request<AppState>) -> tide::Result<Response> {
+    let hold_id = validate_hold_id_param(&req)?;
+
+    let command = VoidHoldCommand::new(HoldId(hold_id))
+        .with_audit_context(audit_context(&req))
+        .with_initiator(principal(&req)?);
+
+    let void_hold_use_case = req.state().void_hold_use_case.clone();
+
+    void_hold_use_case
+        .void_hold(&command)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    success_to_res("Hold Voided!")
+}
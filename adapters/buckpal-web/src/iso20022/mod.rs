@@ -0,0 +1,166 @@
+pub mod pain001;
+pub mod pain002;
+
+use anyhow::{anyhow, Result};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum Iso20022Error {
+    #[error("Malformed XML: {0}")]
+    MalformedXml(String),
+    #[error("Missing element `{0}`")]
+    MissingElement(String),
+    #[error("Invalid value `{value}` for element `{element}`")]
+    InvalidValue { element: String, value: String },
+}
+
+/// An element of a parsed message. Messages are small enough to be read into memory as a whole,
+/// which keeps navigating them by path simple.
+#[derive(Debug, Default)]
+pub struct Element {
+    /// The name without namespace prefix.
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub text: String,
+    pub children: Vec<Element>,
+}
+
+impl Element {
+    pub fn parse(xml: &str) -> Result<Element> {
+        let mut reader = Reader::from_str(xml);
+        reader.trim_text(true);
+        reader.expand_empty_elements(true);
+
+        let mut buf = vec![];
+        let mut stack: Vec<Element> = vec![];
+
+        loop {
+            match reader.read_event(&mut buf) {
+                Ok(Event::Start(start)) => {
+                    let mut element = Element {
+                        name: String::from_utf8_lossy(start.local_name()).into_owned(),
+                        ..Element::default()
+                    };
+                    for attribute in start.attributes() {
+                        let attribute = attribute.map_err(malformed)?;
+                        element.attributes.push((
+                            String::from_utf8_lossy(attribute.key).into_owned(),
+                            attribute
+                                .unescape_and_decode_value(&reader)
+                                .map_err(malformed)?,
+                        ));
+                    }
+                    stack.push(element);
+                }
+                Ok(Event::Text(text)) => {
+                    if let Some(element) = stack.last_mut() {
+                        element
+                            .text
+                            .push_str(&text.unescape_and_decode(&reader).map_err(malformed)?);
+                    }
+                }
+                Ok(Event::End(_)) => {
+                    let element = stack
+                        .pop()
+                        .ok_or_else(|| malformed("unexpected end of element"))?;
+                    match stack.last_mut() {
+                        Some(parent) => parent.children.push(element),
+                        None => return Ok(element),
+                    }
+                }
+                Ok(Event::Eof) => return Err(malformed("unexpected end of document")),
+                Ok(_) => {}
+                Err(err) => return Err(malformed(err)),
+            }
+            buf.clear();
+        }
+    }
+
+    pub fn child(&self, name: &str) -> Option<&Element> {
+        self.children.iter().find(|child| child.name == name)
+    }
+
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    /// Follows a path of element names separated by `/`.
+    pub fn find(&self, path: &str) -> Option<&Element> {
+        path.split('/')
+            .try_fold(self, |element, name| element.child(name))
+    }
+
+    pub fn required(&self, path: &str) -> Result<&Element> {
+        self.find(path)
+            .ok_or_else(|| anyhow!(Iso20022Error::MissingElement(String::from(path))))
+    }
+
+    pub fn required_text(&self, path: &str) -> Result<String> {
+        Ok(self.required(path)?.text.clone())
+    }
+
+    pub fn optional_text(&self, path: &str) -> Option<String> {
+        self.find(path).map(|element| element.text.clone())
+    }
+
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn malformed(err: impl std::fmt::Display) -> anyhow::Error {
+    anyhow!(Iso20022Error::MalformedXml(err.to_string()))
+}
+
+/// Escapes text written into an element or attribute.
+pub fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+/// Validates with `xmllint`, which comes with libxml2, against a published schema vendored in
+/// `schemas/`. Only the ignored schema tests call it, see `schemas/README.md`.
+#[cfg(test)]
+pub fn assert_valid_against_schema(document: &str, schema: &str) {
+    use std::io::Write;
+    use std::path::Path;
+    use std::process::{Command, Stdio};
+
+    let schema = format!("{}/schemas/{}", env!("CARGO_MANIFEST_DIR"), schema);
+    assert!(
+        Path::new(&schema).exists(),
+        "expected the published schema at `{}`, see schemas/README.md",
+        schema
+    );
+
+    let mut xmllint = Command::new("xmllint")
+        .arg("--noout")
+        .arg("--schema")
+        .arg(&schema)
+        .arg("-")
+        .stdin(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("expected xmllint to be installed");
+    xmllint
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(document.as_bytes())
+        .unwrap();
+    let output = xmllint.wait_with_output().unwrap();
+
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
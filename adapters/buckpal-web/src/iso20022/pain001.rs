@@ -0,0 +1,160 @@
+use super::{Element, Iso20022Error};
+use anyhow::{anyhow, Result};
+use buckpal_application::domain::payment_initiation::{
+    CreditTransferTransaction, PaymentInitiation, PaymentInstruction,
+};
+use rust_decimal::Decimal;
+use std::str::FromStr;
+
+/// Reads a customer credit transfer initiation (`pain.001.001.03`). Only the structure is
+/// checked here, whether the values make sense is up to the import.
+pub fn parse(xml: &str) -> Result<PaymentInitiation> {
+    let document = Element::parse(xml)?;
+    let initiation = document.required("CstmrCdtTrfInitn")?;
+
+    let group_header = initiation.required("GrpHdr")?;
+    let control_sum = group_header
+        .optional_text("CtrlSum")
+        .map(|control_sum| parse_decimal("GrpHdr/CtrlSum", &control_sum))
+        .transpose()?;
+
+    let payments = initiation
+        .children("PmtInf")
+        .map(parse_payment)
+        .collect::<Result<Vec<PaymentInstruction>>>()?;
+
+    Ok(PaymentInitiation {
+        message_id: group_header.required_text("MsgId")?,
+        number_of_transactions: group_header.required_text("NbOfTxs")?,
+        control_sum,
+        payments,
+    })
+}
+
+fn parse_payment(payment: &Element) -> Result<PaymentInstruction> {
+    let transactions = payment
+        .children("CdtTrfTxInf")
+        .map(parse_transaction)
+        .collect::<Result<Vec<CreditTransferTransaction>>>()?;
+
+    Ok(PaymentInstruction {
+        payment_information_id: payment.required_text("PmtInfId")?,
+        debtor_account: parse_account(payment.required("DbtrAcct")?)?,
+        transactions,
+    })
+}
+
+fn parse_transaction(transaction: &Element) -> Result<CreditTransferTransaction> {
+    let amount = transaction.required("Amt/InstdAmt")?;
+
+    Ok(CreditTransferTransaction {
+        instruction_id: transaction.optional_text("PmtId/InstrId"),
+        end_to_end_id: transaction.required_text("PmtId/EndToEndId")?,
+        creditor_account: parse_account(transaction.required("CdtrAcct")?)?,
+        amount: parse_decimal("Amt/InstdAmt", &amount.text)?,
+        currency: String::from(amount.attribute("Ccy").unwrap_or_default()),
+        remittance_information: transaction.optional_text("RmtInf/Ustrd"),
+    })
+}
+
+/// Accounts are identified by IBAN or by an identifier of their own.
+fn parse_account(account: &Element) -> Result<String> {
+    account
+        .find("Id/IBAN")
+        .or_else(|| account.find("Id/Othr/Id"))
+        .map(|id| id.text.clone())
+        .ok_or_else(|| {
+            anyhow!(Iso20022Error::MissingElement(format!(
+                "{}/Id",
+                account.name
+            )))
+        })
+}
+
+fn parse_decimal(element: &str, value: &str) -> Result<Decimal> {
+    Decimal::from_str(value).map_err(|_| {
+        anyhow!(Iso20022Error::InvalidValue {
+            element: String::from(element),
+            value: String::from(value),
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use crate::iso20022::Iso20022Error;
+    use rust_decimal::Decimal;
+
+    const PAIN_001: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pain.001.001.03">
+  <CstmrCdtTrfInitn>
+    <GrpHdr>
+      <MsgId>MSG-2021-03-21-001</MsgId>
+      <CreDtTm>2021-03-21T09:30:00</CreDtTm>
+      <NbOfTxs>2</NbOfTxs>
+      <CtrlSum>350.00</CtrlSum>
+      <InitgPty><Nm>ACME Pty Ltd</Nm></InitgPty>
+    </GrpHdr>
+    <PmtInf>
+      <PmtInfId>PMT-1</PmtInfId>
+      <PmtMtd>TRF</PmtMtd>
+      <ReqdExctnDt>2021-03-22</ReqdExctnDt>
+      <Dbtr><Nm>ACME Pty Ltd</Nm></Dbtr>
+      <DbtrAcct><Id><Othr><Id>41</Id></Othr></Id></DbtrAcct>
+      <DbtrAgt><FinInstnId/></DbtrAgt>
+      <CdtTrfTxInf>
+        <PmtId><InstrId>INSTR-1</InstrId><EndToEndId>E2E-1</EndToEndId></PmtId>
+        <Amt><InstdAmt Ccy="AUD">300.00</InstdAmt></Amt>
+        <Cdtr><Nm>Supplier One</Nm></Cdtr>
+        <CdtrAcct><Id><Othr><Id>42</Id></Othr></Id></CdtrAcct>
+        <RmtInf><Ustrd>Invoice 17 &amp; 18</Ustrd></RmtInf>
+      </CdtTrfTxInf>
+      <CdtTrfTxInf>
+        <PmtId><EndToEndId>E2E-2</EndToEndId></PmtId>
+        <Amt><InstdAmt Ccy="AUD">50</InstdAmt></Amt>
+        <Cdtr><Nm>Supplier Two</Nm></Cdtr>
+        <CdtrAcct><Id><Othr><Id>43</Id></Othr></Id></CdtrAcct>
+      </CdtTrfTxInf>
+    </PmtInf>
+  </CstmrCdtTrfInitn>
+</Document>
+"#;
+
+    #[test]
+    fn parses_credit_transfer_initiation() {
+        let initiation = parse(PAIN_001).unwrap();
+
+        assert_eq!(initiation.message_id, "MSG-2021-03-21-001");
+        assert_eq!(initiation.number_of_transactions, "2");
+        assert_eq!(initiation.control_sum, Some(Decimal::new(35000, 2)));
+        assert_eq!(initiation.payments.len(), 1);
+
+        let payment = &initiation.payments[0];
+        assert_eq!(payment.payment_information_id, "PMT-1");
+        assert_eq!(payment.debtor_account, "41");
+        assert_eq!(payment.transactions.len(), 2);
+
+        let transaction = &payment.transactions[0];
+        assert_eq!(transaction.instruction_id.as_deref(), Some("INSTR-1"));
+        assert_eq!(transaction.end_to_end_id, "E2E-1");
+        assert_eq!(transaction.creditor_account, "42");
+        assert_eq!(transaction.amount, Decimal::new(300, 0));
+        assert_eq!(transaction.currency, "AUD");
+        assert_eq!(
+            transaction.remittance_information.as_deref(),
+            Some("Invoice 17 & 18")
+        );
+        assert_eq!(payment.transactions[1].remittance_information, None);
+    }
+
+    #[test]
+    fn missing_element_is_reported() {
+        let err = parse(&PAIN_001.replace("<MsgId>MSG-2021-03-21-001</MsgId>", "")).unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<Iso20022Error>(),
+            Some(Iso20022Error::MissingElement(path)) if path == "MsgId"
+        ));
+    }
+}
@@ -0,0 +1,164 @@
+use super::escape;
+use buckpal_application::domain::payment_initiation::{
+    PaymentStatus, PaymentStatusReport, StatusReason,
+};
+use chrono::{DateTime, SecondsFormat, Utc};
+
+/// The longest additional information ISO 20022 allows on a status reason.
+const MAX_ADDITIONAL_INFORMATION_LENGTH: usize = 105;
+
+/// Writes a customer payment status report (`pain.002.001.03`) in reply to an initiation.
+pub fn write(report: &PaymentStatusReport, created_at: &DateTime<Utc>) -> String {
+    let mut payments = String::new();
+    for payment in &report.payments {
+        let mut transactions = String::new();
+        for transaction in &payment.transactions {
+            transactions.push_str(&format!(
+                "<TxInfAndSts>{instruction_id}<OrgnlEndToEndId>{end_to_end_id}</OrgnlEndToEndId><TxSts>{status}</TxSts>{reason}{transfer_id}</TxInfAndSts>\n",
+                instruction_id = transaction
+                    .instruction_id
+                    .as_deref()
+                    .map(|instruction_id| format!(
+                        "<OrgnlInstrId>{}</OrgnlInstrId>",
+                        escape(instruction_id)
+                    ))
+                    .unwrap_or_default(),
+                end_to_end_id = escape(&transaction.end_to_end_id),
+                status = to_status(transaction.status),
+                reason = to_reason(transaction.reason.as_ref()),
+                transfer_id = transaction
+                    .transfer_id
+                    .as_ref()
+                    .map(|transfer_id| format!("<AcctSvcrRef>{}</AcctSvcrRef>", transfer_id.0))
+                    .unwrap_or_default()
+            ));
+        }
+
+        payments.push_str(&format!(
+            "<OrgnlPmtInfAndSts><OrgnlPmtInfId>{}</OrgnlPmtInfId>\n{}</OrgnlPmtInfAndSts>\n",
+            escape(&payment.payment_information_id),
+            transactions
+        ));
+    }
+
+    // the declared number is echoed only if it is one, it may be why the file was rejected
+    let number_of_transactions = if is_numeric(&report.original_number_of_transactions) {
+        format!(
+            "<OrgnlNbOfTxs>{}</OrgnlNbOfTxs>",
+            report.original_number_of_transactions
+        )
+    } else {
+        String::new()
+    };
+
+    format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:pain.002.001.03">
+<CstmrPmtStsRpt>
+<GrpHdr><MsgId>PAIN002-{message_id}</MsgId><CreDtTm>{created_at}</CreDtTm></GrpHdr>
+<OrgnlGrpInfAndSts><OrgnlMsgId>{original_message_id}</OrgnlMsgId><OrgnlMsgNmId>pain.001.001.03</OrgnlMsgNmId>{number_of_transactions}<GrpSts>{status}</GrpSts>{reason}</OrgnlGrpInfAndSts>
+{payments}</CstmrPmtStsRpt>
+</Document>
+"#,
+        message_id = created_at.format("%Y%m%d%H%M%S%f"),
+        created_at = created_at.to_rfc3339_opts(SecondsFormat::Secs, true),
+        original_message_id = escape(&report.original_message_id),
+        number_of_transactions = number_of_transactions,
+        status = to_status(report.status),
+        reason = to_reason(report.reason.as_ref()),
+        payments = payments
+    )
+}
+
+fn to_status(status: PaymentStatus) -> &'static str {
+    match status {
+        PaymentStatus::AcceptedSettlementCompleted => "ACSC",
+        PaymentStatus::PartiallyAccepted => "PART",
+        PaymentStatus::Rejected => "RJCT",
+    }
+}
+
+fn to_reason(reason: Option<&StatusReason>) -> String {
+    match reason {
+        Some(reason) => {
+            let additional_information: String = reason
+                .additional_information
+                .chars()
+                .take(MAX_ADDITIONAL_INFORMATION_LENGTH)
+                .collect();
+
+            format!(
+                "<StsRsnInf><Rsn><Cd>{}</Cd></Rsn><AddtlInf>{}</AddtlInf></StsRsnInf>",
+                reason.code,
+                escape(&additional_information)
+            )
+        }
+        None => String::new(),
+    }
+}
+
+fn is_numeric(value: &str) -> bool {
+    !value.is_empty() && value.len() <= 15 && value.chars().all(|c| c.is_ascii_digit())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::write;
+    use crate::iso20022::assert_valid_against_schema;
+    use buckpal_application::domain::payment_initiation::{
+        PaymentInstructionStatus, PaymentStatus, PaymentStatusReport, StatusReason,
+        TransactionStatus,
+    };
+    use buckpal_application::domain::transfer::TransferId;
+    use chrono::Utc;
+
+    fn given_a_status_report() -> PaymentStatusReport {
+        PaymentStatusReport {
+            original_message_id: String::from("MSG-2021-03-21-001"),
+            original_number_of_transactions: String::from("2"),
+            status: PaymentStatus::PartiallyAccepted,
+            reason: None,
+            payments: vec![PaymentInstructionStatus {
+                payment_information_id: String::from("PMT-1"),
+                transactions: vec![
+                    TransactionStatus {
+                        instruction_id: Some(String::from("INSTR-1")),
+                        end_to_end_id: String::from("E2E-1"),
+                        status: PaymentStatus::AcceptedSettlementCompleted,
+                        reason: None,
+                        transfer_id: Some(TransferId(17)),
+                    },
+                    TransactionStatus {
+                        instruction_id: None,
+                        end_to_end_id: String::from("E2E-2"),
+                        status: PaymentStatus::Rejected,
+                        reason: Some(StatusReason::new(
+                            "AM04",
+                            "May withdraw failed with the following balance: `20` <insufficient>",
+                        )),
+                        transfer_id: None,
+                    },
+                ],
+            }],
+        }
+    }
+
+    #[test]
+    fn writes_status_report() {
+        let pain002 = write(&given_a_status_report(), &Utc::now());
+
+        assert!(pain002.contains("<GrpSts>PART</GrpSts>"));
+        assert!(pain002.contains(
+            "<OrgnlInstrId>INSTR-1</OrgnlInstrId><OrgnlEndToEndId>E2E-1</OrgnlEndToEndId><TxSts>ACSC</TxSts><AcctSvcrRef>17</AcctSvcrRef>"
+        ));
+        assert!(pain002.contains("<TxSts>RJCT</TxSts><StsRsnInf><Rsn><Cd>AM04</Cd></Rsn>"));
+    }
+
+    #[test]
+    #[ignore = "needs xmllint and the published pain.002.001.03 schema in schemas/"]
+    fn writes_status_report_valid_against_schema() {
+        let pain002 = write(&given_a_status_report(), &Utc::now());
+
+        assert_valid_against_schema(&pain002, "pain.002.001.03.xsd");
+    }
+}
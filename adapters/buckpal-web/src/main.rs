@@ -1,19 +1,123 @@
 #[macro_use]
 extern crate log;
 
+mod accounts;
+mod activity_chain;
+mod audit;
+mod authentication;
+mod chain_checkpoint_signer;
+mod events;
+mod external_payments;
+mod formatters;
+mod holds;
+mod iso20022;
+mod payment_initiations;
+mod reconciliation;
+mod statements;
+mod transfer_receipt_signer;
+mod transfer_receipts;
+mod transfers;
 mod utils;
+mod v1;
+mod webhook_sender;
+mod webhooks;
 
-use crate::utils::success_to_res;
-use anyhow::Result;
-use buckpal_application::application::port::incoming::send_money_use_case::{
-    SendMoneyCommand, SendMoneyUseCase,
+use crate::accounts::{handle_get_balance, handle_get_balance_history, handle_list_activities};
+use crate::audit::{audit_context, SourceIpResolver};
+use crate::authentication::{parse_principals, principal, ApiKeyAuthentication};
+use crate::chain_checkpoint_signer::HmacChainCheckpointSigner;
+use crate::events::LogDomainEventSubscriber;
+use crate::formatters::StatementFormatters;
+use crate::holds::{handle_authorize_transfer, handle_capture_transfer, handle_void_hold};
+use crate::reconciliation::to_report_response;
+use crate::statements::{handle_download_statement, handle_get_statement};
+use crate::transfer_receipt_signer::Ed25519TransferReceiptSigner;
+use crate::transfers::{
+    handle_get_transfer, handle_reverse_transfer, idempotency_key_header, send_money_error,
+    to_amount, SendMoneyResponse,
+};
+use crate::utils::json_to_res;
+use crate::webhook_sender::HttpWebhookSender;
+use anyhow::{anyhow, Result};
+use async_std::task;
+use buckpal_application::application::port::incoming::{
+    authorize_transfer_use_case::AuthorizeTransferUseCase,
+    balance_history_query::BalanceHistoryQuery,
+    batch_send_money_use_case::BatchSendMoneyUseCase,
+    capture_transfer_use_case::CaptureTransferUseCase,
+    checkpoint_activity_chains_use_case::CheckpointActivityChainsUseCase,
+    deliver_webhooks_use_case::DeliverWebhooksUseCase,
+    export_external_payments_use_case::ExportExternalPaymentsUseCase,
+    generate_statement_query::GenerateStatementQuery,
+    get_account_balance_query::GetAccountBalanceQuery,
+    get_transfer_query::GetTransferQuery,
+    get_transfer_receipt_query::GetTransferReceiptQuery,
+    import_payment_initiation_use_case::ImportPaymentInitiationUseCase,
+    list_activities_query::ListActivitiesQuery,
+    list_audit_log_query::ListAuditLogQuery,
+    manage_webhooks_use_case::ManageWebhooksUseCase,
+    reconcile_ledger_use_case::ReconcileLedgerUseCase,
+    reverse_transfer_use_case::ReverseTransferUseCase,
+    send_money_use_case::{SendMoneyCommand, SendMoneyUseCase},
+    verify_account_balances_use_case::VerifyAccountBalancesUseCase,
+    verify_activity_chain_query::VerifyActivityChainQuery,
+    void_hold_use_case::VoidHoldUseCase,
+};
+use buckpal_application::application::port::outgoing::{
+    load_account_port::LoadAccountPort, record_transfer_port::RecordTransferPort,
+    update_account_state_port::UpdateAccountStatePort,
 };
 use buckpal_application::application::service::{
+    aba_properties::AbaProperties,
+    audited_authorize_transfer_service::AuditedAuthorizeTransferService,
+    audited_batch_send_money_service::AuditedBatchSendMoneyService,
+    audited_capture_transfer_service::AuditedCaptureTransferService,
+    audited_deliver_webhooks_service::AuditedDeliverWebhooksService,
+    audited_get_account_balance_service::AuditedGetAccountBalanceService,
+    audited_import_payment_initiation_service::AuditedImportPaymentInitiationService,
+    audited_manage_webhooks_service::AuditedManageWebhooksService,
+    audited_reverse_transfer_service::AuditedReverseTransferService,
+    audited_send_money_service::AuditedSendMoneyService,
+    audited_void_hold_service::AuditedVoidHoldService,
+    authorize_transfer_service::AuthorizeTransferService,
+    balance_history_service::BalanceHistoryService,
+    batch_send_money_service::BatchSendMoneyService,
+    capture_transfer_service::CaptureTransferService,
+    checkpoint_activity_chains_service::CheckpointActivityChainsService,
+    deliver_webhooks_service::DeliverWebhooksService,
+    export_external_payments_service::ExportExternalPaymentsService,
+    generate_statement_service::GenerateStatementService,
+    get_account_balance_service::GetAccountBalanceService,
+    get_transfer_receipt_service::GetTransferReceiptService,
+    get_transfer_service::GetTransferService,
+    idempotent_send_money_service::IdempotentSendMoneyService,
+    import_payment_initiation_service::ImportPaymentInitiationService,
+    in_memory_domain_event_publisher::InMemoryDomainEventPublisher,
+    list_activities_service::ListActivitiesService, list_audit_log_service::ListAuditLogService,
+    manage_webhooks_service::ManageWebhooksService,
     money_transfer_properties::MoneyTransferProperties, no_op_account_lock::NoOpAccountLock,
-    send_money_service::SendMoneyService,
+    no_op_domain_event_publisher::NoOpDomainEventPublisher,
+    reconcile_ledger_service::ReconcileLedgerService,
+    reverse_transfer_service::ReverseTransferService, send_money_service::SendMoneyService,
+    verify_account_balances_service::VerifyAccountBalancesService,
+    verify_activity_chain_service::VerifyActivityChainService, void_hold_service::VoidHoldService,
+    webhook_event_subscriber::WebhookEventSubscriber, webhook_properties::WebhookProperties,
 };
+use buckpal_application::domain::aba::AbaUser;
 use buckpal_application::domain::account::AccountId;
 use buckpal_persistence::account_persistence_adapter::AccountPersistenceAdapter;
+use buckpal_persistence::activity_chain_persistence_adapter::ActivityChainPersistenceAdapter;
+use buckpal_persistence::audit_persistence_adapter::AuditPersistenceAdapter;
+use buckpal_persistence::event_sourced_account_persistence_adapter::EventSourcedAccountPersistenceAdapter;
+use buckpal_persistence::external_payment_persistence_adapter::ExternalPaymentPersistenceAdapter;
+use buckpal_persistence::idempotency_persistence_adapter::IdempotencyPersistenceAdapter;
+use buckpal_persistence::outbox_relay::OutboxRelay;
+use buckpal_persistence::payment_initiation_persistence_adapter::PaymentInitiationPersistenceAdapter;
+use buckpal_persistence::reconciliation_persistence_adapter::ReconciliationPersistenceAdapter;
+use buckpal_persistence::statement_persistence_adapter::StatementPersistenceAdapter;
+use buckpal_persistence::transfer_persistence_adapter::TransferPersistenceAdapter;
+use buckpal_persistence::webhook_persistence_adapter::WebhookPersistenceAdapter;
+use chrono::Duration;
 use rusty_money::{money, Money};
 use sqlx::postgres::PgPoolOptions;
 use std::env;
@@ -23,12 +127,74 @@ use tide::{security::CorsMiddleware, Error, ParamError, Request, Response, Serve
 #[derive(Clone)]
 struct AppState {
     send_money_use_case: Arc<dyn SendMoneyUseCase + Send + Sync>,
+    get_transfer_query: Arc<dyn GetTransferQuery + Send + Sync>,
+    reverse_transfer_use_case: Arc<dyn ReverseTransferUseCase + Send + Sync>,
+    authorize_transfer_use_case: Arc<dyn AuthorizeTransferUseCase + Send + Sync>,
+    capture_transfer_use_case: Arc<dyn CaptureTransferUseCase + Send + Sync>,
+    void_hold_use_case: Arc<dyn VoidHoldUseCase + Send + Sync>,
+    list_activities_query: Arc<dyn ListActivitiesQuery + Send + Sync>,
+    get_account_balance_query: Arc<dyn GetAccountBalanceQuery + Send + Sync>,
+    balance_history_query: Arc<dyn BalanceHistoryQuery + Send + Sync>,
+    generate_statement_query: Arc<dyn GenerateStatementQuery + Send + Sync>,
+    statement_formatters: Arc<StatementFormatters>,
+    import_payment_initiation_use_case: Arc<dyn ImportPaymentInitiationUseCase + Send + Sync>,
+    batch_send_money_use_case: Arc<dyn BatchSendMoneyUseCase + Send + Sync>,
+    /// Only set up when the ABA direct entry user is configured.
+    export_external_payments_use_case: Option<Arc<dyn ExportExternalPaymentsUseCase + Send + Sync>>,
+    manage_webhooks_use_case: Arc<dyn ManageWebhooksUseCase + Send + Sync>,
+    deliver_webhooks_use_case: Arc<dyn DeliverWebhooksUseCase + Send + Sync>,
+    /// Only set up when the checkpoint key is configured.
+    verify_activity_chain_query: Option<Arc<dyn VerifyActivityChainQuery + Send + Sync>>,
+    /// Only set up when the receipt signing key is configured.
+    get_transfer_receipt_query: Option<Arc<dyn GetTransferReceiptQuery + Send + Sync>>,
+    list_audit_log_query: Arc<dyn ListAuditLogQuery + Send + Sync>,
 }
 
 impl AppState {
-    fn new(send_money_use_case: Arc<dyn SendMoneyUseCase + Send + Sync>) -> Self {
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        send_money_use_case: Arc<dyn SendMoneyUseCase + Send + Sync>,
+        get_transfer_query: Arc<dyn GetTransferQuery + Send + Sync>,
+        reverse_transfer_use_case: Arc<dyn ReverseTransferUseCase + Send + Sync>,
+        authorize_transfer_use_case: Arc<dyn AuthorizeTransferUseCase + Send + Sync>,
+        capture_transfer_use_case: Arc<dyn CaptureTransferUseCase + Send + Sync>,
+        void_hold_use_case: Arc<dyn VoidHoldUseCase + Send + Sync>,
+        list_activities_query: Arc<dyn ListActivitiesQuery + Send + Sync>,
+        get_account_balance_query: Arc<dyn GetAccountBalanceQuery + Send + Sync>,
+        balance_history_query: Arc<dyn BalanceHistoryQuery + Send + Sync>,
+        generate_statement_query: Arc<dyn GenerateStatementQuery + Send + Sync>,
+        statement_formatters: Arc<StatementFormatters>,
+        import_payment_initiation_use_case: Arc<dyn ImportPaymentInitiationUseCase + Send + Sync>,
+        batch_send_money_use_case: Arc<dyn BatchSendMoneyUseCase + Send + Sync>,
+        export_external_payments_use_case: Option<
+            Arc<dyn ExportExternalPaymentsUseCase + Send + Sync>,
+        >,
+        manage_webhooks_use_case: Arc<dyn ManageWebhooksUseCase + Send + Sync>,
+        deliver_webhooks_use_case: Arc<dyn DeliverWebhooksUseCase + Send + Sync>,
+        verify_activity_chain_query: Option<Arc<dyn VerifyActivityChainQuery + Send + Sync>>,
+        get_transfer_receipt_query: Option<Arc<dyn GetTransferReceiptQuery + Send + Sync>>,
+        list_audit_log_query: Arc<dyn ListAuditLogQuery + Send + Sync>,
+    ) -> Self {
         Self {
             send_money_use_case,
+            get_transfer_query,
+            reverse_transfer_use_case,
+            authorize_transfer_use_case,
+            capture_transfer_use_case,
+            void_hold_use_case,
+            list_activities_query,
+            get_account_balance_query,
+            balance_history_query,
+            generate_statement_query,
+            statement_formatters,
+            import_payment_initiation_use_case,
+            batch_send_money_use_case,
+            export_external_payments_use_case,
+            manage_webhooks_use_case,
+            deliver_webhooks_use_case,
+            verify_activity_chain_query,
+            get_transfer_receipt_query,
+            list_audit_log_query,
         }
     }
 }
@@ -64,23 +230,117 @@ fn validate_accounts_send_params(req: &Request<AppState>) -> tide::Result<(i32,
     Ok((source_account_id, target_account_id, amount))
 }
 
+/// Legacy route kept for existing clients, new clients should use `POST /v1/transfers`.
 async fn handle_accounts_send(req: Request<AppState>) -> tide::Result<Response> {
     let (source_account_id, target_account_id, amount) = validate_accounts_send_params(&req)?;
 
-    let command = SendMoneyCommand::new(
+    let mut command = SendMoneyCommand::new(
         AccountId(source_account_id),
         AccountId(target_account_id),
         money!(amount, "AUD"),
-    );
+    )
+    .with_audit_context(audit_context(&req))
+    .with_initiator(principal(&req)?);
+    if let Some(idempotency_key) = idempotency_key_header(&req) {
+        command = command.with_idempotency_key(idempotency_key);
+    }
 
     let send_money_use_case = req.state().send_money_use_case.clone();
 
-    send_money_use_case
+    let transfer_id = send_money_use_case
         .send_money(&command)
         .await
-        .map_err(|err| Error::from_str(StatusCode::BadRequest, err.to_string()))?;
+        .map_err(send_money_error)?;
+
+    let send_money_response = SendMoneyResponse {
+        message: String::from("Money Sent!"),
+        transfer_id: transfer_id.0,
+    };
 
-    success_to_res("Money Sent!")
+    let mut res = json_to_res(StatusCode::Ok, &send_money_response)?;
+    res.insert_header("Deprecation", "true");
+    res.insert_header("Link", "</v1/transfers>; rel=\"successor-version\"");
+
+    Ok(res)
+}
+
+/// Where accounts are loaded from and their changes written to, chosen by `LEDGER_MODE`.
+#[derive(Clone)]
+enum LedgerAdapter {
+    Classic(AccountPersistenceAdapter),
+    EventSourced(EventSourcedAccountPersistenceAdapter),
+}
+
+impl LedgerAdapter {
+    fn load_account_port(&self) -> Box<dyn LoadAccountPort + Send + Sync> {
+        match self {
+            Self::Classic(adapter) => Box::new(adapter.clone()),
+            Self::EventSourced(adapter) => Box::new(adapter.clone()),
+        }
+    }
+
+    fn update_account_state_port(&self) -> Box<dyn UpdateAccountStatePort + Send + Sync> {
+        match self {
+            Self::Classic(adapter) => Box::new(adapter.clone()),
+            Self::EventSourced(adapter) => Box::new(adapter.clone()),
+        }
+    }
+
+    fn record_transfer_port(&self) -> Box<dyn RecordTransferPort + Send + Sync> {
+        match self {
+            Self::Classic(adapter) => Box::new(adapter.clone()),
+            Self::EventSourced(adapter) => Box::new(adapter.clone()),
+        }
+    }
+}
+
+fn new_send_money_use_case(
+    ledger_adapter: &LedgerAdapter,
+    account_persistence_adapter: &AccountPersistenceAdapter,
+    transfer_persistence_adapter: &TransferPersistenceAdapter,
+    idempotency_persistence_adapter: &IdempotencyPersistenceAdapter,
+    audit_persistence_adapter: &AuditPersistenceAdapter,
+    money_transfer_properties: &MoneyTransferProperties,
+) -> AuditedSendMoneyService {
+    let send_money_use_case = IdempotentSendMoneyService::new(
+        Box::new(SendMoneyService::new(
+            ledger_adapter.load_account_port(),
+            Box::new(NoOpAccountLock::default()),
+            ledger_adapter.record_transfer_port(),
+            Box::new(transfer_persistence_adapter.clone()),
+            Box::new(account_persistence_adapter.clone()),
+            // the accounts and the transfer write their events to the outbox
+            Box::new(NoOpDomainEventPublisher::default()),
+            Box::new(account_persistence_adapter.clone()),
+            money_transfer_properties.clone(),
+        )),
+        Box::new(idempotency_persistence_adapter.clone()),
+        Box::new(account_persistence_adapter.clone()),
+        money_transfer_properties.clone(),
+    );
+
+    // replays of idempotent requests are audited as well
+    AuditedSendMoneyService::new(
+        Box::new(send_money_use_case),
+        Box::new(audit_persistence_adapter.clone()),
+    )
+}
+
+fn new_reverse_transfer_use_case(
+    ledger_adapter: &LedgerAdapter,
+    account_persistence_adapter: &AccountPersistenceAdapter,
+    transfer_persistence_adapter: &TransferPersistenceAdapter,
+) -> ReverseTransferService {
+    ReverseTransferService::new(
+        ledger_adapter.load_account_port(),
+        Box::new(NoOpAccountLock::default()),
+        ledger_adapter.update_account_state_port(),
+        Box::new(transfer_persistence_adapter.clone()),
+        Box::new(transfer_persistence_adapter.clone()),
+        // the accounts and the transfers write their events to the outbox
+        Box::new(NoOpDomainEventPublisher::default()),
+        Box::new(account_persistence_adapter.clone()),
+    )
 }
 
 #[async_std::main]
@@ -97,27 +357,448 @@ async fn main() -> Result<()> {
         .connect(&database_url)
         .await?;
 
-    let account_persistence_adapter = AccountPersistenceAdapter::new(pool);
-    let no_op_account_lock = NoOpAccountLock::default();
-    let money_transfer_properties = MoneyTransferProperties::new();
-    let send_money_use_case = SendMoneyService::new(
+    let reconcile_ledger_use_case = ReconcileLedgerService::new(Box::new(
+        ReconciliationPersistenceAdapter::new(pool.clone()),
+    ));
+
+    // `buckpal-web reconcile` prints the report instead of starting the server, exiting with 1
+    // if the ledger doesn't reconcile
+    if env::args().nth(1).as_deref() == Some("reconcile") {
+        let report = reconcile_ledger_use_case.reconcile_ledger().await?;
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&to_report_response(&report))?
+        );
+
+        if !report.is_clean() {
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
+    let account_persistence_adapter = AccountPersistenceAdapter::new(pool.clone());
+    let ledger_adapter = match env::var("LEDGER_MODE").as_deref() {
+        Ok("event-sourced") => LedgerAdapter::EventSourced(
+            EventSourcedAccountPersistenceAdapter::new(pool.clone()).with_snapshot_interval(
+                env::var("LEDGER_SNAPSHOT_INTERVAL")
+                    .unwrap_or_else(|_| String::from("100"))
+                    .parse()?,
+            ),
+        ),
+        Ok("classic") | Err(_) => LedgerAdapter::Classic(account_persistence_adapter.clone()),
+        Ok(other) => {
+            return Err(anyhow!(
+                "Unknown LEDGER_MODE `{}`, expected `classic` or `event-sourced`",
+                other
+            ))
+        }
+    };
+    let transfer_persistence_adapter = TransferPersistenceAdapter::new(pool.clone());
+    let idempotency_persistence_adapter = IdempotencyPersistenceAdapter::new(pool.clone());
+    let statement_persistence_adapter = StatementPersistenceAdapter::new(pool.clone());
+    let payment_initiation_persistence_adapter =
+        PaymentInitiationPersistenceAdapter::new(pool.clone());
+    let external_payment_persistence_adapter = ExternalPaymentPersistenceAdapter::new(pool.clone());
+    let webhook_persistence_adapter = WebhookPersistenceAdapter::new(pool.clone());
+    let audit_persistence_adapter = AuditPersistenceAdapter::new(pool.clone());
+    let activity_chain_persistence_adapter = ActivityChainPersistenceAdapter::new(pool.clone());
+    let outbox_relay = OutboxRelay::new(
+        pool,
+        Box::new(
+            InMemoryDomainEventPublisher::new()
+                .with_subscriber(Arc::new(LogDomainEventSubscriber {}))
+                .with_subscriber(Arc::new(WebhookEventSubscriber::new(Box::new(
+                    webhook_persistence_adapter.clone(),
+                )))),
+        ),
+    );
+    let outbox_relay_interval_ms: u64 = env::var("OUTBOX_RELAY_INTERVAL_MS")
+        .unwrap_or_else(|_| String::from("1000"))
+        .parse()?;
+    let webhook_delivery_interval_ms: u64 = env::var("WEBHOOK_DELIVERY_INTERVAL_MS")
+        .unwrap_or_else(|_| String::from("1000"))
+        .parse()?;
+    let webhook_timeout_ms: u64 = env::var("WEBHOOK_TIMEOUT_MS")
+        .unwrap_or_else(|_| String::from("10000"))
+        .parse()?;
+    let balance_verification_interval_ms: u64 = env::var("BALANCE_VERIFICATION_INTERVAL_MS")
+        .unwrap_or_else(|_| String::from("3600000"))
+        .parse()?;
+    let reconciliation_interval_ms: u64 = env::var("RECONCILIATION_INTERVAL_MS")
+        .unwrap_or_else(|_| String::from("86400000"))
+        .parse()?;
+    let audit_balance_queries: bool = env::var("AUDIT_BALANCE_QUERIES")
+        .unwrap_or_else(|_| String::from("false"))
+        .parse()?;
+    let api_key_authentication = ApiKeyAuthentication::new(&env::var("API_KEYS")?)?;
+    let audit_log_readers = parse_principals(&env::var("AUDIT_LOG_READERS").unwrap_or_default())?;
+    let source_ip_resolver =
+        SourceIpResolver::new(&env::var("TRUSTED_PROXIES").unwrap_or_default())?;
+    let chain_checkpoint_interval_ms: u64 = env::var("CHAIN_CHECKPOINT_INTERVAL_MS")
+        .unwrap_or_else(|_| String::from("3600000"))
+        .parse()?;
+    // activities are chained regardless, checkpoints and verification need the key
+    let chain_checkpoint_signer = match env::var("CHAIN_CHECKPOINT_KEY") {
+        Ok(key) => Some(HmacChainCheckpointSigner::new(&key)),
+        Err(_) => {
+            info!("CHAIN_CHECKPOINT_KEY is not set, activity chains won't be checkpointed");
+            None
+        }
+    };
+    let transfer_receipt_signer = match env::var("RECEIPT_SIGNING_KEY") {
+        Ok(key) => Some(Ed25519TransferReceiptSigner::new(&key)?),
+        Err(_) => {
+            info!("RECEIPT_SIGNING_KEY is not set, transfer receipts won't be issued");
+            None
+        }
+    };
+    let idempotency_key_retention_hours: i64 = env::var("IDEMPOTENCY_KEY_RETENTION_HOURS")
+        .unwrap_or_else(|_| String::from("24"))
+        .parse()?;
+    let hold_expiry_hours: i64 = env::var("HOLD_EXPIRY_HOURS")
+        .unwrap_or_else(|_| String::from("168"))
+        .parse()?;
+    let money_transfer_properties = MoneyTransferProperties::new()
+        .with_hold_expiry(Duration::hours(hold_expiry_hours))
+        .with_idempotency_key_retention(Duration::hours(idempotency_key_retention_hours));
+    // external payments are only exported once the direct entry user is configured
+    let aba_properties = match env::var("ABA_FINANCIAL_INSTITUTION") {
+        Ok(financial_institution) => Some(
+            AbaProperties::new(AbaUser::new(
+                &financial_institution,
+                &env::var("ABA_USER_NAME")?,
+                &env::var("ABA_USER_ID")?,
+                &env::var("ABA_TRACE_BSB")?,
+                &env::var("ABA_TRACE_ACCOUNT_NUMBER")?,
+                &env::var("ABA_REMITTER_NAME")?,
+            )?)
+            .with_description(
+                &env::var("ABA_DESCRIPTION").unwrap_or_else(|_| String::from("PAYMENTS")),
+            )
+            .with_self_balancing(
+                env::var("ABA_SELF_BALANCING")
+                    .unwrap_or_else(|_| String::from("false"))
+                    .parse()?,
+            ),
+        ),
+        Err(_) => {
+            info!("ABA_FINANCIAL_INSTITUTION is not set, external payments won't be exported");
+            None
+        }
+    };
+    let send_money_use_case = new_send_money_use_case(
+        &ledger_adapter,
+        &account_persistence_adapter,
+        &transfer_persistence_adapter,
+        &idempotency_persistence_adapter,
+        &audit_persistence_adapter,
+        &money_transfer_properties,
+    );
+    // the transfers of imports and batches are audited by the send money use case as well
+    let import_payment_initiation_use_case = AuditedImportPaymentInitiationService::new(
+        Box::new(ImportPaymentInitiationService::new(
+            Box::new(new_send_money_use_case(
+                &ledger_adapter,
+                &account_persistence_adapter,
+                &transfer_persistence_adapter,
+                &idempotency_persistence_adapter,
+                &audit_persistence_adapter,
+                &money_transfer_properties,
+            )),
+            Box::new(payment_initiation_persistence_adapter),
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let batch_send_money_use_case = AuditedBatchSendMoneyService::new(
+        Box::new(BatchSendMoneyService::new(
+            Box::new(new_send_money_use_case(
+                &ledger_adapter,
+                &account_persistence_adapter,
+                &transfer_persistence_adapter,
+                &idempotency_persistence_adapter,
+                &audit_persistence_adapter,
+                &money_transfer_properties,
+            )),
+            Box::new(new_reverse_transfer_use_case(
+                &ledger_adapter,
+                &account_persistence_adapter,
+                &transfer_persistence_adapter,
+            )),
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let reverse_transfer_use_case = AuditedReverseTransferService::new(
+        Box::new(new_reverse_transfer_use_case(
+            &ledger_adapter,
+            &account_persistence_adapter,
+            &transfer_persistence_adapter,
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let authorize_transfer_use_case = AuditedAuthorizeTransferService::new(
+        Box::new(AuthorizeTransferService::new(
+            ledger_adapter.load_account_port(),
+            Box::new(NoOpAccountLock::default()),
+            ledger_adapter.update_account_state_port(),
+            Box::new(account_persistence_adapter.clone()),
+            money_transfer_properties,
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let capture_transfer_use_case = AuditedCaptureTransferService::new(
+        Box::new(CaptureTransferService::new(
+            Box::new(account_persistence_adapter.clone()),
+            ledger_adapter.load_account_port(),
+            Box::new(NoOpAccountLock::default()),
+            ledger_adapter.update_account_state_port(),
+            ledger_adapter.record_transfer_port(),
+            Box::new(transfer_persistence_adapter.clone()),
+            // the accounts and the transfer write their events to the outbox
+            Box::new(NoOpDomainEventPublisher::default()),
+            Box::new(account_persistence_adapter.clone()),
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let void_hold_use_case = AuditedVoidHoldService::new(
+        Box::new(VoidHoldService::new(
+            Box::new(account_persistence_adapter.clone()),
+            ledger_adapter.load_account_port(),
+            ledger_adapter.update_account_state_port(),
+            Box::new(account_persistence_adapter.clone()),
+        )),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let export_external_payments_use_case = aba_properties.map(|aba_properties| {
+        Arc::new(ExportExternalPaymentsService::new(
+            Box::new(external_payment_persistence_adapter),
+            aba_properties,
+        )) as Arc<dyn ExportExternalPaymentsUseCase + Send + Sync>
+    });
+    let manage_webhooks_use_case = AuditedManageWebhooksService::new(
+        Box::new(ManageWebhooksService::new(Box::new(
+            webhook_persistence_adapter.clone(),
+        ))),
+        Box::new(audit_persistence_adapter.clone()),
+    );
+    let deliver_webhooks_use_case: Arc<dyn DeliverWebhooksUseCase + Send + Sync> =
+        Arc::new(AuditedDeliverWebhooksService::new(
+            Box::new(DeliverWebhooksService::new(
+                Box::new(webhook_persistence_adapter),
+                Box::new(HttpWebhookSender::new(std::time::Duration::from_millis(
+                    webhook_timeout_ms,
+                ))),
+                WebhookProperties::new(),
+            )),
+            Box::new(audit_persistence_adapter.clone()),
+        ));
+    let verify_account_balances_use_case =
+        VerifyAccountBalancesService::new(Box::new(account_persistence_adapter.clone()));
+    // grants are kept in `account_access` whichever ledger is used
+    let get_account_balance_query = GetAccountBalanceService::new(
+        ledger_adapter.load_account_port(),
+        Box::new(account_persistence_adapter.clone()),
         Box::new(account_persistence_adapter.clone()),
-        Box::new(no_op_account_lock),
-        Box::new(account_persistence_adapter),
-        money_transfer_properties,
     );
+    let get_account_balance_query: Arc<dyn GetAccountBalanceQuery + Send + Sync> =
+        if audit_balance_queries {
+            Arc::new(AuditedGetAccountBalanceService::new(
+                Box::new(get_account_balance_query),
+                Box::new(audit_persistence_adapter.clone()),
+            ))
+        } else {
+            Arc::new(get_account_balance_query)
+        };
+    let list_audit_log_query =
+        ListAuditLogService::new(Box::new(audit_persistence_adapter), audit_log_readers);
+    let list_activities_query = ListActivitiesService::new(
+        Box::new(account_persistence_adapter.clone()),
+        Box::new(account_persistence_adapter.clone()),
+    );
+    let balance_history_query = BalanceHistoryService::new(
+        ledger_adapter.load_account_port(),
+        Box::new(account_persistence_adapter.clone()),
+    );
+    let generate_statement_query = GenerateStatementService::new(
+        ledger_adapter.load_account_port(),
+        Box::new(statement_persistence_adapter.clone()),
+        Box::new(statement_persistence_adapter),
+        Box::new(transfer_persistence_adapter.clone()),
+        Box::new(account_persistence_adapter.clone()),
+    );
+    let get_transfer_receipt_query = transfer_receipt_signer.map(|signer| {
+        info!(
+            "Signing transfer receipts with public key {}",
+            signer.public_key()
+        );
+        Arc::new(GetTransferReceiptService::new(
+            Box::new(transfer_persistence_adapter.clone()),
+            Box::new(signer),
+            Box::new(account_persistence_adapter.clone()),
+        )) as Arc<dyn GetTransferReceiptQuery + Send + Sync>
+    });
+    let get_transfer_query = GetTransferService::new(
+        Box::new(transfer_persistence_adapter),
+        Box::new(account_persistence_adapter.clone()),
+    );
+    let verify_activity_chain_query = chain_checkpoint_signer.clone().map(|signer| {
+        Arc::new(VerifyActivityChainService::new(
+            Box::new(activity_chain_persistence_adapter.clone()),
+            Box::new(signer),
+            Box::new(account_persistence_adapter),
+        )) as Arc<dyn VerifyActivityChainQuery + Send + Sync>
+    });
+    let checkpoint_activity_chains_use_case = chain_checkpoint_signer.map(|signer| {
+        CheckpointActivityChainsService::new(
+            Box::new(activity_chain_persistence_adapter),
+            Box::new(signer),
+        )
+    });
 
-    let app_state = AppState::new(Arc::new(send_money_use_case));
+    let app_state = AppState::new(
+        Arc::new(send_money_use_case),
+        Arc::new(get_transfer_query),
+        Arc::new(reverse_transfer_use_case),
+        Arc::new(authorize_transfer_use_case),
+        Arc::new(capture_transfer_use_case),
+        Arc::new(void_hold_use_case),
+        Arc::new(list_activities_query),
+        get_account_balance_query,
+        Arc::new(balance_history_query),
+        Arc::new(generate_statement_query),
+        Arc::new(StatementFormatters::default()),
+        Arc::new(import_payment_initiation_use_case),
+        Arc::new(batch_send_money_use_case),
+        export_external_payments_use_case,
+        Arc::new(manage_webhooks_use_case),
+        deliver_webhooks_use_case.clone(),
+        verify_activity_chain_query,
+        get_transfer_receipt_query,
+        Arc::new(list_audit_log_query),
+    );
 
-    let mut app = Server::with_state(app_state);
+    let mut app = Server::with_state(app_state.clone());
 
     let cors = CorsMiddleware::new();
 
     app.with(cors);
+    // after CORS, which answers preflight requests without credentials on its own
+    app.with(source_ip_resolver);
+    app.with(api_key_authentication);
+
+    app.at("/v1").nest(v1::server(app_state));
 
     app.at("/accounts/send/:sourceAccountId/:targetAccountId/:amount")
         .post(handle_accounts_send);
 
+    app.at("/accounts/:accountId/balance")
+        .get(handle_get_balance);
+    app.at("/accounts/:accountId/balance-history")
+        .get(handle_get_balance_history);
+    app.at("/accounts/:accountId/statements")
+        .get(handle_get_statement);
+    app.at("/accounts/:accountId/statements/download")
+        .get(handle_download_statement);
+    app.at("/accounts/:accountId/activities")
+        .get(handle_list_activities);
+
+    app.at("/transfers/:transferId").get(handle_get_transfer);
+    app.at("/transfers/:transferId/reversals")
+        .post(handle_reverse_transfer);
+
+    app.at("/holds").post(handle_authorize_transfer);
+    app.at("/holds/:holdId/capture")
+        .post(handle_capture_transfer);
+    app.at("/holds/:holdId/void").post(handle_void_hold);
+
+    task::spawn(async move {
+        outbox_relay
+            .run(std::time::Duration::from_millis(outbox_relay_interval_ms))
+            .await
+    });
+    task::spawn(async move {
+        loop {
+            if let Err(err) = deliver_webhooks_use_case.deliver_due_webhooks().await {
+                error!("Delivering webhooks failed: {}", err);
+            }
+
+            task::sleep(std::time::Duration::from_millis(
+                webhook_delivery_interval_ms,
+            ))
+            .await;
+        }
+    });
+    task::spawn(async move {
+        loop {
+            match verify_account_balances_use_case
+                .verify_account_balances()
+                .await
+            {
+                Ok(drifts) => {
+                    for drift in drifts {
+                        warn!(
+                            "Balance of account {} drifted by {}: kept {} up to activity {:?}, booked {} up to activity {:?}",
+                            drift.account_id.0,
+                            to_amount(&drift.difference()),
+                            to_amount(&drift.kept_balance),
+                            drift.kept_last_activity_id.as_ref().map(|id| id.0),
+                            to_amount(&drift.booked_balance),
+                            drift.last_activity_id.as_ref().map(|id| id.0)
+                        );
+                    }
+                }
+                Err(err) => error!("Verifying balances failed: {}", err),
+            }
+
+            task::sleep(std::time::Duration::from_millis(
+                balance_verification_interval_ms,
+            ))
+            .await;
+        }
+    });
+    task::spawn(async move {
+        loop {
+            match reconcile_ledger_use_case.reconcile_ledger().await {
+                Ok(report) if !report.is_clean() => {
+                    match serde_json::to_string(&to_report_response(&report)) {
+                        Ok(json) => warn!(
+                            "Ledger doesn't reconcile, {} violations: {}",
+                            report.violations.len(),
+                            json
+                        ),
+                        Err(err) => error!("Serializing reconciliation report failed: {}", err),
+                    }
+                }
+                Ok(_) => info!("Ledger reconciles"),
+                Err(err) => error!("Reconciling ledger failed: {}", err),
+            }
+
+            task::sleep(std::time::Duration::from_millis(reconciliation_interval_ms)).await;
+        }
+    });
+    if let Some(checkpoint_activity_chains_use_case) = checkpoint_activity_chains_use_case {
+        task::spawn(async move {
+            loop {
+                match checkpoint_activity_chains_use_case
+                    .checkpoint_activity_chains()
+                    .await
+                {
+                    Ok(checkpoint) => info!(
+                        "Checkpointed {} activity chains as {:?} with digest {}",
+                        checkpoint.heads.len(),
+                        checkpoint.id.as_ref().map(|id| id.0),
+                        checkpoint.digest
+                    ),
+                    Err(err) => error!("Checkpointing activity chains failed: {}", err),
+                }
+
+                task::sleep(std::time::Duration::from_millis(
+                    chain_checkpoint_interval_ms,
+                ))
+                .await;
+            }
+        });
+    }
+
     info!("Starting at: {}", listen_addr);
 
     app.listen(listen_addr).await?;
@@ -0,0 +1,43 @@
+use crate::audit::audit_context;
+use crate::authentication::principal;
+use crate::iso20022::{pain001, pain002};
+use crate::AppState;
+use buckpal_application::domain::payment_initiation::PaymentStatus;
+use chrono::Utc;
+use tide::{Error, Request, Response, StatusCode};
+
+/// Imports a `pain.001` file and answers with its `pain.002` status report. Files rejected as a
+/// whole are answered with a client error, their report says why.
+pub async fn handle_import_payment_initiation(
+    mut req: Request<AppState>,
+) -> tide::Result<Response> {
+    let principal = principal(&req)?;
+    let audit_context = audit_context(&req);
+    let body = req.body_string().await?;
+
+    let initiation = pain001::parse(&body).map_err(|err| {
+        Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!("Invalid pain.001 file: {}", err),
+        )
+    })?;
+
+    let import_payment_initiation_use_case = req.state().import_payment_initiation_use_case.clone();
+
+    let report = import_payment_initiation_use_case
+        .import_payment_initiation(&initiation, Some(&principal), &audit_context)
+        .await
+        .map_err(|err| Error::from_str(StatusCode::InternalServerError, err.to_string()))?;
+
+    let status = match (&report.status, &report.reason) {
+        (PaymentStatus::Rejected, Some(reason)) if reason.code == "DU01" => StatusCode::Conflict,
+        (PaymentStatus::Rejected, Some(_)) => StatusCode::UnprocessableEntity,
+        _ => StatusCode::Ok,
+    };
+
+    let mut res = Response::new(status);
+    res.set_body(pain002::write(&report, &Utc::now()));
+    res.set_content_type("application/xml");
+
+    Ok(res)
+}
@@ -0,0 +1,109 @@
+use crate::transfers::to_amount;
+use buckpal_application::domain::reconciliation::{ReconciliationReport, ReconciliationViolation};
+use serde::Serialize;
+
+/// The report as printed by `buckpal-web reconcile` and logged by the reconciliation job.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconciliationReportResponse {
+    reconciled_at: String,
+    clean: bool,
+    violations: Vec<ReconciliationViolationResponse>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum ReconciliationViolationResponse {
+    #[serde(rename_all = "camelCase")]
+    UnmatchedActivity {
+        activity_id: Option<i32>,
+        account_id: i32,
+        source_account_id: i32,
+        target_account_id: i32,
+        amount: i64,
+        timestamp: String,
+        transfer_id: Option<i32>,
+    },
+    #[serde(rename_all = "camelCase")]
+    MoneyNotConserved { deposited: i64, withdrawn: i64 },
+    #[serde(rename_all = "camelCase")]
+    NegativeBalance { account_id: i32, balance: i64 },
+}
+
+fn to_violation_response(violation: &ReconciliationViolation) -> ReconciliationViolationResponse {
+    match violation {
+        ReconciliationViolation::UnmatchedActivity(activity) => {
+            ReconciliationViolationResponse::UnmatchedActivity {
+                activity_id: activity.id.as_ref().map(|id| id.0),
+                account_id: activity.owner_account_id.0,
+                source_account_id: activity.source_account_id.0,
+                target_account_id: activity.target_account_id.0,
+                amount: to_amount(&activity.money),
+                timestamp: activity.timestamp.to_rfc3339(),
+                transfer_id: activity.transfer_id.as_ref().map(|id| id.0),
+            }
+        }
+        ReconciliationViolation::MoneyNotConserved(totals) => {
+            ReconciliationViolationResponse::MoneyNotConserved {
+                deposited: to_amount(&totals.deposited),
+                withdrawn: to_amount(&totals.withdrawn),
+            }
+        }
+        ReconciliationViolation::NegativeBalance {
+            account_id,
+            balance,
+        } => ReconciliationViolationResponse::NegativeBalance {
+            account_id: account_id.0,
+            balance: to_amount(balance),
+        },
+    }
+}
+
+pub fn to_report_response(report: &ReconciliationReport) -> ReconciliationReportResponse {
+    ReconciliationReportResponse {
+        reconciled_at: report.reconciled_at.to_rfc3339(),
+        clean: report.is_clean(),
+        violations: report
+            .violations
+            .iter()
+            .map(to_violation_response)
+            .collect(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::to_report_response;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::reconciliation::{
+        LedgerTotals, ReconciliationReport, ReconciliationViolation,
+    };
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+
+    #[test]
+    fn tags_violations_with_their_type() {
+        let report = ReconciliationReport::new(
+            Utc::now(),
+            vec![
+                ReconciliationViolation::MoneyNotConserved(LedgerTotals::new(
+                    money!(500, "AUD"),
+                    money!(700, "AUD"),
+                )),
+                ReconciliationViolation::NegativeBalance {
+                    account_id: AccountId(42),
+                    balance: money!(-200, "AUD"),
+                },
+            ],
+        );
+
+        let json = serde_json::to_value(&to_report_response(&report)).unwrap();
+
+        assert_eq!(json["clean"], false);
+        assert_eq!(json["violations"][0]["type"], "money_not_conserved");
+        assert_eq!(json["violations"][0]["withdrawn"], 700);
+        assert_eq!(json["violations"][1]["type"], "negative_balance");
+        assert_eq!(json["violations"][1]["accountId"], 42);
+        assert_eq!(json["violations"][1]["balance"], -200);
+    }
+}
@@ -0,0 +1,245 @@
+use crate::accounts::{parse_date, validate_account_id_param};
+use crate::authentication::principal;
+use crate::transfers::{error_status, to_amount};
+use crate::utils::{json_to_res, wants_format};
+use crate::AppState;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::activity::ActivityKind;
+use buckpal_application::domain::statement::{Statement, StatementLine};
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use tide::http::mime;
+use tide::{Error, Request, Response, StatusCode};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StatementParams {
+    /// A calendar month as `YYYY-MM`, alternatively to `from` and `to`.
+    pub month: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub format: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatementLineResponse {
+    activity_id: Option<i32>,
+    timestamp: String,
+    kind: &'static str,
+    counterparty_account_id: i32,
+    amount: i64,
+    running_balance: i64,
+    transfer_id: Option<i32>,
+    reference: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct StatementResponse {
+    id: Option<i32>,
+    account_id: i32,
+    from: String,
+    to: String,
+    currency: &'static str,
+    opening_balance: i64,
+    total_in: i64,
+    total_out: i64,
+    closing_balance: i64,
+    generated_at: String,
+    lines: Vec<StatementLineResponse>,
+}
+
+pub fn to_kind(kind: ActivityKind) -> &'static str {
+    match kind {
+        ActivityKind::Deposit => "deposit",
+        ActivityKind::Withdrawal => "withdrawal",
+    }
+}
+
+/// Resolves the requested period, either a whole calendar month or an explicit range of days.
+pub fn to_period(params: &StatementParams) -> tide::Result<(NaiveDate, NaiveDate)> {
+    if let Some(month) = &params.month {
+        let from = parse_date("month", &format!("{}-01", month))?;
+        let next_month = if from.month() == 12 {
+            NaiveDate::from_ymd(from.year() + 1, 1, 1)
+        } else {
+            NaiveDate::from_ymd(from.year(), from.month() + 1, 1)
+        };
+
+        return Ok((from, next_month.pred()));
+    }
+
+    match (&params.from, &params.to) {
+        (Some(from), Some(to)) => Ok((parse_date("from", from)?, parse_date("to", to)?)),
+        _ => Err(Error::from_str(
+            StatusCode::UnprocessableEntity,
+            "Either `month` or both `from` and `to` are required",
+        )),
+    }
+}
+
+fn to_line_response(line: &StatementLine) -> StatementLineResponse {
+    StatementLineResponse {
+        activity_id: line.activity_id.clone().map(|id| id.0),
+        timestamp: line.timestamp.to_rfc3339(),
+        kind: to_kind(line.kind),
+        counterparty_account_id: line.counterparty_account_id.0,
+        amount: to_amount(&line.money),
+        running_balance: to_amount(&line.running_balance),
+        transfer_id: line.transfer_id.clone().map(|id| id.0),
+        reference: line.reference.clone(),
+    }
+}
+
+fn to_statement_response(statement: &Statement) -> StatementResponse {
+    StatementResponse {
+        id: statement.id.clone().map(|id| id.0),
+        account_id: statement.account_id.0,
+        from: statement.from.format("%Y-%m-%d").to_string(),
+        to: statement.to.format("%Y-%m-%d").to_string(),
+        currency: "AUD",
+        opening_balance: to_amount(&statement.opening_balance),
+        total_in: to_amount(&statement.total_in),
+        total_out: to_amount(&statement.total_out),
+        closing_balance: to_amount(&statement.closing_balance),
+        generated_at: statement.generated_at.to_rfc3339(),
+        lines: statement.lines.iter().map(to_line_response).collect(),
+    }
+}
+
+/// Renders a self-contained page that prints on a single sheet per statement.
+fn to_html(statement: &Statement) -> String {
+    let mut rows = String::new();
+    for line in &statement.lines {
+        let (money_in, money_out) = match line.kind {
+            ActivityKind::Deposit => (to_amount(&line.money).to_string(), String::new()),
+            ActivityKind::Withdrawal => (String::new(), to_amount(&line.money).to_string()),
+        };
+
+        rows.push_str(&format!(
+            "<tr><td>{}</td><td>{}</td><td>{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td><td class=\"amount\">{}</td></tr>\n",
+            line.timestamp.format("%Y-%m-%d %H:%M:%S"),
+            to_kind(line.kind),
+            line.counterparty_account_id.0,
+            money_in,
+            money_out,
+            to_amount(&line.running_balance)
+        ));
+    }
+
+    format!(
+        r#"<!DOCTYPE html>
+<html>
+<head>
+<meta charset="utf-8">
+<title>Statement for account {account_id}, {from} to {to}</title>
+<style>
+body {{ font-family: sans-serif; margin: 2em; }}
+table {{ border-collapse: collapse; width: 100%; }}
+th, td {{ border-bottom: 1px solid #ccc; padding: 0.3em 0.6em; text-align: left; }}
+.amount {{ text-align: right; }}
+@media print {{ body {{ margin: 0; }} }}
+</style>
+</head>
+<body>
+<h1>Statement for account {account_id}</h1>
+<p>Period: {from} to {to} (AUD)<br>Generated at: {generated_at}</p>
+<table>
+<tr><th>Opening balance</th><td class="amount">{opening_balance}</td></tr>
+<tr><th>Total in</th><td class="amount">{total_in}</td></tr>
+<tr><th>Total out</th><td class="amount">{total_out}</td></tr>
+<tr><th>Closing balance</th><td class="amount">{closing_balance}</td></tr>
+</table>
+<h2>Activities</h2>
+<table>
+<tr><th>Date</th><th>Kind</th><th>Counterparty</th><th class="amount">In</th><th class="amount">Out</th><th class="amount">Balance</th></tr>
+{rows}</table>
+</body>
+</html>
+"#,
+        account_id = statement.account_id.0,
+        from = statement.from.format("%Y-%m-%d"),
+        to = statement.to.format("%Y-%m-%d"),
+        generated_at = statement.generated_at.to_rfc3339(),
+        opening_balance = to_amount(&statement.opening_balance),
+        total_in = to_amount(&statement.total_in),
+        total_out = to_amount(&statement.total_out),
+        closing_balance = to_amount(&statement.closing_balance),
+        rows = rows
+    )
+}
+
+pub async fn handle_get_statement(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+    let params: StatementParams = req.query()?;
+
+    let (from, to) = to_period(&params)?;
+
+    let generate_statement_query = req.state().generate_statement_query.clone();
+
+    let statement = generate_statement_query
+        .generate_statement(&AccountId(account_id), &from, &to, &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    if wants_format(&req, params.format.as_deref(), "html", "text/html") {
+        let mut res = Response::new(StatusCode::Ok);
+        res.set_body(to_html(&statement));
+        res.set_content_type(mime::HTML);
+
+        return Ok(res);
+    }
+
+    json_to_res(StatusCode::Ok, &to_statement_response(&statement))
+}
+
+/// Downloads the statement in one of the registered formats, chosen by the `format` query
+/// parameter or else by the `Accept` header.
+pub async fn handle_download_statement(req: Request<AppState>) -> tide::Result<Response> {
+    let account_id = validate_account_id_param(&req)?;
+    let params: StatementParams = req.query()?;
+
+    let (from, to) = to_period(&params)?;
+
+    let statement_formatters = req.state().statement_formatters.clone();
+
+    let formatter = match params.format.as_deref() {
+        Some(format) => statement_formatters.find_by_name(format),
+        None => req
+            .header("Accept")
+            .and_then(|accept| statement_formatters.find_by_accept(accept.last().as_str())),
+    }
+    .ok_or_else(|| {
+        Error::from_str(
+            StatusCode::NotAcceptable,
+            format!(
+                "Unsupported statement format, supported formats are: {}",
+                statement_formatters.names().join(", ")
+            ),
+        )
+    })?;
+
+    let generate_statement_query = req.state().generate_statement_query.clone();
+
+    let statement = generate_statement_query
+        .generate_statement(&AccountId(account_id), &from, &to, &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let mut res = Response::new(StatusCode::Ok);
+    res.set_body(formatter.format(&statement));
+    res.set_content_type(formatter.content_type());
+    res.insert_header(
+        "Content-Disposition",
+        format!(
+            "attachment; filename=\"statement-{}-{}-{}.{}\"",
+            account_id,
+            statement.from.format("%Y%m%d"),
+            statement.to.format("%Y%m%d"),
+            formatter.file_extension()
+        ),
+    );
+
+    Ok(res)
+}
@@ -0,0 +1,67 @@
+use crate::transfer_receipts::to_receipt_content;
+use anyhow::{anyhow, Result};
+use buckpal_application::application::port::outgoing::transfer_receipt_signer::TransferReceiptSigner;
+use buckpal_application::domain::transfer_receipt::TransferReceipt;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use std::sync::Arc;
+
+/// Signs receipts with Ed25519, with a key configured locally rather than kept in the database.
+#[derive(Clone)]
+pub struct Ed25519TransferReceiptSigner {
+    keypair: Arc<Keypair>,
+}
+
+impl Ed25519TransferReceiptSigner {
+    /// Takes the hex encoded 32 bytes of the secret key.
+    pub fn new(secret_key: &str) -> Result<Self> {
+        let secret = hex::decode(secret_key)
+            .map_err(|err| anyhow!("Receipt signing key isn't hex: {}", err))?;
+        let secret = SecretKey::from_bytes(&secret)
+            .map_err(|err| anyhow!("Receipt signing key is invalid: {}", err))?;
+        let public = PublicKey::from(&secret);
+
+        Ok(Self {
+            keypair: Arc::new(Keypair { secret, public }),
+        })
+    }
+
+    /// The hex encoded public key receipts are verified with.
+    pub fn public_key(&self) -> String {
+        hex::encode(self.keypair.public.to_bytes())
+    }
+}
+
+impl TransferReceiptSigner for Ed25519TransferReceiptSigner {
+    fn sign(&self, receipt: &TransferReceipt) -> Result<String> {
+        let signature = self.keypair.sign(&to_receipt_content(receipt).message());
+
+        Ok(hex::encode(signature.to_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Ed25519TransferReceiptSigner;
+    use crate::transfer_receipts::to_receipt_response;
+    use buckpal_application::application::port::outgoing::transfer_receipt_signer::TransferReceiptSigner;
+    use buckpal_application::domain::transfer::transfer_test_data::TransferBuilder;
+    use buckpal_application::domain::transfer_receipt::TransferReceipt;
+    use buckpal_receipt::verify_receipt;
+
+    #[test]
+    fn signs_receipts_clients_can_verify() {
+        let signer = Ed25519TransferReceiptSigner::new(&"07".repeat(32)).unwrap();
+        let receipt =
+            TransferReceipt::for_transfer(&TransferBuilder::default_transfer().build()).unwrap();
+        let signature = signer.sign(&receipt).unwrap();
+
+        let receipt = to_receipt_response(&receipt.with_signature(&signature));
+
+        assert!(verify_receipt(&receipt, &signer.public_key()).is_ok());
+    }
+
+    #[test]
+    fn rejects_short_key() {
+        assert!(Ed25519TransferReceiptSigner::new("0707").is_err());
+    }
+}
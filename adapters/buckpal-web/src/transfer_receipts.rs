@@ -0,0 +1,53 @@
+use crate::authentication::principal;
+use crate::transfers::{error_status, to_amount, validate_transfer_id_param};
+use crate::v1::data_to_res;
+use crate::AppState;
+use buckpal_application::domain::transfer::{TransferError, TransferId};
+use buckpal_application::domain::transfer_receipt::TransferReceipt;
+use buckpal_receipt::{ReceiptContent, TransferReceipt as TransferReceiptResponse};
+use chrono::SecondsFormat;
+use tide::{Error, Request, Response, StatusCode};
+
+/// The signed content of a receipt, as laid out for clients to verify.
+pub fn to_receipt_content(receipt: &TransferReceipt) -> ReceiptContent {
+    ReceiptContent {
+        transfer_id: receipt.transfer_id.0,
+        source_account_id: receipt.source_account_id.0,
+        target_account_id: receipt.target_account_id.0,
+        amount: to_amount(&receipt.money),
+        currency: String::from(receipt.money.currency().iso_alpha_code),
+        timestamp: receipt
+            .timestamp
+            .to_rfc3339_opts(SecondsFormat::Micros, true),
+    }
+}
+
+pub fn to_receipt_response(receipt: &TransferReceipt) -> TransferReceiptResponse {
+    TransferReceiptResponse {
+        content: to_receipt_content(receipt),
+        signature: receipt.signature.clone(),
+    }
+}
+
+pub async fn handle_get_transfer_receipt(req: Request<AppState>) -> tide::Result<Response> {
+    let transfer_id = validate_transfer_id_param(&req)?;
+
+    let get_transfer_receipt_query = req
+        .state()
+        .get_transfer_receipt_query
+        .clone()
+        .expect("expected the route to only be served with a receipt signing key");
+
+    let receipt = get_transfer_receipt_query
+        .get_transfer_receipt(&TransferId(transfer_id), &principal(&req)?)
+        .await
+        .map_err(|err| {
+            let status = match err.downcast_ref::<TransferError>() {
+                Some(TransferError::NotCompleted(_)) => StatusCode::Conflict,
+                _ => error_status(&err),
+            };
+            Error::from_str(status, err.to_string())
+        })?;
+
+    data_to_res(&req, StatusCode::Ok, to_receipt_response(&receipt))
+}
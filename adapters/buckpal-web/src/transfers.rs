@@ -0,0 +1,206 @@
+use crate::audit::audit_context;
+use crate::authentication::principal;
+use crate::utils::json_to_res;
+use crate::AppState;
+use buckpal_application::application::port::incoming::{
+    get_transfer_query::TransferDetails, reverse_transfer_use_case::ReverseTransferCommand,
+};
+use buckpal_application::application::service::error::{error_category, ServiceError};
+use buckpal_application::domain::activity::{Activity, ActivityKind};
+use buckpal_application::domain::audit::ErrorCategory;
+use buckpal_application::domain::idempotency_record::IdempotencyKey;
+use buckpal_application::domain::transfer::{TransferId, TransferStatus};
+use buckpal_persistence::event_sourced_account_persistence_adapter::EventSourcedAccountError;
+use rusty_money::{money, Money};
+use serde::{Deserialize, Serialize};
+use tide::{Error, ParamError, Request, Response, StatusCode};
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendMoneyResponse {
+    pub message: String,
+    pub transfer_id: i32,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReverseTransferRequest {
+    amount: Option<i64>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityResponse {
+    id: Option<i32>,
+    kind: &'static str,
+    owner_account_id: i32,
+    source_account_id: i32,
+    target_account_id: i32,
+    timestamp: String,
+    amount: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferResponse {
+    id: i32,
+    source_account_id: i32,
+    target_account_id: i32,
+    timestamp: String,
+    amount: i64,
+    currency: &'static str,
+    status: &'static str,
+    reversed_transfer_id: Option<i32>,
+    reference: Option<String>,
+    legs: Vec<ActivityResponse>,
+}
+
+pub fn to_amount(money: &Money) -> i64 {
+    use rust_decimal::prelude::*;
+
+    // here we want to explode, no way to recover
+    money.amount().to_i64().unwrap()
+}
+
+fn to_status(status: TransferStatus) -> &'static str {
+    match status {
+        TransferStatus::Pending => "pending",
+        TransferStatus::Completed => "completed",
+        TransferStatus::Failed => "failed",
+        TransferStatus::PartiallyReversed => "partially_reversed",
+        TransferStatus::Reversed => "reversed",
+    }
+}
+
+pub fn to_activity_response(activity: &Activity) -> ActivityResponse {
+    ActivityResponse {
+        id: activity.id.clone().map(|id| id.0),
+        kind: match activity.kind() {
+            ActivityKind::Deposit => "deposit",
+            ActivityKind::Withdrawal => "withdrawal",
+        },
+        owner_account_id: activity.owner_account_id.0,
+        source_account_id: activity.source_account_id.0,
+        target_account_id: activity.target_account_id.0,
+        timestamp: activity.timestamp.to_rfc3339(),
+        amount: to_amount(&activity.money),
+    }
+}
+
+pub fn to_transfer_response(details: &TransferDetails) -> TransferResponse {
+    let transfer = &details.transfer;
+
+    TransferResponse {
+        id: transfer
+            .id
+            .clone()
+            .expect("expected persisted transfer to have an ID")
+            .0,
+        source_account_id: transfer.source_account_id.0,
+        target_account_id: transfer.target_account_id.0,
+        timestamp: transfer.timestamp.to_rfc3339(),
+        amount: to_amount(&transfer.money),
+        currency: "AUD",
+        status: to_status(transfer.status),
+        reversed_transfer_id: transfer.reversed_transfer_id.clone().map(|id| id.0),
+        reference: transfer.reference.clone(),
+        legs: details.legs.iter().map(to_activity_response).collect(),
+    }
+}
+
+/// Maps a failure of the send money use case to the matching HTTP status.
+pub fn send_money_error(err: anyhow::Error) -> Error {
+    if let Some(EventSourcedAccountError::ConcurrentModification { .. }) =
+        err.downcast_ref::<EventSourcedAccountError>()
+    {
+        return Error::from_str(StatusCode::Conflict, err.to_string());
+    }
+
+    let status = match err.downcast_ref::<ServiceError>() {
+        Some(ServiceError::IdempotencyKeyReused(_)) => StatusCode::UnprocessableEntity,
+        Some(ServiceError::UnknownAccountIdentifier(_)) => StatusCode::UnprocessableEntity,
+        Some(ServiceError::InvalidAmount(_)) => StatusCode::UnprocessableEntity,
+        _ => error_status(&err),
+    };
+
+    Error::from_str(status, err.to_string())
+}
+
+/// Maps a failure of a use case to the HTTP status matching its category.
+pub fn error_status(err: &anyhow::Error) -> StatusCode {
+    match error_category(err) {
+        ErrorCategory::Rejected => StatusCode::BadRequest,
+        ErrorCategory::Conflict => StatusCode::Conflict,
+        ErrorCategory::NotFound => StatusCode::NotFound,
+        ErrorCategory::Forbidden => StatusCode::Forbidden,
+        ErrorCategory::Internal => StatusCode::InternalServerError,
+    }
+}
+
+pub fn idempotency_key_header(req: &Request<AppState>) -> Option<IdempotencyKey> {
+    req.header("Idempotency-Key")
+        .map(|values| IdempotencyKey(String::from(values.last().as_str())))
+}
+
+pub fn validate_transfer_id_param(req: &Request<AppState>) -> tide::Result<i32> {
+    req.param("transferId")
+        .map_err(|err: ParamError<std::num::ParseIntError>| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid transferId: {}", err.to_string()),
+            )
+        })
+}
+
+pub async fn handle_get_transfer(req: Request<AppState>) -> tide::Result<Response> {
+    let transfer_id = validate_transfer_id_param(&req)?;
+
+    let get_transfer_query = req.state().get_transfer_query.clone();
+
+    let details = get_transfer_query
+        .get_transfer(&TransferId(transfer_id), &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    json_to_res(StatusCode::Ok, &to_transfer_response(&details))
+}
+
+pub async fn handle_reverse_transfer(mut req: Request<AppState>) -> tide::Result<Response> {
+    let transfer_id = validate_transfer_id_param(&req)?;
+
+    // the body is optional, without an amount everything that is left gets reversed
+    let body = req.body_string().await?;
+    let reverse_transfer_request = if body.trim().is_empty() {
+        ReverseTransferRequest { amount: None }
+    } else {
+        serde_json::from_str(&body).map_err(|err| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid body: {}", err.to_string()),
+            )
+        })?
+    };
+
+    let command = ReverseTransferCommand::new(
+        TransferId(transfer_id),
+        reverse_transfer_request
+            .amount
+            .map(|amount| money!(amount, "AUD")),
+    )
+    .with_audit_context(audit_context(&req))
+    .with_initiator(principal(&req)?);
+
+    let reverse_transfer_use_case = req.state().reverse_transfer_use_case.clone();
+
+    let reversal_id = reverse_transfer_use_case
+        .reverse_transfer(&command)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let send_money_response = SendMoneyResponse {
+        message: String::from("Transfer Reversed!"),
+        transfer_id: reversal_id.0,
+    };
+
+    json_to_res(StatusCode::Ok, &send_money_response)
+}
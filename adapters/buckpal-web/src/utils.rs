@@ -1,29 +1,48 @@
-use tide::{Body, Error, Response, StatusCode};
+use serde::Serialize;
+use tide::{Body, Error, Request, Response, StatusCode};
 
 #[derive(Debug, serde::Deserialize, serde::Serialize)]
-struct SendMoneyResponse {
+struct MessageResponse {
     message: String,
 }
 
+pub fn json_to_res<T: Serialize>(status: StatusCode, body: &T) -> tide::Result<Response> {
+    let mut res = Response::new(status);
+    res.set_body(Body::from_json(body)?);
+
+    Ok(res)
+}
+
+/// Whether the client asked for the given format, either with `?format=<format>` or by naming its
+/// media type in the `Accept` header.
+pub fn wants_format<State>(
+    req: &Request<State>,
+    format: Option<&str>,
+    name: &str,
+    media_type: &str,
+) -> bool {
+    match format {
+        Some(format) => format.eq_ignore_ascii_case(name),
+        None => req
+            .header("Accept")
+            .map(|accept| accept.last().as_str().contains(media_type))
+            .unwrap_or(false),
+    }
+}
+
 pub fn success_to_res(message: &str) -> tide::Result<Response> {
-    let send_money_response = SendMoneyResponse {
+    let message_response = MessageResponse {
         message: String::from(message),
     };
 
-    let mut res = Response::new(StatusCode::Ok);
-    res.set_body(Body::from_json(&send_money_response)?);
-
-    Ok(res)
+    json_to_res(StatusCode::Ok, &message_response)
 }
 
 #[allow(dead_code)]
 pub fn err_to_res(err: Error) -> tide::Result<Response> {
-    let send_money_response = SendMoneyResponse {
+    let message_response = MessageResponse {
         message: format!("Unable to process request: {}", err.to_string()),
     };
 
-    let mut res = Response::new(err.status());
-    res.set_body(Body::from_json(&send_money_response)?);
-
-    Ok(res)
+    json_to_res(err.status(), &message_response)
 }
@@ -0,0 +1,527 @@
+use crate::activity_chain::handle_verify_activity_chain;
+use crate::audit::{audit_context, handle_list_audit_log};
+use crate::authentication::principal;
+use crate::external_payments::handle_export_external_payments;
+use crate::payment_initiations::handle_import_payment_initiation;
+use crate::transfer_receipts::handle_get_transfer_receipt;
+use crate::transfers::{
+    error_status, idempotency_key_header, send_money_error, to_transfer_response,
+    validate_transfer_id_param,
+};
+use crate::utils::json_to_res;
+use crate::webhooks::{
+    handle_delete_webhook, handle_list_webhook_deliveries, handle_list_webhooks,
+    handle_redeliver_webhook, handle_register_webhook,
+};
+use crate::AppState;
+use async_trait::async_trait;
+use buckpal_application::application::port::incoming::{
+    batch_send_money_use_case::{
+        BatchLineOutcome, BatchLineResult, BatchMode, BatchSendMoneyCommand, BatchSendMoneyReport,
+    },
+    reverse_transfer_use_case::ReverseTransferCommand,
+    send_money_use_case::{SendMoneyCommand, TransferTarget},
+};
+use buckpal_application::application::service::error::ServiceError;
+use buckpal_application::domain::account::AccountId;
+use buckpal_application::domain::account_identifier::AccountIdentifier;
+use buckpal_application::domain::transfer::TransferId;
+use chrono::Utc;
+use rusty_money::{money, Money};
+use serde::{Deserialize, Serialize};
+use std::sync::atomic::{AtomicU64, Ordering};
+use tide::{Body, Error, Middleware, Next, Request, Response, Server, StatusCode};
+
+/// The only currency accounts are kept in.
+const SUPPORTED_CURRENCY: &str = "AUD";
+
+const REVERSE_ON_FAILURE: &str = "reverse-on-failure";
+const BEST_EFFORT: &str = "best-effort";
+
+/// Wraps every successful response of the versioned API.
+#[derive(Debug, Serialize)]
+pub struct DataEnvelope<T: Serialize> {
+    pub data: T,
+    pub meta: Meta,
+}
+
+/// Wraps every failed response of the versioned API.
+#[derive(Debug, Serialize)]
+pub struct ErrorEnvelope {
+    pub error: ErrorBody,
+    pub meta: Meta,
+}
+
+/// Describes the request a response of the versioned API answers.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    pub request_id: String,
+}
+
+/// Identifies a request of the versioned API, by its `X-Request-Id` header or, without one, by
+/// an id made up when it arrived.
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+/// Attaches a [`RequestId`] to every request, echoes it in the `X-Request-Id` header of the
+/// response and wraps failures in an [`ErrorEnvelope`].
+#[derive(Debug, Default)]
+struct RequestMetadata {
+    requests: AtomicU64,
+}
+
+#[async_trait]
+impl Middleware<AppState> for RequestMetadata {
+    async fn handle(&self, mut req: Request<AppState>, next: Next<'_, AppState>) -> tide::Result {
+        let request_id = match req.header("X-Request-Id") {
+            Some(values) => String::from(values.last().as_str()),
+            None => format!(
+                "{:x}-{:x}",
+                Utc::now().timestamp_millis(),
+                self.requests.fetch_add(1, Ordering::Relaxed)
+            ),
+        };
+        req.set_ext(RequestId(request_id.clone()));
+
+        let mut res = next.run(req).await;
+
+        let envelope = res.error().map(|err| ErrorEnvelope {
+            error: ErrorBody {
+                status: err.status().into(),
+                message: err.to_string(),
+            },
+            meta: Meta {
+                request_id: request_id.clone(),
+            },
+        });
+        if let Some(envelope) = envelope {
+            res.set_body(Body::from_json(&envelope)?);
+        }
+        res.insert_header("X-Request-Id", request_id);
+
+        Ok(res)
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub status: u16,
+    pub message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CreateTransferRequest {
+    source_account_id: i32,
+    /// The target account is given either by its id, by a BSB and account number or by an IBAN.
+    target_account_id: Option<i32>,
+    target_bsb: Option<String>,
+    target_account_number: Option<String>,
+    target_iban: Option<String>,
+    amount: i64,
+    currency: String,
+    reference: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchTransferRequest {
+    transfers: Vec<CreateTransferRequest>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BatchParams {
+    /// Either `reverse-on-failure`, the default, or `best-effort`.
+    mode: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchLineResponse {
+    line: usize,
+    status: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    transfer_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reversal_id: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct BatchResponse {
+    mode: &'static str,
+    /// `rejected` if validation failed, `completed` if every transfer was sent, `rolledBack` if
+    /// a reverse-on-failure batch was reversed, `rollbackFailed` if some of its transfers could
+    /// not be reversed and `partiallyCompleted` otherwise.
+    status: &'static str,
+    lines: Vec<BatchLineResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReverseTransferRequest {
+    amount: Option<i64>,
+    currency: Option<String>,
+}
+
+/// Builds the `/v1` API, meant to be nested into the main server. Exporting external payments,
+/// verifying activity chains and issuing receipts are only served once they are configured.
+pub fn server(state: AppState) -> Server<AppState> {
+    let exports_external_payments = state.export_external_payments_use_case.is_some();
+    let verifies_activity_chains = state.verify_activity_chain_query.is_some();
+    let issues_transfer_receipts = state.get_transfer_receipt_query.is_some();
+    let mut v1 = tide::with_state(state);
+
+    v1.with(RequestMetadata::default());
+
+    if verifies_activity_chains {
+        v1.at("/accounts/:accountId/chain-verification")
+            .get(handle_verify_activity_chain);
+    }
+    v1.at("/audit-log").get(handle_list_audit_log);
+    if exports_external_payments {
+        v1.at("/external-payment-batches")
+            .post(handle_export_external_payments);
+    }
+    v1.at("/payment-initiations")
+        .post(handle_import_payment_initiation);
+    v1.at("/transfers").post(handle_create_transfer);
+    v1.at("/transfers/batch").post(handle_batch_transfers);
+    v1.at("/transfers/:transferId").get(handle_get_transfer);
+    if issues_transfer_receipts {
+        v1.at("/transfers/:transferId/receipt")
+            .get(handle_get_transfer_receipt);
+    }
+    v1.at("/transfers/:transferId/reversals")
+        .post(handle_reverse_transfer);
+    v1.at("/webhook-deliveries/:deliveryId/redeliveries")
+        .post(handle_redeliver_webhook);
+    v1.at("/webhooks")
+        .post(handle_register_webhook)
+        .get(handle_list_webhooks);
+    v1.at("/webhooks/:webhookId").delete(handle_delete_webhook);
+    v1.at("/webhooks/:webhookId/deliveries")
+        .get(handle_list_webhook_deliveries);
+
+    v1
+}
+
+pub fn data_to_res<T: Serialize>(
+    req: &Request<AppState>,
+    status: StatusCode,
+    data: T,
+) -> tide::Result<Response> {
+    json_to_res(
+        status,
+        &DataEnvelope {
+            data,
+            meta: Meta {
+                request_id: request_id(req).unwrap_or_default(),
+            },
+        },
+    )
+}
+
+/// The id of a request of the versioned API.
+pub fn request_id(req: &Request<AppState>) -> Option<String> {
+    req.ext::<RequestId>()
+        .map(|request_id| request_id.0.clone())
+}
+
+fn validate_currency(currency: &str) -> tide::Result<()> {
+    if currency != SUPPORTED_CURRENCY {
+        return Err(Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!(
+                "Unsupported currency `{}`, only `{}` is supported",
+                currency, SUPPORTED_CURRENCY
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn invalid_body(err: impl std::fmt::Display) -> Error {
+    Error::from_str(
+        StatusCode::UnprocessableEntity,
+        format!("Invalid body: {}", err),
+    )
+}
+
+async fn handle_create_transfer(mut req: Request<AppState>) -> tide::Result<Response> {
+    let create_transfer_request: CreateTransferRequest =
+        req.body_json().await.map_err(invalid_body)?;
+
+    let mut command = to_send_money_command(create_transfer_request)?
+        .with_audit_context(audit_context(&req))
+        .with_initiator(principal(&req)?);
+    if let Some(idempotency_key) = idempotency_key_header(&req) {
+        command = command.with_idempotency_key(idempotency_key);
+    }
+
+    let send_money_use_case = req.state().send_money_use_case.clone();
+    let get_transfer_query = req.state().get_transfer_query.clone();
+
+    let transfer_id = send_money_use_case
+        .send_money(&command)
+        .await
+        .map_err(send_money_error)?;
+
+    let details = get_transfer_query
+        .get_transfer(&transfer_id, &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    data_to_res(&req, StatusCode::Created, to_transfer_response(&details))
+}
+
+async fn handle_get_transfer(req: Request<AppState>) -> tide::Result<Response> {
+    let transfer_id = validate_transfer_id_param(&req)?;
+
+    let get_transfer_query = req.state().get_transfer_query.clone();
+
+    let details = get_transfer_query
+        .get_transfer(&TransferId(transfer_id), &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    data_to_res(&req, StatusCode::Ok, to_transfer_response(&details))
+}
+
+async fn handle_reverse_transfer(mut req: Request<AppState>) -> tide::Result<Response> {
+    let transfer_id = validate_transfer_id_param(&req)?;
+
+    // the body is optional, without an amount everything that is left gets reversed
+    let body = req.body_string().await?;
+    let reverse_transfer_request = if body.trim().is_empty() {
+        ReverseTransferRequest {
+            amount: None,
+            currency: None,
+        }
+    } else {
+        serde_json::from_str(&body).map_err(invalid_body)?
+    };
+
+    if let Some(currency) = &reverse_transfer_request.currency {
+        validate_currency(currency)?;
+    }
+
+    let command = ReverseTransferCommand::new(
+        TransferId(transfer_id),
+        reverse_transfer_request
+            .amount
+            .map(|amount| money!(amount, "AUD")),
+    )
+    .with_audit_context(audit_context(&req))
+    .with_initiator(principal(&req)?);
+
+    let reverse_transfer_use_case = req.state().reverse_transfer_use_case.clone();
+    let get_transfer_query = req.state().get_transfer_query.clone();
+
+    let reversal_id = reverse_transfer_use_case
+        .reverse_transfer(&command)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    let details = get_transfer_query
+        .get_transfer(&reversal_id, &principal(&req)?)
+        .await
+        .map_err(|err| Error::from_str(error_status(&err), err.to_string()))?;
+
+    data_to_res(&req, StatusCode::Created, to_transfer_response(&details))
+}
+
+fn to_transfer_target(request: &CreateTransferRequest) -> tide::Result<TransferTarget> {
+    let target = match (
+        request.target_account_id,
+        &request.target_bsb,
+        &request.target_account_number,
+        &request.target_iban,
+    ) {
+        (Some(target_account_id), None, None, None) => {
+            Ok(TransferTarget::Account(AccountId(target_account_id)))
+        }
+        (None, Some(bsb), Some(account_number), None) => {
+            AccountIdentifier::bsb(bsb, account_number).map(TransferTarget::Identifier)
+        }
+        (None, None, None, Some(iban)) => {
+            AccountIdentifier::iban(iban).map(TransferTarget::Identifier)
+        }
+        _ => {
+            return Err(invalid_body(concat!(
+                "exactly one of `targetAccountId`, `targetBsb` with `targetAccountNumber` ",
+                "or `targetIban` is required"
+            )))
+        }
+    };
+
+    target.map_err(invalid_body)
+}
+
+fn to_send_money_command(request: CreateTransferRequest) -> tide::Result<SendMoneyCommand> {
+    validate_currency(&request.currency)?;
+
+    let mut command = SendMoneyCommand::new_to_target(
+        AccountId(request.source_account_id),
+        to_transfer_target(&request)?,
+        money!(request.amount, "AUD"),
+    );
+    if let Some(reference) = request.reference {
+        command = command.with_reference(reference);
+    }
+
+    Ok(command)
+}
+
+fn to_batch_mode(params: &BatchParams) -> tide::Result<BatchMode> {
+    match params.mode.as_deref() {
+        None | Some(REVERSE_ON_FAILURE) => Ok(BatchMode::ReverseOnFailure),
+        Some(BEST_EFFORT) => Ok(BatchMode::BestEffort),
+        Some(other) => Err(Error::from_str(
+            StatusCode::UnprocessableEntity,
+            format!(
+                "Invalid mode `{}`, expected `{}` or `{}`",
+                other, REVERSE_ON_FAILURE, BEST_EFFORT
+            ),
+        )),
+    }
+}
+
+/// Reads the transfers of a batch from a JSON body, or from CSV with a header row naming the
+/// same fields as the JSON. Every transfer is read before any is rejected, so that all
+/// problems are reported at once.
+fn parse_batch(content_type: Option<&str>, body: &str) -> tide::Result<Vec<SendMoneyCommand>> {
+    let requests: Vec<std::result::Result<CreateTransferRequest, String>> =
+        if content_type.map_or(false, |content_type| content_type.starts_with("text/csv")) {
+            csv::ReaderBuilder::new()
+                .trim(csv::Trim::All)
+                .from_reader(body.as_bytes())
+                .deserialize()
+                .map(|request| request.map_err(|err| err.to_string()))
+                .collect()
+        } else {
+            let batch: BatchTransferRequest = serde_json::from_str(body).map_err(invalid_body)?;
+            batch.transfers.into_iter().map(Ok).collect()
+        };
+
+    let mut commands = vec![];
+    let mut errors = vec![];
+    for (index, request) in requests.into_iter().enumerate() {
+        match request
+            .and_then(|request| to_send_money_command(request).map_err(|err| err.to_string()))
+        {
+            Ok(command) => commands.push(command),
+            Err(err) => errors.push(format!("Transfer {}: {}", index + 1, err)),
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(invalid_body(errors.join("; ")));
+    }
+
+    Ok(commands)
+}
+
+fn to_batch_line_response(result: &BatchLineResult) -> BatchLineResponse {
+    let (status, transfer_id, reversal_id, error) = match &result.outcome {
+        BatchLineOutcome::Sent(transfer_id) => ("sent", Some(transfer_id.0), None, None),
+        BatchLineOutcome::Failed(reason) => ("failed", None, None, Some(reason.clone())),
+        BatchLineOutcome::Reversed {
+            transfer_id,
+            reversal_id,
+        } => ("reversed", Some(transfer_id.0), Some(reversal_id.0), None),
+        BatchLineOutcome::ReversalFailed {
+            transfer_id,
+            reason,
+        } => (
+            "reversalFailed",
+            Some(transfer_id.0),
+            None,
+            Some(reason.clone()),
+        ),
+        BatchLineOutcome::NotExecuted => ("notExecuted", None, None, None),
+    };
+
+    BatchLineResponse {
+        line: result.line,
+        status,
+        transfer_id,
+        reversal_id,
+        error,
+    }
+}
+
+fn to_batch_response(report: &BatchSendMoneyReport) -> BatchResponse {
+    let all_sent = report
+        .lines
+        .iter()
+        .all(|line| matches!(line.outcome, BatchLineOutcome::Sent(_)));
+
+    let status = if !report.valid {
+        "rejected"
+    } else if all_sent {
+        "completed"
+    } else if report.has_failed_reversals() {
+        "rollbackFailed"
+    } else if report.mode == BatchMode::ReverseOnFailure {
+        "rolledBack"
+    } else {
+        "partiallyCompleted"
+    };
+
+    BatchResponse {
+        mode: match report.mode {
+            BatchMode::ReverseOnFailure => REVERSE_ON_FAILURE,
+            BatchMode::BestEffort => BEST_EFFORT,
+        },
+        status,
+        lines: report.lines.iter().map(to_batch_line_response).collect(),
+    }
+}
+
+async fn handle_batch_transfers(mut req: Request<AppState>) -> tide::Result<Response> {
+    let params: BatchParams = req.query()?;
+    let mode = to_batch_mode(&params)?;
+    let audit_context = audit_context(&req);
+    let principal = principal(&req)?;
+
+    let content_type = req.content_type().map(|mime| mime.essence().to_string());
+    let body = req.body_string().await?;
+    let commands = parse_batch(content_type.as_deref(), &body)?
+        .into_iter()
+        .map(|command| {
+            command
+                .with_audit_context(audit_context.clone())
+                .with_initiator(principal.clone())
+        })
+        .collect();
+
+    let batch_send_money_use_case = req.state().batch_send_money_use_case.clone();
+
+    let report = batch_send_money_use_case
+        .batch_send_money(
+            &BatchSendMoneyCommand::new(commands, mode).with_audit_context(audit_context),
+        )
+        .await
+        .map_err(|err| {
+            let status = match err.downcast_ref::<ServiceError>() {
+                Some(ServiceError::InvalidBatchSize { .. }) => StatusCode::UnprocessableEntity,
+                _ => StatusCode::InternalServerError,
+            };
+            Error::from_str(status, err.to_string())
+        })?;
+
+    let status = if !report.valid {
+        StatusCode::UnprocessableEntity
+    } else if report.has_failed_reversals() {
+        StatusCode::InternalServerError
+    } else {
+        StatusCode::Ok
+    };
+
+    data_to_res(&req, status, to_batch_response(&report))
+}
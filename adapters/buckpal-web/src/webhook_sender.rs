@@ -0,0 +1,266 @@
+use crate::transfers::to_amount;
+use anyhow::{anyhow, Result};
+use async_std::future::timeout;
+use async_trait::async_trait;
+use buckpal_application::application::port::outgoing::webhook_sender::WebhookSender;
+use buckpal_application::domain::domain_event::DomainEvent;
+use buckpal_application::domain::webhook::{WebhookDelivery, WebhookSubscription};
+use chrono::Utc;
+use hmac::{Hmac, Mac, NewMac};
+use serde::Serialize;
+use sha2::Sha256;
+use std::time::Duration;
+
+/// Carries the signature of a payload, `t=<unix timestamp>,v1=<hex HMAC-SHA256>`.
+pub const SIGNATURE_HEADER: &str = "Buckpal-Signature";
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookPayload {
+    /// Identifies the event, the same across retries and redeliveries.
+    id: String,
+    #[serde(rename = "type")]
+    event_type: &'static str,
+    data: WebhookEventData,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookEventData {
+    transfer_id: i32,
+    /// The account whose balance changed, absent for events of a transfer as a whole.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    account_id: Option<i32>,
+    source_account_id: i32,
+    target_account_id: i32,
+    amount: i64,
+    currency: &'static str,
+    timestamp: String,
+}
+
+fn to_payload(event: &DomainEvent) -> WebhookPayload {
+    let (account_id, transfer_id, source_account_id, target_account_id, money, timestamp) =
+        match event {
+            DomainEvent::MoneyWithdrawn {
+                account_id,
+                target_account_id,
+                transfer_id,
+                money,
+                timestamp,
+            } => (
+                Some(account_id),
+                transfer_id,
+                account_id,
+                target_account_id,
+                money,
+                timestamp,
+            ),
+            DomainEvent::MoneyDeposited {
+                account_id,
+                source_account_id,
+                transfer_id,
+                money,
+                timestamp,
+            } => (
+                Some(account_id),
+                transfer_id,
+                source_account_id,
+                account_id,
+                money,
+                timestamp,
+            ),
+            DomainEvent::TransferCompleted {
+                transfer_id,
+                source_account_id,
+                target_account_id,
+                money,
+                timestamp,
+            } => (
+                None,
+                transfer_id,
+                source_account_id,
+                target_account_id,
+                money,
+                timestamp,
+            ),
+        };
+
+    WebhookPayload {
+        id: event.id(),
+        event_type: event.name(),
+        data: WebhookEventData {
+            transfer_id: transfer_id.0,
+            account_id: account_id.map(|account_id| account_id.0),
+            source_account_id: source_account_id.0,
+            target_account_id: target_account_id.0,
+            amount: to_amount(money),
+            currency: "AUD",
+            timestamp: timestamp.to_rfc3339(),
+        },
+    }
+}
+
+/// Signs `<timestamp>.<body>` with the secret, so that receivers can check both where the
+/// payload came from and that it isn't replayed long after it was sent.
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_varkey(secret.as_bytes()).expect("HMAC can take a key of any size");
+    mac.update(format!("{}.{}", timestamp, body).as_bytes());
+
+    hex::encode(mac.finalize().into_bytes())
+}
+
+/// Posts signed JSON payloads, counting anything but a 2xx answer within the timeout as a
+/// failure.
+#[derive(Debug, Clone)]
+pub struct HttpWebhookSender {
+    timeout: Duration,
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout }
+    }
+}
+
+#[async_trait]
+impl WebhookSender for HttpWebhookSender {
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> Result<()> {
+        let body = serde_json::to_string(&to_payload(&delivery.event))?;
+        let timestamp = Utc::now().timestamp();
+        let signature = format!(
+            "t={},v1={}",
+            timestamp,
+            sign(&subscription.secret, timestamp, &body)
+        );
+
+        let mut request = surf::post(&subscription.url)
+            .header(SIGNATURE_HEADER, signature)
+            .header("Buckpal-Event", delivery.event.name())
+            .content_type("application/json")
+            .body(body);
+        if let Some(delivery_id) = &delivery.id {
+            request = request.header("Buckpal-Delivery", delivery_id.0.to_string());
+        }
+
+        let res = timeout(self.timeout, request)
+            .await
+            .map_err(|_| anyhow!("No answer within {}ms", self.timeout.as_millis()))?
+            .map_err(|err| anyhow!("Sending failed: {}", err))?;
+
+        if !res.status().is_success() {
+            return Err(anyhow!("Endpoint answered with {}", res.status()));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sign, HttpWebhookSender, SIGNATURE_HEADER};
+    use async_std::channel::{unbounded, Sender};
+    use async_std::net::TcpListener;
+    use async_std::task;
+    use buckpal_application::application::port::outgoing::webhook_sender::WebhookSender;
+    use buckpal_application::domain::account::AccountId;
+    use buckpal_application::domain::domain_event::DomainEvent;
+    use buckpal_application::domain::transfer::TransferId;
+    use buckpal_application::domain::webhook::{
+        WebhookDelivery, WebhookDeliveryId, WebhookSubscription, WebhookSubscriptionId,
+    };
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+    use std::time::Duration;
+    use tide::{Request, Response, StatusCode};
+
+    const SECRET: &str = "0123456789abcdef";
+
+    /// Starts an endpoint on a free local port that answers with the given status and passes
+    /// on the signature header and body of every request.
+    async fn given_an_endpoint(status: StatusCode, received: Sender<(String, String)>) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hooks", listener.local_addr().unwrap());
+
+        let mut endpoint = tide::with_state(received);
+        endpoint.at("/hooks").post(
+            move |mut req: Request<Sender<(String, String)>>| async move {
+                let signature = req.header(SIGNATURE_HEADER).unwrap().as_str().to_string();
+                let body = req.body_string().await?;
+                req.state().send((signature, body)).await.unwrap();
+
+                Ok(Response::new(status))
+            },
+        );
+        task::spawn(async move { endpoint.listen(listener).await });
+
+        url
+    }
+
+    fn given_a_delivery(url: &str) -> (WebhookSubscription, WebhookDelivery) {
+        let subscription =
+            WebhookSubscription::new(url, SECRET, &[String::from("MoneyDeposited")], Utc::now())
+                .unwrap();
+        let mut delivery = WebhookDelivery::new(
+            WebhookSubscriptionId(1),
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(42),
+                source_account_id: AccountId(41),
+                transfer_id: TransferId(7),
+                money: money!(500, "AUD"),
+                timestamp: Utc::now(),
+            },
+            Utc::now(),
+        );
+        delivery.id = Some(WebhookDeliveryId(3));
+
+        (subscription, delivery)
+    }
+
+    #[async_std::test]
+    async fn posts_signed_payload() {
+        let (received, receiving) = unbounded();
+        let url = given_an_endpoint(StatusCode::NoContent, received).await;
+        let (subscription, delivery) = given_a_delivery(&url);
+
+        HttpWebhookSender::new(Duration::from_secs(5))
+            .send(&subscription, &delivery)
+            .await
+            .unwrap();
+
+        let (signature, body) = receiving.recv().await.unwrap();
+        let timestamp: i64 = signature
+            .strip_prefix("t=")
+            .and_then(|signature| signature.split(',').next())
+            .unwrap()
+            .parse()
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_str(&body).unwrap();
+
+        assert_eq!(
+            signature,
+            format!("t={},v1={}", timestamp, sign(SECRET, timestamp, &body))
+        );
+        assert_eq!(payload["id"], "MoneyDeposited-7-42");
+        assert_eq!(payload["type"], "MoneyDeposited");
+        assert_eq!(payload["data"]["accountId"], 42);
+        assert_eq!(payload["data"]["amount"], 500);
+    }
+
+    #[async_std::test]
+    async fn rejected_payload_fails() {
+        let (received, _receiving) = unbounded();
+        let url = given_an_endpoint(StatusCode::ServiceUnavailable, received).await;
+        let (subscription, delivery) = given_a_delivery(&url);
+
+        let result = HttpWebhookSender::new(Duration::from_secs(5))
+            .send(&subscription, &delivery)
+            .await;
+
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,211 @@
+use crate::audit::audit_context;
+use crate::v1::data_to_res;
+use crate::AppState;
+use buckpal_application::application::port::incoming::manage_webhooks_use_case::RegisterWebhookCommand;
+use buckpal_application::application::service::error::ServiceError;
+use buckpal_application::domain::webhook::{
+    WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus, WebhookError, WebhookSubscription,
+    WebhookSubscriptionId,
+};
+use serde::{Deserialize, Serialize};
+use tide::{Error, ParamError, Request, Response, StatusCode};
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RegisterWebhookRequest {
+    url: String,
+    secret: String,
+    event_types: Vec<String>,
+}
+
+/// A subscription as shown to clients, without its secret.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookResponse {
+    id: i32,
+    url: String,
+    event_types: Vec<String>,
+    created_at: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WebhookDeliveryResponse {
+    id: i32,
+    webhook_id: i32,
+    event_id: String,
+    event_type: &'static str,
+    status: &'static str,
+    attempts: i32,
+    next_attempt_at: Option<String>,
+    last_error: Option<String>,
+    created_at: String,
+    delivered_at: Option<String>,
+}
+
+fn to_webhook_response(subscription: &WebhookSubscription) -> WebhookResponse {
+    WebhookResponse {
+        id: subscription
+            .id
+            .clone()
+            .expect("expected persisted webhook to have an ID")
+            .0,
+        url: subscription.url.clone(),
+        event_types: subscription.event_types.clone(),
+        created_at: subscription.created_at.to_rfc3339(),
+    }
+}
+
+fn to_webhook_delivery_response(delivery: &WebhookDelivery) -> WebhookDeliveryResponse {
+    let pending = delivery.status == WebhookDeliveryStatus::Pending;
+
+    WebhookDeliveryResponse {
+        id: delivery
+            .id
+            .clone()
+            .expect("expected persisted delivery to have an ID")
+            .0,
+        webhook_id: delivery.subscription_id.0,
+        event_id: delivery.event.id(),
+        event_type: delivery.event.name(),
+        status: match delivery.status {
+            WebhookDeliveryStatus::Pending => "pending",
+            WebhookDeliveryStatus::Delivered => "delivered",
+            WebhookDeliveryStatus::DeadLettered => "dead_lettered",
+        },
+        attempts: delivery.attempts,
+        next_attempt_at: Some(delivery.next_attempt_at.to_rfc3339()).filter(|_| pending),
+        last_error: delivery.last_error.clone(),
+        created_at: delivery.created_at.to_rfc3339(),
+        delivered_at: delivery
+            .delivered_at
+            .map(|delivered_at| delivered_at.to_rfc3339()),
+    }
+}
+
+/// Maps a failure of the webhook use cases to the matching HTTP status.
+fn webhook_error(err: anyhow::Error) -> Error {
+    let status = if let Some(webhook_error) = err.downcast_ref::<WebhookError>() {
+        match webhook_error {
+            WebhookError::DeliveryPending => StatusCode::Conflict,
+            _ => StatusCode::UnprocessableEntity,
+        }
+    } else {
+        match err.downcast_ref::<ServiceError>() {
+            Some(ServiceError::WebhookNotFound(_))
+            | Some(ServiceError::WebhookDeliveryNotFound(_)) => StatusCode::NotFound,
+            _ => StatusCode::InternalServerError,
+        }
+    };
+
+    Error::from_str(status, err.to_string())
+}
+
+fn validate_id_param(req: &Request<AppState>, name: &str) -> tide::Result<i32> {
+    req.param(name)
+        .map_err(|err: ParamError<std::num::ParseIntError>| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid {}: {}", name, err.to_string()),
+            )
+        })
+}
+
+pub async fn handle_register_webhook(mut req: Request<AppState>) -> tide::Result<Response> {
+    let register_webhook_request: RegisterWebhookRequest =
+        req.body_json().await.map_err(|err| {
+            Error::from_str(
+                StatusCode::UnprocessableEntity,
+                format!("Invalid body: {}", err),
+            )
+        })?;
+
+    let command = RegisterWebhookCommand::new(
+        &register_webhook_request.url,
+        &register_webhook_request.secret,
+        register_webhook_request.event_types,
+    )
+    .with_audit_context(audit_context(&req));
+
+    let manage_webhooks_use_case = req.state().manage_webhooks_use_case.clone();
+
+    let subscription = manage_webhooks_use_case
+        .register_webhook(&command)
+        .await
+        .map_err(webhook_error)?;
+
+    data_to_res(
+        &req,
+        StatusCode::Created,
+        to_webhook_response(&subscription),
+    )
+}
+
+pub async fn handle_list_webhooks(req: Request<AppState>) -> tide::Result<Response> {
+    let manage_webhooks_use_case = req.state().manage_webhooks_use_case.clone();
+
+    let subscriptions = manage_webhooks_use_case
+        .list_webhooks()
+        .await
+        .map_err(webhook_error)?;
+
+    data_to_res(
+        &req,
+        StatusCode::Ok,
+        subscriptions
+            .iter()
+            .map(to_webhook_response)
+            .collect::<Vec<_>>(),
+    )
+}
+
+pub async fn handle_delete_webhook(req: Request<AppState>) -> tide::Result<Response> {
+    let webhook_id = validate_id_param(&req, "webhookId")?;
+
+    let manage_webhooks_use_case = req.state().manage_webhooks_use_case.clone();
+
+    manage_webhooks_use_case
+        .delete_webhook(&WebhookSubscriptionId(webhook_id), &audit_context(&req))
+        .await
+        .map_err(webhook_error)?;
+
+    Ok(Response::new(StatusCode::NoContent))
+}
+
+pub async fn handle_list_webhook_deliveries(req: Request<AppState>) -> tide::Result<Response> {
+    let webhook_id = validate_id_param(&req, "webhookId")?;
+
+    let manage_webhooks_use_case = req.state().manage_webhooks_use_case.clone();
+
+    let deliveries = manage_webhooks_use_case
+        .list_deliveries(&WebhookSubscriptionId(webhook_id))
+        .await
+        .map_err(webhook_error)?;
+
+    data_to_res(
+        &req,
+        StatusCode::Ok,
+        deliveries
+            .iter()
+            .map(to_webhook_delivery_response)
+            .collect::<Vec<_>>(),
+    )
+}
+
+/// Queues a delivered or dead-lettered event to be sent again right away.
+pub async fn handle_redeliver_webhook(req: Request<AppState>) -> tide::Result<Response> {
+    let delivery_id = validate_id_param(&req, "deliveryId")?;
+
+    let deliver_webhooks_use_case = req.state().deliver_webhooks_use_case.clone();
+
+    let delivery = deliver_webhooks_use_case
+        .redeliver(&WebhookDeliveryId(delivery_id), &audit_context(&req))
+        .await
+        .map_err(webhook_error)?;
+
+    data_to_res(
+        &req,
+        StatusCode::Accepted,
+        to_webhook_delivery_response(&delivery),
+    )
+}
@@ -0,0 +1,44 @@
+use crate::domain::account::AccountId;
+use crate::domain::audit::AuditContext;
+use crate::domain::hold::HoldId;
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use rusty_money::Money;
+
+pub struct AuthorizeTransferCommand {
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    /// Who reserves the money.
+    pub initiator: Option<Principal>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
+}
+
+impl AuthorizeTransferCommand {
+    pub fn new(source_account_id: AccountId, target_account_id: AccountId, money: Money) -> Self {
+        Self {
+            source_account_id,
+            target_account_id,
+            money,
+            initiator: None,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_initiator(mut self, initiator: Principal) -> Self {
+        self.initiator = Some(initiator);
+        self
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[async_trait]
+pub trait AuthorizeTransferUseCase {
+    async fn authorize_transfer(&self, command: &AuthorizeTransferCommand) -> Result<HoldId>;
+}
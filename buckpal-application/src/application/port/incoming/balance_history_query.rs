@@ -0,0 +1,25 @@
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+use rusty_money::Money;
+
+/// The balance of an account at the end of a day (UTC).
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct DailyBalance {
+    pub date: NaiveDate,
+    pub closing_balance: Money,
+}
+
+#[async_trait]
+pub trait BalanceHistoryQuery {
+    /// The closing balance of every day from `from` up to and including `to`.
+    async fn get_balance_history(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+        principal: &Principal,
+    ) -> Result<Vec<DailyBalance>>;
+}
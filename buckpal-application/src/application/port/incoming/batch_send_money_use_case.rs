@@ -0,0 +1,90 @@
+use crate::application::port::incoming::send_money_use_case::SendMoneyCommand;
+use crate::domain::audit::AuditContext;
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum BatchMode {
+    /// Once a transfer fails the rest is not executed and the ones sent before are reversed,
+    /// newest first. This is not atomic: every transfer and reversal is visible as soon as it
+    /// is made, and a reversal can fail in turn.
+    ReverseOnFailure,
+    /// Every transfer is attempted, failures don't affect the other transfers.
+    BestEffort,
+}
+
+pub struct BatchSendMoneyCommand {
+    pub commands: Vec<SendMoneyCommand>,
+    pub mode: BatchMode,
+    /// Where the batch came from, recorded in the audit log. Every transfer is recorded with
+    /// the context of its own command.
+    pub audit_context: AuditContext,
+}
+
+impl BatchSendMoneyCommand {
+    pub fn new(commands: Vec<SendMoneyCommand>, mode: BatchMode) -> Self {
+        Self {
+            commands,
+            mode,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum BatchLineOutcome {
+    Sent(TransferId),
+    /// The transfer could not be sent, or the line was invalid.
+    Failed(String),
+    /// The transfer was sent and then reversed because a later line failed.
+    Reversed {
+        transfer_id: TransferId,
+        reversal_id: TransferId,
+    },
+    /// The transfer was sent, but reversing it after a later line failed did not work either.
+    ReversalFailed {
+        transfer_id: TransferId,
+        reason: String,
+    },
+    /// The line was valid but was not executed because the batch was aborted or rejected.
+    NotExecuted,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BatchLineResult {
+    /// The position of the line in the batch, starting at 1.
+    pub line: usize,
+    pub outcome: BatchLineOutcome,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BatchSendMoneyReport {
+    pub mode: BatchMode,
+    /// Whether the batch passed validation, nothing is executed otherwise.
+    pub valid: bool,
+    pub lines: Vec<BatchLineResult>,
+}
+
+impl BatchSendMoneyReport {
+    /// Whether a transfer that had to be reversed is still in place.
+    pub fn has_failed_reversals(&self) -> bool {
+        self.lines
+            .iter()
+            .any(|line| matches!(line.outcome, BatchLineOutcome::ReversalFailed { .. }))
+    }
+}
+
+#[async_trait]
+pub trait BatchSendMoneyUseCase {
+    /// Validates every line before sending any money, then sends the transfers in order.
+    async fn batch_send_money(
+        &self,
+        command: &BatchSendMoneyCommand,
+    ) -> Result<BatchSendMoneyReport>;
+}
@@ -0,0 +1,43 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::hold::HoldId;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+use rusty_money::Money;
+
+pub struct CaptureTransferCommand {
+    pub hold_id: HoldId,
+    /// The money to settle, or `None` to capture everything that was held.
+    pub money: Option<Money>,
+    /// Who settles the hold, recorded with the activities of the transfer.
+    pub initiator: Option<Principal>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
+}
+
+impl CaptureTransferCommand {
+    pub fn new(hold_id: HoldId, money: Option<Money>) -> Self {
+        Self {
+            hold_id,
+            money,
+            initiator: None,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_initiator(mut self, initiator: Principal) -> Self {
+        self.initiator = Some(initiator);
+        self
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[async_trait]
+pub trait CaptureTransferUseCase {
+    async fn capture_transfer(&self, command: &CaptureTransferCommand) -> Result<TransferId>;
+}
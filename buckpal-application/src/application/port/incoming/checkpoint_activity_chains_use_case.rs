@@ -0,0 +1,10 @@
+use crate::domain::activity_chain::ChainCheckpoint;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait CheckpointActivityChainsUseCase {
+    /// Signs the current heads of all chains, unless they didn't move since the latest
+    /// checkpoint, which is returned then.
+    async fn checkpoint_activity_chains(&self) -> Result<ChainCheckpoint>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookDeliveryId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait DeliverWebhooksUseCase {
+    /// Attempts every delivery that is due and returns how many of them succeeded.
+    async fn deliver_due_webhooks(&self) -> Result<usize>;
+
+    /// Queues a delivered or dead-lettered event to be sent again.
+    async fn redeliver(
+        &self,
+        delivery_id: &WebhookDeliveryId,
+        audit_context: &AuditContext,
+    ) -> Result<WebhookDelivery>;
+}
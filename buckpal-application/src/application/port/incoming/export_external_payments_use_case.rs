@@ -0,0 +1,14 @@
+use crate::domain::external_payment::ExternalPaymentBatch;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+#[async_trait]
+pub trait ExportExternalPaymentsUseCase {
+    /// Writes every payment to an external account that has not been paid out yet into a new
+    /// direct entry file. Returns nothing if there is nothing to pay out.
+    async fn export_external_payments(
+        &self,
+        processing_date: &NaiveDate,
+    ) -> Result<Option<ExternalPaymentBatch>>;
+}
@@ -0,0 +1,18 @@
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use crate::domain::statement::Statement;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+#[async_trait]
+pub trait GenerateStatementQuery {
+    /// The statement of the account for the days from `from` up to and including `to`.
+    async fn generate_statement(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+        principal: &Principal,
+    ) -> Result<Statement>;
+}
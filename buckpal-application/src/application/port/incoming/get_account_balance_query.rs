@@ -1,9 +1,27 @@
 use crate::domain::account::AccountId;
+use crate::domain::audit::AuditContext;
+use crate::domain::principal::Principal;
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use rusty_money::Money;
 
 #[async_trait]
 pub trait GetAccountBalanceQuery {
-    async fn get_account_balance(&self, account_id: &AccountId) -> Result<Money>;
+    async fn get_account_balance(
+        &self,
+        account_id: &AccountId,
+        principal: &Principal,
+        audit_context: &AuditContext,
+    ) -> Result<Money>;
+
+    /// The balance of the account at the given point in time, including all activities up to
+    /// and including it.
+    async fn get_balance_as_of(
+        &self,
+        account_id: &AccountId,
+        instant: &DateTime<Utc>,
+        principal: &Principal,
+        audit_context: &AuditContext,
+    ) -> Result<Money>;
 }
@@ -0,0 +1,22 @@
+use crate::domain::activity::Activity;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// A transfer together with the mirrored activities written for it.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TransferDetails {
+    pub transfer: Transfer,
+    /// The activities owned by the source and the target account.
+    pub legs: Vec<Activity>,
+}
+
+#[async_trait]
+pub trait GetTransferQuery {
+    async fn get_transfer(
+        &self,
+        transfer_id: &TransferId,
+        principal: &Principal,
+    ) -> Result<TransferDetails>;
+}
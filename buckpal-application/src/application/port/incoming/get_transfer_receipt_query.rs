@@ -0,0 +1,15 @@
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
+use crate::domain::transfer_receipt::TransferReceipt;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait GetTransferReceiptQuery {
+    /// Issues the signed receipt of a completed transfer.
+    async fn get_transfer_receipt(
+        &self,
+        transfer_id: &TransferId,
+        principal: &Principal,
+    ) -> Result<TransferReceipt>;
+}
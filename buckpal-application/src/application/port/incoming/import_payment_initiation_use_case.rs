@@ -0,0 +1,19 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::payment_initiation::{PaymentInitiation, PaymentStatusReport};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ImportPaymentInitiationUseCase {
+    /// Executes every transaction of the initiation as a transfer once the whole initiation was
+    /// found valid, and reports the status of each of them. Rejections are part of the report,
+    /// errors are only returned when the initiation could not be processed at all. The transfers
+    /// are initiated by the given principal and audited with the given context.
+    async fn import_payment_initiation(
+        &self,
+        initiation: &PaymentInitiation,
+        initiator: Option<&Principal>,
+        audit_context: &AuditContext,
+    ) -> Result<PaymentStatusReport>;
+}
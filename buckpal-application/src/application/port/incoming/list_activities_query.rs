@@ -0,0 +1,52 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity::{Activity, ActivityId, ActivityKind};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+/// Narrows down the activities of an account. Every criterion is optional, an empty filter
+/// matches all activities.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct ActivityFilter {
+    /// Only activities at or after this timestamp.
+    pub from: Option<DateTime<Utc>>,
+    /// Only activities before this timestamp.
+    pub to: Option<DateTime<Utc>>,
+    /// Only activities with this account on the other side.
+    pub counterparty_account_id: Option<AccountId>,
+    /// Only deposits or only withdrawals.
+    pub kind: Option<ActivityKind>,
+    /// Only activities of at least this amount.
+    pub min_amount: Option<Money>,
+    /// Only activities of at most this amount.
+    pub max_amount: Option<Money>,
+}
+
+/// Points just past the last activity of a page. Activities are listed newest first, ordered by
+/// timestamp and then ID.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ActivityCursor {
+    pub timestamp: DateTime<Utc>,
+    pub activity_id: ActivityId,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ActivityPage {
+    pub activities: Vec<Activity>,
+    /// Where the next page starts, empty on the last page.
+    pub next_cursor: Option<ActivityCursor>,
+}
+
+#[async_trait]
+pub trait ListActivitiesQuery {
+    async fn list_activities(
+        &self,
+        account_id: &AccountId,
+        filter: &ActivityFilter,
+        cursor: Option<&ActivityCursor>,
+        limit: i64,
+        principal: &Principal,
+    ) -> Result<ActivityPage>;
+}
@@ -0,0 +1,35 @@
+use crate::domain::audit::{AuditEntry, AuditEntryId};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// Narrows down the audit log. Every criterion is optional, an empty filter matches all entries.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct AuditLogFilter {
+    pub actor: Option<Principal>,
+    pub action: Option<String>,
+    /// Only entries at or after this timestamp.
+    pub from: Option<DateTime<Utc>>,
+    /// Only entries before this timestamp.
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AuditLogPage {
+    pub entries: Vec<AuditEntry>,
+    /// The entry the next page starts before, empty on the last page.
+    pub next_cursor: Option<AuditEntryId>,
+}
+
+#[async_trait]
+pub trait ListAuditLogQuery {
+    /// Lists the entries newest first.
+    async fn list_audit_log(
+        &self,
+        filter: &AuditLogFilter,
+        before: Option<&AuditEntryId>,
+        limit: i64,
+        principal: &Principal,
+    ) -> Result<AuditLogPage>;
+}
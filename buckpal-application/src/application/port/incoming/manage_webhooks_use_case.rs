@@ -0,0 +1,52 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RegisterWebhookCommand {
+    pub url: String,
+    pub secret: String,
+    pub event_types: Vec<String>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
+}
+
+impl RegisterWebhookCommand {
+    pub fn new(url: &str, secret: &str, event_types: Vec<String>) -> Self {
+        Self {
+            url: String::from(url),
+            secret: String::from(secret),
+            event_types,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[async_trait]
+pub trait ManageWebhooksUseCase {
+    async fn register_webhook(
+        &self,
+        command: &RegisterWebhookCommand,
+    ) -> Result<WebhookSubscription>;
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>>;
+
+    /// Removes the subscription together with its deliveries, pending ones are not sent anymore.
+    async fn delete_webhook(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        audit_context: &AuditContext,
+    ) -> Result<()>;
+
+    /// Lists the deliveries of a subscription, the latest first.
+    async fn list_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Vec<WebhookDelivery>>;
+}
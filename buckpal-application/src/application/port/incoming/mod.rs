@@ -1,2 +1,21 @@
+pub mod authorize_transfer_use_case;
+pub mod balance_history_query;
+pub mod batch_send_money_use_case;
+pub mod capture_transfer_use_case;
+pub mod checkpoint_activity_chains_use_case;
+pub mod deliver_webhooks_use_case;
+pub mod export_external_payments_use_case;
+pub mod generate_statement_query;
 pub mod get_account_balance_query;
+pub mod get_transfer_query;
+pub mod get_transfer_receipt_query;
+pub mod import_payment_initiation_use_case;
+pub mod list_activities_query;
+pub mod list_audit_log_query;
+pub mod manage_webhooks_use_case;
+pub mod reconcile_ledger_use_case;
+pub mod reverse_transfer_use_case;
 pub mod send_money_use_case;
+pub mod verify_account_balances_use_case;
+pub mod verify_activity_chain_query;
+pub mod void_hold_use_case;
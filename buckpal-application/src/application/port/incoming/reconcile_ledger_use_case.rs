@@ -0,0 +1,10 @@
+use crate::domain::reconciliation::ReconciliationReport;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ReconcileLedgerUseCase {
+    /// Checks the activities of all accounts against the invariants of the ledger, reporting
+    /// every violation found.
+    async fn reconcile_ledger(&self) -> Result<ReconciliationReport>;
+}
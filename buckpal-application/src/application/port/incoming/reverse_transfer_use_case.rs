@@ -0,0 +1,42 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+use rusty_money::Money;
+
+pub struct ReverseTransferCommand {
+    pub transfer_id: TransferId,
+    /// The money to send back, or `None` to reverse everything that has not been reversed yet.
+    pub money: Option<Money>,
+    /// Who sends the money back, recorded with the activities of the reversal.
+    pub initiator: Option<Principal>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
+}
+
+impl ReverseTransferCommand {
+    pub fn new(transfer_id: TransferId, money: Option<Money>) -> Self {
+        Self {
+            transfer_id,
+            money,
+            initiator: None,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_initiator(mut self, initiator: Principal) -> Self {
+        self.initiator = Some(initiator);
+        self
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[async_trait]
+pub trait ReverseTransferUseCase {
+    async fn reverse_transfer(&self, command: &ReverseTransferCommand) -> Result<TransferId>;
+}
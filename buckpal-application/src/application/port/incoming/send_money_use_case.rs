@@ -1,25 +1,108 @@
 use crate::domain::account::AccountId;
+use crate::domain::account_identifier::AccountIdentifier;
+use crate::domain::audit::AuditContext;
+use crate::domain::idempotency_record::IdempotencyKey;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
 use anyhow::Result;
 use async_trait::async_trait;
 use rusty_money::Money;
 
+/// The account money is sent to.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum TransferTarget {
+    Account(AccountId),
+    /// An account addressed by an external identifier, resolved when the money is sent.
+    Identifier(AccountIdentifier),
+}
+
+impl TransferTarget {
+    /// Whether the target is known to be the given account without resolving it first.
+    pub fn is_account(&self, account_id: &AccountId) -> bool {
+        matches!(self, Self::Account(target_account_id) if target_account_id == account_id)
+    }
+}
+
 pub struct SendMoneyCommand {
     pub source_account_id: AccountId,
-    pub target_account_id: AccountId,
+    pub target: TransferTarget,
     pub money: Money,
+    /// Identifies retries of the same request, so that the money is only sent once.
+    pub idempotency_key: Option<IdempotencyKey>,
+    /// A free-form reference stored on the transfer, e.g. an invoice number.
+    pub reference: Option<String>,
+    /// Who sends the money, recorded with the activities of the transfer.
+    pub initiator: Option<Principal>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
 }
 
 impl SendMoneyCommand {
     pub fn new(source_account_id: AccountId, target_account_id: AccountId, money: Money) -> Self {
+        Self::new_to_target(
+            source_account_id,
+            TransferTarget::Account(target_account_id),
+            money,
+        )
+    }
+
+    pub fn new_to_target(
+        source_account_id: AccountId,
+        target: TransferTarget,
+        money: Money,
+    ) -> Self {
         Self {
             source_account_id,
-            target_account_id,
+            target,
             money,
+            idempotency_key: None,
+            reference: None,
+            initiator: None,
+            audit_context: AuditContext::default(),
         }
     }
+
+    pub fn with_idempotency_key(mut self, idempotency_key: IdempotencyKey) -> Self {
+        self.idempotency_key = Some(idempotency_key);
+        self
+    }
+
+    pub fn with_reference(mut self, reference: String) -> Self {
+        self.reference = Some(reference);
+        self
+    }
+
+    pub fn with_initiator(mut self, initiator: Principal) -> Self {
+        self.initiator = Some(initiator);
+        self
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+
+    /// Describes the requested transfer, used to detect an idempotency key being reused for a
+    /// different request.
+    pub fn fingerprint(&self) -> String {
+        use rust_decimal::prelude::*;
+
+        let target = match &self.target {
+            TransferTarget::Account(account_id) => account_id.0.to_string(),
+            TransferTarget::Identifier(identifier) => identifier.to_string(),
+        };
+
+        format!(
+            "send_money:{}:{}:{}:{}",
+            self.source_account_id.0,
+            target,
+            self.money.amount().to_i64().unwrap(),
+            self.reference.clone().unwrap_or_default()
+        )
+    }
 }
 
 #[async_trait]
 pub trait SendMoneyUseCase {
-    async fn send_money(&self, command: &SendMoneyCommand) -> Result<()>;
+    async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId>;
 }
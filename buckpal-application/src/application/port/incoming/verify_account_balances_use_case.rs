@@ -0,0 +1,9 @@
+use crate::domain::account_balance::BalanceDrift;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait VerifyAccountBalancesUseCase {
+    /// Finds the accounts whose kept balance drifted from the sum of their activities.
+    async fn verify_account_balances(&self) -> Result<Vec<BalanceDrift>>;
+}
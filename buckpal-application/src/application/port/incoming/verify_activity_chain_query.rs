@@ -0,0 +1,15 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity_chain::ChainVerification;
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait VerifyActivityChainQuery {
+    /// Walks the chain of activities of the account, finding the first link that doesn't hold.
+    async fn verify_activity_chain(
+        &self,
+        account_id: &AccountId,
+        principal: &Principal,
+    ) -> Result<ChainVerification>;
+}
@@ -0,0 +1,38 @@
+use crate::domain::audit::AuditContext;
+use crate::domain::hold::HoldId;
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct VoidHoldCommand {
+    pub hold_id: HoldId,
+    /// Who cancels the hold.
+    pub initiator: Option<Principal>,
+    /// Where the command came from, recorded in the audit log.
+    pub audit_context: AuditContext,
+}
+
+impl VoidHoldCommand {
+    pub fn new(hold_id: HoldId) -> Self {
+        Self {
+            hold_id,
+            initiator: None,
+            audit_context: AuditContext::default(),
+        }
+    }
+
+    pub fn with_initiator(mut self, initiator: Principal) -> Self {
+        self.initiator = Some(initiator);
+        self
+    }
+
+    pub fn with_audit_context(mut self, audit_context: AuditContext) -> Self {
+        self.audit_context = audit_context;
+        self
+    }
+}
+
+#[async_trait]
+pub trait VoidHoldUseCase {
+    async fn void_hold(&self, command: &VoidHoldCommand) -> Result<()>;
+}
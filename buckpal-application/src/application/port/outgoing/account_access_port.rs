@@ -0,0 +1,10 @@
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait AccountAccessPort {
+    /// Whether the principal was granted access to the account.
+    async fn has_access(&self, account_id: &AccountId, principal: &Principal) -> Result<bool>;
+}
@@ -0,0 +1,24 @@
+use crate::domain::account::AccountId;
+use crate::domain::account_balance::{AccountBalance, BalanceDrift};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+#[async_trait]
+pub trait AccountBalancePort {
+    /// Loads the kept balance of the account, none if it has no activities yet.
+    async fn load_account_balance(&self, account_id: &AccountId) -> Result<Option<AccountBalance>>;
+
+    /// Sums the activities of the account up to and including the given point in time, failing
+    /// with `AccountError::AccountNotFound` if there is no such account.
+    async fn load_balance_as_of(
+        &self,
+        account_id: &AccountId,
+        instant: &DateTime<Utc>,
+    ) -> Result<Money>;
+
+    /// Cross-checks the kept balances against the sums of the activities, loading the accounts
+    /// where they differ.
+    async fn load_balance_drifts(&self) -> Result<Vec<BalanceDrift>>;
+}
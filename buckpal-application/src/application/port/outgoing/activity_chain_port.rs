@@ -0,0 +1,19 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity_chain::{ChainCheckpoint, ChainHead, ChainedActivity};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ActivityChainPort {
+    /// Loads all activities of the account with their links, in the order they were persisted.
+    async fn load_chained_activities(&self, account_id: &AccountId)
+        -> Result<Vec<ChainedActivity>>;
+
+    async fn load_chain_head(&self, account_id: &AccountId) -> Result<Option<ChainHead>>;
+
+    async fn load_chain_heads(&self) -> Result<Vec<ChainHead>>;
+
+    async fn load_latest_checkpoint(&self) -> Result<Option<ChainCheckpoint>>;
+
+    async fn save_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<ChainCheckpoint>;
+}
@@ -0,0 +1,19 @@
+use crate::application::port::incoming::list_audit_log_query::AuditLogFilter;
+use crate::domain::audit::{AuditEntry, AuditEntryId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Keeps the audit log. Entries are only ever appended, never changed or removed.
+#[async_trait]
+pub trait AuditPort {
+    async fn record(&self, entry: &AuditEntry) -> Result<AuditEntry>;
+
+    /// Loads at most `limit` entries matching the filter, newest first, starting before the
+    /// given entry if one is given.
+    async fn load_entries(
+        &self,
+        filter: &AuditLogFilter,
+        before: Option<&AuditEntryId>,
+        limit: i64,
+    ) -> Result<Vec<AuditEntry>>;
+}
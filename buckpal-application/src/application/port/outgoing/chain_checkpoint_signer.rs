@@ -0,0 +1,6 @@
+/// Signs the digests of checkpoints with a key kept outside of the database.
+pub trait ChainCheckpointSigner {
+    fn sign(&self, digest: &str) -> String;
+
+    fn verify(&self, digest: &str, signature: &str) -> bool;
+}
@@ -0,0 +1,10 @@
+use crate::domain::domain_event::DomainEvent;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait DomainEventPublisher {
+    /// Hands the events of a committed change to whoever is interested in them, in the order
+    /// they were recorded.
+    async fn publish(&self, events: &[DomainEvent]) -> Result<()>;
+}
@@ -0,0 +1,14 @@
+use crate::domain::external_payment::{ExternalPayment, ExternalPaymentBatch};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait ExternalPaymentPort {
+    /// Loads the completed transfers to external accounts that are not part of any batch yet,
+    /// oldest first.
+    async fn load_unbatched_payments(&self) -> Result<Vec<ExternalPayment>>;
+
+    /// Records the batch together with the transfers it pays out. Fails without recording
+    /// anything if one of them is part of another batch already.
+    async fn save_batch(&self, batch: &ExternalPaymentBatch) -> Result<ExternalPaymentBatch>;
+}
@@ -0,0 +1,17 @@
+use crate::domain::idempotency_record::IdempotencyRecord;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait IdempotencyPort {
+    /// Stores the record unless an unexpired record with the same key and source account exists,
+    /// in which case the existing record is returned and nothing is stored.
+    async fn claim_key(&self, record: &IdempotencyRecord) -> Result<Option<IdempotencyRecord>>;
+
+    /// Stores the outcome of a request that claimed its key.
+    async fn complete_key(&self, record: &IdempotencyRecord) -> Result<IdempotencyRecord>;
+
+    /// Removes all records that expired before the given point in time.
+    async fn remove_expired_keys(&self, now: &DateTime<Utc>) -> Result<u64>;
+}
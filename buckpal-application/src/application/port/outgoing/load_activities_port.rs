@@ -0,0 +1,18 @@
+use crate::application::port::incoming::list_activities_query::{ActivityCursor, ActivityFilter};
+use crate::domain::account::AccountId;
+use crate::domain::activity::Activity;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait LoadActivitiesPort {
+    /// Loads at most `limit` activities owned by the account matching the filter, newest first,
+    /// starting after the cursor if one is given.
+    async fn load_activities(
+        &self,
+        account_id: &AccountId,
+        filter: &ActivityFilter,
+        cursor: Option<&ActivityCursor>,
+        limit: i64,
+    ) -> Result<Vec<Activity>>;
+}
@@ -0,0 +1,8 @@
+use crate::domain::hold::{Hold, HoldId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait LoadHoldPort {
+    async fn load_hold(&self, hold_id: &HoldId) -> Result<Hold>;
+}
@@ -0,0 +1,16 @@
+use crate::domain::account::AccountId;
+use crate::domain::statement::Statement;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+#[async_trait]
+pub trait LoadStatementPort {
+    /// Loads the statement previously issued for exactly this period, if there is one.
+    async fn load_statement(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+    ) -> Result<Option<Statement>>;
+}
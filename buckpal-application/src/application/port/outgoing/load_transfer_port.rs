@@ -0,0 +1,15 @@
+use crate::domain::activity::Activity;
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait LoadTransferPort {
+    /// Loads the transfer, fails with `TransferError::TransferNotFound` if there is no such
+    /// transfer.
+    async fn load_transfer(&self, transfer_id: &TransferId) -> Result<Transfer>;
+
+    async fn load_transfer_activities(&self, transfer_id: &TransferId) -> Result<Vec<Activity>>;
+
+    async fn load_reversals(&self, transfer_id: &TransferId) -> Result<Vec<Transfer>>;
+}
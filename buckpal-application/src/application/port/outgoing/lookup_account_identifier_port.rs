@@ -0,0 +1,10 @@
+use crate::domain::account::AccountId;
+use crate::domain::account_identifier::AccountIdentifier;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait LookupAccountIdentifierPort {
+    /// Resolves an external identifier to the account it is registered for, if there is one.
+    async fn lookup_account_id(&self, identifier: &AccountIdentifier) -> Result<Option<AccountId>>;
+}
@@ -1,3 +1,24 @@
+pub mod account_access_port;
+pub mod account_balance_port;
 pub mod account_lock;
+pub mod activity_chain_port;
+pub mod audit_port;
+pub mod chain_checkpoint_signer;
+pub mod domain_event_publisher;
+pub mod external_payment_port;
+pub mod idempotency_port;
 pub mod load_account_port;
+pub mod load_activities_port;
+pub mod load_hold_port;
+pub mod load_statement_port;
+pub mod load_transfer_port;
+pub mod lookup_account_identifier_port;
+pub mod payment_initiation_port;
+pub mod reconciliation_port;
+pub mod record_transfer_port;
+pub mod save_statement_port;
+pub mod transfer_receipt_signer;
 pub mod update_account_state_port;
+pub mod update_transfer_state_port;
+pub mod webhook_port;
+pub mod webhook_sender;
@@ -0,0 +1,11 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait PaymentInitiationPort {
+    /// Records the message id of an initiation about to be executed. Returns `false` without
+    /// recording anything if the message id was recorded before.
+    async fn claim_message_id(&self, message_id: &str, received_at: &DateTime<Utc>)
+        -> Result<bool>;
+}
@@ -0,0 +1,18 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity::Activity;
+use crate::domain::reconciliation::LedgerTotals;
+use anyhow::Result;
+use async_trait::async_trait;
+use rusty_money::Money;
+
+#[async_trait]
+pub trait ReconciliationPort {
+    /// Loads the withdrawals and deposits that lack the activity mirroring them on the other
+    /// side of their transfer.
+    async fn load_unmatched_activities(&self) -> Result<Vec<Activity>>;
+
+    async fn load_ledger_totals(&self) -> Result<LedgerTotals>;
+
+    /// Loads the accounts whose activities add up to less than zero, with what they add up to.
+    async fn load_negative_balances(&self) -> Result<Vec<(AccountId, Money)>>;
+}
@@ -0,0 +1,21 @@
+use crate::domain::account::Account;
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait RecordTransferPort {
+    /// Reserves the id of a transfer that is yet to be recorded, so that its legs can refer to
+    /// it before anything is persisted.
+    async fn reserve_transfer_id(&self) -> Result<TransferId>;
+
+    /// Persists the transfer under its reserved id together with the new activities and events
+    /// of both of its accounts, in one transaction: either all of it is recorded or nothing is.
+    /// Holds the transfer settled are claimed first and must still be active.
+    async fn record_transfer(
+        &self,
+        transfer: &Transfer,
+        source_account: &Account,
+        target_account: &Account,
+    ) -> Result<Transfer>;
+}
@@ -0,0 +1,10 @@
+use crate::domain::statement::Statement;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait SaveStatementPort {
+    /// Persists a newly generated statement. Statements are never updated, if a statement for
+    /// the same period was saved in the meantime that one is returned instead.
+    async fn save_statement(&self, statement: &Statement) -> Result<Statement>;
+}
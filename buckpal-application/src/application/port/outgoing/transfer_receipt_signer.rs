@@ -0,0 +1,8 @@
+use crate::domain::transfer_receipt::TransferReceipt;
+use anyhow::Result;
+
+/// Signs receipts with a key only buckpal holds, whose public half is handed to customers.
+pub trait TransferReceiptSigner {
+    /// Signs everything but the signature of the receipt.
+    fn sign(&self, receipt: &TransferReceipt) -> Result<String>;
+}
@@ -1,9 +1,14 @@
 use crate::domain::account::Account;
 use crate::domain::activity::Activity;
+use crate::domain::hold::Hold;
 use anyhow::Result;
 use async_trait::async_trait;
 
 #[async_trait]
 pub trait UpdateAccountStatePort {
     async fn update_activities(&self, account: &Account) -> Result<Vec<Activity>>;
+
+    /// Persists new holds of the account as well as status changes of existing ones, returning
+    /// the newly created holds.
+    async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>>;
 }
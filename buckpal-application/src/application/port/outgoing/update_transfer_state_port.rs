@@ -0,0 +1,12 @@
+use crate::domain::transfer::Transfer;
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait UpdateTransferStatePort {
+    async fn create_transfer(&self, transfer: &Transfer) -> Result<Transfer>;
+
+    /// Updates the status of the transfer. Updating a reversal brings the status of the reversed
+    /// transfer up to date with all of its completed reversals as well.
+    async fn update_transfer(&self, transfer: &Transfer) -> Result<Transfer>;
+}
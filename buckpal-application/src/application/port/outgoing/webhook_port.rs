@@ -0,0 +1,51 @@
+use crate::domain::domain_event::DomainEvent;
+use crate::domain::webhook::{
+    WebhookDelivery, WebhookDeliveryId, WebhookSubscription, WebhookSubscriptionId,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+#[async_trait]
+pub trait WebhookPort {
+    async fn save_subscription(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> Result<WebhookSubscription>;
+
+    async fn load_subscriptions(&self) -> Result<Vec<WebhookSubscription>>;
+
+    async fn load_subscription(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Option<WebhookSubscription>>;
+
+    /// Deletes the subscription and its deliveries. Returns `false` if there was none.
+    async fn delete_subscription(&self, subscription_id: &WebhookSubscriptionId) -> Result<bool>;
+
+    /// Loads the subscriptions the event is to be delivered to.
+    async fn load_subscriptions_for(&self, event: &DomainEvent)
+        -> Result<Vec<WebhookSubscription>>;
+
+    /// Queues the deliveries, skipping those of events already queued for their subscription.
+    async fn save_deliveries(&self, deliveries: &[WebhookDelivery]) -> Result<()>;
+
+    async fn load_delivery(
+        &self,
+        delivery_id: &WebhookDeliveryId,
+    ) -> Result<Option<WebhookDelivery>>;
+
+    async fn load_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Vec<WebhookDelivery>>;
+
+    /// Loads the oldest pending deliveries due at the given point in time.
+    async fn load_due_deliveries(
+        &self,
+        now: &DateTime<Utc>,
+        limit: i64,
+    ) -> Result<Vec<WebhookDelivery>>;
+
+    async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<()>;
+}
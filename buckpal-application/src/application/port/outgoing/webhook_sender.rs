@@ -0,0 +1,14 @@
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription};
+use anyhow::Result;
+use async_trait::async_trait;
+
+#[async_trait]
+pub trait WebhookSender {
+    /// Sends the signed event of the delivery to the endpoint of the subscription. Fails unless
+    /// the endpoint acknowledged it.
+    async fn send(
+        &self,
+        subscription: &WebhookSubscription,
+        delivery: &WebhookDelivery,
+    ) -> Result<()>;
+}
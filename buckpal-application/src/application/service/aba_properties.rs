@@ -0,0 +1,43 @@
+use crate::domain::aba::AbaUser;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AbaProperties {
+    user: AbaUser,
+    description: String,
+    self_balancing: bool,
+}
+
+impl AbaProperties {
+    pub fn new(user: AbaUser) -> Self {
+        Self {
+            user,
+            description: String::from("PAYMENTS"),
+            self_balancing: false,
+        }
+    }
+
+    /// Overrides the description of entries shown on the descriptive record.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = String::from(description);
+        self
+    }
+
+    /// Adds a balancing debit of the trace account to every file, for banks that only accept
+    /// files whose credits and debits net to zero.
+    pub fn with_self_balancing(mut self, self_balancing: bool) -> Self {
+        self.self_balancing = self_balancing;
+        self
+    }
+
+    pub fn user(&self) -> &AbaUser {
+        &self.user
+    }
+
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    pub fn self_balancing(&self) -> bool {
+        self.self_balancing
+    }
+}
@@ -0,0 +1,198 @@
+use crate::application::port::outgoing::account_access_port::AccountAccessPort;
+use crate::application::service::error::ServiceError;
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::{anyhow, Result};
+
+/// Fails with `ServiceError::AccountAccessDenied` unless the principal may access the account.
+pub async fn check_account_access(
+    account_access_port: &(dyn AccountAccessPort + Send + Sync),
+    account_id: &AccountId,
+    principal: &Principal,
+) -> Result<()> {
+    if may_access(account_access_port, account_id, principal).await? {
+        Ok(())
+    } else {
+        Err(anyhow!(ServiceError::AccountAccessDenied {
+            principal: principal.clone(),
+            account_id: account_id.0,
+        }))
+    }
+}
+
+/// Fails with `ServiceError::TransferAccessDenied` unless the principal may access either
+/// account of the transfer.
+pub async fn check_transfer_access(
+    account_access_port: &(dyn AccountAccessPort + Send + Sync),
+    transfer_id: &TransferId,
+    transfer: &Transfer,
+    principal: &Principal,
+) -> Result<()> {
+    for account_id in &[&transfer.source_account_id, &transfer.target_account_id] {
+        if may_access(account_access_port, account_id, principal).await? {
+            return Ok(());
+        }
+    }
+
+    Err(anyhow!(ServiceError::TransferAccessDenied {
+        principal: principal.clone(),
+        transfer_id: transfer_id.0,
+    }))
+}
+
+/// The initiator of a command, commands that don't name one may not act on any account.
+pub fn initiator(initiator: Option<&Principal>) -> Result<&Principal> {
+    initiator.ok_or_else(|| anyhow!(ServiceError::InitiatorMissing))
+}
+
+/// System jobs act on behalf of buckpal itself and may access every account, everyone else only
+/// the accounts they were granted access to.
+async fn may_access(
+    account_access_port: &(dyn AccountAccessPort + Send + Sync),
+    account_id: &AccountId,
+    principal: &Principal,
+) -> Result<bool> {
+    if let Principal::SystemJob(_) = principal {
+        return Ok(true);
+    }
+
+    account_access_port.has_access(account_id, principal).await
+}
+
+#[cfg(test)]
+pub mod account_access_test_data {
+    use crate::application::port::outgoing::account_access_port::AccountAccessPort;
+    use crate::domain::account::AccountId;
+    use crate::domain::principal::Principal;
+    use anyhow::Result;
+    use async_trait::async_trait;
+
+    /// Grants a single principal access to the given accounts, or to all of them.
+    #[derive(Debug, Clone)]
+    pub struct MockAccountAccess {
+        principal: Principal,
+        account_ids: Option<Vec<AccountId>>,
+    }
+
+    impl MockAccountAccess {
+        pub fn to_all_accounts(principal: Principal) -> Self {
+            Self {
+                principal,
+                account_ids: None,
+            }
+        }
+
+        pub fn to_accounts(principal: Principal, account_ids: &[AccountId]) -> Self {
+            Self {
+                principal,
+                account_ids: Some(account_ids.to_vec()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl AccountAccessPort for MockAccountAccess {
+        async fn has_access(&self, account_id: &AccountId, principal: &Principal) -> Result<bool> {
+            Ok(*principal == self.principal
+                && self
+                    .account_ids
+                    .as_ref()
+                    .map_or(true, |account_ids| account_ids.contains(account_id)))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::account_access_test_data::MockAccountAccess;
+    use super::{check_account_access, check_transfer_access, initiator};
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::AccountId;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::transfer_test_data::TransferBuilder;
+    use crate::domain::transfer::TransferId;
+
+    #[async_std::test]
+    async fn grants_access_to_granted_accounts_only() {
+        let account_access = MockAccountAccess::to_accounts(default_principal(), &[AccountId(41)]);
+
+        assert!(
+            check_account_access(&account_access, &AccountId(41), &default_principal())
+                .await
+                .is_ok()
+        );
+        assert!(matches!(
+            check_account_access(&account_access, &AccountId(42), &default_principal())
+                .await
+                .unwrap_err()
+                .downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+        assert!(check_account_access(
+            &account_access,
+            &AccountId(41),
+            &Principal::User(String::from("mallory"))
+        )
+        .await
+        .is_err());
+    }
+
+    #[async_std::test]
+    async fn system_jobs_access_every_account() {
+        let account_access = MockAccountAccess::to_accounts(default_principal(), &[]);
+
+        assert!(check_account_access(
+            &account_access,
+            &AccountId(42),
+            &Principal::SystemJob(String::from("reconciliation"))
+        )
+        .await
+        .is_ok());
+    }
+
+    #[async_std::test]
+    async fn grants_access_to_transfers_of_either_account() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_source_account(&AccountId(41))
+            .with_target_account(&AccountId(42))
+            .build();
+
+        for account_id in &[AccountId(41), AccountId(42)] {
+            let account_access =
+                MockAccountAccess::to_accounts(default_principal(), &[account_id.clone()]);
+
+            assert!(check_transfer_access(
+                &account_access,
+                &TransferId(7),
+                &transfer,
+                &default_principal()
+            )
+            .await
+            .is_ok());
+        }
+
+        let account_access = MockAccountAccess::to_accounts(default_principal(), &[AccountId(43)]);
+        assert!(matches!(
+            check_transfer_access(
+                &account_access,
+                &TransferId(7),
+                &transfer,
+                &default_principal()
+            )
+            .await
+            .unwrap_err()
+            .downcast_ref::<ServiceError>(),
+            Some(ServiceError::TransferAccessDenied { transfer_id: 7, .. })
+        ));
+    }
+
+    #[test]
+    fn commands_without_initiator_are_denied() {
+        assert!(matches!(
+            initiator(None).unwrap_err().downcast_ref::<ServiceError>(),
+            Some(ServiceError::InitiatorMissing)
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::error::error_category;
+use crate::domain::audit::{AuditContext, AuditEntry, AuditOutcome};
+use anyhow::Result;
+use chrono::Utc;
+
+/// The outcome of an invocation as the audit log records it.
+pub fn audit_outcome<T>(result: &Result<T>) -> AuditOutcome {
+    match result {
+        Ok(_) => AuditOutcome::Succeeded,
+        Err(err) => AuditOutcome::Failed(error_category(err)),
+    }
+}
+
+/// Records a command in the audit log after it was executed. Its effects can't be undone at
+/// that point, so an entry that can't be recorded is logged instead of failing the command.
+pub async fn record_command(
+    audit_port: &(dyn AuditPort + Send + Sync),
+    action: &str,
+    details: &str,
+    audit_context: &AuditContext,
+    outcome: AuditOutcome,
+) {
+    let entry = AuditEntry::new(Utc::now(), action, details, audit_context, outcome);
+
+    if let Err(err) = audit_port.record(&entry).await {
+        log::error!(
+            "Recording `{}` ({}) in the audit log failed: {}",
+            action,
+            details,
+            err
+        );
+    }
+}
+
+#[cfg(test)]
+pub mod audit_test_data {
+    use crate::application::port::incoming::list_audit_log_query::AuditLogFilter;
+    use crate::application::port::outgoing::audit_port::AuditPort;
+    use crate::domain::audit::{AuditContext, AuditEntry, AuditEntryId};
+    use crate::domain::principal::Principal;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use std::sync::{Arc, Mutex};
+
+    pub fn default_audit_context() -> AuditContext {
+        AuditContext::new(
+            Some(Principal::User(String::from("alice"))),
+            Some(String::from("127.0.0.1")),
+            Some(String::from("req-1")),
+        )
+    }
+
+    /// Keeps the recorded entries in memory, or fails to record any if it is unavailable.
+    #[derive(Debug, Default, Clone)]
+    pub struct RecordingAuditPort {
+        unavailable: bool,
+        entries: Arc<Mutex<Vec<AuditEntry>>>,
+    }
+
+    impl RecordingAuditPort {
+        pub fn unavailable() -> Self {
+            Self {
+                unavailable: true,
+                ..Self::default()
+            }
+        }
+
+        pub fn entries(&self) -> Vec<AuditEntry> {
+            self.entries.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl AuditPort for RecordingAuditPort {
+        async fn record(&self, entry: &AuditEntry) -> Result<AuditEntry> {
+            if self.unavailable {
+                return Err(anyhow!("audit log unavailable"));
+            }
+
+            let mut entries = self.entries.lock().unwrap();
+
+            let mut entry = entry.clone();
+            entry.id = Some(AuditEntryId(entries.len() as i32 + 1));
+            entries.push(entry.clone());
+
+            Ok(entry)
+        }
+
+        async fn load_entries(
+            &self,
+            _filter: &AuditLogFilter,
+            _before: Option<&AuditEntryId>,
+            _limit: i64,
+        ) -> Result<Vec<AuditEntry>> {
+            Ok(self.entries())
+        }
+    }
+}
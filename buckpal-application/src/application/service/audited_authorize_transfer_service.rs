@@ -0,0 +1,112 @@
+use crate::application::port::incoming::authorize_transfer_use_case::{
+    AuthorizeTransferCommand, AuthorizeTransferUseCase,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::hold::HoldId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every authorization in the audit log together with its outcome.
+pub struct AuditedAuthorizeTransferService {
+    authorize_transfer_use_case: Box<dyn AuthorizeTransferUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedAuthorizeTransferService {
+    pub fn new(
+        authorize_transfer_use_case: Box<dyn AuthorizeTransferUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            authorize_transfer_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl AuthorizeTransferUseCase for AuditedAuthorizeTransferService {
+    async fn authorize_transfer(&self, command: &AuthorizeTransferCommand) -> Result<HoldId> {
+        let result = self
+            .authorize_transfer_use_case
+            .authorize_transfer(command)
+            .await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "authorize_transfer",
+            &describe(command, result.as_ref().ok()),
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
+
+fn describe(command: &AuthorizeTransferCommand, hold_id: Option<&HoldId>) -> String {
+    use rust_decimal::prelude::*;
+
+    let mut details = format!(
+        "source={} target={} amount={}",
+        command.source_account_id.0,
+        command.target_account_id.0,
+        command.money.amount().to_i64().unwrap()
+    );
+    if let Some(hold_id) = hold_id {
+        details.push_str(&format!(" hold={}", hold_id.0));
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedAuthorizeTransferService;
+    use crate::application::port::incoming::authorize_transfer_use_case::{
+        AuthorizeTransferCommand, AuthorizeTransferUseCase,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::audit::AuditOutcome;
+    use crate::domain::hold::HoldId;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn records_authorization() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedAuthorizeTransferService::new(
+            Box::new(MockAuthorizeTransferUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        let hold_id = service
+            .authorize_transfer(
+                &AuthorizeTransferCommand::new(AccountId(41), AccountId(42), money!(300, "AUD"))
+                    .with_audit_context(default_audit_context()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(hold_id, HoldId(3));
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "authorize_transfer");
+        assert_eq!(entries[0].details, "source=41 target=42 amount=300 hold=3");
+        assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+    }
+
+    struct MockAuthorizeTransferUseCase {}
+
+    #[async_trait]
+    impl AuthorizeTransferUseCase for MockAuthorizeTransferUseCase {
+        async fn authorize_transfer(&self, _command: &AuthorizeTransferCommand) -> Result<HoldId> {
+            Ok(HoldId(3))
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use crate::application::port::incoming::batch_send_money_use_case::{
+    BatchLineOutcome, BatchMode, BatchSendMoneyCommand, BatchSendMoneyReport, BatchSendMoneyUseCase,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::audit::{AuditOutcome, ErrorCategory};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every batch in the audit log together with its outcome. The transfers of the batch
+/// are recorded on their own by the use case sending them.
+pub struct AuditedBatchSendMoneyService {
+    batch_send_money_use_case: Box<dyn BatchSendMoneyUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedBatchSendMoneyService {
+    pub fn new(
+        batch_send_money_use_case: Box<dyn BatchSendMoneyUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            batch_send_money_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl BatchSendMoneyUseCase for AuditedBatchSendMoneyService {
+    async fn batch_send_money(
+        &self,
+        command: &BatchSendMoneyCommand,
+    ) -> Result<BatchSendMoneyReport> {
+        let result = self
+            .batch_send_money_use_case
+            .batch_send_money(command)
+            .await;
+
+        let outcome = match &result {
+            Ok(report) if !report.valid => AuditOutcome::Failed(ErrorCategory::Rejected),
+            result => audit_outcome(result),
+        };
+
+        record_command(
+            self.audit_port.as_ref(),
+            "batch_send_money",
+            &describe(command, result.as_ref().ok()),
+            &command.audit_context,
+            outcome,
+        )
+        .await;
+
+        result
+    }
+}
+
+fn describe(command: &BatchSendMoneyCommand, report: Option<&BatchSendMoneyReport>) -> String {
+    let mode = match command.mode {
+        BatchMode::ReverseOnFailure => "reverse_on_failure",
+        BatchMode::BestEffort => "best_effort",
+    };
+
+    let mut details = format!("mode={} lines={}", mode, command.commands.len());
+    if let Some(report) = report {
+        let sent = report
+            .lines
+            .iter()
+            .filter(|line| matches!(line.outcome, BatchLineOutcome::Sent(_)))
+            .count();
+        details.push_str(&format!(" sent={}", sent));
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedBatchSendMoneyService;
+    use crate::application::port::incoming::batch_send_money_use_case::{
+        BatchLineOutcome, BatchLineResult, BatchMode, BatchSendMoneyCommand, BatchSendMoneyReport,
+        BatchSendMoneyUseCase,
+    };
+    use crate::application::port::incoming::send_money_use_case::SendMoneyCommand;
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::audit::{AuditOutcome, ErrorCategory};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn records_invalid_batch_as_rejected() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedBatchSendMoneyService::new(
+            Box::new(MockBatchSendMoneyUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        let report = service
+            .batch_send_money(
+                &BatchSendMoneyCommand::new(
+                    vec![SendMoneyCommand::new(
+                        AccountId(41),
+                        AccountId(42),
+                        money!(300, "AUD"),
+                    )],
+                    BatchMode::ReverseOnFailure,
+                )
+                .with_audit_context(default_audit_context()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(report.valid, false);
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "batch_send_money");
+        assert_eq!(entries[0].details, "mode=reverse_on_failure lines=1 sent=0");
+        assert_eq!(
+            entries[0].outcome,
+            AuditOutcome::Failed(ErrorCategory::Rejected)
+        );
+    }
+
+    struct MockBatchSendMoneyUseCase {}
+
+    #[async_trait]
+    impl BatchSendMoneyUseCase for MockBatchSendMoneyUseCase {
+        async fn batch_send_money(
+            &self,
+            command: &BatchSendMoneyCommand,
+        ) -> Result<BatchSendMoneyReport> {
+            Ok(BatchSendMoneyReport {
+                mode: command.mode,
+                valid: false,
+                lines: vec![BatchLineResult {
+                    line: 1,
+                    outcome: BatchLineOutcome::NotExecuted,
+                }],
+            })
+        }
+    }
+}
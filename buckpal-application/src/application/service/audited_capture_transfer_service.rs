@@ -0,0 +1,112 @@
+use crate::application::port::incoming::capture_transfer_use_case::{
+    CaptureTransferCommand, CaptureTransferUseCase,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every capture of a hold in the audit log together with its outcome.
+pub struct AuditedCaptureTransferService {
+    capture_transfer_use_case: Box<dyn CaptureTransferUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedCaptureTransferService {
+    pub fn new(
+        capture_transfer_use_case: Box<dyn CaptureTransferUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            capture_transfer_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl CaptureTransferUseCase for AuditedCaptureTransferService {
+    async fn capture_transfer(&self, command: &CaptureTransferCommand) -> Result<TransferId> {
+        let result = self
+            .capture_transfer_use_case
+            .capture_transfer(command)
+            .await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "capture_transfer",
+            &describe(command, result.as_ref().ok()),
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
+
+fn describe(command: &CaptureTransferCommand, transfer_id: Option<&TransferId>) -> String {
+    use rust_decimal::prelude::*;
+
+    let mut details = format!("hold={}", command.hold_id.0);
+    if let Some(money) = &command.money {
+        details.push_str(&format!(" amount={}", money.amount().to_i64().unwrap()));
+    }
+    if let Some(transfer_id) = transfer_id {
+        details.push_str(&format!(" transfer={}", transfer_id.0));
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedCaptureTransferService;
+    use crate::application::port::incoming::capture_transfer_use_case::{
+        CaptureTransferCommand, CaptureTransferUseCase,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::application::service::error::ServiceError;
+    use crate::domain::audit::{AuditOutcome, ErrorCategory};
+    use crate::domain::hold::HoldId;
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+
+    #[async_std::test]
+    async fn records_failed_capture() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedCaptureTransferService::new(
+            Box::new(MockCaptureTransferUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        let result = service
+            .capture_transfer(
+                &CaptureTransferCommand::new(HoldId(3), None)
+                    .with_audit_context(default_audit_context()),
+            )
+            .await;
+
+        assert!(result.is_err());
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "capture_transfer");
+        assert_eq!(entries[0].details, "hold=3");
+        assert_eq!(
+            entries[0].outcome,
+            AuditOutcome::Failed(ErrorCategory::Rejected)
+        );
+    }
+
+    struct MockCaptureTransferUseCase {}
+
+    #[async_trait]
+    impl CaptureTransferUseCase for MockCaptureTransferUseCase {
+        async fn capture_transfer(&self, _command: &CaptureTransferCommand) -> Result<TransferId> {
+            Err(anyhow!(ServiceError::MayWithdrawFailed(100)))
+        }
+    }
+}
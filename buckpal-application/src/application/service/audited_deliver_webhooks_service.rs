@@ -0,0 +1,55 @@
+use crate::application::port::incoming::deliver_webhooks_use_case::DeliverWebhooksUseCase;
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookDeliveryId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every requested redelivery in the audit log together with its outcome. The
+/// deliveries the worker makes on its own aren't recorded.
+pub struct AuditedDeliverWebhooksService {
+    deliver_webhooks_use_case: Box<dyn DeliverWebhooksUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedDeliverWebhooksService {
+    pub fn new(
+        deliver_webhooks_use_case: Box<dyn DeliverWebhooksUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            deliver_webhooks_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl DeliverWebhooksUseCase for AuditedDeliverWebhooksService {
+    async fn deliver_due_webhooks(&self) -> Result<usize> {
+        self.deliver_webhooks_use_case.deliver_due_webhooks().await
+    }
+
+    async fn redeliver(
+        &self,
+        delivery_id: &WebhookDeliveryId,
+        audit_context: &AuditContext,
+    ) -> Result<WebhookDelivery> {
+        let result = self
+            .deliver_webhooks_use_case
+            .redeliver(delivery_id, audit_context)
+            .await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "redeliver_webhook",
+            &format!("delivery={}", delivery_id.0),
+            audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
@@ -0,0 +1,98 @@
+use crate::application::port::incoming::get_account_balance_query::GetAccountBalanceQuery;
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::error::error_category;
+use crate::domain::account::AccountId;
+use crate::domain::audit::{AuditContext, AuditEntry, AuditOutcome};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+/// Records every balance query in the audit log, for where reading balances has to be
+/// accounted for as well.
+pub struct AuditedGetAccountBalanceService {
+    get_account_balance_query: Box<dyn GetAccountBalanceQuery + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedGetAccountBalanceService {
+    pub fn new(
+        get_account_balance_query: Box<dyn GetAccountBalanceQuery + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            get_account_balance_query,
+            audit_port,
+        }
+    }
+
+    async fn record(
+        &self,
+        action: &str,
+        details: &str,
+        audit_context: &AuditContext,
+        result: Result<Money>,
+    ) -> Result<Money> {
+        let outcome = match &result {
+            Ok(_) => AuditOutcome::Succeeded,
+            Err(err) => AuditOutcome::Failed(error_category(err)),
+        };
+
+        self.audit_port
+            .record(&AuditEntry::new(
+                Utc::now(),
+                action,
+                details,
+                audit_context,
+                outcome,
+            ))
+            .await?;
+
+        result
+    }
+}
+
+#[async_trait]
+impl GetAccountBalanceQuery for AuditedGetAccountBalanceService {
+    async fn get_account_balance(
+        &self,
+        account_id: &AccountId,
+        principal: &Principal,
+        audit_context: &AuditContext,
+    ) -> Result<Money> {
+        let result = self
+            .get_account_balance_query
+            .get_account_balance(account_id, principal, audit_context)
+            .await;
+
+        self.record(
+            "get_account_balance",
+            &format!("account={}", account_id.0),
+            audit_context,
+            result,
+        )
+        .await
+    }
+
+    async fn get_balance_as_of(
+        &self,
+        account_id: &AccountId,
+        instant: &DateTime<Utc>,
+        principal: &Principal,
+        audit_context: &AuditContext,
+    ) -> Result<Money> {
+        let result = self
+            .get_account_balance_query
+            .get_balance_as_of(account_id, instant, principal, audit_context)
+            .await;
+
+        self.record(
+            "get_balance_as_of",
+            &format!("account={} as_of={}", account_id.0, instant.to_rfc3339()),
+            audit_context,
+            result,
+        )
+        .await
+    }
+}
@@ -0,0 +1,64 @@
+use crate::application::port::incoming::import_payment_initiation_use_case::ImportPaymentInitiationUseCase;
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::audit::{AuditContext, AuditOutcome, ErrorCategory};
+use crate::domain::payment_initiation::{PaymentInitiation, PaymentStatus, PaymentStatusReport};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every imported payment initiation in the audit log together with its outcome. The
+/// transfers it executes are recorded on their own by the use case sending them.
+pub struct AuditedImportPaymentInitiationService {
+    import_payment_initiation_use_case: Box<dyn ImportPaymentInitiationUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedImportPaymentInitiationService {
+    pub fn new(
+        import_payment_initiation_use_case: Box<dyn ImportPaymentInitiationUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            import_payment_initiation_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ImportPaymentInitiationUseCase for AuditedImportPaymentInitiationService {
+    async fn import_payment_initiation(
+        &self,
+        initiation: &PaymentInitiation,
+        initiator: Option<&Principal>,
+        audit_context: &AuditContext,
+    ) -> Result<PaymentStatusReport> {
+        let result = self
+            .import_payment_initiation_use_case
+            .import_payment_initiation(initiation, initiator, audit_context)
+            .await;
+
+        let outcome = match &result {
+            Ok(report) if report.status == PaymentStatus::Rejected => {
+                AuditOutcome::Failed(ErrorCategory::Rejected)
+            }
+            result => audit_outcome(result),
+        };
+
+        record_command(
+            self.audit_port.as_ref(),
+            "import_payment_initiation",
+            &format!(
+                "message_id={} transactions={}",
+                initiation.message_id,
+                initiation.transactions().count()
+            ),
+            audit_context,
+            outcome,
+        )
+        .await;
+
+        result
+    }
+}
@@ -0,0 +1,182 @@
+use crate::application::port::incoming::manage_webhooks_use_case::{
+    ManageWebhooksUseCase, RegisterWebhookCommand,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every registered and deleted webhook in the audit log together with its outcome.
+/// Secrets are left out, listings aren't recorded.
+pub struct AuditedManageWebhooksService {
+    manage_webhooks_use_case: Box<dyn ManageWebhooksUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedManageWebhooksService {
+    pub fn new(
+        manage_webhooks_use_case: Box<dyn ManageWebhooksUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            manage_webhooks_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ManageWebhooksUseCase for AuditedManageWebhooksService {
+    async fn register_webhook(
+        &self,
+        command: &RegisterWebhookCommand,
+    ) -> Result<WebhookSubscription> {
+        let result = self
+            .manage_webhooks_use_case
+            .register_webhook(command)
+            .await;
+
+        let mut details = format!(
+            "url={} event_types={}",
+            command.url,
+            command.event_types.join(",")
+        );
+        if let Ok(subscription) = &result {
+            if let Some(subscription_id) = &subscription.id {
+                details.push_str(&format!(" webhook={}", subscription_id.0));
+            }
+        }
+
+        record_command(
+            self.audit_port.as_ref(),
+            "register_webhook",
+            &details,
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>> {
+        self.manage_webhooks_use_case.list_webhooks().await
+    }
+
+    async fn delete_webhook(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        audit_context: &AuditContext,
+    ) -> Result<()> {
+        let result = self
+            .manage_webhooks_use_case
+            .delete_webhook(subscription_id, audit_context)
+            .await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "delete_webhook",
+            &format!("webhook={}", subscription_id.0),
+            audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Vec<WebhookDelivery>> {
+        self.manage_webhooks_use_case
+            .list_deliveries(subscription_id)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedManageWebhooksService;
+    use crate::application::port::incoming::manage_webhooks_use_case::{
+        ManageWebhooksUseCase, RegisterWebhookCommand,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::domain::audit::{AuditContext, AuditOutcome};
+    use crate::domain::webhook::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    #[async_std::test]
+    async fn records_registered_webhook_without_its_secret() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedManageWebhooksService::new(
+            Box::new(MockManageWebhooksUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        service
+            .register_webhook(
+                &RegisterWebhookCommand::new(
+                    "https://example.com/hooks",
+                    "0123456789abcdef",
+                    vec![String::from("MoneyDeposited")],
+                )
+                .with_audit_context(default_audit_context()),
+            )
+            .await
+            .unwrap();
+
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "register_webhook");
+        assert_eq!(
+            entries[0].details,
+            "url=https://example.com/hooks event_types=MoneyDeposited webhook=5"
+        );
+        assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+    }
+
+    struct MockManageWebhooksUseCase {}
+
+    #[async_trait]
+    impl ManageWebhooksUseCase for MockManageWebhooksUseCase {
+        async fn register_webhook(
+            &self,
+            command: &RegisterWebhookCommand,
+        ) -> Result<WebhookSubscription> {
+            let mut subscription = WebhookSubscription::new(
+                &command.url,
+                &command.secret,
+                &command.event_types,
+                Utc::now(),
+            )?;
+            subscription.id = Some(WebhookSubscriptionId(5));
+
+            Ok(subscription)
+        }
+
+        async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>> {
+            Ok(vec![])
+        }
+
+        async fn delete_webhook(
+            &self,
+            _subscription_id: &WebhookSubscriptionId,
+            _audit_context: &AuditContext,
+        ) -> Result<()> {
+            Ok(())
+        }
+
+        async fn list_deliveries(
+            &self,
+            _subscription_id: &WebhookSubscriptionId,
+        ) -> Result<Vec<WebhookDelivery>> {
+            Ok(vec![])
+        }
+    }
+}
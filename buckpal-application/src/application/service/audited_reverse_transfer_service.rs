@@ -0,0 +1,110 @@
+use crate::application::port::incoming::reverse_transfer_use_case::{
+    ReverseTransferCommand, ReverseTransferUseCase,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every reversal in the audit log together with its outcome.
+pub struct AuditedReverseTransferService {
+    reverse_transfer_use_case: Box<dyn ReverseTransferUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedReverseTransferService {
+    pub fn new(
+        reverse_transfer_use_case: Box<dyn ReverseTransferUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            reverse_transfer_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ReverseTransferUseCase for AuditedReverseTransferService {
+    async fn reverse_transfer(&self, command: &ReverseTransferCommand) -> Result<TransferId> {
+        let result = self
+            .reverse_transfer_use_case
+            .reverse_transfer(command)
+            .await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "reverse_transfer",
+            &describe(command, result.as_ref().ok()),
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
+
+fn describe(command: &ReverseTransferCommand, reversal_id: Option<&TransferId>) -> String {
+    use rust_decimal::prelude::*;
+
+    let mut details = format!("transfer={}", command.transfer_id.0);
+    if let Some(money) = &command.money {
+        details.push_str(&format!(" amount={}", money.amount().to_i64().unwrap()));
+    }
+    if let Some(reversal_id) = reversal_id {
+        details.push_str(&format!(" reversal={}", reversal_id.0));
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedReverseTransferService;
+    use crate::application::port::incoming::reverse_transfer_use_case::{
+        ReverseTransferCommand, ReverseTransferUseCase,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::domain::audit::AuditOutcome;
+    use crate::domain::transfer::TransferId;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn records_reversal() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedReverseTransferService::new(
+            Box::new(MockReverseTransferUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        let reversal_id = service
+            .reverse_transfer(
+                &ReverseTransferCommand::new(TransferId(7), Some(money!(200, "AUD")))
+                    .with_audit_context(default_audit_context()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(reversal_id, TransferId(8));
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "reverse_transfer");
+        assert_eq!(entries[0].details, "transfer=7 amount=200 reversal=8");
+        assert_eq!(entries[0].context, default_audit_context());
+        assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+    }
+
+    struct MockReverseTransferUseCase {}
+
+    #[async_trait]
+    impl ReverseTransferUseCase for MockReverseTransferUseCase {
+        async fn reverse_transfer(&self, _command: &ReverseTransferCommand) -> Result<TransferId> {
+            Ok(TransferId(8))
+        }
+    }
+}
@@ -0,0 +1,163 @@
+use crate::application::port::incoming::send_money_use_case::{
+    SendMoneyCommand, SendMoneyUseCase, TransferTarget,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every command in the audit log together with its outcome, whether or not the money
+/// was sent.
+pub struct AuditedSendMoneyService {
+    send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedSendMoneyService {
+    pub fn new(
+        send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            send_money_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl SendMoneyUseCase for AuditedSendMoneyService {
+    async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId> {
+        let result = self.send_money_use_case.send_money(command).await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "send_money",
+            &describe(command, result.as_ref().ok()),
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
+
+fn describe(command: &SendMoneyCommand, transfer_id: Option<&TransferId>) -> String {
+    use rust_decimal::prelude::*;
+
+    let target = match &command.target {
+        TransferTarget::Account(account_id) => account_id.0.to_string(),
+        TransferTarget::Identifier(identifier) => identifier.to_string(),
+    };
+
+    let mut details = format!(
+        "source={} target={} amount={}",
+        command.source_account_id.0,
+        target,
+        command.money.amount().to_i64().unwrap()
+    );
+    if let Some(idempotency_key) = &command.idempotency_key {
+        details.push_str(&format!(" idempotency_key={}", idempotency_key.0));
+    }
+    if let Some(transfer_id) = transfer_id {
+        details.push_str(&format!(" transfer={}", transfer_id.0));
+    }
+
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedSendMoneyService;
+    use crate::application::port::incoming::send_money_use_case::{
+        SendMoneyCommand, SendMoneyUseCase,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::AccountId;
+    use crate::domain::audit::{AuditOutcome, ErrorCategory};
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn records_sent_money() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedSendMoneyService::new(
+            Box::new(MockSendMoneyUseCase { fail: false }),
+            Box::new(audit_port.clone()),
+        );
+
+        let transfer_id = service.send_money(&given_a_command()).await.unwrap();
+
+        assert_eq!(transfer_id, TransferId(7));
+        let entries = audit_port.entries();
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].action, "send_money");
+        assert_eq!(
+            entries[0].details,
+            "source=41 target=42 amount=300 transfer=7"
+        );
+        assert_eq!(
+            entries[0].context.actor,
+            Some(Principal::User(String::from("alice")))
+        );
+        assert_eq!(entries[0].context.request_id.as_deref(), Some("req-1"));
+        assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+    }
+
+    #[async_std::test]
+    async fn records_rejected_command() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedSendMoneyService::new(
+            Box::new(MockSendMoneyUseCase { fail: true }),
+            Box::new(audit_port.clone()),
+        );
+
+        let result = service.send_money(&given_a_command()).await;
+
+        assert!(result.is_err());
+        assert_eq!(
+            audit_port.entries()[0].outcome,
+            AuditOutcome::Failed(ErrorCategory::Rejected)
+        );
+    }
+
+    #[async_std::test]
+    async fn sent_money_is_reported_even_if_it_cant_be_recorded() {
+        let service = AuditedSendMoneyService::new(
+            Box::new(MockSendMoneyUseCase { fail: false }),
+            Box::new(RecordingAuditPort::unavailable()),
+        );
+
+        let transfer_id = service.send_money(&given_a_command()).await.unwrap();
+
+        assert_eq!(transfer_id, TransferId(7));
+    }
+
+    fn given_a_command() -> SendMoneyCommand {
+        SendMoneyCommand::new(AccountId(41), AccountId(42), money!(300, "AUD"))
+            .with_audit_context(default_audit_context())
+    }
+
+    struct MockSendMoneyUseCase {
+        fail: bool,
+    }
+
+    #[async_trait]
+    impl SendMoneyUseCase for MockSendMoneyUseCase {
+        async fn send_money(&self, _command: &SendMoneyCommand) -> Result<TransferId> {
+            if self.fail {
+                return Err(anyhow!(ServiceError::MayWithdrawFailed(100)));
+            }
+
+            Ok(TransferId(7))
+        }
+    }
+}
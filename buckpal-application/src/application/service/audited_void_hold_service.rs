@@ -0,0 +1,84 @@
+use crate::application::port::incoming::void_hold_use_case::{VoidHoldCommand, VoidHoldUseCase};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::audit_recorder::{audit_outcome, record_command};
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Records every voided hold in the audit log together with its outcome.
+pub struct AuditedVoidHoldService {
+    void_hold_use_case: Box<dyn VoidHoldUseCase + Send + Sync>,
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+}
+
+impl AuditedVoidHoldService {
+    pub fn new(
+        void_hold_use_case: Box<dyn VoidHoldUseCase + Send + Sync>,
+        audit_port: Box<dyn AuditPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            void_hold_use_case,
+            audit_port,
+        }
+    }
+}
+
+#[async_trait]
+impl VoidHoldUseCase for AuditedVoidHoldService {
+    async fn void_hold(&self, command: &VoidHoldCommand) -> Result<()> {
+        let result = self.void_hold_use_case.void_hold(command).await;
+
+        record_command(
+            self.audit_port.as_ref(),
+            "void_hold",
+            &format!("hold={}", command.hold_id.0),
+            &command.audit_context,
+            audit_outcome(&result),
+        )
+        .await;
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuditedVoidHoldService;
+    use crate::application::port::incoming::void_hold_use_case::{
+        VoidHoldCommand, VoidHoldUseCase,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::{
+        default_audit_context, RecordingAuditPort,
+    };
+    use crate::domain::audit::AuditOutcome;
+    use crate::domain::hold::HoldId;
+    use anyhow::Result;
+    use async_trait::async_trait;
+
+    #[async_std::test]
+    async fn records_voided_hold() {
+        let audit_port = RecordingAuditPort::default();
+        let service = AuditedVoidHoldService::new(
+            Box::new(MockVoidHoldUseCase {}),
+            Box::new(audit_port.clone()),
+        );
+
+        service
+            .void_hold(&VoidHoldCommand::new(HoldId(3)).with_audit_context(default_audit_context()))
+            .await
+            .unwrap();
+
+        let entries = audit_port.entries();
+        assert_eq!(entries[0].action, "void_hold");
+        assert_eq!(entries[0].details, "hold=3");
+        assert_eq!(entries[0].outcome, AuditOutcome::Succeeded);
+    }
+
+    struct MockVoidHoldUseCase {}
+
+    #[async_trait]
+    impl VoidHoldUseCase for MockVoidHoldUseCase {
+        async fn void_hold(&self, _command: &VoidHoldCommand) -> Result<()> {
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,255 @@
+use crate::application::port::incoming::authorize_transfer_use_case::{
+    AuthorizeTransferCommand, AuthorizeTransferUseCase,
+};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, account_lock::AccountLock,
+    load_account_port::LoadAccountPort, update_account_state_port::UpdateAccountStatePort,
+};
+use crate::application::service::account_access::{check_account_access, initiator};
+use crate::application::service::error::ServiceError;
+use crate::application::service::money_transfer_properties::MoneyTransferProperties;
+use crate::domain::hold::HoldId;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+pub struct AuthorizeTransferService {
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    account_lock: Box<dyn AccountLock + Send + Sync>,
+    update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    money_transfer_properties: MoneyTransferProperties,
+}
+
+impl AuthorizeTransferService {
+    pub fn new(
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        account_lock: Box<dyn AccountLock + Send + Sync>,
+        update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+        money_transfer_properties: MoneyTransferProperties,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            account_access_port,
+            money_transfer_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl AuthorizeTransferUseCase for AuthorizeTransferService {
+    async fn authorize_transfer(&self, command: &AuthorizeTransferCommand) -> Result<HoldId> {
+        use chrono::{Duration, Utc};
+
+        if command.money > self.money_transfer_properties.maximum_transfer_threshold() {
+            return Err(anyhow!(ServiceError::ThresholdExceededException {
+                threshold: self.money_transfer_properties.maximum_transfer_threshold(),
+                actual: command.money.clone(),
+            }));
+        }
+
+        // a hold reserves money of the source account, only its holders may place one
+        check_account_access(
+            self.account_access_port.as_ref(),
+            &command.source_account_id,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        let mut source_account = self
+            .load_account_port
+            .load_account(&command.source_account_id, &baseline_date)
+            .await?;
+
+        // make sure the target account exists before reserving any money for it
+        let target_account = self
+            .load_account_port
+            .load_account(&command.target_account_id, &baseline_date)
+            .await?;
+
+        let source_account_id = source_account
+            .clone()
+            .id
+            .expect("expected source account ID not to be empty");
+        let target_account_id = target_account
+            .id
+            .expect("expected target account ID not to be empty");
+
+        let now = Utc::now();
+
+        self.account_lock.lock_account(&source_account_id);
+        if let Err(err) = source_account.place_hold(
+            &command.money,
+            &target_account_id,
+            now,
+            now + self.money_transfer_properties.hold_expiry(),
+        ) {
+            self.account_lock.release_account(&source_account_id);
+            return Err(err);
+        }
+
+        let holds = self
+            .update_account_state_port
+            .update_holds(&source_account)
+            .await;
+
+        self.account_lock.release_account(&source_account_id);
+
+        holds?
+            .into_iter()
+            .filter_map(|hold| hold.id)
+            .last()
+            .ok_or_else(|| anyhow!("expected authorized hold to be persisted"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::AuthorizeTransferService;
+    use crate::application::port::incoming::authorize_transfer_use_case::{
+        AuthorizeTransferCommand, AuthorizeTransferUseCase,
+    };
+    use crate::application::port::outgoing::{
+        load_account_port::LoadAccountPort, update_account_state_port::UpdateAccountStatePort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::application::service::money_transfer_properties::MoneyTransferProperties;
+    use crate::application::service::no_op_account_lock::NoOpAccountLock;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::Activity;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::hold::{Hold, HoldId, HoldStatus};
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn authorization_reserves_money_until_the_hold_expires() {
+        let ledger = MockHoldLedger::default();
+        let service = given_a_service(&ledger);
+
+        let command =
+            AuthorizeTransferCommand::new(AccountId(42), AccountId(41), money!(300, "AUD"))
+                .with_initiator(default_principal());
+
+        let hold_id = service.authorize_transfer(&command).await.unwrap();
+
+        let holds = ledger.holds();
+        assert_eq!(hold_id, HoldId(1));
+        assert_eq!(holds.len(), 1);
+        assert_eq!(holds[0].status, HoldStatus::Active);
+        assert_eq!(holds[0].money, money!(300, "AUD"));
+        assert_eq!(
+            holds[0].expires_at - holds[0].created_at,
+            Duration::hours(1)
+        );
+    }
+
+    #[async_std::test]
+    async fn authorization_fails_when_held_money_leaves_too_little() {
+        let ledger = MockHoldLedger::default();
+        let service = given_a_service(&ledger);
+
+        let command =
+            AuthorizeTransferCommand::new(AccountId(42), AccountId(41), money!(300, "AUD"))
+                .with_initiator(default_principal());
+        service.authorize_transfer(&command).await.unwrap();
+
+        let success = service.authorize_transfer(&command).await.is_ok();
+
+        assert_eq!(success, false);
+        assert_eq!(ledger.holds().len(), 1);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_may_not_place_a_hold() {
+        let ledger = MockHoldLedger::default();
+        let service = given_a_service(&ledger);
+
+        let command =
+            AuthorizeTransferCommand::new(AccountId(42), AccountId(41), money!(300, "AUD"))
+                .with_initiator(Principal::User(String::from("mallory")));
+
+        let err = service.authorize_transfer(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+        assert!(ledger.holds().is_empty());
+    }
+
+    fn given_a_service(ledger: &MockHoldLedger) -> AuthorizeTransferService {
+        AuthorizeTransferService::new(
+            Box::new(ledger.clone()),
+            Box::new(NoOpAccountLock::default()),
+            Box::new(ledger.clone()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::new().with_hold_expiry(Duration::hours(1)),
+        )
+    }
+
+    /// Loads every account with a balance of 500 AUD and the holds placed on it so far.
+    #[derive(Debug, Default, Clone)]
+    struct MockHoldLedger {
+        holds: Arc<Mutex<Vec<Hold>>>,
+    }
+
+    impl MockHoldLedger {
+        fn holds(&self) -> Vec<Hold> {
+            self.holds.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockHoldLedger {
+        async fn load_account(
+            &self,
+            account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            let holds: Vec<Hold> = self
+                .holds()
+                .into_iter()
+                .filter(|hold| hold.account_id == *account_id)
+                .collect();
+
+            Ok(AccountBuilder::default_account()
+                .with_account_id(account_id)
+                .with_baseline_balance(&money!(500, "AUD"))
+                .with_activity_window(&ActivityWindow::new(vec![]))
+                .with_holds(&holds)
+                .build())
+        }
+    }
+
+    #[async_trait]
+    impl UpdateAccountStatePort for MockHoldLedger {
+        async fn update_activities(&self, _account: &Account) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>> {
+            let mut holds = self.holds.lock().unwrap();
+            let mut created = vec![];
+            for hold in account.holds.iter().filter(|hold| hold.id.is_none()) {
+                let mut hold = hold.clone();
+                hold.id = Some(HoldId(holds.len() as i32 + 1));
+                holds.push(hold.clone());
+                created.push(hold);
+            }
+
+            Ok(created)
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::application::port::incoming::balance_history_query::{
+    BalanceHistoryQuery, DailyBalance,
+};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_account_port::LoadAccountPort,
+};
+use crate::application::service::account_access::check_account_access;
+use crate::application::service::error::ServiceError;
+use crate::domain::account::{Account, AccountId};
+use crate::domain::activity::Activity;
+use crate::domain::principal::Principal;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// The longest range of days that can be requested at once.
+const MAXIMUM_HISTORY_DAYS: i64 = 366;
+
+pub struct BalanceHistoryService {
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl BalanceHistoryService {
+    pub fn new(
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl BalanceHistoryQuery for BalanceHistoryService {
+    async fn get_balance_history(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+        principal: &Principal,
+    ) -> Result<Vec<DailyBalance>> {
+        self.check_range(from, to)?;
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
+
+        // the baseline balance is the opening balance of the first day, the activity window
+        // holds everything that happened since
+        let account = self
+            .load_account_port
+            .load_account(account_id, &start_of_day(from))
+            .await?;
+
+        Ok(self.calculate_closing_balances(&account, from, to))
+    }
+}
+
+impl BalanceHistoryService {
+    fn check_range(&self, from: &NaiveDate, to: &NaiveDate) -> Result<()> {
+        let days = (*to - *from).num_days() + 1;
+
+        if days < 1 {
+            return Err(anyhow!(ServiceError::InvalidDateRange(String::from(
+                "`from` must not be after `to`"
+            ))));
+        }
+
+        if days > MAXIMUM_HISTORY_DAYS {
+            return Err(anyhow!(ServiceError::InvalidDateRange(format!(
+                "at most {} days can be requested at once",
+                MAXIMUM_HISTORY_DAYS
+            ))));
+        }
+
+        Ok(())
+    }
+
+    /// Walks through the activities once in chronological order, closing a day whenever the
+    /// next activity belongs to a later one.
+    fn calculate_closing_balances(
+        &self,
+        account: &Account,
+        from: &NaiveDate,
+        to: &NaiveDate,
+    ) -> Vec<DailyBalance> {
+        let account_id = match account.id.clone() {
+            Some(account_id) => account_id,
+            None => return vec![],
+        };
+
+        let mut activities: Vec<&Activity> = account.activity_window.activities.iter().collect();
+        activities.sort_by_key(|activity| activity.timestamp);
+        let mut activities = activities.into_iter().peekable();
+
+        let mut balance = account.baseline_balance.clone();
+        let mut balances = vec![];
+        let mut date = *from;
+
+        while date <= *to {
+            let end_of_day = start_of_day(&date.succ());
+
+            while let Some(activity) = activities.peek() {
+                if activity.timestamp >= end_of_day {
+                    break;
+                }
+
+                if activity.target_account_id == account_id {
+                    balance += activity.money.clone();
+                }
+                if activity.source_account_id == account_id {
+                    balance -= activity.money.clone();
+                }
+                activities.next();
+            }
+
+            balances.push(DailyBalance {
+                date,
+                closing_balance: balance.clone(),
+            });
+            date = date.succ();
+        }
+
+        balances
+    }
+}
+
+fn start_of_day(date: &NaiveDate) -> DateTime<Utc> {
+    DateTime::<Utc>::from_utc(date.and_hms(0, 0, 0), Utc)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BalanceHistoryService;
+    use crate::application::port::incoming::balance_history_query::BalanceHistoryQuery;
+    use crate::application::port::outgoing::load_account_port::LoadAccountPort;
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 3, day).and_hms(hour, 0, 0), Utc)
+    }
+
+    #[async_std::test]
+    async fn calculates_closing_balance_of_every_day() {
+        let account_id = AccountId(42);
+        let activity_window = ActivityWindow::new(vec![
+            ActivityBuilder::default_activity()
+                .with_source_account(&AccountId(41))
+                .with_target_account(&account_id)
+                .with_timestamp(&at(3, 10))
+                .with_money(&money!(50, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_source_account(&account_id)
+                .with_target_account(&AccountId(41))
+                .with_timestamp(&at(1, 23))
+                .with_money(&money!(30, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_source_account(&AccountId(41))
+                .with_target_account(&account_id)
+                .with_timestamp(&at(1, 9))
+                .with_money(&money!(20, "AUD"))
+                .build(),
+        ]);
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(100, "AUD"))
+            .with_activity_window(&activity_window)
+            .build();
+
+        let service = BalanceHistoryService::new(
+            Box::new(MockLoadAccountPort { account }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let balances = service
+            .get_balance_history(
+                &account_id,
+                &NaiveDate::from_ymd(2021, 3, 1),
+                &NaiveDate::from_ymd(2021, 3, 3),
+                &default_principal(),
+            )
+            .await
+            .unwrap();
+
+        let closing_balances: Vec<Money> = balances
+            .into_iter()
+            .map(|balance| balance.closing_balance)
+            .collect();
+        assert_eq!(
+            closing_balances,
+            vec![money!(90, "AUD"), money!(90, "AUD"), money!(140, "AUD")]
+        );
+    }
+
+    #[async_std::test]
+    async fn rejects_inverted_range() {
+        let account = AccountBuilder::default_account().build();
+        let service = BalanceHistoryService::new(
+            Box::new(MockLoadAccountPort { account }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let success = service
+            .get_balance_history(
+                &AccountId(42),
+                &NaiveDate::from_ymd(2021, 3, 3),
+                &NaiveDate::from_ymd(2021, 3, 1),
+                &default_principal(),
+            )
+            .await
+            .is_ok();
+
+        assert_eq!(success, false);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let account = AccountBuilder::default_account().build();
+        let service = BalanceHistoryService::new(
+            Box::new(MockLoadAccountPort { account }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let err = service
+            .get_balance_history(
+                &AccountId(42),
+                &NaiveDate::from_ymd(2021, 3, 1),
+                &NaiveDate::from_ymd(2021, 3, 3),
+                &Principal::User(String::from("mallory")),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+    }
+
+    struct MockLoadAccountPort {
+        account: Account,
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockLoadAccountPort {
+        async fn load_account(
+            &self,
+            _account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            Ok(self.account.clone())
+        }
+    }
+}
@@ -0,0 +1,342 @@
+use crate::application::port::incoming::batch_send_money_use_case::{
+    BatchLineOutcome, BatchLineResult, BatchMode, BatchSendMoneyCommand, BatchSendMoneyReport,
+    BatchSendMoneyUseCase,
+};
+use crate::application::port::incoming::reverse_transfer_use_case::{
+    ReverseTransferCommand, ReverseTransferUseCase,
+};
+use crate::application::port::incoming::send_money_use_case::{SendMoneyCommand, SendMoneyUseCase};
+use crate::application::service::error::ServiceError;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+const MAXIMUM_BATCH_SIZE: usize = 1000;
+
+/// Sends the transfers of a batch one after the other. Reverse-on-failure batches are rolled
+/// back with compensating reversals of the transfers sent before the one that failed, not in a
+/// single transaction.
+pub struct BatchSendMoneyService {
+    send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+    reverse_transfer_use_case: Box<dyn ReverseTransferUseCase + Send + Sync>,
+}
+
+impl BatchSendMoneyService {
+    pub fn new(
+        send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+        reverse_transfer_use_case: Box<dyn ReverseTransferUseCase + Send + Sync>,
+    ) -> Self {
+        Self {
+            send_money_use_case,
+            reverse_transfer_use_case,
+        }
+    }
+}
+
+#[async_trait]
+impl BatchSendMoneyUseCase for BatchSendMoneyService {
+    async fn batch_send_money(
+        &self,
+        command: &BatchSendMoneyCommand,
+    ) -> Result<BatchSendMoneyReport> {
+        if command.commands.is_empty() || command.commands.len() > MAXIMUM_BATCH_SIZE {
+            return Err(anyhow!(ServiceError::InvalidBatchSize {
+                requested: command.commands.len(),
+                maximum: MAXIMUM_BATCH_SIZE,
+            }));
+        }
+
+        let invalid: Vec<Option<String>> = command.commands.iter().map(validate).collect();
+        if invalid.iter().any(Option::is_some) {
+            let lines = invalid
+                .into_iter()
+                .enumerate()
+                .map(|(index, reason)| BatchLineResult {
+                    line: index + 1,
+                    outcome: reason
+                        .map(BatchLineOutcome::Failed)
+                        .unwrap_or(BatchLineOutcome::NotExecuted),
+                })
+                .collect();
+
+            return Ok(BatchSendMoneyReport {
+                mode: command.mode,
+                valid: false,
+                lines,
+            });
+        }
+
+        let mut lines = vec![];
+        let mut aborted = false;
+
+        for (index, send_money_command) in command.commands.iter().enumerate() {
+            let outcome = if aborted {
+                BatchLineOutcome::NotExecuted
+            } else {
+                match self
+                    .send_money_use_case
+                    .send_money(send_money_command)
+                    .await
+                {
+                    Ok(transfer_id) => BatchLineOutcome::Sent(transfer_id),
+                    Err(err) => {
+                        aborted = command.mode == BatchMode::ReverseOnFailure;
+                        BatchLineOutcome::Failed(err.to_string())
+                    }
+                }
+            };
+
+            lines.push(BatchLineResult {
+                line: index + 1,
+                outcome,
+            });
+        }
+
+        if aborted {
+            self.roll_back(&command.commands, &mut lines).await;
+        }
+
+        Ok(BatchSendMoneyReport {
+            mode: command.mode,
+            valid: true,
+            lines,
+        })
+    }
+}
+
+impl BatchSendMoneyService {
+    /// Reverses every sent transfer, newest first, on behalf of whoever sent it.
+    async fn roll_back(&self, commands: &[SendMoneyCommand], lines: &mut [BatchLineResult]) {
+        for line in lines.iter_mut().rev() {
+            let transfer_id = match &line.outcome {
+                BatchLineOutcome::Sent(transfer_id) => transfer_id.clone(),
+                _ => continue,
+            };
+
+            let reversal = self
+                .reverse_transfer_use_case
+                .reverse_transfer(&ReverseTransferCommand {
+                    initiator: commands[line.line - 1].initiator.clone(),
+                    ..ReverseTransferCommand::new(transfer_id.clone(), None)
+                })
+                .await;
+
+            line.outcome = match reversal {
+                Ok(reversal_id) => BatchLineOutcome::Reversed {
+                    transfer_id,
+                    reversal_id,
+                },
+                Err(err) => BatchLineOutcome::ReversalFailed {
+                    transfer_id,
+                    reason: err.to_string(),
+                },
+            };
+        }
+    }
+}
+
+fn validate(command: &SendMoneyCommand) -> Option<String> {
+    if command.target.is_account(&command.source_account_id) {
+        return Some(String::from("Source and target account must differ"));
+    }
+    if !command.money.is_positive() {
+        return Some(String::from("Amount must be positive"));
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::BatchSendMoneyService;
+    use crate::application::port::incoming::batch_send_money_use_case::{
+        BatchLineOutcome, BatchMode, BatchSendMoneyCommand, BatchSendMoneyUseCase,
+    };
+    use crate::application::port::incoming::reverse_transfer_use_case::{
+        ReverseTransferCommand, ReverseTransferUseCase,
+    };
+    use crate::application::port::incoming::send_money_use_case::{
+        SendMoneyCommand, SendMoneyUseCase,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn reverse_on_failure_batch_is_rolled_back_on_failure() {
+        let transfers = MockTransfers::default();
+        let service = given_a_service(&transfers);
+
+        let report = service
+            .batch_send_money(&BatchSendMoneyCommand::new(
+                given_commands(&[300, 2000, 100]),
+                BatchMode::ReverseOnFailure,
+            ))
+            .await
+            .unwrap();
+
+        assert!(report.valid);
+        assert!(!report.has_failed_reversals());
+        assert_eq!(
+            report.lines[0].outcome,
+            BatchLineOutcome::Reversed {
+                transfer_id: TransferId(1),
+                reversal_id: TransferId(101),
+            }
+        );
+        assert!(matches!(
+            report.lines[1].outcome,
+            BatchLineOutcome::Failed(_)
+        ));
+        assert_eq!(report.lines[2].outcome, BatchLineOutcome::NotExecuted);
+        assert_eq!(transfers.sent(), 2);
+    }
+
+    #[async_std::test]
+    async fn failed_reversal_is_reported() {
+        let transfers = MockTransfers {
+            failing_reversals: true,
+            ..MockTransfers::default()
+        };
+        let service = given_a_service(&transfers);
+
+        let report = service
+            .batch_send_money(&BatchSendMoneyCommand::new(
+                given_commands(&[300, 100, 2000]),
+                BatchMode::ReverseOnFailure,
+            ))
+            .await
+            .unwrap();
+
+        assert!(report.has_failed_reversals());
+        assert!(matches!(
+            report.lines[0].outcome,
+            BatchLineOutcome::ReversalFailed {
+                transfer_id: TransferId(1),
+                ..
+            }
+        ));
+        assert!(matches!(
+            report.lines[1].outcome,
+            BatchLineOutcome::ReversalFailed {
+                transfer_id: TransferId(2),
+                ..
+            }
+        ));
+    }
+
+    #[async_std::test]
+    async fn best_effort_batch_continues_after_failure() {
+        let transfers = MockTransfers::default();
+        let service = given_a_service(&transfers);
+
+        let report = service
+            .batch_send_money(&BatchSendMoneyCommand::new(
+                given_commands(&[300, 2000, 100]),
+                BatchMode::BestEffort,
+            ))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report.lines[0].outcome,
+            BatchLineOutcome::Sent(TransferId(1))
+        );
+        assert!(matches!(
+            report.lines[1].outcome,
+            BatchLineOutcome::Failed(_)
+        ));
+        assert_eq!(
+            report.lines[2].outcome,
+            BatchLineOutcome::Sent(TransferId(3))
+        );
+        assert_eq!(transfers.reversed(), 0);
+    }
+
+    #[async_std::test]
+    async fn invalid_line_prevents_any_transfer() {
+        let transfers = MockTransfers::default();
+        let service = given_a_service(&transfers);
+
+        let mut commands = given_commands(&[300, 100]);
+        commands.push(SendMoneyCommand::new(
+            AccountId(41),
+            AccountId(41),
+            money!(100, "AUD"),
+        ));
+
+        let report = service
+            .batch_send_money(&BatchSendMoneyCommand::new(commands, BatchMode::BestEffort))
+            .await
+            .unwrap();
+
+        assert!(!report.valid);
+        assert_eq!(report.lines[0].outcome, BatchLineOutcome::NotExecuted);
+        assert!(matches!(
+            report.lines[2].outcome,
+            BatchLineOutcome::Failed(_)
+        ));
+        assert_eq!(transfers.sent(), 0);
+    }
+
+    fn given_a_service(transfers: &MockTransfers) -> BatchSendMoneyService {
+        BatchSendMoneyService::new(Box::new(transfers.clone()), Box::new(transfers.clone()))
+    }
+
+    fn given_commands(amounts: &[i64]) -> Vec<SendMoneyCommand> {
+        amounts
+            .iter()
+            .map(|amount| {
+                SendMoneyCommand::new(AccountId(41), AccountId(42), money!(*amount, "AUD"))
+            })
+            .collect()
+    }
+
+    /// Sends anything up to 1000, reversals get IDs counting up from 101 unless they fail.
+    #[derive(Debug, Default, Clone)]
+    struct MockTransfers {
+        sent: Arc<Mutex<i32>>,
+        reversed: Arc<Mutex<i32>>,
+        failing_reversals: bool,
+    }
+
+    impl MockTransfers {
+        fn sent(&self) -> i32 {
+            *self.sent.lock().unwrap()
+        }
+
+        fn reversed(&self) -> i32 {
+            *self.reversed.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl SendMoneyUseCase for MockTransfers {
+        async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId> {
+            let mut sent = self.sent.lock().unwrap();
+            *sent += 1;
+
+            if command.money > money!(1000, "AUD") {
+                return Err(anyhow!("Insufficient funds"));
+            }
+
+            Ok(TransferId(*sent))
+        }
+    }
+
+    #[async_trait]
+    impl ReverseTransferUseCase for MockTransfers {
+        async fn reverse_transfer(&self, _command: &ReverseTransferCommand) -> Result<TransferId> {
+            if self.failing_reversals {
+                return Err(anyhow!("Reversal failed"));
+            }
+
+            let mut reversed = self.reversed.lock().unwrap();
+            *reversed += 1;
+
+            Ok(TransferId(100 + *reversed))
+        }
+    }
+}
@@ -0,0 +1,455 @@
+use crate::application::port::incoming::capture_transfer_use_case::{
+    CaptureTransferCommand, CaptureTransferUseCase,
+};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, account_lock::AccountLock,
+    domain_event_publisher::DomainEventPublisher, load_account_port::LoadAccountPort,
+    load_hold_port::LoadHoldPort, record_transfer_port::RecordTransferPort,
+    update_account_state_port::UpdateAccountStatePort,
+    update_transfer_state_port::UpdateTransferStatePort,
+};
+use crate::application::service::account_access::{check_account_access, initiator};
+use crate::domain::account::{Account, AccountError};
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct CaptureTransferService {
+    load_hold_port: Box<dyn LoadHoldPort + Send + Sync>,
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    account_lock: Box<dyn AccountLock + Send + Sync>,
+    update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+    record_transfer_port: Box<dyn RecordTransferPort + Send + Sync>,
+    update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+    domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl CaptureTransferService {
+    pub fn new(
+        load_hold_port: Box<dyn LoadHoldPort + Send + Sync>,
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        account_lock: Box<dyn AccountLock + Send + Sync>,
+        update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+        record_transfer_port: Box<dyn RecordTransferPort + Send + Sync>,
+        update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+        domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_hold_port,
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            record_transfer_port,
+            update_transfer_state_port,
+            domain_event_publisher,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl CaptureTransferUseCase for CaptureTransferService {
+    async fn capture_transfer(&self, command: &CaptureTransferCommand) -> Result<TransferId> {
+        use chrono::{Duration, Utc};
+
+        let hold = self.load_hold_port.load_hold(&command.hold_id).await?;
+
+        // capturing debits the account the money is held on
+        check_account_access(
+            self.account_access_port.as_ref(),
+            &hold.account_id,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
+        let money = command.money.clone().unwrap_or_else(|| hold.money.clone());
+
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        let mut source_account = self
+            .load_account_port
+            .load_account(&hold.account_id, &baseline_date)
+            .await?;
+
+        let mut target_account = self
+            .load_account_port
+            .load_account(&hold.target_account_id, &baseline_date)
+            .await?;
+
+        let source_account_id = hold.account_id.clone();
+        let target_account_id = hold.target_account_id.clone();
+
+        // nothing is persisted until both legs are known, the legs only need the id
+        let transfer_id = self.record_transfer_port.reserve_transfer_id().await?;
+        let mut transfer = Transfer::new_without_id(
+            source_account_id.clone(),
+            target_account_id.clone(),
+            Utc::now(),
+            money.clone(),
+        );
+
+        self.account_lock.lock_account(&source_account_id);
+        if let Err(err) = source_account.capture_hold(
+            &command.hold_id,
+            &money,
+            &transfer_id,
+            command.initiator.as_ref(),
+        ) {
+            if let Some(AccountError::HoldExpired(_)) = err.downcast_ref::<AccountError>() {
+                self.expire_hold(&source_account).await;
+            }
+            self.account_lock.release_account(&source_account_id);
+            self.fail_transfer(&mut transfer).await;
+            return Err(err);
+        }
+
+        self.account_lock.lock_account(&target_account_id);
+        if let Err(err) = target_account.deposit(
+            &money,
+            &source_account_id,
+            &transfer_id,
+            command.initiator.as_ref(),
+        ) {
+            self.account_lock.release_account(&source_account_id);
+            self.account_lock.release_account(&target_account_id);
+            self.fail_transfer(&mut transfer).await;
+            return Err(err);
+        }
+
+        // the hold is claimed in the same transaction as the legs, of two concurrent captures
+        // of it only one moves any money
+        transfer.id = Some(transfer_id.clone());
+        transfer.complete();
+        let recorded = self
+            .record_transfer_port
+            .record_transfer(&transfer, &source_account, &target_account)
+            .await;
+
+        self.account_lock.release_account(&source_account_id);
+        self.account_lock.release_account(&target_account_id);
+
+        recorded?;
+
+        let mut events = source_account.take_events();
+        events.append(&mut target_account.take_events());
+        events.append(&mut transfer.take_events());
+
+        // the transfer is committed at this point, a failing subscriber must not report it as
+        // failed to the client
+        if let Err(err) = self.domain_event_publisher.publish(&events).await {
+            log::error!(
+                "Publishing the events of transfer {} failed: {}",
+                transfer_id.0,
+                err
+            );
+        }
+
+        Ok(transfer_id)
+    }
+}
+
+impl CaptureTransferService {
+    /// Persists a hold found to have run out, so that it stops reserving money for good.
+    async fn expire_hold(&self, source_account: &Account) {
+        if let Err(err) = self
+            .update_account_state_port
+            .update_holds(source_account)
+            .await
+        {
+            log::error!("Marking expired hold as expired failed: {}", err);
+        }
+    }
+
+    /// Records the transfer as failed. The caller is told why the transfer failed, a transfer
+    /// that can't be recorded as well is only logged.
+    async fn fail_transfer(&self, transfer: &mut Transfer) {
+        transfer.fail();
+        if let Err(err) = self
+            .update_transfer_state_port
+            .create_transfer(transfer)
+            .await
+        {
+            log::error!(
+                "Recording the failed transfer from account {} failed: {}",
+                transfer.source_account_id.0,
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CaptureTransferService;
+    use crate::application::port::incoming::capture_transfer_use_case::{
+        CaptureTransferCommand, CaptureTransferUseCase,
+    };
+    use crate::application::port::outgoing::{
+        load_account_port::LoadAccountPort, load_hold_port::LoadHoldPort,
+        record_transfer_port::RecordTransferPort,
+        update_account_state_port::UpdateAccountStatePort,
+        update_transfer_state_port::UpdateTransferStatePort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::application::service::no_op_account_lock::NoOpAccountLock;
+    use crate::application::service::no_op_domain_event_publisher::NoOpDomainEventPublisher;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::Activity;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::hold::hold_test_data::HoldBuilder;
+    use crate::domain::hold::{Hold, HoldId, HoldStatus};
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::{Transfer, TransferId, TransferStatus};
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn capture_settles_part_of_the_hold() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&ledger, &transfer_state);
+
+        let command = CaptureTransferCommand::new(HoldId(3), Some(money!(60, "AUD")))
+            .with_initiator(default_principal());
+
+        let transfer_id = service.capture_transfer(&command).await.unwrap();
+
+        assert_eq!(transfer_id, TransferId(1));
+        assert_eq!(transfer_state.last_status(), None);
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Captured);
+        assert_eq!(
+            ledger.updated_accounts(),
+            vec![AccountId(42), AccountId(41)]
+        );
+    }
+
+    #[async_std::test]
+    async fn captured_hold_can_not_be_captured_again() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&ledger, &transfer_state);
+
+        let command =
+            CaptureTransferCommand::new(HoldId(3), None).with_initiator(default_principal());
+        service.capture_transfer(&command).await.unwrap();
+
+        let success = service.capture_transfer(&command).await.is_ok();
+
+        assert_eq!(success, false);
+        assert_eq!(transfer_state.last_status(), Some(TransferStatus::Failed));
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Captured);
+        assert_eq!(ledger.updated_accounts().len(), 2);
+    }
+
+    #[async_std::test]
+    async fn capture_of_an_expired_hold_marks_it_as_expired() {
+        let ledger = MockHoldLedger::with_hold(
+            HoldBuilder::default_hold()
+                .with_expires_at(&(Utc::now() - Duration::minutes(1)))
+                .build(),
+        );
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&ledger, &transfer_state);
+
+        let command =
+            CaptureTransferCommand::new(HoldId(3), None).with_initiator(default_principal());
+
+        let err = service.capture_transfer(&command).await.unwrap_err();
+
+        assert_eq!(err.to_string(), "Hold `3` has expired");
+        assert_eq!(transfer_state.last_status(), Some(TransferStatus::Failed));
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Expired);
+        assert!(ledger.updated_accounts().is_empty());
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_may_not_capture_the_hold() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&ledger, &transfer_state);
+
+        let command = CaptureTransferCommand::new(HoldId(3), None)
+            .with_initiator(Principal::User(String::from("mallory")));
+
+        let err = service.capture_transfer(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { .. })
+        ));
+        assert_eq!(transfer_state.last_status(), None);
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Active);
+        assert!(ledger.updated_accounts().is_empty());
+    }
+
+    fn given_a_service(
+        ledger: &MockHoldLedger,
+        transfer_state: &MockTransferState,
+    ) -> CaptureTransferService {
+        CaptureTransferService::new(
+            Box::new(ledger.clone()),
+            Box::new(ledger.clone()),
+            Box::new(NoOpAccountLock::default()),
+            Box::new(ledger.clone()),
+            Box::new(ledger.clone()),
+            Box::new(transfer_state.clone()),
+            Box::new(NoOpDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        )
+    }
+
+    /// Keeps holds like the persistence adapter does: accounts are loaded with their active holds
+    /// only, and only an active hold may change its status.
+    #[derive(Debug, Clone)]
+    struct MockHoldLedger {
+        holds: Arc<Mutex<Vec<Hold>>>,
+        updated_accounts: Arc<Mutex<Vec<AccountId>>>,
+    }
+
+    impl MockHoldLedger {
+        fn with_hold(hold: Hold) -> Self {
+            Self {
+                holds: Arc::new(Mutex::new(vec![hold])),
+                updated_accounts: Arc::new(Mutex::new(vec![])),
+            }
+        }
+
+        fn hold_status(&self, hold_id: &HoldId) -> HoldStatus {
+            self.holds
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|hold| hold.id.as_ref() == Some(hold_id))
+                .map(|hold| hold.status)
+                .unwrap()
+        }
+
+        fn updated_accounts(&self) -> Vec<AccountId> {
+            self.updated_accounts.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl LoadHoldPort for MockHoldLedger {
+        async fn load_hold(&self, hold_id: &HoldId) -> Result<Hold> {
+            self.holds
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|hold| hold.id.as_ref() == Some(hold_id))
+                .cloned()
+                .ok_or_else(|| anyhow!("No matching hold found from stub"))
+        }
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockHoldLedger {
+        async fn load_account(
+            &self,
+            account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            let holds: Vec<Hold> = self
+                .holds
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|hold| hold.account_id == *account_id && hold.status == HoldStatus::Active)
+                .cloned()
+                .collect();
+
+            Ok(AccountBuilder::default_account()
+                .with_account_id(account_id)
+                .with_baseline_balance(&money!(500, "AUD"))
+                .with_activity_window(&ActivityWindow::new(vec![]))
+                .with_holds(&holds)
+                .build())
+        }
+    }
+
+    #[async_trait]
+    impl UpdateAccountStatePort for MockHoldLedger {
+        async fn update_activities(&self, account: &Account) -> Result<Vec<Activity>> {
+            self.updated_accounts
+                .lock()
+                .unwrap()
+                .push(account.id.clone().unwrap());
+
+            Ok(vec![])
+        }
+
+        async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>> {
+            let mut holds = self.holds.lock().unwrap();
+            for hold in account
+                .holds
+                .iter()
+                .filter(|hold| hold.status != HoldStatus::Active)
+            {
+                let stored = holds
+                    .iter_mut()
+                    .find(|stored| stored.id == hold.id && stored.status == HoldStatus::Active)
+                    .ok_or_else(|| anyhow!("Hold is no longer active"))?;
+                *stored = hold.clone();
+            }
+
+            Ok(vec![])
+        }
+    }
+
+    #[async_trait]
+    impl RecordTransferPort for MockHoldLedger {
+        async fn reserve_transfer_id(&self) -> Result<TransferId> {
+            Ok(TransferId(1))
+        }
+
+        async fn record_transfer(
+            &self,
+            transfer: &Transfer,
+            source_account: &Account,
+            target_account: &Account,
+        ) -> Result<Transfer> {
+            self.update_holds(source_account).await?;
+            self.update_activities(source_account).await?;
+            self.update_activities(target_account).await?;
+
+            Ok(transfer.clone())
+        }
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockTransferState {
+        statuses: Arc<Mutex<Vec<TransferStatus>>>,
+    }
+
+    impl MockTransferState {
+        fn last_status(&self) -> Option<TransferStatus> {
+            self.statuses.lock().unwrap().last().cloned()
+        }
+    }
+
+    #[async_trait]
+    impl UpdateTransferStatePort for MockTransferState {
+        async fn create_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            let mut statuses = self.statuses.lock().unwrap();
+            statuses.push(transfer.status);
+
+            let mut transfer = transfer.clone();
+            transfer.id = Some(TransferId(statuses.len() as i32));
+            Ok(transfer)
+        }
+
+        async fn update_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            self.statuses.lock().unwrap().push(transfer.status);
+
+            Ok(transfer.clone())
+        }
+    }
+}
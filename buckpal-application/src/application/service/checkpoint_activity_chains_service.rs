@@ -0,0 +1,152 @@
+use crate::application::port::incoming::checkpoint_activity_chains_use_case::CheckpointActivityChainsUseCase;
+use crate::application::port::outgoing::{
+    activity_chain_port::ActivityChainPort, chain_checkpoint_signer::ChainCheckpointSigner,
+};
+use crate::domain::activity_chain::{digest_heads, ChainCheckpoint};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct CheckpointActivityChainsService {
+    activity_chain_port: Box<dyn ActivityChainPort + Send + Sync>,
+    chain_checkpoint_signer: Box<dyn ChainCheckpointSigner + Send + Sync>,
+}
+
+impl CheckpointActivityChainsService {
+    pub fn new(
+        activity_chain_port: Box<dyn ActivityChainPort + Send + Sync>,
+        chain_checkpoint_signer: Box<dyn ChainCheckpointSigner + Send + Sync>,
+    ) -> Self {
+        Self {
+            activity_chain_port,
+            chain_checkpoint_signer,
+        }
+    }
+}
+
+#[async_trait]
+impl CheckpointActivityChainsUseCase for CheckpointActivityChainsService {
+    async fn checkpoint_activity_chains(&self) -> Result<ChainCheckpoint> {
+        let heads = self.activity_chain_port.load_chain_heads().await?;
+        let digest = digest_heads(&heads);
+
+        if let Some(latest_checkpoint) = self.activity_chain_port.load_latest_checkpoint().await? {
+            if latest_checkpoint.digest == digest {
+                return Ok(latest_checkpoint);
+            }
+        }
+
+        let signature = self.chain_checkpoint_signer.sign(&digest);
+
+        self.activity_chain_port
+            .save_checkpoint(&ChainCheckpoint::new(heads, &signature, Utc::now()))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CheckpointActivityChainsService;
+    use crate::application::port::incoming::checkpoint_activity_chains_use_case::CheckpointActivityChainsUseCase;
+    use crate::application::port::outgoing::{
+        activity_chain_port::ActivityChainPort, chain_checkpoint_signer::ChainCheckpointSigner,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::ActivityId;
+    use crate::domain::activity_chain::{
+        digest_heads, ChainCheckpoint, ChainCheckpointId, ChainHead, ChainedActivity,
+    };
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use std::sync::Mutex;
+
+    #[async_std::test]
+    async fn signs_and_saves_heads() {
+        let heads = vec![ChainHead::new(AccountId(42), ActivityId(7), "abc")];
+        let port = MockActivityChainPort::new(heads.clone(), None);
+        let service = CheckpointActivityChainsService::new(Box::new(port), Box::new(MockSigner {}));
+
+        let checkpoint = service.checkpoint_activity_chains().await.unwrap();
+
+        assert_eq!(checkpoint.id, Some(ChainCheckpointId(1)));
+        assert_eq!(checkpoint.heads, heads);
+        assert_eq!(checkpoint.digest, digest_heads(&heads));
+        assert_eq!(
+            checkpoint.signature,
+            format!("signed:{}", checkpoint.digest)
+        );
+    }
+
+    #[async_std::test]
+    async fn keeps_latest_checkpoint_while_heads_dont_move() {
+        let heads = vec![ChainHead::new(AccountId(42), ActivityId(7), "abc")];
+        let mut latest_checkpoint = ChainCheckpoint::new(heads.clone(), "signed", Utc::now());
+        latest_checkpoint.id = Some(ChainCheckpointId(5));
+        let port = MockActivityChainPort::new(heads, Some(latest_checkpoint.clone()));
+        let service = CheckpointActivityChainsService::new(Box::new(port), Box::new(MockSigner {}));
+
+        let checkpoint = service.checkpoint_activity_chains().await.unwrap();
+
+        assert_eq!(checkpoint, latest_checkpoint);
+    }
+
+    struct MockSigner {}
+
+    impl ChainCheckpointSigner for MockSigner {
+        fn sign(&self, digest: &str) -> String {
+            format!("signed:{}", digest)
+        }
+
+        fn verify(&self, digest: &str, signature: &str) -> bool {
+            self.sign(digest) == signature
+        }
+    }
+
+    struct MockActivityChainPort {
+        heads: Vec<ChainHead>,
+        latest_checkpoint: Option<ChainCheckpoint>,
+        saved: Mutex<Vec<ChainCheckpoint>>,
+    }
+
+    impl MockActivityChainPort {
+        fn new(heads: Vec<ChainHead>, latest_checkpoint: Option<ChainCheckpoint>) -> Self {
+            Self {
+                heads,
+                latest_checkpoint,
+                saved: Mutex::new(vec![]),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl ActivityChainPort for MockActivityChainPort {
+        async fn load_chained_activities(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Vec<ChainedActivity>> {
+            Ok(vec![])
+        }
+
+        async fn load_chain_head(&self, _account_id: &AccountId) -> Result<Option<ChainHead>> {
+            Ok(None)
+        }
+
+        async fn load_chain_heads(&self) -> Result<Vec<ChainHead>> {
+            Ok(self.heads.clone())
+        }
+
+        async fn load_latest_checkpoint(&self) -> Result<Option<ChainCheckpoint>> {
+            Ok(self.latest_checkpoint.clone())
+        }
+
+        async fn save_checkpoint(&self, checkpoint: &ChainCheckpoint) -> Result<ChainCheckpoint> {
+            let mut saved = self.saved.lock().unwrap();
+            let mut checkpoint = checkpoint.clone();
+            checkpoint.id = Some(ChainCheckpointId(saved.len() as i32 + 1));
+            saved.push(checkpoint.clone());
+
+            Ok(checkpoint)
+        }
+    }
+}
@@ -0,0 +1,312 @@
+use crate::application::port::incoming::deliver_webhooks_use_case::DeliverWebhooksUseCase;
+use crate::application::port::outgoing::{
+    webhook_port::WebhookPort, webhook_sender::WebhookSender,
+};
+use crate::application::service::error::ServiceError;
+use crate::application::service::webhook_properties::WebhookProperties;
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookDeliveryId};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct DeliverWebhooksService {
+    webhook_port: Box<dyn WebhookPort + Send + Sync>,
+    webhook_sender: Box<dyn WebhookSender + Send + Sync>,
+    webhook_properties: WebhookProperties,
+}
+
+impl DeliverWebhooksService {
+    pub fn new(
+        webhook_port: Box<dyn WebhookPort + Send + Sync>,
+        webhook_sender: Box<dyn WebhookSender + Send + Sync>,
+        webhook_properties: WebhookProperties,
+    ) -> Self {
+        Self {
+            webhook_port,
+            webhook_sender,
+            webhook_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl DeliverWebhooksUseCase for DeliverWebhooksService {
+    async fn deliver_due_webhooks(&self) -> Result<usize> {
+        let deliveries = self
+            .webhook_port
+            .load_due_deliveries(&Utc::now(), self.webhook_properties.batch_size())
+            .await?;
+
+        let mut delivered = 0;
+        for mut delivery in deliveries {
+            let subscription = match self
+                .webhook_port
+                .load_subscription(&delivery.subscription_id)
+                .await?
+            {
+                Some(subscription) => subscription,
+                // deleted since the deliveries were loaded, which takes its deliveries with it
+                None => continue,
+            };
+
+            match self.webhook_sender.send(&subscription, &delivery).await {
+                Ok(()) => {
+                    delivery.succeed(Utc::now());
+                    delivered += 1;
+                }
+                Err(err) => delivery.fail(
+                    &err.to_string(),
+                    Utc::now(),
+                    self.webhook_properties.retry_policy(),
+                ),
+            }
+
+            self.webhook_port.update_delivery(&delivery).await?;
+        }
+
+        Ok(delivered)
+    }
+
+    async fn redeliver(
+        &self,
+        delivery_id: &WebhookDeliveryId,
+        _audit_context: &AuditContext,
+    ) -> Result<WebhookDelivery> {
+        let mut delivery = self
+            .webhook_port
+            .load_delivery(delivery_id)
+            .await?
+            .ok_or_else(|| anyhow!(ServiceError::WebhookDeliveryNotFound(delivery_id.0)))?;
+
+        delivery.redeliver(Utc::now())?;
+        self.webhook_port.update_delivery(&delivery).await?;
+
+        Ok(delivery)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeliverWebhooksService;
+    use crate::application::port::incoming::deliver_webhooks_use_case::DeliverWebhooksUseCase;
+    use crate::application::port::outgoing::{
+        webhook_port::WebhookPort, webhook_sender::WebhookSender,
+    };
+    use crate::application::service::webhook_properties::WebhookProperties;
+    use crate::domain::account::AccountId;
+    use crate::domain::audit::AuditContext;
+    use crate::domain::domain_event::DomainEvent;
+    use crate::domain::transfer::TransferId;
+    use crate::domain::webhook::{
+        RetryPolicy, WebhookDelivery, WebhookDeliveryId, WebhookDeliveryStatus,
+        WebhookSubscription, WebhookSubscriptionId,
+    };
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn acknowledged_delivery_is_marked_delivered() {
+        let port = MockWebhookPort::with_due_delivery();
+        let service = given_a_service(&port, MockWebhookSender { fails: false }, 8);
+
+        let delivered = service.deliver_due_webhooks().await.unwrap();
+
+        let delivery = port.delivery();
+        assert_eq!(delivered, 1);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Delivered);
+        assert_eq!(delivery.attempts, 1);
+        assert!(delivery.delivered_at.is_some());
+    }
+
+    #[async_std::test]
+    async fn failed_delivery_is_retried_later_then_dead_lettered() {
+        let port = MockWebhookPort::with_due_delivery();
+        let service = given_a_service(&port, MockWebhookSender { fails: true }, 2);
+
+        let delivered = service.deliver_due_webhooks().await.unwrap();
+
+        let delivery = port.delivery();
+        assert_eq!(delivered, 0);
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert!(delivery.next_attempt_at > Utc::now());
+        assert_eq!(
+            delivery.last_error,
+            Some(String::from("500 Internal Server Error"))
+        );
+
+        port.make_due();
+        service.deliver_due_webhooks().await.unwrap();
+
+        assert_eq!(port.delivery().status, WebhookDeliveryStatus::DeadLettered);
+
+        let delivery = service
+            .redeliver(&WebhookDeliveryId(1), &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(port.delivery().attempts, 0);
+    }
+
+    fn given_a_service(
+        port: &MockWebhookPort,
+        sender: MockWebhookSender,
+        max_attempts: i32,
+    ) -> DeliverWebhooksService {
+        DeliverWebhooksService::new(
+            Box::new(port.clone()),
+            Box::new(sender),
+            WebhookProperties::new().with_retry_policy(RetryPolicy {
+                max_attempts,
+                ..RetryPolicy::default()
+            }),
+        )
+    }
+
+    /// Knows a single subscription with a single delivery.
+    #[derive(Debug, Clone)]
+    struct MockWebhookPort {
+        subscription: WebhookSubscription,
+        delivery: Arc<Mutex<WebhookDelivery>>,
+    }
+
+    impl MockWebhookPort {
+        fn with_due_delivery() -> Self {
+            let mut subscription = WebhookSubscription::new(
+                "http://localhost:9000/hooks",
+                "0123456789abcdef",
+                &[String::from("MoneyDeposited")],
+                Utc::now(),
+            )
+            .unwrap();
+            subscription.id = Some(WebhookSubscriptionId(1));
+
+            let mut delivery = WebhookDelivery::new(
+                WebhookSubscriptionId(1),
+                DomainEvent::MoneyDeposited {
+                    account_id: AccountId(2),
+                    source_account_id: AccountId(1),
+                    transfer_id: TransferId(7),
+                    money: money!(500, "AUD"),
+                    timestamp: Utc::now(),
+                },
+                Utc::now(),
+            );
+            delivery.id = Some(WebhookDeliveryId(1));
+
+            Self {
+                subscription,
+                delivery: Arc::new(Mutex::new(delivery)),
+            }
+        }
+
+        fn delivery(&self) -> WebhookDelivery {
+            self.delivery.lock().unwrap().clone()
+        }
+
+        fn make_due(&self) {
+            self.delivery.lock().unwrap().next_attempt_at = Utc::now();
+        }
+    }
+
+    #[async_trait]
+    impl WebhookPort for MockWebhookPort {
+        async fn save_subscription(
+            &self,
+            subscription: &WebhookSubscription,
+        ) -> Result<WebhookSubscription> {
+            Ok(subscription.clone())
+        }
+
+        async fn load_subscriptions(&self) -> Result<Vec<WebhookSubscription>> {
+            Ok(vec![self.subscription.clone()])
+        }
+
+        async fn load_subscription(
+            &self,
+            subscription_id: &WebhookSubscriptionId,
+        ) -> Result<Option<WebhookSubscription>> {
+            Ok(Some(self.subscription.clone())
+                .filter(|subscription| subscription.id.as_ref() == Some(subscription_id)))
+        }
+
+        async fn delete_subscription(
+            &self,
+            _subscription_id: &WebhookSubscriptionId,
+        ) -> Result<bool> {
+            Ok(false)
+        }
+
+        async fn load_subscriptions_for(
+            &self,
+            event: &DomainEvent,
+        ) -> Result<Vec<WebhookSubscription>> {
+            Ok(vec![self.subscription.clone()]
+                .into_iter()
+                .filter(|subscription| subscription.is_subscribed_to(event))
+                .collect())
+        }
+
+        async fn save_deliveries(&self, _deliveries: &[WebhookDelivery]) -> Result<()> {
+            Ok(())
+        }
+
+        async fn load_delivery(
+            &self,
+            delivery_id: &WebhookDeliveryId,
+        ) -> Result<Option<WebhookDelivery>> {
+            Ok(Some(self.delivery()).filter(|delivery| delivery.id.as_ref() == Some(delivery_id)))
+        }
+
+        async fn load_deliveries(
+            &self,
+            _subscription_id: &WebhookSubscriptionId,
+        ) -> Result<Vec<WebhookDelivery>> {
+            Ok(vec![self.delivery()])
+        }
+
+        async fn load_due_deliveries(
+            &self,
+            now: &DateTime<Utc>,
+            _limit: i64,
+        ) -> Result<Vec<WebhookDelivery>> {
+            Ok(Some(self.delivery())
+                .filter(|delivery| {
+                    delivery.status == WebhookDeliveryStatus::Pending
+                        && delivery.next_attempt_at <= *now
+                })
+                .into_iter()
+                .collect())
+        }
+
+        async fn update_delivery(&self, delivery: &WebhookDelivery) -> Result<()> {
+            *self.delivery.lock().unwrap() = delivery.clone();
+
+            Ok(())
+        }
+    }
+
+    #[derive(Debug)]
+    struct MockWebhookSender {
+        fails: bool,
+    }
+
+    #[async_trait]
+    impl WebhookSender for MockWebhookSender {
+        async fn send(
+            &self,
+            _subscription: &WebhookSubscription,
+            _delivery: &WebhookDelivery,
+        ) -> Result<()> {
+            if self.fails {
+                Err(anyhow!("500 Internal Server Error"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
@@ -1,3 +1,8 @@
+use crate::domain::account::AccountError;
+use crate::domain::account_identifier::AccountIdentifierError;
+use crate::domain::audit::ErrorCategory;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferError;
 use rusty_money::Money;
 use thiserror::Error;
 
@@ -5,6 +10,77 @@ use thiserror::Error;
 pub enum ServiceError {
     #[error("Maximum threshold for transferring money exceeded: tried to transfer {threshold:?} but threshold is {actual:?}!")]
     ThresholdExceededException { threshold: Money, actual: Money },
+    #[error("Amount must be positive, got `{0}`")]
+    InvalidAmount(i64),
     #[error("May withdraw failed with the following balance: `{0}`")]
     MayWithdrawFailed(i64),
+    #[error("Idempotency key `{0}` was already used for a different request")]
+    IdempotencyKeyReused(String),
+    #[error("A request with idempotency key `{0}` is still being processed")]
+    IdempotentRequestInProgress(String),
+    #[error("Page size must be between 1 and {maximum}, got `{requested}`")]
+    InvalidPageSize { requested: i64, maximum: i64 },
+    #[error("Invalid activity filter: {0}")]
+    InvalidActivityFilter(String),
+    #[error("Invalid date range: {0}")]
+    InvalidDateRange(String),
+    #[error("No account is registered for `{0}`")]
+    UnknownAccountIdentifier(String),
+    #[error("A batch must have between 1 and {maximum} transfers, got `{requested}`")]
+    InvalidBatchSize { requested: usize, maximum: usize },
+    #[error("Webhook `{0}` does not exist")]
+    WebhookNotFound(i32),
+    #[error("Webhook delivery `{0}` does not exist")]
+    WebhookDeliveryNotFound(i32),
+    #[error("`{principal}` may not access account `{account_id}`")]
+    AccountAccessDenied {
+        principal: Principal,
+        account_id: i32,
+    },
+    #[error("`{principal}` may not access transfer `{transfer_id}`")]
+    TransferAccessDenied {
+        principal: Principal,
+        transfer_id: i32,
+    },
+    #[error("A command must name its initiator to act on an account")]
+    InitiatorMissing,
+    #[error("`{0}` may not read the audit log")]
+    AuditLogAccessDenied(Principal),
+}
+
+/// Sorts the error of a failed invocation into the category it is audited under.
+pub fn error_category(err: &anyhow::Error) -> ErrorCategory {
+    if let Some(err) = err.downcast_ref::<ServiceError>() {
+        return match err {
+            ServiceError::IdempotencyKeyReused(_)
+            | ServiceError::IdempotentRequestInProgress(_) => ErrorCategory::Conflict,
+            ServiceError::UnknownAccountIdentifier(_)
+            | ServiceError::WebhookNotFound(_)
+            | ServiceError::WebhookDeliveryNotFound(_) => ErrorCategory::NotFound,
+            ServiceError::AccountAccessDenied { .. }
+            | ServiceError::TransferAccessDenied { .. }
+            | ServiceError::InitiatorMissing
+            | ServiceError::AuditLogAccessDenied(_) => ErrorCategory::Forbidden,
+            _ => ErrorCategory::Rejected,
+        };
+    }
+
+    if let Some(err) = err.downcast_ref::<AccountError>() {
+        return match err {
+            AccountError::HoldNotFound(_) | AccountError::AccountNotFound(_) => {
+                ErrorCategory::NotFound
+            }
+            _ => ErrorCategory::Rejected,
+        };
+    }
+
+    if let Some(TransferError::TransferNotFound(_)) = err.downcast_ref::<TransferError>() {
+        return ErrorCategory::NotFound;
+    }
+
+    if err.is::<TransferError>() || err.is::<AccountIdentifierError>() {
+        return ErrorCategory::Rejected;
+    }
+
+    ErrorCategory::Internal
 }
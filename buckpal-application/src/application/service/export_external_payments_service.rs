@@ -0,0 +1,177 @@
+use crate::application::port::incoming::export_external_payments_use_case::ExportExternalPaymentsUseCase;
+use crate::application::port::outgoing::external_payment_port::ExternalPaymentPort;
+use crate::application::service::aba_properties::AbaProperties;
+use crate::domain::aba::AbaFile;
+use crate::domain::external_payment::ExternalPaymentBatch;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+
+pub struct ExportExternalPaymentsService {
+    external_payment_port: Box<dyn ExternalPaymentPort + Send + Sync>,
+    aba_properties: AbaProperties,
+}
+
+impl ExportExternalPaymentsService {
+    pub fn new(
+        external_payment_port: Box<dyn ExternalPaymentPort + Send + Sync>,
+        aba_properties: AbaProperties,
+    ) -> Self {
+        Self {
+            external_payment_port,
+            aba_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl ExportExternalPaymentsUseCase for ExportExternalPaymentsService {
+    async fn export_external_payments(
+        &self,
+        processing_date: &NaiveDate,
+    ) -> Result<Option<ExternalPaymentBatch>> {
+        let payments = self.external_payment_port.load_unbatched_payments().await?;
+        if payments.is_empty() {
+            return Ok(None);
+        }
+
+        let file = AbaFile::write(
+            self.aba_properties.user(),
+            self.aba_properties.description(),
+            *processing_date,
+            &payments,
+            self.aba_properties.self_balancing(),
+        )?;
+
+        let batch = ExternalPaymentBatch {
+            id: None,
+            created_at: Utc::now(),
+            processing_date: *processing_date,
+            transfer_ids: payments
+                .iter()
+                .map(|payment| payment.transfer_id.clone())
+                .collect(),
+            record_count: file.record_count,
+            credit_total: file.credit_total,
+            debit_total: file.debit_total,
+            contents: file.contents,
+        };
+
+        // a concurrent export claiming the same payments makes this fail, so none is paid twice
+        let batch = self.external_payment_port.save_batch(&batch).await?;
+
+        Ok(Some(batch))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ExportExternalPaymentsService;
+    use crate::application::port::incoming::export_external_payments_use_case::ExportExternalPaymentsUseCase;
+    use crate::application::port::outgoing::external_payment_port::ExternalPaymentPort;
+    use crate::application::service::aba_properties::AbaProperties;
+    use crate::domain::aba::AbaUser;
+    use crate::domain::account::AccountId;
+    use crate::domain::external_account::ExternalAccount;
+    use crate::domain::external_payment::{
+        ExternalPayment, ExternalPaymentBatch, ExternalPaymentBatchId,
+    };
+    use crate::domain::transfer::TransferId;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::{NaiveDate, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn nothing_to_pay_out_records_no_batch() {
+        let port = MockExternalPaymentPort::default();
+        let service = given_a_service(&port);
+
+        let batch = service
+            .export_external_payments(&NaiveDate::from_ymd(2021, 3, 29))
+            .await
+            .unwrap();
+
+        assert_eq!(batch, None);
+        assert!(port.batches.lock().unwrap().is_empty());
+    }
+
+    #[async_std::test]
+    async fn records_batch_of_unbatched_payments() {
+        let port = MockExternalPaymentPort::default();
+        port.payments
+            .lock()
+            .unwrap()
+            .extend(vec![given_a_payment(7, 250), given_a_payment(8, 1_000)]);
+        let service = given_a_service(&port);
+
+        let batch = service
+            .export_external_payments(&NaiveDate::from_ymd(2021, 3, 29))
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(batch.id, Some(ExternalPaymentBatchId(1)));
+        assert_eq!(batch.transfer_ids, vec![TransferId(7), TransferId(8)]);
+        assert_eq!(batch.record_count, 2);
+        assert_eq!(batch.credit_total, 125_000);
+        assert_eq!(batch.debit_total, 0);
+        assert!(batch.contents.starts_with('0'));
+        assert_eq!(port.batches.lock().unwrap().len(), 1);
+    }
+
+    fn given_a_service(port: &MockExternalPaymentPort) -> ExportExternalPaymentsService {
+        let user = AbaUser::new(
+            "CBA",
+            "Buckpal Pty Ltd",
+            "301500",
+            "062000",
+            "12345678",
+            "Buckpal",
+        )
+        .unwrap();
+
+        ExportExternalPaymentsService::new(Box::new(port.clone()), AbaProperties::new(user))
+    }
+
+    fn given_a_payment(transfer_id: i32, amount: i64) -> ExternalPayment {
+        ExternalPayment {
+            transfer_id: TransferId(transfer_id),
+            source_account_id: AccountId(1),
+            external_account: ExternalAccount::new(
+                AccountId(100),
+                "733-017",
+                "987654",
+                "Jane Citizen",
+            )
+            .unwrap(),
+            money: money!(amount, "AUD"),
+            reference: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[derive(Clone, Default)]
+    struct MockExternalPaymentPort {
+        payments: Arc<Mutex<Vec<ExternalPayment>>>,
+        batches: Arc<Mutex<Vec<ExternalPaymentBatch>>>,
+    }
+
+    #[async_trait]
+    impl ExternalPaymentPort for MockExternalPaymentPort {
+        async fn load_unbatched_payments(&self) -> Result<Vec<ExternalPayment>> {
+            Ok(self.payments.lock().unwrap().clone())
+        }
+
+        async fn save_batch(&self, batch: &ExternalPaymentBatch) -> Result<ExternalPaymentBatch> {
+            let mut batches = self.batches.lock().unwrap();
+
+            let mut batch = batch.clone();
+            batch.id = Some(ExternalPaymentBatchId(batches.len() as i32 + 1));
+            batches.push(batch.clone());
+
+            Ok(batch)
+        }
+    }
+}
@@ -0,0 +1,391 @@
+use crate::application::port::incoming::generate_statement_query::GenerateStatementQuery;
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_account_port::LoadAccountPort,
+    load_statement_port::LoadStatementPort, load_transfer_port::LoadTransferPort,
+    save_statement_port::SaveStatementPort,
+};
+use crate::application::service::account_access::check_account_access;
+use crate::application::service::error::ServiceError;
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use crate::domain::statement::Statement;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::NaiveDate;
+
+/// The longest period of days a statement can cover.
+const MAXIMUM_STATEMENT_DAYS: i64 = 366;
+
+pub struct GenerateStatementService {
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    load_statement_port: Box<dyn LoadStatementPort + Send + Sync>,
+    save_statement_port: Box<dyn SaveStatementPort + Send + Sync>,
+    load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl GenerateStatementService {
+    pub fn new(
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        load_statement_port: Box<dyn LoadStatementPort + Send + Sync>,
+        save_statement_port: Box<dyn SaveStatementPort + Send + Sync>,
+        load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            load_statement_port,
+            save_statement_port,
+            load_transfer_port,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl GenerateStatementQuery for GenerateStatementService {
+    async fn generate_statement(
+        &self,
+        account_id: &AccountId,
+        from: &NaiveDate,
+        to: &NaiveDate,
+        principal: &Principal,
+    ) -> Result<Statement> {
+        use chrono::Utc;
+
+        self.check_period(from, to)?;
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
+
+        // a statement is issued once, re-issuing it returns exactly what was issued before
+        if let Some(statement) = self
+            .load_statement_port
+            .load_statement(account_id, from, to)
+            .await?
+        {
+            return Ok(statement);
+        }
+
+        let account = self
+            .load_account_port
+            .load_account(account_id, &Statement::start_of_day(from))
+            .await?;
+
+        let now = Utc::now();
+        let mut statement = Statement::generate(account_id, &account, *from, *to, now);
+
+        for line in statement.lines.iter_mut() {
+            if let Some(transfer_id) = &line.transfer_id {
+                line.reference = self
+                    .load_transfer_port
+                    .load_transfer(transfer_id)
+                    .await?
+                    .reference;
+            }
+        }
+
+        // activities can still be added to an open period, so its statement is not kept
+        if !statement.is_closed(&now) {
+            return Ok(statement);
+        }
+
+        self.save_statement_port.save_statement(&statement).await
+    }
+}
+
+impl GenerateStatementService {
+    fn check_period(&self, from: &NaiveDate, to: &NaiveDate) -> Result<()> {
+        let days = (*to - *from).num_days() + 1;
+
+        if days < 1 {
+            return Err(anyhow!(ServiceError::InvalidDateRange(String::from(
+                "`from` must not be after `to`"
+            ))));
+        }
+
+        if days > MAXIMUM_STATEMENT_DAYS {
+            return Err(anyhow!(ServiceError::InvalidDateRange(format!(
+                "a statement covers at most {} days",
+                MAXIMUM_STATEMENT_DAYS
+            ))));
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GenerateStatementService;
+    use crate::application::port::incoming::generate_statement_query::GenerateStatementQuery;
+    use crate::application::port::outgoing::{
+        load_account_port::LoadAccountPort, load_statement_port::LoadStatementPort,
+        load_transfer_port::LoadTransferPort, save_statement_port::SaveStatementPort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::Activity;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::statement::{Statement, StatementId};
+    use crate::domain::transfer::transfer_test_data::TransferBuilder;
+    use crate::domain::transfer::{Transfer, TransferId};
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, NaiveDate, Utc};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn closed_period_is_issued_only_once() {
+        let statements = MockStatementStore::default();
+        let service = given_a_service(&statements);
+
+        let from = NaiveDate::from_ymd(2021, 1, 1);
+        let to = NaiveDate::from_ymd(2021, 1, 31);
+
+        let first = service
+            .generate_statement(&AccountId(42), &from, &to, &default_principal())
+            .await
+            .unwrap();
+        let second = service
+            .generate_statement(&AccountId(42), &from, &to, &default_principal())
+            .await
+            .unwrap();
+
+        assert_eq!(first.id, Some(StatementId(1)));
+        assert_eq!(first, second);
+        assert_eq!(statements.saved(), 1);
+    }
+
+    #[async_std::test]
+    async fn open_period_is_not_persisted() {
+        let statements = MockStatementStore::default();
+        let service = given_a_service(&statements);
+
+        let today = Utc::now().date().naive_utc();
+
+        let statement = service
+            .generate_statement(
+                &AccountId(42),
+                &(today - Duration::days(3)),
+                &today,
+                &default_principal(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(statement.id, None);
+        assert_eq!(statements.saved(), 0);
+    }
+
+    #[async_std::test]
+    async fn period_longer_than_a_year_is_rejected() {
+        let statements = MockStatementStore::default();
+        let service = given_a_service(&statements);
+
+        let err = service
+            .generate_statement(
+                &AccountId(42),
+                &NaiveDate::from_ymd(2020, 1, 1),
+                &NaiveDate::from_ymd(2021, 1, 1),
+                &default_principal(),
+            )
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "Invalid date range: a statement covers at most 366 days"
+        );
+        assert_eq!(statements.saved(), 0);
+    }
+
+    #[async_std::test]
+    async fn lines_carry_the_reference_of_their_transfer() {
+        let activity = ActivityBuilder::default_activity()
+            .with_source_account(&AccountId(41))
+            .with_target_account(&AccountId(42))
+            .with_timestamp(&DateTime::<Utc>::from_utc(
+                NaiveDate::from_ymd(2021, 1, 15).and_hms(10, 0, 0),
+                Utc,
+            ))
+            .with_transfer_id(&TransferId(7))
+            .build();
+        let transfer = TransferBuilder::default_transfer()
+            .with_transfer_id(&TransferId(7))
+            .build()
+            .with_reference(Some(String::from("Invoice 2021-001")));
+
+        let service = GenerateStatementService::new(
+            Box::new(MockLoadAccountPort {
+                account: given_an_account(vec![activity]),
+            }),
+            Box::new(MockStatementStore::default()),
+            Box::new(MockStatementStore::default()),
+            Box::new(MockLoadTransferPort {
+                transfers: vec![transfer],
+            }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let statement = service
+            .generate_statement(
+                &AccountId(42),
+                &NaiveDate::from_ymd(2021, 1, 1),
+                &NaiveDate::from_ymd(2021, 1, 31),
+                &default_principal(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(statement.lines.len(), 1);
+        assert_eq!(statement.lines[0].transfer_id, Some(TransferId(7)));
+        assert_eq!(
+            statement.lines[0].reference,
+            Some(String::from("Invoice 2021-001"))
+        );
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied_an_issued_statement() {
+        let statements = MockStatementStore::default();
+        let service = given_a_service(&statements);
+
+        let from = NaiveDate::from_ymd(2021, 1, 1);
+        let to = NaiveDate::from_ymd(2021, 1, 31);
+        service
+            .generate_statement(&AccountId(42), &from, &to, &default_principal())
+            .await
+            .unwrap();
+
+        let err = service
+            .generate_statement(
+                &AccountId(42),
+                &from,
+                &to,
+                &Principal::User(String::from("mallory")),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+    }
+
+    fn given_an_account(activities: Vec<Activity>) -> Account {
+        AccountBuilder::default_account()
+            .with_account_id(&AccountId(42))
+            .with_activity_window(&ActivityWindow::new(activities))
+            .build()
+    }
+
+    fn given_a_service(statements: &MockStatementStore) -> GenerateStatementService {
+        GenerateStatementService::new(
+            Box::new(MockLoadAccountPort {
+                account: given_an_account(vec![]),
+            }),
+            Box::new(statements.clone()),
+            Box::new(statements.clone()),
+            Box::new(MockLoadTransferPort { transfers: vec![] }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        )
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockStatementStore {
+        statements: Arc<Mutex<Vec<Statement>>>,
+    }
+
+    impl MockStatementStore {
+        fn saved(&self) -> usize {
+            self.statements.lock().unwrap().len()
+        }
+    }
+
+    #[async_trait]
+    impl LoadStatementPort for MockStatementStore {
+        async fn load_statement(
+            &self,
+            account_id: &AccountId,
+            from: &NaiveDate,
+            to: &NaiveDate,
+        ) -> Result<Option<Statement>> {
+            Ok(self
+                .statements
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|statement| {
+                    statement.account_id == *account_id
+                        && statement.from == *from
+                        && statement.to == *to
+                })
+                .cloned())
+        }
+    }
+
+    #[async_trait]
+    impl SaveStatementPort for MockStatementStore {
+        async fn save_statement(&self, statement: &Statement) -> Result<Statement> {
+            let mut statements = self.statements.lock().unwrap();
+
+            let mut statement = statement.clone();
+            statement.id = Some(StatementId(statements.len() as i32 + 1));
+            statements.push(statement.clone());
+
+            Ok(statement)
+        }
+    }
+
+    struct MockLoadAccountPort {
+        account: Account,
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockLoadAccountPort {
+        async fn load_account(
+            &self,
+            _account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            Ok(self.account.clone())
+        }
+    }
+
+    struct MockLoadTransferPort {
+        transfers: Vec<Transfer>,
+    }
+
+    #[async_trait]
+    impl LoadTransferPort for MockLoadTransferPort {
+        async fn load_transfer(&self, transfer_id: &TransferId) -> Result<Transfer> {
+            self.transfers
+                .iter()
+                .find(|transfer| transfer.id.as_ref() == Some(transfer_id))
+                .cloned()
+                .ok_or_else(|| anyhow!("No matching transfer found from stub"))
+        }
+
+        async fn load_transfer_activities(
+            &self,
+            _transfer_id: &TransferId,
+        ) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn load_reversals(&self, _transfer_id: &TransferId) -> Result<Vec<Transfer>> {
+            Ok(vec![])
+        }
+    }
+}
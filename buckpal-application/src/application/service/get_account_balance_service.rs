@@ -1,19 +1,57 @@
 use crate::application::port::incoming::get_account_balance_query::GetAccountBalanceQuery;
-use crate::application::port::outgoing::load_account_port::LoadAccountPort;
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, account_balance_port::AccountBalancePort,
+    load_account_port::LoadAccountPort,
+};
+use crate::application::service::account_access::check_account_access;
 use crate::domain::account::AccountId;
+use crate::domain::audit::AuditContext;
+use crate::domain::principal::Principal;
 use anyhow::Result;
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use rusty_money::Money;
 
 pub struct GetAccountBalanceService {
     load_account_port: Box<dyn LoadAccountPort + Sync + Send>,
+    account_balance_port: Box<dyn AccountBalancePort + Sync + Send>,
+    account_access_port: Box<dyn AccountAccessPort + Sync + Send>,
+}
+
+impl GetAccountBalanceService {
+    pub fn new(
+        load_account_port: Box<dyn LoadAccountPort + Sync + Send>,
+        account_balance_port: Box<dyn AccountBalancePort + Sync + Send>,
+        account_access_port: Box<dyn AccountAccessPort + Sync + Send>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_balance_port,
+            account_access_port,
+        }
+    }
 }
 
 #[async_trait]
 impl GetAccountBalanceQuery for GetAccountBalanceService {
-    async fn get_account_balance(&self, account_id: &AccountId) -> Result<Money> {
-        use chrono::Utc;
+    async fn get_account_balance(
+        &self,
+        account_id: &AccountId,
+        principal: &Principal,
+        _audit_context: &AuditContext,
+    ) -> Result<Money> {
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
 
+        if let Some(account_balance) = self
+            .account_balance_port
+            .load_account_balance(account_id)
+            .await?
+        {
+            return Ok(account_balance.balance);
+        }
+
+        // without activities there is no kept balance, loading the account tells whether it
+        // exists at all
         let account = self
             .load_account_port
             .load_account(account_id, &Utc::now())
@@ -21,4 +59,236 @@ impl GetAccountBalanceQuery for GetAccountBalanceService {
 
         Ok(account.calculate_balance())
     }
+
+    async fn get_balance_as_of(
+        &self,
+        account_id: &AccountId,
+        instant: &DateTime<Utc>,
+        principal: &Principal,
+        _audit_context: &AuditContext,
+    ) -> Result<Money> {
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
+
+        self.account_balance_port
+            .load_balance_as_of(account_id, instant)
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GetAccountBalanceService;
+    use crate::application::port::incoming::get_account_balance_query::GetAccountBalanceQuery;
+    use crate::application::port::outgoing::{
+        account_balance_port::AccountBalancePort, load_account_port::LoadAccountPort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountError, AccountId};
+    use crate::domain::account_balance::{AccountBalance, BalanceDrift};
+    use crate::domain::activity::ActivityId;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::audit::AuditContext;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn serves_kept_balance() {
+        let service = GetAccountBalanceService::new(
+            Box::new(MockLoadAccountPort { account: None }),
+            Box::new(MockAccountBalancePort {
+                account_balance: Some(AccountBalance::new(
+                    AccountId(42),
+                    money!(700, "AUD"),
+                    ActivityId(9),
+                )),
+                balances_as_of: vec![],
+            }),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        );
+
+        let balance = service
+            .get_account_balance(
+                &AccountId(42),
+                &default_principal(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(balance, money!(700, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn loads_account_without_kept_balance() {
+        let account = AccountBuilder::default_account()
+            .with_baseline_balance(&money!(0, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .build();
+        let service = GetAccountBalanceService::new(
+            Box::new(MockLoadAccountPort {
+                account: Some(account),
+            }),
+            Box::new(MockAccountBalancePort {
+                account_balance: None,
+                balances_as_of: vec![],
+            }),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        );
+
+        let balance = service
+            .get_account_balance(
+                &AccountId(42),
+                &default_principal(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(balance, money!(0, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn serves_balance_as_of_instant_without_loading_account() {
+        let instant = Utc::now() - Duration::days(30);
+        let service = GetAccountBalanceService::new(
+            Box::new(MockLoadAccountPort { account: None }),
+            Box::new(MockAccountBalancePort {
+                account_balance: None,
+                balances_as_of: vec![(AccountId(42), instant, money!(300, "AUD"))],
+            }),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        );
+
+        let balance = service
+            .get_balance_as_of(
+                &AccountId(42),
+                &instant,
+                &default_principal(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(balance, money!(300, "AUD"));
+    }
+
+    #[async_std::test]
+    async fn balance_as_of_instant_of_unknown_account_is_not_found() {
+        let service = GetAccountBalanceService::new(
+            Box::new(MockLoadAccountPort { account: None }),
+            Box::new(MockAccountBalancePort {
+                account_balance: None,
+                balances_as_of: vec![],
+            }),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        );
+
+        let err = service
+            .get_balance_as_of(
+                &AccountId(41),
+                &Utc::now(),
+                &default_principal(),
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<AccountError>(),
+            Some(AccountError::AccountNotFound(41))
+        ));
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let service = GetAccountBalanceService::new(
+            Box::new(MockLoadAccountPort { account: None }),
+            Box::new(MockAccountBalancePort {
+                account_balance: None,
+                balances_as_of: vec![],
+            }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+        let mallory = Principal::User(String::from("mallory"));
+
+        let err = service
+            .get_account_balance(&AccountId(42), &mallory, &AuditContext::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+
+        let err = service
+            .get_balance_as_of(
+                &AccountId(42),
+                &Utc::now(),
+                &mallory,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+    }
+
+    struct MockLoadAccountPort {
+        account: Option<Account>,
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockLoadAccountPort {
+        async fn load_account(
+            &self,
+            _account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            self.account
+                .clone()
+                .ok_or_else(|| anyhow!("expected the kept balance to be used"))
+        }
+    }
+
+    struct MockAccountBalancePort {
+        account_balance: Option<AccountBalance>,
+        balances_as_of: Vec<(AccountId, DateTime<Utc>, Money)>,
+    }
+
+    #[async_trait]
+    impl AccountBalancePort for MockAccountBalancePort {
+        async fn load_account_balance(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Option<AccountBalance>> {
+            Ok(self.account_balance.clone())
+        }
+
+        async fn load_balance_as_of(
+            &self,
+            account_id: &AccountId,
+            instant: &DateTime<Utc>,
+        ) -> Result<Money> {
+            self.balances_as_of
+                .iter()
+                .find(|(id, at, _)| id == account_id && at == instant)
+                .map(|(_, _, balance)| balance.clone())
+                .ok_or_else(|| anyhow!(AccountError::AccountNotFound(account_id.0)))
+        }
+
+        async fn load_balance_drifts(&self) -> Result<Vec<BalanceDrift>> {
+            Ok(vec![])
+        }
+    }
 }
@@ -0,0 +1,173 @@
+use crate::application::port::incoming::get_transfer_receipt_query::GetTransferReceiptQuery;
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_transfer_port::LoadTransferPort,
+    transfer_receipt_signer::TransferReceiptSigner,
+};
+use crate::application::service::account_access::check_transfer_access;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
+use crate::domain::transfer_receipt::TransferReceipt;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct GetTransferReceiptService {
+    load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+    transfer_receipt_signer: Box<dyn TransferReceiptSigner + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl GetTransferReceiptService {
+    pub fn new(
+        load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+        transfer_receipt_signer: Box<dyn TransferReceiptSigner + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_transfer_port,
+            transfer_receipt_signer,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl GetTransferReceiptQuery for GetTransferReceiptService {
+    async fn get_transfer_receipt(
+        &self,
+        transfer_id: &TransferId,
+        principal: &Principal,
+    ) -> Result<TransferReceipt> {
+        let transfer = self.load_transfer_port.load_transfer(transfer_id).await?;
+        check_transfer_access(
+            self.account_access_port.as_ref(),
+            transfer_id,
+            &transfer,
+            principal,
+        )
+        .await?;
+
+        // receipts aren't stored, signing is deterministic so the same one is issued every time
+        let receipt = TransferReceipt::for_transfer(&transfer)?;
+        let signature = self.transfer_receipt_signer.sign(&receipt)?;
+
+        Ok(receipt.with_signature(&signature))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GetTransferReceiptService;
+    use crate::application::port::incoming::get_transfer_receipt_query::GetTransferReceiptQuery;
+    use crate::application::port::outgoing::{
+        load_transfer_port::LoadTransferPort, transfer_receipt_signer::TransferReceiptSigner,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::Activity;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::transfer_test_data::TransferBuilder;
+    use crate::domain::transfer::{Transfer, TransferId, TransferStatus};
+    use crate::domain::transfer_receipt::TransferReceipt;
+    use anyhow::Result;
+    use async_trait::async_trait;
+
+    #[async_std::test]
+    async fn signs_receipt_of_completed_transfer() {
+        let service = GetTransferReceiptService::new(
+            Box::new(MockLoadTransferPort {
+                transfer: TransferBuilder::default_transfer().build(),
+            }),
+            Box::new(MockTransferReceiptSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let receipt = service
+            .get_transfer_receipt(&TransferId(7), &default_principal())
+            .await
+            .unwrap();
+
+        assert_eq!(receipt.transfer_id, TransferId(7));
+        assert_eq!(receipt.signature, "signed:7");
+    }
+
+    #[async_std::test]
+    async fn refuses_receipt_of_pending_transfer() {
+        let service = GetTransferReceiptService::new(
+            Box::new(MockLoadTransferPort {
+                transfer: TransferBuilder::default_transfer()
+                    .with_status(TransferStatus::Pending)
+                    .build(),
+            }),
+            Box::new(MockTransferReceiptSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let result = service
+            .get_transfer_receipt(&TransferId(7), &default_principal())
+            .await;
+
+        assert!(result.is_err());
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let service = GetTransferReceiptService::new(
+            Box::new(MockLoadTransferPort {
+                transfer: TransferBuilder::default_transfer().build(),
+            }),
+            Box::new(MockTransferReceiptSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let err = service
+            .get_transfer_receipt(&TransferId(7), &Principal::User(String::from("mallory")))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::TransferAccessDenied { transfer_id: 7, .. })
+        ));
+    }
+
+    struct MockLoadTransferPort {
+        transfer: Transfer,
+    }
+
+    #[async_trait]
+    impl LoadTransferPort for MockLoadTransferPort {
+        async fn load_transfer(&self, _transfer_id: &TransferId) -> Result<Transfer> {
+            Ok(self.transfer.clone())
+        }
+
+        async fn load_transfer_activities(
+            &self,
+            _transfer_id: &TransferId,
+        ) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn load_reversals(&self, _transfer_id: &TransferId) -> Result<Vec<Transfer>> {
+            Ok(vec![])
+        }
+    }
+
+    struct MockTransferReceiptSigner {}
+
+    impl TransferReceiptSigner for MockTransferReceiptSigner {
+        fn sign(&self, receipt: &TransferReceipt) -> Result<String> {
+            Ok(format!("signed:{}", receipt.transfer_id.0))
+        }
+    }
+}
@@ -0,0 +1,51 @@
+use crate::application::port::incoming::get_transfer_query::{GetTransferQuery, TransferDetails};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_transfer_port::LoadTransferPort,
+};
+use crate::application::service::account_access::check_transfer_access;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct GetTransferService {
+    load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl GetTransferService {
+    pub fn new(
+        load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_transfer_port,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl GetTransferQuery for GetTransferService {
+    async fn get_transfer(
+        &self,
+        transfer_id: &TransferId,
+        principal: &Principal,
+    ) -> Result<TransferDetails> {
+        let transfer = self.load_transfer_port.load_transfer(transfer_id).await?;
+        check_transfer_access(
+            self.account_access_port.as_ref(),
+            transfer_id,
+            &transfer,
+            principal,
+        )
+        .await?;
+
+        let legs = self
+            .load_transfer_port
+            .load_transfer_activities(transfer_id)
+            .await?;
+
+        Ok(TransferDetails { transfer, legs })
+    }
+}
@@ -0,0 +1,400 @@
+use crate::application::port::incoming::send_money_use_case::{SendMoneyCommand, SendMoneyUseCase};
+use crate::application::port::outgoing::account_access_port::AccountAccessPort;
+use crate::application::port::outgoing::idempotency_port::IdempotencyPort;
+use crate::application::service::account_access::{check_account_access, initiator};
+use crate::application::service::error::{error_category, ServiceError};
+use crate::application::service::money_transfer_properties::MoneyTransferProperties;
+use crate::domain::audit::ErrorCategory;
+use crate::domain::idempotency_record::{IdempotencyRecord, IdempotencyStatus};
+use crate::domain::transfer::TransferId;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+/// Sends money at most once per idempotency key. Commands without a key are passed through to
+/// the wrapped use case, repeated commands with a known key are answered with the outcome of the
+/// first one. Keys are scoped to the source account of the command. A request that failed for
+/// any other reason than a rejection, e.g. an unreachable database, may have sent the money
+/// before it failed, so its key stays claimed until it expires rather than letting a retry send
+/// the money a second time. Only principals that may debit the source account can claim a key.
+pub struct IdempotentSendMoneyService {
+    send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+    idempotency_port: Box<dyn IdempotencyPort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    money_transfer_properties: MoneyTransferProperties,
+}
+
+impl IdempotentSendMoneyService {
+    pub fn new(
+        send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+        idempotency_port: Box<dyn IdempotencyPort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+        money_transfer_properties: MoneyTransferProperties,
+    ) -> Self {
+        Self {
+            send_money_use_case,
+            idempotency_port,
+            account_access_port,
+            money_transfer_properties,
+        }
+    }
+}
+
+#[async_trait]
+impl SendMoneyUseCase for IdempotentSendMoneyService {
+    async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId> {
+        use chrono::Utc;
+
+        let idempotency_key = match command.idempotency_key.clone() {
+            Some(idempotency_key) => idempotency_key,
+            None => return self.send_money_use_case.send_money(command).await,
+        };
+
+        // a key is scoped to the source account, nobody else may claim it
+        check_account_access(
+            self.account_access_port.as_ref(),
+            &command.source_account_id,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
+        let now = Utc::now();
+        self.idempotency_port.remove_expired_keys(&now).await?;
+
+        let mut record = IdempotencyRecord::new_in_progress(
+            idempotency_key,
+            command.source_account_id.clone(),
+            command.fingerprint(),
+            now,
+            now + self.money_transfer_properties.idempotency_key_retention(),
+        );
+
+        if let Some(existing) = self.idempotency_port.claim_key(&record).await? {
+            return self.replay(&record, existing);
+        }
+
+        let result = self.send_money_use_case.send_money(command).await;
+
+        match &result {
+            Ok(transfer_id) => record.succeed(transfer_id.clone()),
+            Err(err) => match error_category(err) {
+                ErrorCategory::Rejected | ErrorCategory::NotFound | ErrorCategory::Forbidden => {
+                    record.fail(err.to_string())
+                }
+                ErrorCategory::Conflict | ErrorCategory::Internal => {
+                    log::warn!(
+                        "Idempotency key `{}` stays in progress, the request failed with: {}",
+                        record.key.0,
+                        err
+                    );
+                    return result;
+                }
+            },
+        }
+
+        // the outcome is already final, a key without it stays in progress until it expires
+        if let Err(err) = self.idempotency_port.complete_key(&record).await {
+            log::error!(
+                "Storing the outcome of idempotency key `{}` failed: {}",
+                record.key.0,
+                err
+            );
+        }
+
+        result
+    }
+}
+
+impl IdempotentSendMoneyService {
+    fn replay(
+        &self,
+        record: &IdempotencyRecord,
+        existing: IdempotencyRecord,
+    ) -> Result<TransferId> {
+        if existing.fingerprint != record.fingerprint {
+            return Err(anyhow!(ServiceError::IdempotencyKeyReused(existing.key.0)));
+        }
+
+        match existing.status {
+            IdempotencyStatus::InProgress => Err(anyhow!(
+                ServiceError::IdempotentRequestInProgress(existing.key.0)
+            )),
+            IdempotencyStatus::Succeeded => existing.transfer_id.ok_or_else(|| {
+                anyhow!("expected transfer ID of succeeded request not to be empty")
+            }),
+            IdempotencyStatus::Failed => Err(anyhow!(existing
+                .error_message
+                .unwrap_or_else(|| String::from("Request failed")))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdempotentSendMoneyService;
+    use crate::application::port::incoming::send_money_use_case::{
+        SendMoneyCommand, SendMoneyUseCase,
+    };
+    use crate::application::port::outgoing::idempotency_port::IdempotencyPort;
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::application::service::money_transfer_properties::MoneyTransferProperties;
+    use crate::domain::account::AccountId;
+    use crate::domain::idempotency_record::{IdempotencyKey, IdempotencyRecord};
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn repeated_request_returns_original_transfer() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let command = given_a_command(&money!(300, "AUD"), "abc");
+
+        let first = service.send_money(&command).await.unwrap();
+        let second = service.send_money(&command).await.unwrap();
+
+        assert_eq!(first, TransferId(1));
+        assert_eq!(second, TransferId(1));
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn repeated_failed_request_returns_original_error() {
+        let send_money_use_case = MockSendMoneyUseCase {
+            fail: true,
+            ..MockSendMoneyUseCase::default()
+        };
+        let service = given_a_service(&send_money_use_case);
+
+        let command = given_a_command(&money!(300, "AUD"), "abc");
+
+        let first = service.send_money(&command).await.unwrap_err();
+        let second = service.send_money(&command).await.unwrap_err();
+
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn request_failing_for_infrastructure_reasons_is_not_executed_again() {
+        let send_money_use_case = MockSendMoneyUseCase {
+            unavailable: true,
+            ..MockSendMoneyUseCase::default()
+        };
+        let service = given_a_service(&send_money_use_case);
+
+        let command = given_a_command(&money!(300, "AUD"), "abc");
+
+        service.send_money(&command).await.unwrap_err();
+        let err = service.send_money(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::IdempotentRequestInProgress(_))
+        ));
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn key_stays_claimed_when_outcome_can_not_be_stored() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = IdempotentSendMoneyService::new(
+            Box::new(send_money_use_case.clone()),
+            Box::new(MockIdempotencyPort {
+                fail_completions: true,
+                ..MockIdempotencyPort::default()
+            }),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        );
+
+        let command = given_a_command(&money!(300, "AUD"), "abc");
+
+        let first = service.send_money(&command).await.unwrap();
+        let second = service.send_money(&command).await.unwrap_err();
+
+        assert_eq!(first, TransferId(1));
+        assert!(matches!(
+            second.downcast_ref::<ServiceError>(),
+            Some(ServiceError::IdempotentRequestInProgress(_))
+        ));
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn reusing_key_for_different_request_is_rejected() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        service
+            .send_money(&given_a_command(&money!(300, "AUD"), "abc"))
+            .await
+            .unwrap();
+        let err = service
+            .send_money(&given_a_command(&money!(400, "AUD"), "abc"))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::IdempotencyKeyReused(_))
+        ));
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn same_key_is_a_different_request_for_another_source_account() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let first = service
+            .send_money(&given_a_command(&money!(300, "AUD"), "abc"))
+            .await
+            .unwrap();
+        let second = service
+            .send_money(
+                &SendMoneyCommand::new(AccountId(43), AccountId(42), money!(300, "AUD"))
+                    .with_idempotency_key(IdempotencyKey(String::from("abc")))
+                    .with_initiator(default_principal()),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(first, TransferId(1));
+        assert_eq!(second, TransferId(2));
+        assert_eq!(send_money_use_case.invocations(), 2);
+    }
+
+    #[async_std::test]
+    async fn requests_without_key_are_always_executed() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let command = SendMoneyCommand::new(AccountId(41), AccountId(42), money!(300, "AUD"));
+
+        service.send_money(&command).await.unwrap();
+        service.send_money(&command).await.unwrap();
+
+        assert_eq!(send_money_use_case.invocations(), 2);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_can_not_claim_a_key() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let err = service
+            .send_money(
+                &given_a_command(&money!(300, "AUD"), "abc")
+                    .with_initiator(Principal::User(String::from("mallory"))),
+            )
+            .await
+            .unwrap_err();
+        let transfer_id = service
+            .send_money(&given_a_command(&money!(300, "AUD"), "abc"))
+            .await
+            .unwrap();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 41, .. })
+        ));
+        assert_eq!(transfer_id, TransferId(1));
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    fn given_a_service(send_money_use_case: &MockSendMoneyUseCase) -> IdempotentSendMoneyService {
+        IdempotentSendMoneyService::new(
+            Box::new(send_money_use_case.clone()),
+            Box::new(MockIdempotencyPort::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        )
+    }
+
+    fn given_a_command(money: &Money, idempotency_key: &str) -> SendMoneyCommand {
+        SendMoneyCommand::new(AccountId(41), AccountId(42), money.clone())
+            .with_idempotency_key(IdempotencyKey(String::from(idempotency_key)))
+            .with_initiator(default_principal())
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockSendMoneyUseCase {
+        fail: bool,
+        unavailable: bool,
+        invocations: Arc<Mutex<i32>>,
+    }
+
+    impl MockSendMoneyUseCase {
+        fn invocations(&self) -> i32 {
+            *self.invocations.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl SendMoneyUseCase for MockSendMoneyUseCase {
+        async fn send_money(&self, _command: &SendMoneyCommand) -> Result<TransferId> {
+            let mut invocations = self.invocations.lock().unwrap();
+            *invocations += 1;
+
+            if self.fail {
+                return Err(anyhow!(ServiceError::MayWithdrawFailed(-200)));
+            }
+            if self.unavailable {
+                return Err(anyhow!("Database is unreachable"));
+            }
+
+            Ok(TransferId(*invocations))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockIdempotencyPort {
+        records: Mutex<Vec<IdempotencyRecord>>,
+        fail_completions: bool,
+    }
+
+    #[async_trait]
+    impl IdempotencyPort for MockIdempotencyPort {
+        async fn claim_key(&self, record: &IdempotencyRecord) -> Result<Option<IdempotencyRecord>> {
+            let mut records = self.records.lock().unwrap();
+
+            match records.iter().find(|existing| {
+                existing.key == record.key && existing.source_account_id == record.source_account_id
+            }) {
+                Some(existing) => Ok(Some(existing.clone())),
+                None => {
+                    records.push(record.clone());
+                    Ok(None)
+                }
+            }
+        }
+
+        async fn complete_key(&self, record: &IdempotencyRecord) -> Result<IdempotencyRecord> {
+            if self.fail_completions {
+                return Err(anyhow!("Database is unreachable"));
+            }
+            let mut records = self.records.lock().unwrap();
+            records.retain(|existing| {
+                existing.key != record.key || existing.source_account_id != record.source_account_id
+            });
+            records.push(record.clone());
+
+            Ok(record.clone())
+        }
+
+        async fn remove_expired_keys(&self, now: &DateTime<Utc>) -> Result<u64> {
+            let mut records = self.records.lock().unwrap();
+            let before = records.len();
+            records.retain(|existing| existing.expires_at > *now);
+
+            Ok((before - records.len()) as u64)
+        }
+    }
+}
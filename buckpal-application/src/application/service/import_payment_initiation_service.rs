@@ -0,0 +1,590 @@
+use crate::application::port::incoming::import_payment_initiation_use_case::ImportPaymentInitiationUseCase;
+use crate::application::port::incoming::send_money_use_case::{
+    SendMoneyCommand, SendMoneyUseCase, TransferTarget,
+};
+use crate::application::port::outgoing::payment_initiation_port::PaymentInitiationPort;
+use crate::application::service::error::ServiceError;
+use crate::domain::account::{AccountError, AccountId};
+use crate::domain::account_identifier::AccountIdentifier;
+use crate::domain::audit::AuditContext;
+use crate::domain::idempotency_record::IdempotencyKey;
+use crate::domain::payment_initiation::{
+    CreditTransferTransaction, PaymentInitiation, PaymentInstructionStatus, PaymentStatus,
+    PaymentStatusReport, StatusReason, TransactionStatus,
+};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use rusty_money::{money, Money};
+use std::collections::HashSet;
+
+/// The only currency accounts are kept in.
+const SUPPORTED_CURRENCY: &str = "AUD";
+
+/// The longest message id ISO 20022 allows.
+const MAX_MESSAGE_ID_LENGTH: usize = 35;
+
+/// Imports payment initiations all at once: nothing is executed unless every transaction of the
+/// initiation is valid, after which each transaction is executed on its own.
+pub struct ImportPaymentInitiationService {
+    send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+    payment_initiation_port: Box<dyn PaymentInitiationPort + Send + Sync>,
+}
+
+impl ImportPaymentInitiationService {
+    pub fn new(
+        send_money_use_case: Box<dyn SendMoneyUseCase + Send + Sync>,
+        payment_initiation_port: Box<dyn PaymentInitiationPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            send_money_use_case,
+            payment_initiation_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ImportPaymentInitiationUseCase for ImportPaymentInitiationService {
+    async fn import_payment_initiation(
+        &self,
+        initiation: &PaymentInitiation,
+        initiator: Option<&Principal>,
+        audit_context: &AuditContext,
+    ) -> Result<PaymentStatusReport> {
+        use chrono::Utc;
+
+        if let Some(reason) = self.validate_group(initiation) {
+            return Ok(PaymentStatusReport::rejected(initiation, reason, &[]));
+        }
+
+        let transaction_reasons = self.validate_transactions(initiation);
+        if !transaction_reasons.is_empty() {
+            let reason = StatusReason::new(
+                "NARR",
+                format!(
+                    "{} of {} transactions are invalid, nothing was executed",
+                    transaction_reasons.len(),
+                    initiation.transactions().count()
+                ),
+            );
+            return Ok(PaymentStatusReport::rejected(
+                initiation,
+                reason,
+                &transaction_reasons,
+            ));
+        }
+
+        if !self
+            .payment_initiation_port
+            .claim_message_id(&initiation.message_id, &Utc::now())
+            .await?
+        {
+            let reason = StatusReason::new(
+                "DU01",
+                format!("Message id `{}` was imported before", initiation.message_id),
+            );
+            return Ok(PaymentStatusReport::rejected(initiation, reason, &[]));
+        }
+
+        let mut payments = vec![];
+        for payment in &initiation.payments {
+            let debtor_account_id = to_account_id(&payment.debtor_account)
+                .expect("expected debtor account to be validated");
+
+            let mut transactions = vec![];
+            for transaction in &payment.transactions {
+                transactions.push(
+                    self.execute(
+                        initiation,
+                        &debtor_account_id,
+                        transaction,
+                        initiator,
+                        audit_context,
+                    )
+                    .await,
+                );
+            }
+
+            payments.push(PaymentInstructionStatus {
+                payment_information_id: payment.payment_information_id.clone(),
+                transactions,
+            });
+        }
+
+        Ok(PaymentStatusReport::executed(initiation, payments))
+    }
+}
+
+impl ImportPaymentInitiationService {
+    fn validate_group(&self, initiation: &PaymentInitiation) -> Option<StatusReason> {
+        if initiation.message_id.is_empty()
+            || initiation.message_id.chars().count() > MAX_MESSAGE_ID_LENGTH
+        {
+            return Some(StatusReason::new(
+                "FF01",
+                format!(
+                    "Message id must be between 1 and {} characters",
+                    MAX_MESSAGE_ID_LENGTH
+                ),
+            ));
+        }
+
+        let count = initiation.transactions().count();
+        if count == 0 {
+            return Some(StatusReason::new("FF01", "The file has no transactions"));
+        }
+        if initiation.number_of_transactions.parse::<usize>().ok() != Some(count) {
+            return Some(StatusReason::new(
+                "AM18",
+                format!(
+                    "Declared number of transactions `{}` does not match the {} transactions in the file",
+                    initiation.number_of_transactions, count
+                ),
+            ));
+        }
+
+        if let Some(control_sum) = initiation.control_sum {
+            let sum: Decimal = initiation
+                .transactions()
+                .map(|transaction| transaction.amount)
+                .sum();
+            if control_sum != sum {
+                return Some(StatusReason::new(
+                    "AM10",
+                    format!(
+                        "Declared control sum `{}` does not match the sum `{}` of the transactions",
+                        control_sum, sum
+                    ),
+                ));
+            }
+        }
+
+        None
+    }
+
+    /// Returns the reason for every invalid transaction, keyed by end to end id.
+    fn validate_transactions(&self, initiation: &PaymentInitiation) -> Vec<(String, StatusReason)> {
+        let mut end_to_end_ids = HashSet::new();
+        let mut reasons = vec![];
+
+        for payment in &initiation.payments {
+            let debtor_account_id = to_account_id(&payment.debtor_account);
+
+            for transaction in &payment.transactions {
+                let reason = if !end_to_end_ids.insert(transaction.end_to_end_id.as_str()) {
+                    Some(StatusReason::new("AM05", "Duplicate end to end id"))
+                } else {
+                    match &debtor_account_id {
+                        Some(debtor_account_id) => {
+                            self.validate_transaction(debtor_account_id, transaction)
+                        }
+                        None => Some(StatusReason::new(
+                            "AC02",
+                            format!("Invalid debtor account `{}`", payment.debtor_account),
+                        )),
+                    }
+                };
+
+                if let Some(reason) = reason {
+                    reasons.push((transaction.end_to_end_id.clone(), reason));
+                }
+            }
+        }
+
+        reasons
+    }
+
+    fn validate_transaction(
+        &self,
+        debtor_account_id: &AccountId,
+        transaction: &CreditTransferTransaction,
+    ) -> Option<StatusReason> {
+        if transaction.currency != SUPPORTED_CURRENCY {
+            return Some(StatusReason::new(
+                "AM03",
+                format!(
+                    "Unsupported currency `{}`, only `{}` is supported",
+                    transaction.currency, SUPPORTED_CURRENCY
+                ),
+            ));
+        }
+        if transaction.amount.is_zero() {
+            return Some(StatusReason::new("AM01", "Amount must not be zero"));
+        }
+        if to_whole_amount(transaction).is_none() {
+            return Some(StatusReason::new(
+                "AM12",
+                format!(
+                    "Amount `{}` must be a positive number of whole dollars",
+                    transaction.amount
+                ),
+            ));
+        }
+
+        match to_target(&transaction.creditor_account) {
+            None => Some(StatusReason::new(
+                "AC03",
+                format!(
+                    "Invalid creditor account `{}`",
+                    transaction.creditor_account
+                ),
+            )),
+            Some(creditor) if creditor.is_account(debtor_account_id) => Some(StatusReason::new(
+                "NARR",
+                "Debtor and creditor account must differ",
+            )),
+            Some(_) => None,
+        }
+    }
+
+    async fn execute(
+        &self,
+        initiation: &PaymentInitiation,
+        debtor_account_id: &AccountId,
+        transaction: &CreditTransferTransaction,
+        initiator: Option<&Principal>,
+        audit_context: &AuditContext,
+    ) -> TransactionStatus {
+        let creditor = to_target(&transaction.creditor_account)
+            .expect("expected creditor account to be validated");
+        let amount = to_whole_amount(transaction).expect("expected amount to be validated");
+
+        let mut command = SendMoneyCommand::new_to_target(
+            debtor_account_id.clone(),
+            creditor,
+            money!(amount, "AUD"),
+        )
+        .with_reference(
+            transaction
+                .remittance_information
+                .clone()
+                .unwrap_or_else(|| transaction.end_to_end_id.clone()),
+        )
+        // the message id can't be imported twice, the key guards against executing a
+        // transaction twice should the import be interrupted and retried
+        .with_idempotency_key(IdempotencyKey(format!(
+            "pain.001:{}:{}",
+            initiation.message_id, transaction.end_to_end_id
+        )));
+        command.initiator = initiator.cloned();
+        command.audit_context = audit_context.clone();
+
+        let result = self.send_money_use_case.send_money(&command).await;
+
+        let (status, reason, transfer_id) = match result {
+            Ok(transfer_id) => (
+                PaymentStatus::AcceptedSettlementCompleted,
+                None,
+                Some(transfer_id),
+            ),
+            Err(err) => (PaymentStatus::Rejected, Some(to_reason(&err)), None),
+        };
+
+        TransactionStatus {
+            instruction_id: transaction.instruction_id.clone(),
+            end_to_end_id: transaction.end_to_end_id.clone(),
+            status,
+            reason,
+            transfer_id,
+        }
+    }
+}
+
+fn to_account_id(account: &str) -> Option<AccountId> {
+    account
+        .trim()
+        .parse::<i32>()
+        .ok()
+        .filter(|account_id| *account_id > 0)
+        .map(AccountId)
+}
+
+/// Creditors are either addressed by their internal account id or by their IBAN.
+fn to_target(account: &str) -> Option<TransferTarget> {
+    to_account_id(account)
+        .map(TransferTarget::Account)
+        .or_else(|| {
+            AccountIdentifier::iban(account)
+                .ok()
+                .map(TransferTarget::Identifier)
+        })
+}
+
+fn to_whole_amount(transaction: &CreditTransferTransaction) -> Option<i64> {
+    use rust_decimal::prelude::*;
+
+    if transaction.amount.is_sign_negative() || !transaction.amount.fract().is_zero() {
+        return None;
+    }
+
+    transaction.amount.to_i64()
+}
+
+fn to_reason(err: &anyhow::Error) -> StatusReason {
+    let code = match (
+        err.downcast_ref::<ServiceError>(),
+        err.downcast_ref::<AccountError>(),
+    ) {
+        (Some(ServiceError::ThresholdExceededException { .. }), _) => "AM02",
+        (Some(ServiceError::InvalidAmount(_)), _) => "AM12",
+        (Some(ServiceError::UnknownAccountIdentifier(_)), _) => "AC03",
+        (_, Some(AccountError::MayWithdrawFailed(_))) => "AM04",
+        _ => "NARR",
+    };
+
+    StatusReason::new(code, err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImportPaymentInitiationService;
+    use crate::application::port::incoming::import_payment_initiation_use_case::ImportPaymentInitiationUseCase;
+    use crate::application::port::incoming::send_money_use_case::{
+        SendMoneyCommand, SendMoneyUseCase,
+    };
+    use crate::application::port::outgoing::payment_initiation_port::PaymentInitiationPort;
+    use crate::domain::account::AccountError;
+    use crate::domain::audit::AuditContext;
+    use crate::domain::payment_initiation::{
+        CreditTransferTransaction, PaymentInitiation, PaymentInstruction, PaymentStatus,
+    };
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use rust_decimal::Decimal;
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn executes_every_transaction_and_reports_each() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let report = service
+            .import_payment_initiation(
+                &given_an_initiation(
+                    "MSG-1",
+                    vec![
+                        given_a_transaction("E2E-1", "42", 300),
+                        given_a_transaction("E2E-2", "43", 5000),
+                    ],
+                ),
+                None,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        let transactions = &report.payments[0].transactions;
+        assert_eq!(report.status, PaymentStatus::PartiallyAccepted);
+        assert_eq!(
+            transactions[0].status,
+            PaymentStatus::AcceptedSettlementCompleted
+        );
+        assert_eq!(transactions[0].transfer_id, Some(TransferId(1)));
+        assert_eq!(transactions[1].status, PaymentStatus::Rejected);
+        assert_eq!(transactions[1].reason.as_ref().unwrap().code, "AM04");
+        assert_eq!(send_money_use_case.invocations(), 2);
+    }
+
+    #[async_std::test]
+    async fn one_invalid_transaction_rejects_the_whole_file() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let mut invalid = given_a_transaction("E2E-2", "43", 300);
+        invalid.currency = String::from("EUR");
+
+        let report = service
+            .import_payment_initiation(
+                &given_an_initiation(
+                    "MSG-1",
+                    vec![given_a_transaction("E2E-1", "42", 300), invalid],
+                ),
+                None,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        let transactions = &report.payments[0].transactions;
+        assert_eq!(report.status, PaymentStatus::Rejected);
+        assert_eq!(transactions[0].reason, None);
+        assert_eq!(transactions[1].reason.as_ref().unwrap().code, "AM03");
+        assert_eq!(send_money_use_case.invocations(), 0);
+    }
+
+    #[async_std::test]
+    async fn creditor_may_be_addressed_by_iban() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let accepted = service
+            .import_payment_initiation(
+                &given_an_initiation(
+                    "MSG-1",
+                    vec![given_a_transaction("E2E-1", "DE89370400440532013000", 300)],
+                ),
+                None,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+        let rejected = service
+            .import_payment_initiation(
+                &given_an_initiation(
+                    "MSG-2",
+                    vec![given_a_transaction("E2E-1", "DE88370400440532013000", 300)],
+                ),
+                None,
+                &AuditContext::default(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(accepted.status, PaymentStatus::AcceptedSettlementCompleted);
+        assert_eq!(rejected.status, PaymentStatus::Rejected);
+        assert_eq!(
+            rejected.payments[0].transactions[0]
+                .reason
+                .as_ref()
+                .unwrap()
+                .code,
+            "AC03"
+        );
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    #[async_std::test]
+    async fn mismatching_control_sum_rejects_the_whole_file() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let mut initiation =
+            given_an_initiation("MSG-1", vec![given_a_transaction("E2E-1", "42", 300)]);
+        initiation.control_sum = Some(Decimal::new(301, 0));
+
+        let report = service
+            .import_payment_initiation(&initiation, None, &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.status, PaymentStatus::Rejected);
+        assert_eq!(report.reason.unwrap().code, "AM10");
+        assert_eq!(send_money_use_case.invocations(), 0);
+    }
+
+    #[async_std::test]
+    async fn duplicate_message_id_is_rejected() {
+        let send_money_use_case = MockSendMoneyUseCase::default();
+        let service = given_a_service(&send_money_use_case);
+
+        let initiation =
+            given_an_initiation("MSG-1", vec![given_a_transaction("E2E-1", "42", 300)]);
+
+        service
+            .import_payment_initiation(&initiation, None, &AuditContext::default())
+            .await
+            .unwrap();
+        let report = service
+            .import_payment_initiation(&initiation, None, &AuditContext::default())
+            .await
+            .unwrap();
+
+        assert_eq!(report.status, PaymentStatus::Rejected);
+        assert_eq!(report.reason.unwrap().code, "DU01");
+        assert_eq!(send_money_use_case.invocations(), 1);
+    }
+
+    fn given_a_service(
+        send_money_use_case: &MockSendMoneyUseCase,
+    ) -> ImportPaymentInitiationService {
+        ImportPaymentInitiationService::new(
+            Box::new(send_money_use_case.clone()),
+            Box::new(MockPaymentInitiationPort::default()),
+        )
+    }
+
+    fn given_an_initiation(
+        message_id: &str,
+        transactions: Vec<CreditTransferTransaction>,
+    ) -> PaymentInitiation {
+        PaymentInitiation {
+            message_id: String::from(message_id),
+            number_of_transactions: transactions.len().to_string(),
+            control_sum: None,
+            payments: vec![PaymentInstruction {
+                payment_information_id: String::from("PMT-1"),
+                debtor_account: String::from("41"),
+                transactions,
+            }],
+        }
+    }
+
+    fn given_a_transaction(
+        end_to_end_id: &str,
+        creditor_account: &str,
+        amount: i64,
+    ) -> CreditTransferTransaction {
+        CreditTransferTransaction {
+            instruction_id: None,
+            end_to_end_id: String::from(end_to_end_id),
+            creditor_account: String::from(creditor_account),
+            amount: Decimal::new(amount, 0),
+            currency: String::from("AUD"),
+            remittance_information: None,
+        }
+    }
+
+    /// Sends anything up to 1000, fails for larger amounts as if the balance was too low.
+    #[derive(Debug, Default, Clone)]
+    struct MockSendMoneyUseCase {
+        invocations: Arc<Mutex<i32>>,
+    }
+
+    impl MockSendMoneyUseCase {
+        fn invocations(&self) -> i32 {
+            *self.invocations.lock().unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl SendMoneyUseCase for MockSendMoneyUseCase {
+        async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId> {
+            use rust_decimal::prelude::*;
+
+            let mut invocations = self.invocations.lock().unwrap();
+            *invocations += 1;
+
+            if command.money.amount().to_i64().unwrap() > 1000 {
+                return Err(anyhow!(AccountError::MayWithdrawFailed(1000)));
+            }
+
+            Ok(TransferId(*invocations))
+        }
+    }
+
+    #[derive(Debug, Default)]
+    struct MockPaymentInitiationPort {
+        message_ids: Mutex<Vec<String>>,
+    }
+
+    #[async_trait]
+    impl PaymentInitiationPort for MockPaymentInitiationPort {
+        async fn claim_message_id(
+            &self,
+            message_id: &str,
+            _received_at: &DateTime<Utc>,
+        ) -> Result<bool> {
+            let mut message_ids = self.message_ids.lock().unwrap();
+
+            if message_ids.iter().any(|existing| existing == message_id) {
+                return Ok(false);
+            }
+            message_ids.push(String::from(message_id));
+
+            Ok(true)
+        }
+    }
+}
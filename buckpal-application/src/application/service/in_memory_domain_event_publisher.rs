@@ -0,0 +1,132 @@
+use crate::application::port::outgoing::domain_event_publisher::DomainEventPublisher;
+use crate::domain::domain_event::DomainEvent;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use std::sync::Arc;
+
+/// An in-process handler of domain events.
+#[async_trait]
+pub trait DomainEventSubscriber {
+    async fn handle(&self, event: &DomainEvent) -> Result<()>;
+}
+
+/// Delivers events to the subscribers living in the same process, one after the other. A
+/// failing subscriber does not keep the event from the others. Clones share their subscribers.
+#[derive(Clone, Default)]
+pub struct InMemoryDomainEventPublisher {
+    subscribers: Vec<Arc<dyn DomainEventSubscriber + Send + Sync>>,
+}
+
+impl InMemoryDomainEventPublisher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_subscriber(
+        mut self,
+        subscriber: Arc<dyn DomainEventSubscriber + Send + Sync>,
+    ) -> Self {
+        self.subscribers.push(subscriber);
+        self
+    }
+}
+
+#[async_trait]
+impl DomainEventPublisher for InMemoryDomainEventPublisher {
+    async fn publish(&self, events: &[DomainEvent]) -> Result<()> {
+        let mut failures = vec![];
+
+        for event in events {
+            for subscriber in &self.subscribers {
+                if let Err(err) = subscriber.handle(event).await {
+                    failures.push(format!("{}: {}", event.name(), err));
+                }
+            }
+        }
+
+        if failures.is_empty() {
+            Ok(())
+        } else {
+            Err(anyhow!(
+                "Delivering domain events failed: {}",
+                failures.join("; ")
+            ))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DomainEventSubscriber, InMemoryDomainEventPublisher};
+    use crate::application::port::outgoing::domain_event_publisher::DomainEventPublisher;
+    use crate::domain::account::AccountId;
+    use crate::domain::domain_event::DomainEvent;
+    use crate::domain::transfer::TransferId;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn failing_subscriber_does_not_keep_events_from_others() {
+        let failing = Arc::new(RecordingSubscriber::failing());
+        let recording = Arc::new(RecordingSubscriber::default());
+        let publisher = InMemoryDomainEventPublisher::new()
+            .with_subscriber(failing.clone())
+            .with_subscriber(recording.clone());
+
+        let events = vec![given_an_event(7), given_an_event(8)];
+        let err = publisher.publish(&events).await.unwrap_err();
+
+        assert_eq!(failing.received(), events);
+        assert_eq!(recording.received(), events);
+        assert_eq!(
+            err.to_string(),
+            "Delivering domain events failed: TransferCompleted: unavailable; \
+             TransferCompleted: unavailable"
+        );
+    }
+
+    fn given_an_event(transfer_id: i32) -> DomainEvent {
+        DomainEvent::TransferCompleted {
+            transfer_id: TransferId(transfer_id),
+            source_account_id: AccountId(1),
+            target_account_id: AccountId(2),
+            money: money!(500, "AUD"),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[derive(Default)]
+    struct RecordingSubscriber {
+        fails: bool,
+        events: Mutex<Vec<DomainEvent>>,
+    }
+
+    impl RecordingSubscriber {
+        fn failing() -> Self {
+            Self {
+                fails: true,
+                events: Mutex::new(vec![]),
+            }
+        }
+
+        fn received(&self) -> Vec<DomainEvent> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl DomainEventSubscriber for RecordingSubscriber {
+        async fn handle(&self, event: &DomainEvent) -> Result<()> {
+            self.events.lock().unwrap().push(event.clone());
+
+            if self.fails {
+                Err(anyhow!("unavailable"))
+            } else {
+                Ok(())
+            }
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use crate::application::port::incoming::list_activities_query::{
+    ActivityCursor, ActivityFilter, ActivityPage, ListActivitiesQuery,
+};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_activities_port::LoadActivitiesPort,
+};
+use crate::application::service::account_access::check_account_access;
+use crate::application::service::error::ServiceError;
+use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+/// The largest page that can be requested at once.
+const MAXIMUM_PAGE_SIZE: i64 = 100;
+
+pub struct ListActivitiesService {
+    load_activities_port: Box<dyn LoadActivitiesPort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl ListActivitiesService {
+    pub fn new(
+        load_activities_port: Box<dyn LoadActivitiesPort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_activities_port,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ListActivitiesQuery for ListActivitiesService {
+    async fn list_activities(
+        &self,
+        account_id: &AccountId,
+        filter: &ActivityFilter,
+        cursor: Option<&ActivityCursor>,
+        limit: i64,
+        principal: &Principal,
+    ) -> Result<ActivityPage> {
+        self.check_limit(limit)?;
+        self.check_filter(filter)?;
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
+
+        // one more than requested tells whether there is a next page
+        let mut activities = self
+            .load_activities_port
+            .load_activities(account_id, filter, cursor, limit + 1)
+            .await?;
+
+        let next_cursor = if activities.len() as i64 > limit {
+            activities.truncate(limit as usize);
+            activities.last().map(|activity| ActivityCursor {
+                timestamp: activity.timestamp,
+                activity_id: activity
+                    .id
+                    .clone()
+                    .expect("expected persisted activity to have an ID"),
+            })
+        } else {
+            None
+        };
+
+        Ok(ActivityPage {
+            activities,
+            next_cursor,
+        })
+    }
+}
+
+impl ListActivitiesService {
+    fn check_limit(&self, limit: i64) -> Result<()> {
+        if !(1..=MAXIMUM_PAGE_SIZE).contains(&limit) {
+            return Err(anyhow!(ServiceError::InvalidPageSize {
+                requested: limit,
+                maximum: MAXIMUM_PAGE_SIZE,
+            }));
+        }
+
+        Ok(())
+    }
+
+    fn check_filter(&self, filter: &ActivityFilter) -> Result<()> {
+        if let (Some(from), Some(to)) = (filter.from, filter.to) {
+            if from >= to {
+                return Err(anyhow!(ServiceError::InvalidActivityFilter(String::from(
+                    "`from` must be before `to`"
+                ))));
+            }
+        }
+
+        if let (Some(min_amount), Some(max_amount)) = (&filter.min_amount, &filter.max_amount) {
+            if min_amount > max_amount {
+                return Err(anyhow!(ServiceError::InvalidActivityFilter(String::from(
+                    "minimum amount must not exceed maximum amount"
+                ))));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListActivitiesService;
+    use crate::application::port::incoming::list_activities_query::{
+        ActivityCursor, ActivityFilter, ListActivitiesQuery,
+    };
+    use crate::application::port::outgoing::load_activities_port::LoadActivitiesPort;
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::{Activity, ActivityId};
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use chrono::{Duration, Utc};
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn returns_cursor_when_more_activities_exist() {
+        let service = given_a_service_with_activities(3);
+
+        let page = service
+            .list_activities(
+                &AccountId(42),
+                &ActivityFilter::default(),
+                None,
+                2,
+                &default_principal(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.activities.len(), 2);
+        assert_eq!(
+            page.next_cursor.map(|cursor| cursor.activity_id),
+            Some(ActivityId(2))
+        );
+    }
+
+    #[async_std::test]
+    async fn last_page_has_no_cursor() {
+        let service = given_a_service_with_activities(2);
+
+        let page = service
+            .list_activities(
+                &AccountId(42),
+                &ActivityFilter::default(),
+                None,
+                2,
+                &default_principal(),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.activities.len(), 2);
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[async_std::test]
+    async fn rejects_inverted_amount_range() {
+        let service = given_a_service_with_activities(2);
+
+        let filter = ActivityFilter {
+            min_amount: Some(money!(500, "AUD")),
+            max_amount: Some(money!(100, "AUD")),
+            ..ActivityFilter::default()
+        };
+
+        let success = service
+            .list_activities(&AccountId(42), &filter, None, 2, &default_principal())
+            .await
+            .is_ok();
+
+        assert_eq!(success, false);
+    }
+
+    #[async_std::test]
+    async fn rejects_oversized_page() {
+        let service = given_a_service_with_activities(2);
+
+        let success = service
+            .list_activities(
+                &AccountId(42),
+                &ActivityFilter::default(),
+                None,
+                101,
+                &default_principal(),
+            )
+            .await
+            .is_ok();
+
+        assert_eq!(success, false);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let service = given_a_service_with_activities(2);
+
+        let err = service
+            .list_activities(
+                &AccountId(42),
+                &ActivityFilter::default(),
+                None,
+                2,
+                &Principal::User(String::from("mallory")),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+    }
+
+    fn given_a_service_with_activities(count: i32) -> ListActivitiesService {
+        let now = Utc::now();
+        let activities = (1..=count)
+            .map(|id| {
+                ActivityBuilder::default_activity()
+                    .with_activity_id(&ActivityId(id))
+                    .with_timestamp(&(now - Duration::minutes(id as i64)))
+                    .build()
+            })
+            .collect();
+
+        ListActivitiesService::new(
+            Box::new(MockLoadActivitiesPort { activities }),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        )
+    }
+
+    struct MockLoadActivitiesPort {
+        activities: Vec<Activity>,
+    }
+
+    #[async_trait]
+    impl LoadActivitiesPort for MockLoadActivitiesPort {
+        async fn load_activities(
+            &self,
+            _account_id: &AccountId,
+            _filter: &ActivityFilter,
+            _cursor: Option<&ActivityCursor>,
+            limit: i64,
+        ) -> Result<Vec<Activity>> {
+            Ok(self
+                .activities
+                .iter()
+                .take(limit as usize)
+                .cloned()
+                .collect())
+        }
+    }
+}
@@ -0,0 +1,129 @@
+use crate::application::port::incoming::list_audit_log_query::{
+    AuditLogFilter, AuditLogPage, ListAuditLogQuery,
+};
+use crate::application::port::outgoing::audit_port::AuditPort;
+use crate::application::service::error::ServiceError;
+use crate::domain::audit::AuditEntryId;
+use crate::domain::principal::Principal;
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+/// The largest page that can be requested at once.
+const MAXIMUM_PAGE_SIZE: i64 = 500;
+
+/// Lists the audit log to the principals allowed to read it only, nobody if there are none.
+pub struct ListAuditLogService {
+    audit_port: Box<dyn AuditPort + Send + Sync>,
+    readers: Vec<Principal>,
+}
+
+impl ListAuditLogService {
+    pub fn new(audit_port: Box<dyn AuditPort + Send + Sync>, readers: Vec<Principal>) -> Self {
+        Self {
+            audit_port,
+            readers,
+        }
+    }
+}
+
+#[async_trait]
+impl ListAuditLogQuery for ListAuditLogService {
+    async fn list_audit_log(
+        &self,
+        filter: &AuditLogFilter,
+        before: Option<&AuditEntryId>,
+        limit: i64,
+        principal: &Principal,
+    ) -> Result<AuditLogPage> {
+        if !self.readers.contains(principal) {
+            return Err(anyhow!(ServiceError::AuditLogAccessDenied(
+                principal.clone()
+            )));
+        }
+
+        if !(1..=MAXIMUM_PAGE_SIZE).contains(&limit) {
+            return Err(anyhow!(ServiceError::InvalidPageSize {
+                requested: limit,
+                maximum: MAXIMUM_PAGE_SIZE,
+            }));
+        }
+
+        if let (Some(from), Some(to)) = (filter.from, filter.to) {
+            if from >= to {
+                return Err(anyhow!(ServiceError::InvalidDateRange(String::from(
+                    "`from` must be before `to`"
+                ))));
+            }
+        }
+
+        // one more than requested tells whether there is a next page
+        let mut entries = self
+            .audit_port
+            .load_entries(filter, before, limit + 1)
+            .await?;
+
+        let next_cursor = if entries.len() as i64 > limit {
+            entries.truncate(limit as usize);
+            entries.last().and_then(|entry| entry.id.clone())
+        } else {
+            None
+        };
+
+        Ok(AuditLogPage {
+            entries,
+            next_cursor,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ListAuditLogService;
+    use crate::application::port::incoming::list_audit_log_query::{
+        AuditLogFilter, ListAuditLogQuery,
+    };
+    use crate::application::service::audit_recorder::audit_test_data::RecordingAuditPort;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::principal::Principal;
+
+    #[async_std::test]
+    async fn readers_can_list_the_audit_log() {
+        let service = given_a_service_readable_by(Principal::Service(String::from("compliance")));
+
+        let page = service
+            .list_audit_log(
+                &AuditLogFilter::default(),
+                None,
+                10,
+                &Principal::Service(String::from("compliance")),
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(page.next_cursor, None);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let service = given_a_service_readable_by(Principal::Service(String::from("compliance")));
+
+        let err = service
+            .list_audit_log(
+                &AuditLogFilter::default(),
+                None,
+                10,
+                &Principal::User(String::from("mallory")),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AuditLogAccessDenied(_))
+        ));
+    }
+
+    fn given_a_service_readable_by(reader: Principal) -> ListAuditLogService {
+        ListAuditLogService::new(Box::new(RecordingAuditPort::default()), vec![reader])
+    }
+}
@@ -0,0 +1,73 @@
+use crate::application::port::incoming::manage_webhooks_use_case::{
+    ManageWebhooksUseCase, RegisterWebhookCommand,
+};
+use crate::application::port::outgoing::webhook_port::WebhookPort;
+use crate::application::service::error::ServiceError;
+use crate::domain::audit::AuditContext;
+use crate::domain::webhook::{WebhookDelivery, WebhookSubscription, WebhookSubscriptionId};
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct ManageWebhooksService {
+    webhook_port: Box<dyn WebhookPort + Send + Sync>,
+}
+
+impl ManageWebhooksService {
+    pub fn new(webhook_port: Box<dyn WebhookPort + Send + Sync>) -> Self {
+        Self { webhook_port }
+    }
+}
+
+#[async_trait]
+impl ManageWebhooksUseCase for ManageWebhooksService {
+    async fn register_webhook(
+        &self,
+        command: &RegisterWebhookCommand,
+    ) -> Result<WebhookSubscription> {
+        let subscription = WebhookSubscription::new(
+            &command.url,
+            &command.secret,
+            &command.event_types,
+            Utc::now(),
+        )?;
+
+        self.webhook_port.save_subscription(&subscription).await
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<WebhookSubscription>> {
+        self.webhook_port.load_subscriptions().await
+    }
+
+    async fn delete_webhook(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+        _audit_context: &AuditContext,
+    ) -> Result<()> {
+        if !self
+            .webhook_port
+            .delete_subscription(subscription_id)
+            .await?
+        {
+            return Err(anyhow!(ServiceError::WebhookNotFound(subscription_id.0)));
+        }
+
+        Ok(())
+    }
+
+    async fn list_deliveries(
+        &self,
+        subscription_id: &WebhookSubscriptionId,
+    ) -> Result<Vec<WebhookDelivery>> {
+        if self
+            .webhook_port
+            .load_subscription(subscription_id)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow!(ServiceError::WebhookNotFound(subscription_id.0)));
+        }
+
+        self.webhook_port.load_deliveries(subscription_id).await
+    }
+}
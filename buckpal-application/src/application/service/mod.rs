@@ -1,5 +1,42 @@
+pub mod aba_properties;
+pub mod account_access;
+pub mod audit_recorder;
+pub mod audited_authorize_transfer_service;
+pub mod audited_batch_send_money_service;
+pub mod audited_capture_transfer_service;
+pub mod audited_deliver_webhooks_service;
+pub mod audited_get_account_balance_service;
+pub mod audited_import_payment_initiation_service;
+pub mod audited_manage_webhooks_service;
+pub mod audited_reverse_transfer_service;
+pub mod audited_send_money_service;
+pub mod audited_void_hold_service;
+pub mod authorize_transfer_service;
+pub mod balance_history_service;
+pub mod batch_send_money_service;
+pub mod capture_transfer_service;
+pub mod checkpoint_activity_chains_service;
+pub mod deliver_webhooks_service;
 pub mod error;
+pub mod export_external_payments_service;
+pub mod generate_statement_service;
 pub mod get_account_balance_service;
+pub mod get_transfer_receipt_service;
+pub mod get_transfer_service;
+pub mod idempotent_send_money_service;
+pub mod import_payment_initiation_service;
+pub mod in_memory_domain_event_publisher;
+pub mod list_activities_service;
+pub mod list_audit_log_service;
+pub mod manage_webhooks_service;
 pub mod money_transfer_properties;
 pub mod no_op_account_lock;
+pub mod no_op_domain_event_publisher;
+pub mod reconcile_ledger_service;
+pub mod reverse_transfer_service;
 pub mod send_money_service;
+pub mod verify_account_balances_service;
+pub mod verify_activity_chain_service;
+pub mod void_hold_service;
+pub mod webhook_event_subscriber;
+pub mod webhook_properties;
@@ -1,14 +1,47 @@
+use chrono::Duration;
 use rusty_money::{money, Money};
 
-#[derive(Debug, Eq, PartialEq, Clone, Default)]
-pub struct MoneyTransferProperties {}
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MoneyTransferProperties {
+    hold_expiry: Duration,
+    idempotency_key_retention: Duration,
+}
+
+impl Default for MoneyTransferProperties {
+    fn default() -> Self {
+        Self::new()
+    }
+}
 
 impl MoneyTransferProperties {
     pub fn new() -> Self {
-        Self {}
+        Self {
+            hold_expiry: Duration::days(7),
+            idempotency_key_retention: Duration::hours(24),
+        }
+    }
+
+    /// Overrides how long an authorized hold reserves money before it expires.
+    pub fn with_hold_expiry(mut self, hold_expiry: Duration) -> Self {
+        self.hold_expiry = hold_expiry;
+        self
+    }
+
+    /// Overrides how long an idempotency key is remembered before it can be used again.
+    pub fn with_idempotency_key_retention(mut self, idempotency_key_retention: Duration) -> Self {
+        self.idempotency_key_retention = idempotency_key_retention;
+        self
     }
 
     pub fn maximum_transfer_threshold(&self) -> Money {
         money!(1_000_000, "AUD")
     }
+
+    pub fn hold_expiry(&self) -> Duration {
+        self.hold_expiry
+    }
+
+    pub fn idempotency_key_retention(&self) -> Duration {
+        self.idempotency_key_retention
+    }
 }
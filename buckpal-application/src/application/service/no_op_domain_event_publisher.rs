@@ -0,0 +1,17 @@
+use crate::application::port::outgoing::domain_event_publisher::DomainEventPublisher;
+use crate::domain::domain_event::DomainEvent;
+use anyhow::Result;
+use async_trait::async_trait;
+
+/// Publishes nothing, for when the events reach their subscribers some other way, e.g. through
+/// a transactional outbox.
+#[derive(Debug, Clone, Default)]
+pub struct NoOpDomainEventPublisher {}
+
+#[async_trait]
+impl DomainEventPublisher for NoOpDomainEventPublisher {
+    async fn publish(&self, _events: &[DomainEvent]) -> Result<()> {
+        // do nothing
+        Ok(())
+    }
+}
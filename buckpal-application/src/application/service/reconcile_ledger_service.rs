@@ -0,0 +1,126 @@
+use crate::application::port::incoming::reconcile_ledger_use_case::ReconcileLedgerUseCase;
+use crate::application::port::outgoing::reconciliation_port::ReconciliationPort;
+use crate::domain::reconciliation::{ReconciliationReport, ReconciliationViolation};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+
+pub struct ReconcileLedgerService {
+    reconciliation_port: Box<dyn ReconciliationPort + Send + Sync>,
+}
+
+impl ReconcileLedgerService {
+    pub fn new(reconciliation_port: Box<dyn ReconciliationPort + Send + Sync>) -> Self {
+        Self {
+            reconciliation_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ReconcileLedgerUseCase for ReconcileLedgerService {
+    async fn reconcile_ledger(&self) -> Result<ReconciliationReport> {
+        let reconciled_at = Utc::now();
+
+        let mut violations: Vec<ReconciliationViolation> = self
+            .reconciliation_port
+            .load_unmatched_activities()
+            .await?
+            .into_iter()
+            .map(ReconciliationViolation::UnmatchedActivity)
+            .collect();
+
+        let totals = self.reconciliation_port.load_ledger_totals().await?;
+        if !totals.is_conserved() {
+            violations.push(ReconciliationViolation::MoneyNotConserved(totals));
+        }
+
+        violations.extend(
+            self.reconciliation_port
+                .load_negative_balances()
+                .await?
+                .into_iter()
+                .map(
+                    |(account_id, balance)| ReconciliationViolation::NegativeBalance {
+                        account_id,
+                        balance,
+                    },
+                ),
+        );
+
+        Ok(ReconciliationReport::new(reconciled_at, violations))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReconcileLedgerService;
+    use crate::application::port::incoming::reconcile_ledger_use_case::ReconcileLedgerUseCase;
+    use crate::application::port::outgoing::reconciliation_port::ReconciliationPort;
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::Activity;
+    use crate::domain::reconciliation::{LedgerTotals, ReconciliationViolation};
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use rusty_money::{money, Money};
+
+    #[async_std::test]
+    async fn consistent_ledger_is_clean() {
+        let service = ReconcileLedgerService::new(Box::new(MockReconciliationPort {
+            unmatched_activities: vec![],
+            totals: LedgerTotals::new(money!(1500, "AUD"), money!(1500, "AUD")),
+            negative_balances: vec![],
+        }));
+
+        let report = service.reconcile_ledger().await.unwrap();
+
+        assert!(report.is_clean());
+    }
+
+    #[async_std::test]
+    async fn reports_every_violation() {
+        let unmatched_activity = ActivityBuilder::default_activity().build();
+        let totals = LedgerTotals::new(money!(1500, "AUD"), money!(2499, "AUD"));
+        let service = ReconcileLedgerService::new(Box::new(MockReconciliationPort {
+            unmatched_activities: vec![unmatched_activity.clone()],
+            totals: totals.clone(),
+            negative_balances: vec![(AccountId(42), money!(-999, "AUD"))],
+        }));
+
+        let report = service.reconcile_ledger().await.unwrap();
+
+        assert_eq!(
+            report.violations,
+            vec![
+                ReconciliationViolation::UnmatchedActivity(unmatched_activity),
+                ReconciliationViolation::MoneyNotConserved(totals),
+                ReconciliationViolation::NegativeBalance {
+                    account_id: AccountId(42),
+                    balance: money!(-999, "AUD"),
+                },
+            ]
+        );
+    }
+
+    struct MockReconciliationPort {
+        unmatched_activities: Vec<Activity>,
+        totals: LedgerTotals,
+        negative_balances: Vec<(AccountId, Money)>,
+    }
+
+    #[async_trait]
+    impl ReconciliationPort for MockReconciliationPort {
+        async fn load_unmatched_activities(&self) -> Result<Vec<Activity>> {
+            Ok(self.unmatched_activities.clone())
+        }
+
+        async fn load_ledger_totals(&self) -> Result<LedgerTotals> {
+            Ok(self.totals.clone())
+        }
+
+        async fn load_negative_balances(&self) -> Result<Vec<(AccountId, Money)>> {
+            Ok(self.negative_balances.clone())
+        }
+    }
+}
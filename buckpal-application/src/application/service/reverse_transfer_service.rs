@@ -0,0 +1,420 @@
+use crate::application::port::incoming::reverse_transfer_use_case::{
+    ReverseTransferCommand, ReverseTransferUseCase,
+};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, account_lock::AccountLock,
+    domain_event_publisher::DomainEventPublisher, load_account_port::LoadAccountPort,
+    load_transfer_port::LoadTransferPort, update_account_state_port::UpdateAccountStatePort,
+    update_transfer_state_port::UpdateTransferStatePort,
+};
+use crate::application::service::account_access::{check_transfer_access, initiator};
+use crate::domain::account::Account;
+use crate::domain::transfer::{Transfer, TransferId};
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct ReverseTransferService {
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    account_lock: Box<dyn AccountLock + Send + Sync>,
+    update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+    load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+    update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+    domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl ReverseTransferService {
+    pub fn new(
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        account_lock: Box<dyn AccountLock + Send + Sync>,
+        update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+        load_transfer_port: Box<dyn LoadTransferPort + Send + Sync>,
+        update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+        domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_account_port,
+            account_lock,
+            update_account_state_port,
+            load_transfer_port,
+            update_transfer_state_port,
+            domain_event_publisher,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl ReverseTransferUseCase for ReverseTransferService {
+    async fn reverse_transfer(&self, command: &ReverseTransferCommand) -> Result<TransferId> {
+        use chrono::{Duration, Utc};
+
+        let original = self
+            .load_transfer_port
+            .load_transfer(&command.transfer_id)
+            .await?;
+
+        // the sender may take a transfer back as well as the recipient may return it
+        check_transfer_access(
+            self.account_access_port.as_ref(),
+            &command.transfer_id,
+            &original,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
+        let reversals = self
+            .load_transfer_port
+            .load_reversals(&command.transfer_id)
+            .await?;
+
+        let money = command
+            .money
+            .clone()
+            .unwrap_or_else(|| original.reversible_amount(&reversals));
+
+        let reversal = original.reverse(&money, &reversals, Utc::now())?;
+
+        let baseline_date = Utc::now() - Duration::days(10);
+
+        // the reversal debits the account that was credited by the original transfer
+        let mut source_account = self
+            .load_account_port
+            .load_account(&reversal.source_account_id, &baseline_date)
+            .await?;
+
+        let mut target_account = self
+            .load_account_port
+            .load_account(&reversal.target_account_id, &baseline_date)
+            .await?;
+
+        let source_account_id = reversal.source_account_id.clone();
+        let target_account_id = reversal.target_account_id.clone();
+
+        let mut reversal = self
+            .update_transfer_state_port
+            .create_transfer(&reversal)
+            .await?;
+        let reversal_id = reversal
+            .clone()
+            .id
+            .expect("expected reversal ID not to be empty");
+
+        self.account_lock.lock_account(&source_account_id);
+        if let Err(err) = source_account.withdraw(
+            &money,
+            &target_account_id,
+            &reversal_id,
+            command.initiator.as_ref(),
+        ) {
+            self.account_lock.release_account(&source_account_id);
+            self.fail_transfer(&mut reversal).await;
+            return Err(err);
+        }
+
+        self.account_lock.lock_account(&target_account_id);
+        if let Err(err) = target_account.deposit(
+            &money,
+            &source_account_id,
+            &reversal_id,
+            command.initiator.as_ref(),
+        ) {
+            self.account_lock.release_account(&source_account_id);
+            self.account_lock.release_account(&target_account_id);
+            self.fail_transfer(&mut reversal).await;
+            return Err(err);
+        }
+
+        let updated = self.update_accounts(&source_account, &target_account).await;
+
+        self.account_lock.release_account(&source_account_id);
+        self.account_lock.release_account(&target_account_id);
+
+        if let Err(err) = updated {
+            self.fail_transfer(&mut reversal).await;
+            return Err(err);
+        }
+
+        reversal.complete();
+        self.update_transfer_state_port
+            .update_transfer(&reversal)
+            .await?;
+
+        let mut events = source_account.take_events();
+        events.append(&mut target_account.take_events());
+        events.append(&mut reversal.take_events());
+
+        // the reversal is committed at this point, a failing subscriber must not report it as
+        // failed to the client
+        if let Err(err) = self.domain_event_publisher.publish(&events).await {
+            log::error!(
+                "Publishing the events of reversal {} failed: {}",
+                reversal_id.0,
+                err
+            );
+        }
+
+        Ok(reversal_id)
+    }
+}
+
+impl ReverseTransferService {
+    async fn update_accounts(
+        &self,
+        source_account: &Account,
+        target_account: &Account,
+    ) -> Result<()> {
+        self.update_account_state_port
+            .update_activities(source_account)
+            .await?;
+        self.update_account_state_port
+            .update_activities(target_account)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Marks the reversal as failed. The caller is told why the reversal failed, a reversal that
+    /// can't be marked as well is only logged.
+    async fn fail_transfer(&self, transfer: &mut Transfer) {
+        transfer.fail();
+        if let Err(err) = self
+            .update_transfer_state_port
+            .update_transfer(transfer)
+            .await
+        {
+            log::error!(
+                "Marking reversal {:?} as failed failed: {}",
+                transfer.id.as_ref().map(|id| id.0),
+                err
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ReverseTransferService;
+    use crate::application::port::incoming::reverse_transfer_use_case::{
+        ReverseTransferCommand, ReverseTransferUseCase,
+    };
+    use crate::application::port::outgoing::{
+        load_account_port::LoadAccountPort, load_transfer_port::LoadTransferPort,
+        update_account_state_port::UpdateAccountStatePort,
+        update_transfer_state_port::UpdateTransferStatePort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::application::service::no_op_account_lock::NoOpAccountLock;
+    use crate::application::service::no_op_domain_event_publisher::NoOpDomainEventPublisher;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::Activity;
+    use crate::domain::activity_window::ActivityWindow;
+    use crate::domain::hold::Hold;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::transfer_test_data::TransferBuilder;
+    use crate::domain::transfer::{Transfer, TransferId, TransferStatus};
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Utc};
+    use rusty_money::{money, Money};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn partial_reversal_sends_money_back() {
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&money!(1000, "AUD"), &transfer_state);
+
+        let command = ReverseTransferCommand::new(TransferId(7), Some(money!(200, "AUD")))
+            .with_initiator(default_principal());
+
+        let reversal_id = service.reverse_transfer(&command).await.unwrap();
+
+        assert_eq!(reversal_id, TransferId(8));
+        assert_eq!(
+            transfer_state.updated_status(&TransferId(8)),
+            Some(TransferStatus::Completed)
+        );
+        // the status of the original is brought up to date along with the reversal
+        assert_eq!(transfer_state.updated_status(&TransferId(7)), None);
+    }
+
+    #[async_std::test]
+    async fn reversal_fails_when_target_account_lacks_funds() {
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&money!(100, "AUD"), &transfer_state);
+
+        let command =
+            ReverseTransferCommand::new(TransferId(7), None).with_initiator(default_principal());
+
+        let success = service.reverse_transfer(&command).await.is_ok();
+
+        assert_eq!(success, false);
+        assert_eq!(
+            transfer_state.updated_status(&TransferId(8)),
+            Some(TransferStatus::Failed)
+        );
+        assert_eq!(transfer_state.updated_status(&TransferId(7)), None);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_may_not_reverse_the_transfer() {
+        let transfer_state = MockTransferState::default();
+        let service = given_a_service(&money!(1000, "AUD"), &transfer_state);
+
+        let command = ReverseTransferCommand::new(TransferId(7), None)
+            .with_initiator(Principal::User(String::from("mallory")));
+
+        let err = service.reverse_transfer(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::TransferAccessDenied { transfer_id: 7, .. })
+        ));
+        assert_eq!(transfer_state.updated_status(&TransferId(8)), None);
+    }
+
+    fn given_a_service(
+        target_account_balance: &Money,
+        transfer_state: &MockTransferState,
+    ) -> ReverseTransferService {
+        let original = TransferBuilder::default_transfer()
+            .with_transfer_id(&TransferId(7))
+            .with_source_account(&AccountId(41))
+            .with_target_account(&AccountId(42))
+            .with_money(&money!(500, "AUD"))
+            .build();
+        transfer_state.transfers.lock().unwrap().push(original);
+
+        let load_account_port = MockLoadAccountPort {
+            available_accounts: vec![
+                given_an_account(&AccountId(41), &money!(0, "AUD")),
+                given_an_account(&AccountId(42), target_account_balance),
+            ],
+        };
+
+        ReverseTransferService::new(
+            Box::new(load_account_port),
+            Box::new(NoOpAccountLock::default()),
+            Box::new(MockUpdateAccountStatePort {}),
+            Box::new(transfer_state.clone()),
+            Box::new(transfer_state.clone()),
+            Box::new(NoOpDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(41)],
+            )),
+        )
+    }
+
+    fn given_an_account(id: &AccountId, baseline_balance: &Money) -> Account {
+        AccountBuilder::default_account()
+            .with_account_id(id)
+            .with_baseline_balance(baseline_balance)
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .build()
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockTransferState {
+        transfers: Arc<Mutex<Vec<Transfer>>>,
+        updates: Arc<Mutex<Vec<Transfer>>>,
+    }
+
+    impl MockTransferState {
+        fn updated_status(&self, transfer_id: &TransferId) -> Option<TransferStatus> {
+            self.updates
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|transfer| transfer.id.as_ref() == Some(transfer_id))
+                .map(|transfer| transfer.status)
+                .last()
+        }
+    }
+
+    #[async_trait]
+    impl LoadTransferPort for MockTransferState {
+        async fn load_transfer(&self, transfer_id: &TransferId) -> Result<Transfer> {
+            self.transfers
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|transfer| transfer.id.as_ref() == Some(transfer_id))
+                .cloned()
+                .ok_or(anyhow!("No matching transfer found from stub"))
+        }
+
+        async fn load_transfer_activities(
+            &self,
+            _transfer_id: &TransferId,
+        ) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn load_reversals(&self, transfer_id: &TransferId) -> Result<Vec<Transfer>> {
+            Ok(self
+                .transfers
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|transfer| transfer.reversed_transfer_id.as_ref() == Some(transfer_id))
+                .cloned()
+                .collect())
+        }
+    }
+
+    #[async_trait]
+    impl UpdateTransferStatePort for MockTransferState {
+        async fn create_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            let mut transfers = self.transfers.lock().unwrap();
+
+            let mut transfer = transfer.clone();
+            transfer.id = Some(TransferId(7 + transfers.len() as i32));
+            transfers.push(transfer.clone());
+
+            Ok(transfer)
+        }
+
+        async fn update_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            self.updates.lock().unwrap().push(transfer.clone());
+
+            Ok(transfer.clone())
+        }
+    }
+
+    struct MockUpdateAccountStatePort {}
+
+    #[async_trait]
+    impl UpdateAccountStatePort for MockUpdateAccountStatePort {
+        async fn update_activities(&self, _account: &Account) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn update_holds(&self, _account: &Account) -> Result<Vec<Hold>> {
+            Ok(vec![])
+        }
+    }
+
+    struct MockLoadAccountPort {
+        available_accounts: Vec<Account>,
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockLoadAccountPort {
+        async fn load_account(
+            &self,
+            account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            self.available_accounts
+                .iter()
+                .find(|account| account.id.as_ref() == Some(account_id))
+                .cloned()
+                .ok_or(anyhow!("No matching account found from stub"))
+        }
+    }
+}
@@ -1,17 +1,28 @@
-use crate::application::port::incoming::send_money_use_case::{SendMoneyCommand, SendMoneyUseCase};
+use crate::application::port::incoming::send_money_use_case::{
+    SendMoneyCommand, SendMoneyUseCase, TransferTarget,
+};
 use crate::application::port::outgoing::{
-    account_lock::AccountLock, load_account_port::LoadAccountPort,
-    update_account_state_port::UpdateAccountStatePort,
+    account_access_port::AccountAccessPort, account_lock::AccountLock,
+    domain_event_publisher::DomainEventPublisher, load_account_port::LoadAccountPort,
+    lookup_account_identifier_port::LookupAccountIdentifierPort,
+    record_transfer_port::RecordTransferPort, update_transfer_state_port::UpdateTransferStatePort,
 };
+use crate::application::service::account_access::{check_account_access, initiator};
 use crate::application::service::error::ServiceError;
 use crate::application::service::money_transfer_properties::MoneyTransferProperties;
+use crate::domain::account::AccountId;
+use crate::domain::transfer::{Transfer, TransferId};
 use anyhow::{anyhow, Result};
 use async_trait::async_trait;
 
 pub struct SendMoneyService {
     load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
     account_lock: Box<dyn AccountLock + Send + Sync>,
-    update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+    record_transfer_port: Box<dyn RecordTransferPort + Send + Sync>,
+    update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+    lookup_account_identifier_port: Box<dyn LookupAccountIdentifierPort + Send + Sync>,
+    domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
     money_transfer_properties: MoneyTransferProperties,
 }
 
@@ -19,13 +30,21 @@ impl SendMoneyService {
     pub fn new(
         load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
         account_lock: Box<dyn AccountLock + Send + Sync>,
-        update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+        record_transfer_port: Box<dyn RecordTransferPort + Send + Sync>,
+        update_transfer_state_port: Box<dyn UpdateTransferStatePort + Send + Sync>,
+        lookup_account_identifier_port: Box<dyn LookupAccountIdentifierPort + Send + Sync>,
+        domain_event_publisher: Box<dyn DomainEventPublisher + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
         money_transfer_properties: MoneyTransferProperties,
     ) -> Self {
         Self {
             load_account_port,
             account_lock,
-            update_account_state_port,
+            record_transfer_port,
+            update_transfer_state_port,
+            lookup_account_identifier_port,
+            domain_event_publisher,
+            account_access_port,
             money_transfer_properties,
         }
     }
@@ -33,13 +52,23 @@ impl SendMoneyService {
 
 #[async_trait]
 impl SendMoneyUseCase for SendMoneyService {
-    async fn send_money(&self, command: &SendMoneyCommand) -> Result<()> {
+    async fn send_money(&self, command: &SendMoneyCommand) -> Result<TransferId> {
         use chrono::{Duration, Utc};
 
+        self.check_amount(command)?;
+
         if let Err(err) = self.check_threshold(command) {
             return Err(err);
         }
 
+        // only the holders of the source account may debit it
+        check_account_access(
+            self.account_access_port.as_ref(),
+            &command.source_account_id,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
         let baseline_date = Utc::now() - Duration::days(10);
 
         let mut source_account = self
@@ -49,7 +78,7 @@ impl SendMoneyUseCase for SendMoneyService {
 
         let mut target_account = self
             .load_account_port
-            .load_account(&command.target_account_id, &baseline_date)
+            .load_account(&self.resolve_target(&command.target).await?, &baseline_date)
             .await?;
 
         let source_account_id = source_account
@@ -61,34 +90,118 @@ impl SendMoneyUseCase for SendMoneyService {
             .id
             .expect("expected target account ID not to be empty");
 
+        // nothing is persisted until both legs are known, the legs only need the id
+        let transfer_id = self.record_transfer_port.reserve_transfer_id().await?;
+        let mut transfer = Transfer::new_without_id(
+            source_account_id.clone(),
+            target_account_id.clone(),
+            Utc::now(),
+            command.money.clone(),
+        )
+        .with_reference(command.reference.clone());
+
         self.account_lock.lock_account(&source_account_id);
-        if let Err(err) = source_account.withdraw(&command.money, &target_account_id) {
+        if let Err(err) = source_account.withdraw(
+            &command.money,
+            &target_account_id,
+            &transfer_id,
+            command.initiator.as_ref(),
+        ) {
             self.account_lock.release_account(&source_account_id);
+            self.fail_transfer(&mut transfer).await;
             return Err(err);
         }
 
         self.account_lock.lock_account(&target_account_id);
-        if let Err(err) = target_account.deposit(&command.money, &source_account_id) {
+        if let Err(err) = target_account.deposit(
+            &command.money,
+            &source_account_id,
+            &transfer_id,
+            command.initiator.as_ref(),
+        ) {
             self.account_lock.release_account(&source_account_id);
             self.account_lock.release_account(&target_account_id);
+            self.fail_transfer(&mut transfer).await;
             return Err(err);
         }
 
-        self.update_account_state_port
-            .update_activities(&source_account)
-            .await?;
-        self.update_account_state_port
-            .update_activities(&target_account)
-            .await?;
+        transfer.id = Some(transfer_id.clone());
+        transfer.complete();
+        let recorded = self
+            .record_transfer_port
+            .record_transfer(&transfer, &source_account, &target_account)
+            .await;
 
         self.account_lock.release_account(&source_account_id);
         self.account_lock.release_account(&target_account_id);
 
-        Ok(())
+        // a transfer that could not be recorded left nothing behind, not even a failed transfer
+        recorded?;
+
+        let mut events = source_account.take_events();
+        events.append(&mut target_account.take_events());
+        events.append(&mut transfer.take_events());
+
+        // the transfer is committed at this point, a failing subscriber must not report it as
+        // failed to the client
+        if let Err(err) = self.domain_event_publisher.publish(&events).await {
+            log::error!(
+                "Publishing the events of transfer {} failed: {}",
+                transfer_id.0,
+                err
+            );
+        }
+
+        Ok(transfer_id)
     }
 }
 
 impl SendMoneyService {
+    async fn resolve_target(&self, target: &TransferTarget) -> Result<AccountId> {
+        match target {
+            TransferTarget::Account(account_id) => Ok(account_id.clone()),
+            TransferTarget::Identifier(identifier) => self
+                .lookup_account_identifier_port
+                .lookup_account_id(identifier)
+                .await?
+                .ok_or_else(|| {
+                    anyhow!(ServiceError::UnknownAccountIdentifier(
+                        identifier.to_string()
+                    ))
+                }),
+        }
+    }
+
+    /// Records the transfer as failed. The caller is told why the transfer failed, a transfer
+    /// that can't be recorded as well is only logged.
+    async fn fail_transfer(&self, transfer: &mut Transfer) {
+        transfer.fail();
+        if let Err(err) = self
+            .update_transfer_state_port
+            .create_transfer(transfer)
+            .await
+        {
+            log::error!(
+                "Recording the failed transfer from account {} failed: {}",
+                transfer.source_account_id.0,
+                err
+            );
+        }
+    }
+
+    /// Rejects amounts that would move money from the target account to the source account.
+    fn check_amount(&self, command: &SendMoneyCommand) -> Result<()> {
+        use rust_decimal::prelude::*;
+
+        if !command.money.is_positive() {
+            return Err(anyhow!(ServiceError::InvalidAmount(
+                command.money.amount().to_i64().unwrap()
+            )));
+        }
+
+        Ok(())
+    }
+
     fn check_threshold(&self, command: &SendMoneyCommand) -> Result<()> {
         if command.money > self.money_transfer_properties.maximum_transfer_threshold() {
             let error = ServiceError::ThresholdExceededException {
@@ -106,16 +219,25 @@ impl SendMoneyService {
 mod tests {
     use super::SendMoneyService;
     use crate::application::port::incoming::send_money_use_case::{
-        SendMoneyCommand, SendMoneyUseCase,
+        SendMoneyCommand, SendMoneyUseCase, TransferTarget,
     };
     use crate::application::port::outgoing::{
-        account_lock::MockAccountLock, load_account_port::LoadAccountPort,
-        update_account_state_port::UpdateAccountStatePort,
+        account_lock::MockAccountLock, domain_event_publisher::DomainEventPublisher,
+        load_account_port::LoadAccountPort,
+        lookup_account_identifier_port::LookupAccountIdentifierPort,
+        record_transfer_port::RecordTransferPort,
+        update_transfer_state_port::UpdateTransferStatePort,
     };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
     use crate::application::service::money_transfer_properties::MoneyTransferProperties;
     use crate::domain::account::account_test_data::AccountBuilder;
     use crate::domain::account::{Account, AccountId};
-    use crate::domain::activity::Activity;
+    use crate::domain::account_identifier::AccountIdentifier;
+    use crate::domain::domain_event::DomainEvent;
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::{Transfer, TransferId, TransferStatus};
     use anyhow::anyhow;
     use anyhow::Result;
     use async_trait::async_trait;
@@ -123,13 +245,15 @@ mod tests {
     use mockall::*;
     use mocktopus::mocking::*;
     use rusty_money::{money, Money};
-    use std::sync::Mutex;
+    use std::sync::{Arc, Mutex};
 
     #[async_std::test]
     async fn given_withdrawal_fails_then_only_source_account_is_locked_and_released() {
         let mut load_account_port = MockLoadAccountPort::default();
         let mut account_lock = MockAccountLock::new();
-        let update_account_state_port = MockUpdateAccountStatePort::default();
+        let record_transfer_port = MockRecordTransferPort::default();
+        let update_transfer_state_port = MockUpdateTransferStatePort::default();
+        let domain_event_publisher = MockDomainEventPublisher::default();
         let money_transfer_properties = MoneyTransferProperties::default();
 
         let source_account_id = AccountId(41);
@@ -159,31 +283,82 @@ mod tests {
             .times(0);
 
         let command =
-            SendMoneyCommand::new(source_account_id, target_account_id, money!(300, "AUD"));
+            SendMoneyCommand::new(source_account_id, target_account_id, money!(300, "AUD"))
+                .with_initiator(default_principal());
 
         let send_money_service = SendMoneyService::new(
             Box::new(load_account_port),
             Box::new(account_lock),
-            Box::new(update_account_state_port),
+            Box::new(record_transfer_port.clone()),
+            Box::new(update_transfer_state_port.clone()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(domain_event_publisher.clone()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
             money_transfer_properties,
         );
 
         let success = send_money_service.send_money(&command).await.is_ok();
         assert_eq!(success, false);
+        assert_eq!(
+            update_transfer_state_port.last_status(),
+            Some(TransferStatus::Failed)
+        );
+        assert!(record_transfer_port.recorded().is_empty());
+        assert!(domain_event_publisher.published().is_empty());
+    }
+
+    #[async_std::test]
+    async fn given_transfer_cannot_be_failed_then_withdrawal_error_is_returned() {
+        let mut load_account_port = MockLoadAccountPort::default();
+        let mut account_lock = MockAccountLock::new();
+        let update_transfer_state_port = MockUpdateTransferStatePort {
+            fail_updates: true,
+            ..MockUpdateTransferStatePort::default()
+        };
+
+        let source_account_id = AccountId(41);
+        let source_account = given_an_account_with_id(&source_account_id, &mut load_account_port);
+
+        let target_account_id = AccountId(42);
+        given_an_account_with_id(&target_account_id, &mut load_account_port);
+
+        given_withdrawal_will_fail(&source_account);
+
+        account_lock.expect_lock_account().returning(|_| ());
+        account_lock.expect_release_account().returning(|_| ());
+
+        let command =
+            SendMoneyCommand::new(source_account_id, target_account_id, money!(300, "AUD"))
+                .with_initiator(default_principal());
+
+        let send_money_service = SendMoneyService::new(
+            Box::new(load_account_port),
+            Box::new(account_lock),
+            Box::new(MockRecordTransferPort::default()),
+            Box::new(update_transfer_state_port),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(MockDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        );
+
+        let err = send_money_service.send_money(&command).await.unwrap_err();
+        assert_eq!(err.to_string(), "Something bad happened");
     }
 
     #[async_std::test]
     async fn transation_succeeds() {
         let mut load_account_port = MockLoadAccountPort::default();
         let mut account_lock = MockAccountLock::new();
-        let mut update_account_state_port = MockUpdateAccountStatePort::default();
+        let record_transfer_port = MockRecordTransferPort::default();
+        let domain_event_publisher = MockDomainEventPublisher::default();
         let money_transfer_properties = MoneyTransferProperties::default();
 
         let source_account = given_source_account(&mut load_account_port);
         let source_account_id = source_account.clone().id.unwrap();
 
         let target_account = given_target_account(&mut load_account_port);
-        let target_account_id = source_account.clone().id.unwrap();
+        let target_account_id = target_account.clone().id.unwrap();
 
         given_withdrawal_will_succeed(&source_account);
         given_deposit_will_succeed(&target_account);
@@ -210,29 +385,158 @@ mod tests {
             .with(predicate::eq(target_account_id.clone()))
             .returning(|_| ());
 
-        then_accounts_have_been_updated(
-            vec![&source_account_id, &target_account_id],
-            &mut update_account_state_port,
-        );
-
-        let command = SendMoneyCommand::new(source_account_id, target_account_id, money);
+        let command =
+            SendMoneyCommand::new(source_account_id.clone(), target_account_id.clone(), money)
+                .with_initiator(default_principal());
 
         let send_money_service = SendMoneyService::new(
             Box::new(load_account_port),
             Box::new(account_lock),
-            Box::new(update_account_state_port),
+            Box::new(record_transfer_port.clone()),
+            Box::new(MockUpdateTransferStatePort::default()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(domain_event_publisher.clone()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
             money_transfer_properties,
         );
 
-        let success = send_money_service.send_money(&command).await.is_ok();
-        assert_eq!(success, true);
+        let transfer_id = send_money_service.send_money(&command).await;
+        assert_eq!(transfer_id.ok(), Some(TransferId(1)));
+        assert_eq!(
+            record_transfer_port.recorded(),
+            vec![(
+                TransferStatus::Completed,
+                source_account_id,
+                target_account_id
+            )]
+        );
+        assert!(matches!(
+            domain_event_publisher.published().last(),
+            Some(DomainEvent::TransferCompleted { transfer_id, .. }) if *transfer_id == TransferId(1)
+        ));
     }
 
-    fn then_accounts_have_been_updated(
-        account_ids: Vec<&AccountId>,
-        update_account_state_port_mock: &mut MockUpdateAccountStatePort,
-    ) {
-        update_account_state_port_mock.expect_update_activities(account_ids);
+    #[async_std::test]
+    async fn non_positive_amount_is_rejected_before_any_transfer_is_created() {
+        let update_transfer_state_port = MockUpdateTransferStatePort::default();
+        let record_transfer_port = MockRecordTransferPort::default();
+
+        let send_money_service = SendMoneyService::new(
+            Box::new(MockLoadAccountPort::default()),
+            Box::new(MockAccountLock::new()),
+            Box::new(record_transfer_port.clone()),
+            Box::new(update_transfer_state_port.clone()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(MockDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        );
+
+        for amount in &[0, -300] {
+            let command =
+                SendMoneyCommand::new(AccountId(41), AccountId(42), money!(*amount, "AUD"))
+                    .with_initiator(default_principal());
+
+            let err = send_money_service.send_money(&command).await.unwrap_err();
+
+            assert!(matches!(
+                err.downcast_ref::<ServiceError>(),
+                Some(ServiceError::InvalidAmount(rejected)) if rejected == amount
+            ));
+        }
+        assert_eq!(update_transfer_state_port.last_status(), None);
+        assert!(record_transfer_port.recorded().is_empty());
+    }
+
+    #[async_std::test]
+    async fn unknown_target_identifier_fails_before_any_transfer_is_created() {
+        let mut load_account_port = MockLoadAccountPort::default();
+        let update_transfer_state_port = MockUpdateTransferStatePort::default();
+
+        let source_account_id = AccountId(41);
+        given_an_account_with_id(&source_account_id, &mut load_account_port);
+
+        let command = SendMoneyCommand::new_to_target(
+            source_account_id,
+            TransferTarget::Identifier(AccountIdentifier::bsb("062-000", "12345678").unwrap()),
+            money!(300, "AUD"),
+        )
+        .with_initiator(default_principal());
+
+        let send_money_service = SendMoneyService::new(
+            Box::new(load_account_port),
+            Box::new(MockAccountLock::new()),
+            Box::new(MockRecordTransferPort::default()),
+            Box::new(update_transfer_state_port.clone()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(MockDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        );
+
+        let err = send_money_service.send_money(&command).await.unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "No account is registered for `BSB 062-000 12345678`"
+        );
+        assert_eq!(update_transfer_state_port.last_status(), None);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_may_not_debit_the_source_account() {
+        let mut load_account_port = MockLoadAccountPort::default();
+        let update_transfer_state_port = MockUpdateTransferStatePort::default();
+        let record_transfer_port = MockRecordTransferPort::default();
+
+        given_source_account(&mut load_account_port);
+        given_target_account(&mut load_account_port);
+
+        let send_money_service = SendMoneyService::new(
+            Box::new(load_account_port),
+            Box::new(MockAccountLock::new()),
+            Box::new(record_transfer_port.clone()),
+            Box::new(update_transfer_state_port.clone()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(MockDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(41)],
+            )),
+            MoneyTransferProperties::default(),
+        );
+
+        let command = SendMoneyCommand::new(AccountId(41), AccountId(42), money!(300, "AUD"))
+            .with_initiator(Principal::User(String::from("mallory")));
+        let err = send_money_service.send_money(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 41, .. })
+        ));
+        assert_eq!(update_transfer_state_port.last_status(), None);
+        assert!(record_transfer_port.recorded().is_empty());
+    }
+
+    #[async_std::test]
+    async fn command_without_initiator_is_denied() {
+        let send_money_service = SendMoneyService::new(
+            Box::new(MockLoadAccountPort::default()),
+            Box::new(MockAccountLock::new()),
+            Box::new(MockRecordTransferPort::default()),
+            Box::new(MockUpdateTransferStatePort::default()),
+            Box::new(MockLookupAccountIdentifierPort::default()),
+            Box::new(MockDomainEventPublisher::default()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+            MoneyTransferProperties::default(),
+        );
+
+        let command = SendMoneyCommand::new(AccountId(41), AccountId(42), money!(300, "AUD"));
+        let err = send_money_service.send_money(&command).await.unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::InitiatorMissing)
+        ));
     }
 
     fn given_target_account(load_account_port_mock: &mut MockLoadAccountPort) -> Account {
@@ -258,80 +562,100 @@ mod tests {
 
     fn given_withdrawal_will_succeed(account: &Account) {
         let cloned = account.clone();
-        Account::withdraw.mock_safe(move |curr, money, target| {
+        Account::withdraw.mock_safe(move |curr, money, target, transfer, initiator| {
             if curr.id == cloned.id {
                 MockResult::Return(Ok(()))
             } else {
-                MockResult::Continue((curr, money, target))
+                MockResult::Continue((curr, money, target, transfer, initiator))
             }
         })
     }
 
     fn given_withdrawal_will_fail(account: &Account) {
         let cloned = account.clone();
-        Account::withdraw.mock_safe(move |curr, money, target| {
+        Account::withdraw.mock_safe(move |curr, money, target, transfer, initiator| {
             if curr.id == cloned.id {
                 MockResult::Return(Err(anyhow!("Something bad happened")))
             } else {
-                MockResult::Continue((curr, money, target))
+                MockResult::Continue((curr, money, target, transfer, initiator))
             }
         })
     }
 
     fn given_deposit_will_succeed(account: &Account) {
         let cloned = account.clone();
-        Account::deposit.mock_safe(move |curr, money, target| {
+        Account::deposit.mock_safe(move |curr, money, target, transfer, initiator| {
             if curr.id == cloned.id {
                 MockResult::Return(Ok(()))
             } else {
-                MockResult::Continue((curr, money, target))
+                MockResult::Continue((curr, money, target, transfer, initiator))
             }
         })
     }
 
-    #[derive(Debug, Default)]
-    struct MockUpdateAccountStatePort {
-        expected_updated_account_ids: Mutex<Vec<AccountId>>,
+    /// Reserves transfer 1 and remembers the status and accounts of every recorded transfer.
+    #[derive(Debug, Default, Clone)]
+    struct MockRecordTransferPort {
+        recorded: Arc<Mutex<Vec<(TransferStatus, AccountId, AccountId)>>>,
     }
 
-    impl MockUpdateAccountStatePort {
-        fn expect_update_activities(&self, account_ids: Vec<&AccountId>) {
-            for account_id in account_ids {
-                self.expected_updated_account_ids
-                    .lock()
-                    .unwrap()
-                    .push(account_id.clone())
-            }
+    impl MockRecordTransferPort {
+        fn recorded(&self) -> Vec<(TransferStatus, AccountId, AccountId)> {
+            self.recorded.lock().unwrap().clone()
         }
     }
 
-    impl Drop for MockUpdateAccountStatePort {
-        fn drop(&mut self) {
-            assert!(
-                self.expected_updated_account_ids.lock().unwrap().len() == 0,
-                "update activities should have been called with the expected account ids"
-            )
+    #[async_trait]
+    impl RecordTransferPort for MockRecordTransferPort {
+        async fn reserve_transfer_id(&self) -> Result<TransferId> {
+            Ok(TransferId(1))
+        }
+
+        async fn record_transfer(
+            &self,
+            transfer: &Transfer,
+            source_account: &Account,
+            target_account: &Account,
+        ) -> Result<Transfer> {
+            self.recorded.lock().unwrap().push((
+                transfer.status,
+                source_account.id.clone().unwrap(),
+                target_account.id.clone().unwrap(),
+            ));
+
+            Ok(transfer.clone())
+        }
+    }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockUpdateTransferStatePort {
+        statuses: Arc<Mutex<Vec<TransferStatus>>>,
+        fail_updates: bool,
+    }
+
+    impl MockUpdateTransferStatePort {
+        fn last_status(&self) -> Option<TransferStatus> {
+            self.statuses.lock().unwrap().last().cloned()
         }
     }
 
     #[async_trait]
-    impl UpdateAccountStatePort for MockUpdateAccountStatePort {
-        async fn update_activities(&self, account: &Account) -> Result<Vec<Activity>> {
-            let index = self
-                .expected_updated_account_ids
-                .lock()
-                .unwrap()
-                .iter()
-                .position(|item| *item == account.id.clone().unwrap())
-                .unwrap();
+    impl UpdateTransferStatePort for MockUpdateTransferStatePort {
+        async fn create_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            if self.fail_updates {
+                return Err(anyhow!("Database is unreachable"));
+            }
+            self.statuses.lock().unwrap().push(transfer.status);
+
+            let mut transfer = transfer.clone();
+            transfer.id = Some(TransferId(1));
+            Ok(transfer)
+        }
 
-            self.expected_updated_account_ids
-                .lock()
-                .unwrap()
-                .remove(index);
+        async fn update_transfer(&self, transfer: &Transfer) -> Result<Transfer> {
+            self.statuses.lock().unwrap().push(transfer.status);
 
-            // return nothing here
-            Ok(vec![])
+            Ok(transfer.clone())
         }
     }
 
@@ -366,4 +690,38 @@ mod tests {
                 .ok_or(anyhow!("No matching account found from stub"))
         }
     }
+
+    #[derive(Debug, Default, Clone)]
+    struct MockDomainEventPublisher {
+        events: Arc<Mutex<Vec<DomainEvent>>>,
+    }
+
+    impl MockDomainEventPublisher {
+        fn published(&self) -> Vec<DomainEvent> {
+            self.events.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl DomainEventPublisher for MockDomainEventPublisher {
+        async fn publish(&self, events: &[DomainEvent]) -> Result<()> {
+            self.events.lock().unwrap().extend_from_slice(events);
+
+            Ok(())
+        }
+    }
+
+    /// Knows no identifiers at all.
+    #[derive(Debug, Default)]
+    struct MockLookupAccountIdentifierPort {}
+
+    #[async_trait]
+    impl LookupAccountIdentifierPort for MockLookupAccountIdentifierPort {
+        async fn lookup_account_id(
+            &self,
+            _identifier: &AccountIdentifier,
+        ) -> Result<Option<AccountId>> {
+            Ok(None)
+        }
+    }
 }
@@ -0,0 +1,24 @@
+use crate::application::port::incoming::verify_account_balances_use_case::VerifyAccountBalancesUseCase;
+use crate::application::port::outgoing::account_balance_port::AccountBalancePort;
+use crate::domain::account_balance::BalanceDrift;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct VerifyAccountBalancesService {
+    account_balance_port: Box<dyn AccountBalancePort + Send + Sync>,
+}
+
+impl VerifyAccountBalancesService {
+    pub fn new(account_balance_port: Box<dyn AccountBalancePort + Send + Sync>) -> Self {
+        Self {
+            account_balance_port,
+        }
+    }
+}
+
+#[async_trait]
+impl VerifyAccountBalancesUseCase for VerifyAccountBalancesService {
+    async fn verify_account_balances(&self) -> Result<Vec<BalanceDrift>> {
+        self.account_balance_port.load_balance_drifts().await
+    }
+}
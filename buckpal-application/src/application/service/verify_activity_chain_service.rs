@@ -0,0 +1,256 @@
+use crate::application::port::incoming::verify_activity_chain_query::VerifyActivityChainQuery;
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, activity_chain_port::ActivityChainPort,
+    chain_checkpoint_signer::ChainCheckpointSigner,
+};
+use crate::application::service::account_access::check_account_access;
+use crate::domain::account::AccountId;
+use crate::domain::activity_chain::{
+    digest_heads, verify_chain, BrokenLink, ChainBreak, ChainVerification,
+};
+use crate::domain::principal::Principal;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct VerifyActivityChainService {
+    activity_chain_port: Box<dyn ActivityChainPort + Send + Sync>,
+    chain_checkpoint_signer: Box<dyn ChainCheckpointSigner + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl VerifyActivityChainService {
+    pub fn new(
+        activity_chain_port: Box<dyn ActivityChainPort + Send + Sync>,
+        chain_checkpoint_signer: Box<dyn ChainCheckpointSigner + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            activity_chain_port,
+            chain_checkpoint_signer,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl VerifyActivityChainQuery for VerifyActivityChainService {
+    async fn verify_activity_chain(
+        &self,
+        account_id: &AccountId,
+        principal: &Principal,
+    ) -> Result<ChainVerification> {
+        check_account_access(self.account_access_port.as_ref(), account_id, principal).await?;
+
+        // a checkpoint is only worth checking against if nobody could have forged it
+        let checkpoint = self.activity_chain_port.load_latest_checkpoint().await?;
+        if let Some(checkpoint) = &checkpoint {
+            if digest_heads(&checkpoint.heads) != checkpoint.digest
+                || !self
+                    .chain_checkpoint_signer
+                    .verify(&checkpoint.digest, &checkpoint.signature)
+            {
+                return Ok(ChainVerification {
+                    account_id: account_id.clone(),
+                    verified_activities: 0,
+                    checkpoint_id: checkpoint.id.clone(),
+                    broken_link: Some(BrokenLink {
+                        activity_id: None,
+                        reason: ChainBreak::CheckpointSignatureInvalid,
+                    }),
+                });
+            }
+        }
+
+        let activities = self
+            .activity_chain_port
+            .load_chained_activities(account_id)
+            .await?;
+        let head = self.activity_chain_port.load_chain_head(account_id).await?;
+        let checkpointed_head = checkpoint.as_ref().and_then(|checkpoint| {
+            checkpoint
+                .heads
+                .iter()
+                .find(|head| head.account_id == *account_id)
+        });
+
+        let mut verification =
+            verify_chain(account_id, &activities, head.as_ref(), checkpointed_head);
+        verification.checkpoint_id = checkpoint.and_then(|checkpoint| checkpoint.id);
+
+        Ok(verification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VerifyActivityChainService;
+    use crate::application::port::incoming::verify_activity_chain_query::VerifyActivityChainQuery;
+    use crate::application::port::outgoing::{
+        activity_chain_port::ActivityChainPort, chain_checkpoint_signer::ChainCheckpointSigner,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::ActivityId;
+    use crate::domain::activity_chain::{
+        hash_activity, ChainBreak, ChainCheckpoint, ChainCheckpointId, ChainHead, ChainedActivity,
+        GENESIS_HASH,
+    };
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::Utc;
+
+    fn given_a_chained_activity() -> (ChainedActivity, ChainHead) {
+        let activity = ActivityBuilder::default_activity()
+            .with_activity_id(&ActivityId(7))
+            .build();
+        let hash = hash_activity(GENESIS_HASH, &activity);
+
+        (
+            ChainedActivity::new(
+                activity,
+                Some(String::from(GENESIS_HASH)),
+                Some(hash.clone()),
+            ),
+            ChainHead::new(AccountId(42), ActivityId(7), &hash),
+        )
+    }
+
+    fn given_a_checkpoint(head: &ChainHead, signature: &str) -> ChainCheckpoint {
+        let mut checkpoint = ChainCheckpoint::new(vec![head.clone()], "", Utc::now());
+        checkpoint.id = Some(ChainCheckpointId(3));
+        checkpoint.signature = String::from(signature);
+
+        checkpoint
+    }
+
+    #[async_std::test]
+    async fn verifies_chain_against_checkpoint() {
+        let (chained_activity, head) = given_a_chained_activity();
+        let checkpoint = given_a_checkpoint(&head, "");
+        let signature = MockSigner {}.sign(&checkpoint.digest);
+        let checkpoint = given_a_checkpoint(&head, &signature);
+        let service = VerifyActivityChainService::new(
+            Box::new(MockActivityChainPort {
+                activities: vec![chained_activity],
+                head: Some(head),
+                checkpoint: Some(checkpoint),
+            }),
+            Box::new(MockSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let verification = service
+            .verify_activity_chain(&AccountId(42), &default_principal())
+            .await
+            .unwrap();
+
+        assert!(verification.is_intact());
+        assert_eq!(verification.verified_activities, 1);
+        assert_eq!(verification.checkpoint_id, Some(ChainCheckpointId(3)));
+    }
+
+    #[async_std::test]
+    async fn forged_checkpoint_breaks_verification() {
+        let (chained_activity, head) = given_a_chained_activity();
+        let service = VerifyActivityChainService::new(
+            Box::new(MockActivityChainPort {
+                activities: vec![chained_activity],
+                head: Some(head.clone()),
+                checkpoint: Some(given_a_checkpoint(&head, "forged")),
+            }),
+            Box::new(MockSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let verification = service
+            .verify_activity_chain(&AccountId(42), &default_principal())
+            .await
+            .unwrap();
+
+        assert_eq!(
+            verification.broken_link.unwrap().reason,
+            ChainBreak::CheckpointSignatureInvalid
+        );
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_is_denied() {
+        let (chained_activity, head) = given_a_chained_activity();
+        let service = VerifyActivityChainService::new(
+            Box::new(MockActivityChainPort {
+                activities: vec![chained_activity],
+                head: Some(head),
+                checkpoint: None,
+            }),
+            Box::new(MockSigner {}),
+            Box::new(MockAccountAccess::to_accounts(
+                default_principal(),
+                &[AccountId(42)],
+            )),
+        );
+
+        let err = service
+            .verify_activity_chain(&AccountId(42), &Principal::User(String::from("mallory")))
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { account_id: 42, .. })
+        ));
+    }
+
+    struct MockSigner {}
+
+    impl ChainCheckpointSigner for MockSigner {
+        fn sign(&self, digest: &str) -> String {
+            format!("signed:{}", digest)
+        }
+
+        fn verify(&self, digest: &str, signature: &str) -> bool {
+            self.sign(digest) == signature
+        }
+    }
+
+    struct MockActivityChainPort {
+        activities: Vec<ChainedActivity>,
+        head: Option<ChainHead>,
+        checkpoint: Option<ChainCheckpoint>,
+    }
+
+    #[async_trait]
+    impl ActivityChainPort for MockActivityChainPort {
+        async fn load_chained_activities(
+            &self,
+            _account_id: &AccountId,
+        ) -> Result<Vec<ChainedActivity>> {
+            Ok(self.activities.clone())
+        }
+
+        async fn load_chain_head(&self, _account_id: &AccountId) -> Result<Option<ChainHead>> {
+            Ok(self.head.clone())
+        }
+
+        async fn load_chain_heads(&self) -> Result<Vec<ChainHead>> {
+            Ok(self.head.iter().cloned().collect())
+        }
+
+        async fn load_latest_checkpoint(&self) -> Result<Option<ChainCheckpoint>> {
+            Ok(self.checkpoint.clone())
+        }
+
+        async fn save_checkpoint(&self, _checkpoint: &ChainCheckpoint) -> Result<ChainCheckpoint> {
+            Err(anyhow!("expected no checkpoint to be saved"))
+        }
+    }
+}
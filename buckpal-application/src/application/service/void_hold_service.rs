@@ -0,0 +1,254 @@
+use crate::application::port::incoming::void_hold_use_case::{VoidHoldCommand, VoidHoldUseCase};
+use crate::application::port::outgoing::{
+    account_access_port::AccountAccessPort, load_account_port::LoadAccountPort,
+    load_hold_port::LoadHoldPort, update_account_state_port::UpdateAccountStatePort,
+};
+use crate::application::service::account_access::{check_account_access, initiator};
+use crate::domain::account::AccountError;
+use anyhow::Result;
+use async_trait::async_trait;
+
+pub struct VoidHoldService {
+    load_hold_port: Box<dyn LoadHoldPort + Send + Sync>,
+    load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+    update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+    account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+}
+
+impl VoidHoldService {
+    pub fn new(
+        load_hold_port: Box<dyn LoadHoldPort + Send + Sync>,
+        load_account_port: Box<dyn LoadAccountPort + Send + Sync>,
+        update_account_state_port: Box<dyn UpdateAccountStatePort + Send + Sync>,
+        account_access_port: Box<dyn AccountAccessPort + Send + Sync>,
+    ) -> Self {
+        Self {
+            load_hold_port,
+            load_account_port,
+            update_account_state_port,
+            account_access_port,
+        }
+    }
+}
+
+#[async_trait]
+impl VoidHoldUseCase for VoidHoldService {
+    async fn void_hold(&self, command: &VoidHoldCommand) -> Result<()> {
+        use chrono::{Duration, Utc};
+
+        let hold = self.load_hold_port.load_hold(&command.hold_id).await?;
+
+        check_account_access(
+            self.account_access_port.as_ref(),
+            &hold.account_id,
+            initiator(command.initiator.as_ref())?,
+        )
+        .await?;
+
+        let mut account = self
+            .load_account_port
+            .load_account(&hold.account_id, &(Utc::now() - Duration::days(10)))
+            .await?;
+
+        if let Err(err) = account.void_hold(&command.hold_id) {
+            if let Some(AccountError::HoldExpired(_)) = err.downcast_ref::<AccountError>() {
+                // persist that the hold ran out, so that it stops reserving money for good
+                if let Err(update_err) = self.update_account_state_port.update_holds(&account).await
+                {
+                    log::error!("Marking expired hold as expired failed: {}", update_err);
+                }
+            }
+            return Err(err);
+        }
+
+        self.update_account_state_port
+            .update_holds(&account)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VoidHoldService;
+    use crate::application::port::incoming::void_hold_use_case::{
+        VoidHoldCommand, VoidHoldUseCase,
+    };
+    use crate::application::port::outgoing::{
+        load_account_port::LoadAccountPort, load_hold_port::LoadHoldPort,
+        update_account_state_port::UpdateAccountStatePort,
+    };
+    use crate::application::service::account_access::account_access_test_data::MockAccountAccess;
+    use crate::application::service::error::ServiceError;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::{Account, AccountId};
+    use crate::domain::activity::Activity;
+    use crate::domain::hold::hold_test_data::HoldBuilder;
+    use crate::domain::hold::{Hold, HoldId, HoldStatus};
+    use crate::domain::principal::principal_test_data::default_principal;
+    use crate::domain::principal::Principal;
+    use anyhow::{anyhow, Result};
+    use async_trait::async_trait;
+    use chrono::{DateTime, Duration, Utc};
+    use std::sync::{Arc, Mutex};
+
+    #[async_std::test]
+    async fn void_cancels_the_hold() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let service = given_a_service(&ledger);
+
+        service
+            .void_hold(&VoidHoldCommand::new(HoldId(3)).with_initiator(default_principal()))
+            .await
+            .unwrap();
+
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Voided);
+    }
+
+    #[async_std::test]
+    async fn voided_hold_can_not_be_voided_again() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let service = given_a_service(&ledger);
+
+        let command = VoidHoldCommand::new(HoldId(3)).with_initiator(default_principal());
+        service.void_hold(&command).await.unwrap();
+
+        let success = service.void_hold(&command).await.is_ok();
+
+        assert_eq!(success, false);
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Voided);
+    }
+
+    #[async_std::test]
+    async fn void_of_an_expired_hold_marks_it_as_expired() {
+        let ledger = MockHoldLedger::with_hold(
+            HoldBuilder::default_hold()
+                .with_expires_at(&(Utc::now() - Duration::minutes(1)))
+                .build(),
+        );
+        let service = given_a_service(&ledger);
+
+        let err = service
+            .void_hold(&VoidHoldCommand::new(HoldId(3)).with_initiator(default_principal()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.to_string(), "Hold `3` has expired");
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Expired);
+    }
+
+    #[async_std::test]
+    async fn foreign_principal_may_not_void_the_hold() {
+        let ledger = MockHoldLedger::with_hold(HoldBuilder::default_hold().build());
+        let service = given_a_service(&ledger);
+
+        let err = service
+            .void_hold(
+                &VoidHoldCommand::new(HoldId(3))
+                    .with_initiator(Principal::User(String::from("mallory"))),
+            )
+            .await
+            .unwrap_err();
+
+        assert!(matches!(
+            err.downcast_ref::<ServiceError>(),
+            Some(ServiceError::AccountAccessDenied { .. })
+        ));
+        assert_eq!(ledger.hold_status(&HoldId(3)), HoldStatus::Active);
+    }
+
+    fn given_a_service(ledger: &MockHoldLedger) -> VoidHoldService {
+        VoidHoldService::new(
+            Box::new(ledger.clone()),
+            Box::new(ledger.clone()),
+            Box::new(ledger.clone()),
+            Box::new(MockAccountAccess::to_all_accounts(default_principal())),
+        )
+    }
+
+    /// Keeps holds like the persistence adapter does: accounts are loaded with their active holds
+    /// only, and only an active hold may change its status.
+    #[derive(Debug, Clone)]
+    struct MockHoldLedger {
+        holds: Arc<Mutex<Vec<Hold>>>,
+    }
+
+    impl MockHoldLedger {
+        fn with_hold(hold: Hold) -> Self {
+            Self {
+                holds: Arc::new(Mutex::new(vec![hold])),
+            }
+        }
+
+        fn hold_status(&self, hold_id: &HoldId) -> HoldStatus {
+            self.holds
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|hold| hold.id.as_ref() == Some(hold_id))
+                .map(|hold| hold.status)
+                .unwrap()
+        }
+    }
+
+    #[async_trait]
+    impl LoadHoldPort for MockHoldLedger {
+        async fn load_hold(&self, hold_id: &HoldId) -> Result<Hold> {
+            self.holds
+                .lock()
+                .unwrap()
+                .iter()
+                .find(|hold| hold.id.as_ref() == Some(hold_id))
+                .cloned()
+                .ok_or_else(|| anyhow!("No matching hold found from stub"))
+        }
+    }
+
+    #[async_trait]
+    impl LoadAccountPort for MockHoldLedger {
+        async fn load_account(
+            &self,
+            account_id: &AccountId,
+            _baseline_date: &DateTime<Utc>,
+        ) -> Result<Account> {
+            let holds: Vec<Hold> = self
+                .holds
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|hold| hold.account_id == *account_id && hold.status == HoldStatus::Active)
+                .cloned()
+                .collect();
+
+            Ok(AccountBuilder::default_account()
+                .with_account_id(account_id)
+                .with_holds(&holds)
+                .build())
+        }
+    }
+
+    #[async_trait]
+    impl UpdateAccountStatePort for MockHoldLedger {
+        async fn update_activities(&self, _account: &Account) -> Result<Vec<Activity>> {
+            Ok(vec![])
+        }
+
+        async fn update_holds(&self, account: &Account) -> Result<Vec<Hold>> {
+            let mut holds = self.holds.lock().unwrap();
+            for hold in account
+                .holds
+                .iter()
+                .filter(|hold| hold.status != HoldStatus::Active)
+            {
+                let stored = holds
+                    .iter_mut()
+                    .find(|stored| stored.id == hold.id && stored.status == HoldStatus::Active)
+                    .ok_or_else(|| anyhow!("Hold is no longer active"))?;
+                *stored = hold.clone();
+            }
+
+            Ok(vec![])
+        }
+    }
+}
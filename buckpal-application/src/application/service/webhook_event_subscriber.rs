@@ -0,0 +1,48 @@
+use crate::application::port::outgoing::webhook_port::WebhookPort;
+use crate::application::service::in_memory_domain_event_publisher::DomainEventSubscriber;
+use crate::domain::domain_event::DomainEvent;
+use crate::domain::webhook::WebhookDelivery;
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+
+/// Queues a delivery of every event for each webhook subscribed to it. Queuing the same event
+/// twice, as an at least once publisher may, delivers it once.
+pub struct WebhookEventSubscriber {
+    webhook_port: Box<dyn WebhookPort + Send + Sync>,
+}
+
+impl WebhookEventSubscriber {
+    pub fn new(webhook_port: Box<dyn WebhookPort + Send + Sync>) -> Self {
+        Self { webhook_port }
+    }
+}
+
+#[async_trait]
+impl DomainEventSubscriber for WebhookEventSubscriber {
+    async fn handle(&self, event: &DomainEvent) -> Result<()> {
+        let now = Utc::now();
+
+        let deliveries: Vec<WebhookDelivery> = self
+            .webhook_port
+            .load_subscriptions_for(event)
+            .await?
+            .into_iter()
+            .map(|subscription| {
+                WebhookDelivery::new(
+                    subscription
+                        .id
+                        .expect("expected persisted subscription to have an ID"),
+                    event.clone(),
+                    now,
+                )
+            })
+            .collect();
+
+        if deliveries.is_empty() {
+            return Ok(());
+        }
+
+        self.webhook_port.save_deliveries(&deliveries).await
+    }
+}
@@ -0,0 +1,41 @@
+use crate::domain::webhook::RetryPolicy;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookProperties {
+    retry_policy: RetryPolicy,
+    batch_size: i64,
+}
+
+impl Default for WebhookProperties {
+    fn default() -> Self {
+        Self {
+            retry_policy: RetryPolicy::default(),
+            batch_size: 50,
+        }
+    }
+}
+
+impl WebhookProperties {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    /// Sets the maximum number of deliveries attempted in one go.
+    pub fn with_batch_size(mut self, batch_size: i64) -> Self {
+        self.batch_size = batch_size;
+        self
+    }
+
+    pub fn retry_policy(&self) -> &RetryPolicy {
+        &self.retry_policy
+    }
+
+    pub fn batch_size(&self) -> i64 {
+        self.batch_size
+    }
+}
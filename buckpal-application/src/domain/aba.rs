@@ -0,0 +1,374 @@
+use crate::domain::external_account::{AccountNumber, Bsb, ExternalAccountError};
+use crate::domain::external_payment::ExternalPayment;
+use chrono::NaiveDate;
+use rust_decimal::prelude::*;
+use thiserror::Error;
+
+/// The largest amount in cents the 10 digit amount fields can hold.
+const MAXIMUM_AMOUNT: i64 = 9_999_999_999;
+/// The largest number of detail records the 6 digit count field can hold.
+const MAXIMUM_RECORD_COUNT: usize = 999_999;
+
+const RECORD_SEPARATOR: &str = "\r\n";
+const CREDIT: &str = "50";
+const DEBIT: &str = "13";
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum AbaError {
+    #[error("Financial institution must be a three letter abbreviation, got `{0}`")]
+    InvalidFinancialInstitution(String),
+    #[error("User identification number must be six digits, got `{0}`")]
+    InvalidUserId(String),
+    #[error("Name of user must not be empty")]
+    MissingUserName,
+    #[error("Name of remitter must not be empty")]
+    MissingRemitterName,
+    #[error("Invalid trace account: {0}")]
+    InvalidTraceAccount(#[from] ExternalAccountError),
+    #[error("Amount of `{0}` does not fit a direct entry file")]
+    AmountOutOfRange(String),
+    #[error("A direct entry file holds at most {maximum} detail records, got `{requested}`")]
+    TooManyRecords { requested: usize, maximum: usize },
+}
+
+/// The user of the direct entry system as registered with the sponsoring bank, together with
+/// the account payments are drawn from.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AbaUser {
+    /// The abbreviation of the bank the file is lodged with, e.g. `CBA`.
+    pub financial_institution: String,
+    /// The name of the user supplying the file.
+    pub user_name: String,
+    /// The direct entry user identification number allocated by the bank.
+    pub user_id: String,
+    /// The account returned payments are credited back to, which is also the one debited by a
+    /// balancing record.
+    pub trace_bsb: Bsb,
+    pub trace_account_number: AccountNumber,
+    /// The name payments are shown to come from on the statements of their recipients.
+    pub remitter_name: String,
+}
+
+impl AbaUser {
+    pub fn new(
+        financial_institution: &str,
+        user_name: &str,
+        user_id: &str,
+        trace_bsb: &str,
+        trace_account_number: &str,
+        remitter_name: &str,
+    ) -> Result<Self, AbaError> {
+        if financial_institution.len() != 3
+            || !financial_institution
+                .chars()
+                .all(|c| c.is_ascii_alphabetic())
+        {
+            return Err(AbaError::InvalidFinancialInstitution(String::from(
+                financial_institution,
+            )));
+        }
+        if user_id.len() != 6 || !user_id.chars().all(|c| c.is_ascii_digit()) {
+            return Err(AbaError::InvalidUserId(String::from(user_id)));
+        }
+        if user_name.trim().is_empty() {
+            return Err(AbaError::MissingUserName);
+        }
+        if remitter_name.trim().is_empty() {
+            return Err(AbaError::MissingRemitterName);
+        }
+
+        Ok(Self {
+            financial_institution: financial_institution.to_ascii_uppercase(),
+            user_name: String::from(user_name.trim()),
+            user_id: String::from(user_id),
+            trace_bsb: Bsb::parse(trace_bsb)?,
+            trace_account_number: AccountNumber::parse(trace_account_number)?,
+            remitter_name: String::from(remitter_name.trim()),
+        })
+    }
+}
+
+/// A direct entry file in the format of the Australian Bankers' Association, also known as a
+/// Cemtex file: a descriptive record, a credit detail record per payment and a file total
+/// record, each 120 characters long.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AbaFile {
+    pub contents: String,
+    /// The number of detail records, including the balancing record if there is one.
+    pub record_count: i64,
+    /// The sum of all credit records in cents.
+    pub credit_total: i64,
+    /// The sum of all debit records in cents.
+    pub debit_total: i64,
+}
+
+impl AbaFile {
+    /// Writes a file paying out the given payments on the processing date. A self-balancing
+    /// file ends with a debit of the trace account over the total of all payments, as some
+    /// banks require.
+    pub fn write(
+        user: &AbaUser,
+        description: &str,
+        processing_date: NaiveDate,
+        payments: &[ExternalPayment],
+        self_balancing: bool,
+    ) -> Result<Self, AbaError> {
+        let record_count = payments.len() + if self_balancing { 1 } else { 0 };
+        if record_count > MAXIMUM_RECORD_COUNT {
+            return Err(AbaError::TooManyRecords {
+                requested: record_count,
+                maximum: MAXIMUM_RECORD_COUNT,
+            });
+        }
+
+        let mut records = vec![descriptive_record(user, description, processing_date)];
+        let mut credit_total: i64 = 0;
+
+        for payment in payments {
+            let amount = to_cents(payment)?;
+            credit_total = credit_total
+                .checked_add(amount)
+                .filter(|total| *total <= MAXIMUM_AMOUNT)
+                .ok_or_else(|| AbaError::AmountOutOfRange(String::from("total of all payments")))?;
+
+            let reference = payment
+                .reference
+                .clone()
+                .unwrap_or_else(|| payment.transfer_id.0.to_string());
+
+            records.push(detail_record(
+                user,
+                &payment.external_account.bsb,
+                &payment.external_account.account_number,
+                CREDIT,
+                amount,
+                &payment.external_account.account_name,
+                &reference,
+            ));
+        }
+
+        let debit_total = if self_balancing {
+            records.push(detail_record(
+                user,
+                &user.trace_bsb,
+                &user.trace_account_number,
+                DEBIT,
+                credit_total,
+                &user.user_name,
+                description,
+            ));
+            credit_total
+        } else {
+            0
+        };
+
+        records.push(format!(
+            "7999-999{}{}{}{}{}{}{}",
+            blank(12),
+            numeric(credit_total - debit_total, 10),
+            numeric(credit_total, 10),
+            numeric(debit_total, 10),
+            blank(24),
+            numeric(record_count as i64, 6),
+            blank(40),
+        ));
+
+        let mut contents = records.join(RECORD_SEPARATOR);
+        contents.push_str(RECORD_SEPARATOR);
+
+        Ok(Self {
+            contents,
+            record_count: record_count as i64,
+            credit_total,
+            debit_total,
+        })
+    }
+}
+
+fn descriptive_record(user: &AbaUser, description: &str, processing_date: NaiveDate) -> String {
+    format!(
+        "0{}01{}{}{}{}{}{}{}",
+        blank(17),
+        alpha(&user.financial_institution, 3),
+        blank(7),
+        alpha(&user.user_name, 26),
+        user.user_id,
+        alpha(description, 12),
+        processing_date.format("%d%m%y"),
+        blank(40),
+    )
+}
+
+fn detail_record(
+    user: &AbaUser,
+    bsb: &Bsb,
+    account_number: &AccountNumber,
+    transaction_code: &str,
+    amount: i64,
+    account_name: &str,
+    lodgement_reference: &str,
+) -> String {
+    format!(
+        "1{}{:>9} {}{}{}{}{}{:>9}{}{}",
+        bsb,
+        account_number.as_str(),
+        transaction_code,
+        numeric(amount, 10),
+        alpha(account_name, 32),
+        alpha(lodgement_reference, 18),
+        user.trace_bsb,
+        user.trace_account_number.as_str(),
+        alpha(&user.remitter_name, 16),
+        // no withholding tax is deducted
+        numeric(0, 8),
+    )
+}
+
+fn to_cents(payment: &ExternalPayment) -> Result<i64, AbaError> {
+    (payment.money.amount() * Decimal::new(100, 0))
+        .to_i64()
+        .filter(|amount| *amount > 0 && *amount <= MAXIMUM_AMOUNT)
+        .ok_or_else(|| AbaError::AmountOutOfRange(payment.money.amount().to_string()))
+}
+
+/// Left justifies the value within a blank filled field, replacing every character outside of
+/// the direct entry character set by a blank and cutting off whatever does not fit.
+fn alpha(value: &str, width: usize) -> String {
+    let value: String = value
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || "&'()*+,-./ ".contains(c) {
+                c
+            } else {
+                ' '
+            }
+        })
+        .take(width)
+        .collect();
+
+    format!("{:<width$}", value, width = width)
+}
+
+/// Right justifies the number within a zero filled field.
+fn numeric(value: i64, width: usize) -> String {
+    format!("{:0>width$}", value, width = width)
+}
+
+fn blank(width: usize) -> String {
+    " ".repeat(width)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AbaError, AbaFile, AbaUser};
+    use crate::domain::account::AccountId;
+    use crate::domain::external_account::ExternalAccount;
+    use crate::domain::external_payment::ExternalPayment;
+    use crate::domain::transfer::TransferId;
+    use chrono::{NaiveDate, Utc};
+    use rusty_money::{money, Money};
+
+    fn given_a_user() -> AbaUser {
+        AbaUser::new(
+            "cba",
+            "Buckpal Pty Ltd",
+            "301500",
+            "062000",
+            "12345678",
+            "Buckpal",
+        )
+        .unwrap()
+    }
+
+    fn given_a_payment(transfer_id: i32, amount: i64, reference: Option<&str>) -> ExternalPayment {
+        ExternalPayment {
+            transfer_id: TransferId(transfer_id),
+            source_account_id: AccountId(1),
+            external_account: ExternalAccount::new(
+                AccountId(100),
+                "733-017",
+                "987654",
+                "Jane Citizen",
+            )
+            .unwrap(),
+            money: money!(amount, "AUD"),
+            reference: reference.map(String::from),
+            timestamp: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn writes_descriptive_detail_and_total_records() {
+        let file = AbaFile::write(
+            &given_a_user(),
+            "Payments",
+            NaiveDate::from_ymd(2021, 3, 28),
+            &[
+                given_a_payment(7, 250, Some("Invoice #1234")),
+                given_a_payment(8, 1_000, None),
+            ],
+            false,
+        )
+        .unwrap();
+
+        let records: Vec<&str> = file.contents.split_terminator("\r\n").collect();
+
+        assert_eq!(records.len(), 4);
+        assert!(records.iter().all(|record| record.len() == 120));
+        assert_eq!(
+            records[0].trim_end(),
+            "0                 01CBA       Buckpal Pty Ltd           301500Payments    280321"
+        );
+        assert_eq!(
+            records[1],
+            "1733-017   987654 500000025000Jane Citizen                    Invoice  1234     062-000 12345678Buckpal         00000000"
+        );
+        assert_eq!(&records[2][62..80], "8                 ");
+        assert_eq!(
+            records[3].trim_end(),
+            "7999-999            000012500000001250000000000000                        000002"
+        );
+        assert_eq!(file.record_count, 2);
+        assert_eq!(file.credit_total, 125_000);
+        assert_eq!(file.debit_total, 0);
+    }
+
+    #[test]
+    fn self_balancing_file_debits_trace_account() {
+        let file = AbaFile::write(
+            &given_a_user(),
+            "Payments",
+            NaiveDate::from_ymd(2021, 3, 28),
+            &[
+                given_a_payment(7, 250, None),
+                given_a_payment(8, 1_000, None),
+            ],
+            true,
+        )
+        .unwrap();
+
+        let records: Vec<&str> = file.contents.split_terminator("\r\n").collect();
+
+        assert_eq!(records.len(), 5);
+        assert_eq!(&records[3][..30], "1062-000 12345678 130000125000");
+        assert_eq!(
+            records[4].trim_end(),
+            "7999-999            000000000000001250000000125000                        000003"
+        );
+        assert_eq!(file.record_count, 3);
+        assert_eq!(file.debit_total, file.credit_total);
+    }
+
+    #[test]
+    fn rejects_invalid_user() {
+        assert_eq!(
+            AbaUser::new("CB", "Buckpal", "301500", "062000", "12345678", "Buckpal"),
+            Err(AbaError::InvalidFinancialInstitution(String::from("CB")))
+        );
+        assert_eq!(
+            AbaUser::new("CBA", "Buckpal", "3015", "062000", "12345678", "Buckpal"),
+            Err(AbaError::InvalidUserId(String::from("3015")))
+        );
+        assert!(AbaUser::new("CBA", "Buckpal", "301500", "0620", "12345678", "Buckpal").is_err());
+    }
+}
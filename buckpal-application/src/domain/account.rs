@@ -1,5 +1,10 @@
 use crate::domain::activity_window::ActivityWindow;
+use crate::domain::domain_event::DomainEvent;
+use crate::domain::hold::{Hold, HoldId, HoldStatus};
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
 use rusty_money::{money, Money};
 use thiserror::Error;
 
@@ -9,6 +14,16 @@ pub enum AccountError {
     MayWithdrawFailed(i64),
     #[error("Account id is invalid, can't `{0}`")]
     InvalidAccountId(String),
+    #[error("Account `{0}` does not exist")]
+    AccountNotFound(i32),
+    #[error("Hold `{0}` does not exist on this account")]
+    HoldNotFound(i32),
+    #[error("Hold `{0}` is no longer active")]
+    HoldNotActive(i32),
+    #[error("Hold `{0}` has expired")]
+    HoldExpired(i32),
+    #[error("Can't capture `{requested}` from a hold of `{held}`")]
+    InvalidCaptureAmount { requested: i64, held: i64 },
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -23,6 +38,13 @@ pub struct Account {
     pub baseline_balance: Money,
     /// The window of latest activities on this account.
     pub activity_window: ActivityWindow,
+    /// The holds reserving money on this account that have not been captured or voided.
+    pub holds: Vec<Hold>,
+    /// The events recorded since they were last taken. The event-sourced ledger appends them to
+    /// the stream of the account, elsewhere they only go to the outbox.
+    pub events: Vec<DomainEvent>,
+    /// The version of the event stream the account was rebuilt from, 0 if it wasn't.
+    pub version: i32,
 }
 
 #[cfg_attr(test, mocktopus::macros::mockable)]
@@ -34,6 +56,9 @@ impl Account {
             id: None,
             baseline_balance,
             activity_window,
+            holds: vec![],
+            events: vec![],
+            version: 0,
         }
     }
 
@@ -47,6 +72,9 @@ impl Account {
             id: Some(account_id),
             baseline_balance,
             activity_window,
+            holds: vec![],
+            events: vec![],
+            version: 0,
         }
     }
 
@@ -60,9 +88,39 @@ impl Account {
         self.baseline_balance.clone() + window_balance
     }
 
-    /// Tries to withdraw a certain amount of money from this account.
-    /// If successful, creates a new activity with a negative value.
-    pub fn withdraw(&mut self, money: &Money, target_account_id: &AccountId) -> Result<()> {
+    /// Calculates the balance of the account at the given point in time, ignoring all activities
+    /// of the activity window after it. The baseline balance must not include any activity after
+    /// the given point in time.
+    pub fn calculate_balance_as_of(&self, instant: &DateTime<Utc>) -> Money {
+        let window_balance = self.id.clone().map_or_else(
+            || money!(0, "AUD"),
+            |id| self.activity_window.up_to(instant).calculate_balance(&id),
+        );
+        self.baseline_balance.clone() + window_balance
+    }
+
+    /// Calculates the balance that can be spent at the given point in time, which is the total
+    /// balance minus the money reserved by active holds.
+    pub fn calculate_available_balance(&self, now: &DateTime<Utc>) -> Money {
+        let held = self
+            .holds
+            .iter()
+            .filter(|hold| hold.is_active(now))
+            .map(|hold| hold.money.clone())
+            .fold(money!(0, "AUD"), |acc, x| acc + x);
+
+        self.calculate_balance() - held
+    }
+
+    /// Tries to withdraw a certain amount of money from this account as a leg of the given
+    /// transfer. If successful, creates a new activity with a negative value.
+    pub fn withdraw(
+        &mut self,
+        money: &Money,
+        target_account_id: &AccountId,
+        transfer_id: &TransferId,
+        initiator: Option<&Principal>,
+    ) -> Result<()> {
         self.may_withdraw(&money)?;
 
         let id = match self.id.clone() {
@@ -75,7 +133,6 @@ impl Account {
         };
 
         use crate::domain::activity::Activity;
-        use chrono::Utc;
 
         let withdrawal = Activity::new(
             id.clone(),
@@ -83,13 +140,22 @@ impl Account {
             target_account_id.clone(),
             Utc::now(),
             money.clone(),
-        );
+            Some(transfer_id.clone()),
+        )
+        .with_initiator(initiator.cloned());
         self.activity_window.add_activity(&withdrawal);
+        self.events.push(DomainEvent::MoneyWithdrawn {
+            account_id: withdrawal.owner_account_id,
+            target_account_id: withdrawal.target_account_id,
+            transfer_id: transfer_id.clone(),
+            money: withdrawal.money,
+            timestamp: withdrawal.timestamp,
+        });
         Ok(())
     }
 
     fn may_withdraw(&self, money: &Money) -> Result<()> {
-        let balance = self.calculate_balance() - money.clone();
+        let balance = self.calculate_available_balance(&Utc::now()) - money.clone();
 
         if balance.is_zero() || balance.is_positive() {
             Ok(())
@@ -103,10 +169,16 @@ impl Account {
         }
     }
 
-    /// Tries to deposit a certain amount of money to this account.
+    /// Tries to deposit a certain amount of money to this account as a leg of the given transfer.
     /// If sucessful, creates a new activity with a positive value.
     /// return true if the deposit was successful, false if not.
-    pub fn deposit(&mut self, money: &Money, source_account_id: &AccountId) -> Result<()> {
+    pub fn deposit(
+        &mut self,
+        money: &Money,
+        source_account_id: &AccountId,
+        transfer_id: &TransferId,
+        initiator: Option<&Principal>,
+    ) -> Result<()> {
         let id = match self.id.clone() {
             Some(id) => id,
             None => {
@@ -117,7 +189,6 @@ impl Account {
         };
 
         use crate::domain::activity::Activity;
-        use chrono::Utc;
 
         let deposit = Activity::new(
             id.clone(),
@@ -125,10 +196,118 @@ impl Account {
             id,
             Utc::now(),
             money.clone(),
-        );
+            Some(transfer_id.clone()),
+        )
+        .with_initiator(initiator.cloned());
         self.activity_window.add_activity(&deposit);
+        self.events.push(DomainEvent::MoneyDeposited {
+            account_id: deposit.owner_account_id,
+            source_account_id: deposit.source_account_id,
+            transfer_id: transfer_id.clone(),
+            money: deposit.money,
+            timestamp: deposit.timestamp,
+        });
         Ok(())
     }
+
+    /// Hands out the events recorded so far, leaving none behind.
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Tries to reserve a certain amount of money on this account for a later transfer to the
+    /// target account. Fails if the available balance does not cover the money.
+    pub fn place_hold(
+        &mut self,
+        money: &Money,
+        target_account_id: &AccountId,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Result<Hold> {
+        self.may_withdraw(&money)?;
+
+        let id = match self.id.clone() {
+            Some(id) => id,
+            None => {
+                return Err(anyhow!(AccountError::InvalidAccountId(String::from(
+                    "place hold"
+                ))))
+            }
+        };
+
+        let hold = Hold::new_without_id(
+            id,
+            target_account_id.clone(),
+            money.clone(),
+            created_at,
+            expires_at,
+        );
+        self.holds.push(hold.clone());
+        Ok(hold)
+    }
+
+    /// Tries to settle an active hold by withdrawing up to the held money as a leg of the given
+    /// transfer. Whatever is not captured becomes available again.
+    pub fn capture_hold(
+        &mut self,
+        hold_id: &HoldId,
+        money: &Money,
+        transfer_id: &TransferId,
+        initiator: Option<&Principal>,
+    ) -> Result<Hold> {
+        let index = self.find_active_hold(hold_id)?;
+        let hold = self.holds[index].clone();
+
+        if !money.is_positive() || *money > hold.money {
+            use rust_decimal::prelude::*;
+
+            return Err(anyhow!(AccountError::InvalidCaptureAmount {
+                requested: money.amount().to_i64().unwrap(),
+                held: hold.money.amount().to_i64().unwrap(),
+            }));
+        }
+
+        self.holds[index].status = HoldStatus::Captured;
+        self.holds[index].transfer_id = Some(transfer_id.clone());
+
+        if let Err(err) = self.withdraw(money, &hold.target_account_id, transfer_id, initiator) {
+            self.holds[index] = hold;
+            return Err(err);
+        }
+
+        Ok(self.holds[index].clone())
+    }
+
+    /// Tries to cancel an active hold, making the held money available again.
+    pub fn void_hold(&mut self, hold_id: &HoldId) -> Result<Hold> {
+        let index = self.find_active_hold(hold_id)?;
+
+        self.holds[index].status = HoldStatus::Voided;
+        Ok(self.holds[index].clone())
+    }
+
+    fn find_active_hold(&mut self, hold_id: &HoldId) -> Result<usize> {
+        let index = match self
+            .holds
+            .iter()
+            .position(|hold| hold.id.as_ref() == Some(hold_id))
+        {
+            Some(index) => index,
+            None => return Err(anyhow!(AccountError::HoldNotFound(hold_id.0))),
+        };
+
+        let hold = &mut self.holds[index];
+        if hold.status != HoldStatus::Active {
+            return Err(anyhow!(AccountError::HoldNotActive(hold_id.0)));
+        }
+
+        if !hold.is_active(&Utc::now()) {
+            hold.status = HoldStatus::Expired;
+            return Err(anyhow!(AccountError::HoldExpired(hold_id.0)));
+        }
+
+        Ok(index)
+    }
 }
 
 #[cfg(test)]
@@ -136,6 +315,12 @@ mod tests {
     use super::account_test_data::AccountBuilder;
     use super::{AccountId, ActivityWindow};
     use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::domain_event::DomainEvent;
+    use crate::domain::hold::hold_test_data::HoldBuilder;
+    use crate::domain::hold::{HoldId, HoldStatus};
+    use crate::domain::principal::Principal;
+    use crate::domain::transfer::TransferId;
+    use chrono::{Duration, Utc};
     use rusty_money::{money, Money};
 
     #[test]
@@ -162,6 +347,33 @@ mod tests {
         assert_eq!(balance, money!(1555, "AUD"));
     }
 
+    #[test]
+    fn calculates_balance_as_of() {
+        let account_id = AccountId(1);
+        let instant = Utc::now() - Duration::days(1);
+        let activity_window = ActivityWindow::new(vec![
+            ActivityBuilder::default_activity()
+                .with_target_account(&account_id)
+                .with_timestamp(&instant)
+                .with_money(&money!(999, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_target_account(&account_id)
+                .with_timestamp(&(instant + Duration::seconds(1)))
+                .with_money(&money!(1, "AUD"))
+                .build(),
+        ]);
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(555, "AUD"))
+            .with_activity_window(&activity_window)
+            .build();
+
+        let balance = account.calculate_balance_as_of(&instant);
+
+        assert_eq!(balance, money!(1554, "AUD"));
+    }
+
     #[test]
     fn withdrawal_succeeds() {
         let account_id = AccountId(1);
@@ -182,7 +394,7 @@ mod tests {
             .build();
 
         let success = account
-            .withdraw(&money!(555, "AUD"), &AccountId(99))
+            .withdraw(&money!(555, "AUD"), &AccountId(99), &TransferId(7), None)
             .is_ok();
 
         assert_eq!(success, true);
@@ -210,7 +422,7 @@ mod tests {
             .build();
 
         let success = account
-            .withdraw(&money!(1556, "AUD"), &AccountId(99))
+            .withdraw(&money!(1556, "AUD"), &AccountId(99), &TransferId(7), None)
             .is_ok();
 
         assert_eq!(success, false);
@@ -237,16 +449,190 @@ mod tests {
             .with_activity_window(&activity_window)
             .build();
 
-        let success = account.deposit(&money!(445, "AUD"), &AccountId(99)).is_ok();
+        let success = account
+            .deposit(&money!(445, "AUD"), &AccountId(99), &TransferId(7), None)
+            .is_ok();
 
         assert_eq!(success, true);
         assert_eq!(account.activity_window.activities.len(), 3);
         assert_eq!(account.calculate_balance(), money!(2000, "AUD"));
     }
+
+    #[test]
+    fn deposit_records_initiator() {
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&AccountId(1))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .build();
+        let initiator = Principal::User(String::from("alice"));
+
+        account
+            .deposit(
+                &money!(445, "AUD"),
+                &AccountId(99),
+                &TransferId(7),
+                Some(&initiator),
+            )
+            .unwrap();
+
+        assert_eq!(
+            account.activity_window.activities[0].initiator,
+            Some(initiator)
+        );
+    }
+
+    #[test]
+    fn withdrawal_and_deposit_record_events() {
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&AccountId(1))
+            .with_baseline_balance(&money!(555, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .build();
+
+        account
+            .withdraw(&money!(100, "AUD"), &AccountId(99), &TransferId(7), None)
+            .unwrap();
+        account
+            .withdraw(&money!(1000, "AUD"), &AccountId(99), &TransferId(8), None)
+            .unwrap_err();
+        account
+            .deposit(&money!(50, "AUD"), &AccountId(98), &TransferId(9), None)
+            .unwrap();
+
+        let events = account.take_events();
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::MoneyWithdrawn { account_id, target_account_id, transfer_id, .. }
+                if *account_id == AccountId(1)
+                    && *target_account_id == AccountId(99)
+                    && *transfer_id == TransferId(7)
+        ));
+        assert!(matches!(
+            &events[1],
+            DomainEvent::MoneyDeposited { account_id, source_account_id, transfer_id, .. }
+                if *account_id == AccountId(1)
+                    && *source_account_id == AccountId(98)
+                    && *transfer_id == TransferId(9)
+        ));
+        assert!(account.take_events().is_empty());
+    }
+
+    #[test]
+    fn active_holds_reduce_available_balance() {
+        let account_id = AccountId(1);
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(1000, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .build();
+
+        let hold = account
+            .place_hold(
+                &money!(600, "AUD"),
+                &AccountId(99),
+                Utc::now(),
+                Utc::now() + Duration::days(1),
+            )
+            .unwrap();
+
+        assert_eq!(hold.status, HoldStatus::Active);
+        assert_eq!(account.calculate_balance(), money!(1000, "AUD"));
+        assert_eq!(
+            account.calculate_available_balance(&Utc::now()),
+            money!(400, "AUD")
+        );
+        assert_eq!(
+            account
+                .withdraw(&money!(401, "AUD"), &AccountId(99), &TransferId(7), None)
+                .is_ok(),
+            false
+        );
+    }
+
+    #[test]
+    fn expired_holds_do_not_reduce_available_balance() {
+        let account_id = AccountId(1);
+        let expired_hold = HoldBuilder::default_hold()
+            .with_account(&account_id)
+            .with_money(&money!(600, "AUD"))
+            .with_expires_at(&(Utc::now() - Duration::minutes(1)))
+            .build();
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(1000, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .with_holds(&[expired_hold])
+            .build();
+
+        assert_eq!(
+            account.calculate_available_balance(&Utc::now()),
+            money!(1000, "AUD")
+        );
+        assert_eq!(
+            account
+                .capture_hold(&HoldId(3), &money!(600, "AUD"), &TransferId(7), None)
+                .is_ok(),
+            false
+        );
+    }
+
+    #[test]
+    fn capturing_hold_withdraws_money() {
+        let account_id = AccountId(1);
+        let hold = HoldBuilder::default_hold()
+            .with_hold_id(&HoldId(3))
+            .with_account(&account_id)
+            .with_money(&money!(600, "AUD"))
+            .build();
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(1000, "AUD"))
+            .with_activity_window(&ActivityWindow::new(vec![]))
+            .with_holds(&[hold])
+            .build();
+
+        let captured = account
+            .capture_hold(&HoldId(3), &money!(500, "AUD"), &TransferId(7), None)
+            .unwrap();
+
+        assert_eq!(captured.status, HoldStatus::Captured);
+        assert_eq!(captured.transfer_id, Some(TransferId(7)));
+        assert_eq!(account.activity_window.activities.len(), 1);
+        assert_eq!(account.calculate_balance(), money!(500, "AUD"));
+        assert_eq!(
+            account.calculate_available_balance(&Utc::now()),
+            money!(500, "AUD")
+        );
+    }
+
+    #[test]
+    fn voided_hold_can_not_be_captured() {
+        let account_id = AccountId(1);
+        let hold = HoldBuilder::default_hold()
+            .with_hold_id(&HoldId(3))
+            .with_account(&account_id)
+            .build();
+        let mut account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_holds(&[hold])
+            .build();
+
+        let voided = account.void_hold(&HoldId(3)).unwrap();
+
+        assert_eq!(voided.status, HoldStatus::Voided);
+        assert_eq!(
+            account
+                .capture_hold(&HoldId(3), &money!(100, "AUD"), &TransferId(7), None)
+                .is_ok(),
+            false
+        );
+    }
 }
 
 pub mod account_test_data {
-    use super::{Account, AccountId};
+    use super::{Account, AccountId, Hold};
     use crate::domain::activity::activity_test_data::ActivityBuilder;
     use crate::domain::activity_window::ActivityWindow;
     use rusty_money::{money, Money};
@@ -293,6 +679,15 @@ pub mod account_test_data {
             new
         }
 
+        pub fn with_holds(&mut self, holds: &[Hold]) -> &mut Self {
+            let mut account = self.account.clone();
+            account.holds = holds.to_vec();
+
+            let mut new = self;
+            new.account = account;
+            new
+        }
+
         pub fn build(&self) -> Account {
             self.account.clone()
         }
@@ -0,0 +1,42 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity::ActivityId;
+use rusty_money::Money;
+
+/// The booked balance of an account as kept up to date with every activity, so that it can be
+/// read without summing the activities.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AccountBalance {
+    pub account_id: AccountId,
+    pub balance: Money,
+    /// The latest activity included in the balance.
+    pub last_activity_id: ActivityId,
+}
+
+impl AccountBalance {
+    pub fn new(account_id: AccountId, balance: Money, last_activity_id: ActivityId) -> Self {
+        Self {
+            account_id,
+            balance,
+            last_activity_id,
+        }
+    }
+}
+
+/// An account whose kept balance doesn't match the sum of its activities.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BalanceDrift {
+    pub account_id: AccountId,
+    /// The kept balance, zero if there is none.
+    pub kept_balance: Money,
+    pub kept_last_activity_id: Option<ActivityId>,
+    /// The sum of the activities.
+    pub booked_balance: Money,
+    pub last_activity_id: Option<ActivityId>,
+}
+
+impl BalanceDrift {
+    /// How much the kept balance is off by, positive if it is too high.
+    pub fn difference(&self) -> Money {
+        self.kept_balance.clone() - self.booked_balance.clone()
+    }
+}
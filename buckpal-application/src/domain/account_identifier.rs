@@ -0,0 +1,183 @@
+use crate::domain::external_account::{AccountNumber, Bsb, ExternalAccountError};
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum AccountIdentifierError {
+    #[error("Invalid BSB and account number: {0}")]
+    InvalidBsbAccount(#[from] ExternalAccountError),
+    #[error(
+        "IBAN must be a country code, two check digits and up to 30 letters or digits, got `{0}`"
+    )]
+    MalformedIban(String),
+    #[error("IBAN `{0}` does not match its check digits")]
+    InvalidIbanCheckDigits(String),
+}
+
+/// An International Bank Account Number, kept in its electronic form without spaces.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Iban(String);
+
+impl Iban {
+    /// Parses an IBAN in either its electronic or its grouped paper form and verifies its
+    /// check digits (ISO 7064 MOD 97-10).
+    pub fn parse(value: &str) -> Result<Self, AccountIdentifierError> {
+        let iban: String = value
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>()
+            .to_ascii_uppercase();
+
+        let well_formed = iban.len() >= 15
+            && iban.len() <= 34
+            && iban.chars().take(2).all(|c| c.is_ascii_uppercase())
+            && iban.chars().skip(2).take(2).all(|c| c.is_ascii_digit())
+            && iban.chars().all(|c| c.is_ascii_alphanumeric());
+        if !well_formed {
+            return Err(AccountIdentifierError::MalformedIban(String::from(value)));
+        }
+
+        if mod_97(&iban) != 1 {
+            return Err(AccountIdentifierError::InvalidIbanCheckDigits(iban));
+        }
+
+        Ok(Self(iban))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The ISO 3166 code of the country the account is held in.
+    pub fn country_code(&self) -> &str {
+        &self.0[..2]
+    }
+}
+
+impl fmt::Display for Iban {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Moves the country code and check digits to the end, replaces every letter by two digits
+/// (A = 10, ..., Z = 35) and takes the remainder of the resulting number divided by 97.
+fn mod_97(iban: &str) -> u32 {
+    iban.chars()
+        .cycle()
+        .skip(4)
+        .take(iban.len())
+        .fold(0, |remainder, c| {
+            let value = c.to_digit(36).expect("expected IBAN to be alphanumeric");
+            if value < 10 {
+                (remainder * 10 + value) % 97
+            } else {
+                (remainder * 100 + value) % 97
+            }
+        })
+}
+
+/// A way of addressing an account other than its internal id, as used by other banks and
+/// their customers.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum AccountIdentifier {
+    /// An Australian account, addressed by the BSB of its branch and its account number.
+    Bsb {
+        bsb: Bsb,
+        account_number: AccountNumber,
+    },
+    Iban(Iban),
+}
+
+impl AccountIdentifier {
+    pub fn bsb(bsb: &str, account_number: &str) -> Result<Self, AccountIdentifierError> {
+        Ok(Self::Bsb {
+            bsb: Bsb::parse(bsb)?,
+            account_number: AccountNumber::parse(account_number)?,
+        })
+    }
+
+    pub fn iban(iban: &str) -> Result<Self, AccountIdentifierError> {
+        Ok(Self::Iban(Iban::parse(iban)?))
+    }
+
+    /// The name of the identification scheme, e.g. `IBAN`.
+    pub fn scheme(&self) -> &'static str {
+        match self {
+            Self::Bsb { .. } => "BSB",
+            Self::Iban(_) => "IBAN",
+        }
+    }
+
+    /// The identifier in its normalised form, unique within its scheme.
+    pub fn value(&self) -> String {
+        match self {
+            Self::Bsb {
+                bsb,
+                account_number,
+            } => format!("{} {}", bsb, account_number),
+            Self::Iban(iban) => iban.to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AccountIdentifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.scheme(), self.value())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountIdentifier, AccountIdentifierError, Iban};
+
+    #[test]
+    fn accepts_valid_ibans_in_either_form() {
+        for value in &[
+            "DE89370400440532013000",
+            "GB82 WEST 1234 5698 7654 32",
+            "fr1420041010050500013m02606",
+            "NO9386011117947",
+        ] {
+            assert!(
+                Iban::parse(value).is_ok(),
+                "expected `{}` to be valid",
+                value
+            );
+        }
+
+        assert_eq!(
+            Iban::parse("GB82 WEST 1234 5698 7654 32").unwrap().as_str(),
+            "GB82WEST12345698765432"
+        );
+    }
+
+    #[test]
+    fn rejects_iban_with_wrong_check_digits() {
+        assert_eq!(
+            Iban::parse("DE88370400440532013000"),
+            Err(AccountIdentifierError::InvalidIbanCheckDigits(
+                String::from("DE88370400440532013000")
+            ))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_iban() {
+        for value in &["DE89", "1234370400440532013000", "DE8937040044053201300!"] {
+            assert_eq!(
+                Iban::parse(value),
+                Err(AccountIdentifierError::MalformedIban(String::from(*value)))
+            );
+        }
+    }
+
+    #[test]
+    fn normalises_bsb_account() {
+        let identifier = AccountIdentifier::bsb("062000", "12345678").unwrap();
+
+        assert_eq!(identifier.scheme(), "BSB");
+        assert_eq!(identifier.value(), "062-000 12345678");
+        assert!(AccountIdentifier::bsb("062000", "1234567890").is_err());
+    }
+}
@@ -1,10 +1,20 @@
 use crate::domain::account::AccountId;
+use crate::domain::principal::Principal;
+use crate::domain::transfer::TransferId;
 use chrono::{DateTime, Utc};
 use rusty_money::Money;
 
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct ActivityId(pub i32);
 
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ActivityKind {
+    /// The owner account was credited.
+    Deposit,
+    /// The owner account was debited.
+    Withdrawal,
+}
+
 /// A money transfer activity between Accounts
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub struct Activity {
@@ -19,6 +29,11 @@ pub struct Activity {
     pub timestamp: DateTime<Utc>,
     /// The money that was transferred between the accounts.
     pub money: Money,
+    /// The transfer this activity is a leg of.
+    pub transfer_id: Option<TransferId>,
+    /// Who initiated the transfer, unknown for activities recorded before principals were
+    /// introduced.
+    pub initiator: Option<Principal>,
 }
 
 impl Activity {
@@ -28,6 +43,7 @@ impl Activity {
         target_account_id: AccountId,
         timestamp: DateTime<Utc>,
         money: Money,
+        transfer_id: Option<TransferId>,
     ) -> Self {
         Self {
             id: None,
@@ -36,6 +52,8 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            transfer_id,
+            initiator: None,
         }
     }
 
@@ -46,6 +64,7 @@ impl Activity {
         target_account_id: AccountId,
         timestamp: DateTime<Utc>,
         money: Money,
+        transfer_id: Option<TransferId>,
     ) -> Self {
         Self {
             id: activity_id,
@@ -54,12 +73,36 @@ impl Activity {
             target_account_id,
             timestamp,
             money,
+            transfer_id,
+            initiator: None,
+        }
+    }
+
+    pub fn with_initiator(mut self, initiator: Option<Principal>) -> Self {
+        self.initiator = initiator;
+        self
+    }
+
+    /// Whether the activity credited or debited its owner account.
+    pub fn kind(&self) -> ActivityKind {
+        if self.target_account_id == self.owner_account_id {
+            ActivityKind::Deposit
+        } else {
+            ActivityKind::Withdrawal
+        }
+    }
+
+    /// The account on the other side of the activity.
+    pub fn counterparty_account_id(&self) -> &AccountId {
+        match self.kind() {
+            ActivityKind::Deposit => &self.source_account_id,
+            ActivityKind::Withdrawal => &self.target_account_id,
         }
     }
 }
 
 pub mod activity_test_data {
-    use super::{AccountId, Activity};
+    use super::{AccountId, Activity, ActivityId, TransferId};
     use chrono::{DateTime, Utc};
     use rusty_money::{money, Money};
 
@@ -75,11 +118,21 @@ pub mod activity_test_data {
                 AccountId(41),
                 Utc::now(),
                 money!(999, "AUD"),
+                None,
             );
 
             Self { activity }
         }
 
+        pub fn with_activity_id(&mut self, activity_id: &ActivityId) -> &mut Self {
+            let mut activity = self.activity.clone();
+            activity.id = Some(activity_id.clone());
+
+            let mut new = self;
+            new.activity = activity;
+            new
+        }
+
         pub fn with_timestamp(&mut self, timestamp: &DateTime<Utc>) -> &mut Self {
             let mut activity = self.activity.clone();
             activity.timestamp = *timestamp;
@@ -116,6 +169,15 @@ pub mod activity_test_data {
             new
         }
 
+        pub fn with_transfer_id(&mut self, transfer_id: &TransferId) -> &mut Self {
+            let mut activity = self.activity.clone();
+            activity.transfer_id = Some(transfer_id.clone());
+
+            let mut new = self;
+            new.activity = activity;
+            new
+        }
+
         pub fn build(&self) -> Activity {
             self.activity.clone()
         }
@@ -0,0 +1,466 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity::{Activity, ActivityId};
+use chrono::{DateTime, Utc};
+use sha2::{Digest, Sha256};
+
+/// What the first activity of every chain links to.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Hashes the canonical content of the persisted activity together with the hash of the
+/// activity before it in the chain of its owner account, as hex encoded SHA-256. The initiator is
+/// only appended when known, so activities recorded before principals were introduced keep the
+/// hash they were chained with.
+pub fn hash_activity(previous_hash: &str, activity: &Activity) -> String {
+    use rust_decimal::prelude::*;
+
+    let mut canonical = format!(
+        "{}|{}|{}|{}|{}|{}|{}|{}",
+        previous_hash,
+        activity
+            .id
+            .as_ref()
+            .map(|id| id.0.to_string())
+            .unwrap_or_default(),
+        activity.owner_account_id.0,
+        activity.source_account_id.0,
+        activity.target_account_id.0,
+        // here we want to explode, no way to recover
+        activity.money.amount().to_i64().unwrap(),
+        activity
+            .transfer_id
+            .as_ref()
+            .map(|id| id.0.to_string())
+            .unwrap_or_default(),
+        // activities are persisted to the microsecond
+        activity.timestamp.format("%Y-%m-%dT%H:%M:%S%.6fZ"),
+    );
+    if let Some(initiator) = &activity.initiator {
+        canonical.push_str(&format!("|{}", initiator));
+    }
+
+    hex::encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// A persisted activity with its link in the chain, none for activities written before
+/// activities were chained.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainedActivity {
+    pub activity: Activity,
+    pub previous_hash: Option<String>,
+    pub hash: Option<String>,
+}
+
+impl ChainedActivity {
+    pub fn new(activity: Activity, previous_hash: Option<String>, hash: Option<String>) -> Self {
+        Self {
+            activity,
+            previous_hash,
+            hash,
+        }
+    }
+}
+
+/// The latest activity of the chain of an account.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainHead {
+    pub account_id: AccountId,
+    pub activity_id: ActivityId,
+    pub hash: String,
+}
+
+impl ChainHead {
+    pub fn new(account_id: AccountId, activity_id: ActivityId, hash: &str) -> Self {
+        Self {
+            account_id,
+            activity_id,
+            hash: String::from(hash),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainCheckpointId(pub i32);
+
+/// The heads of all chains at a point in time, signed so that the chains can't be rewritten up
+/// to them without it showing.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainCheckpoint {
+    pub id: Option<ChainCheckpointId>,
+    pub heads: Vec<ChainHead>,
+    /// See `digest_heads`.
+    pub digest: String,
+    pub signature: String,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ChainCheckpoint {
+    pub fn new(heads: Vec<ChainHead>, signature: &str, created_at: DateTime<Utc>) -> Self {
+        let digest = digest_heads(&heads);
+
+        Self {
+            id: None,
+            heads,
+            digest,
+            signature: String::from(signature),
+            created_at,
+        }
+    }
+}
+
+/// Hashes the heads ordered by account, one `<account id>:<activity id>:<hash>` line each, as
+/// hex encoded SHA-256.
+pub fn digest_heads(heads: &[ChainHead]) -> String {
+    let mut heads = heads.to_vec();
+    heads.sort_by_key(|head| head.account_id.0);
+
+    let mut hasher = Sha256::new();
+    for head in heads {
+        hasher.update(
+            format!(
+                "{}:{}:{}\n",
+                head.account_id.0, head.activity_id.0, head.hash
+            )
+            .as_bytes(),
+        );
+    }
+
+    hex::encode(hasher.finalize())
+}
+
+/// Why a chain doesn't hold.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ChainBreak {
+    /// The activity has no link, though activities before it have.
+    Unchained,
+    /// The activity doesn't link to the activity before it, which was removed or reordered.
+    PreviousHashMismatch { expected: String, actual: String },
+    /// The content of the activity changed since it was chained.
+    HashMismatch { expected: String, actual: String },
+    /// The chain doesn't end at the head kept for the account, activities were removed from its
+    /// end.
+    HeadMismatch { expected: String, actual: String },
+    /// The chain no longer contains the head signed in the latest checkpoint, it was rewritten.
+    CheckpointMismatch { expected: String, actual: String },
+    /// The latest checkpoint isn't signed or doesn't match its heads.
+    CheckpointSignatureInvalid,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct BrokenLink {
+    /// The first activity the chain breaks at, none if it breaks past the last one.
+    pub activity_id: Option<ActivityId>,
+    pub reason: ChainBreak,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ChainVerification {
+    pub account_id: AccountId,
+    /// The number of chained activities verified before the chain broke, if it did.
+    pub verified_activities: usize,
+    /// The checkpoint the chain was verified against.
+    pub checkpoint_id: Option<ChainCheckpointId>,
+    pub broken_link: Option<BrokenLink>,
+}
+
+impl ChainVerification {
+    fn broken(
+        account_id: &AccountId,
+        verified_activities: usize,
+        activity_id: Option<ActivityId>,
+        reason: ChainBreak,
+    ) -> Self {
+        Self {
+            account_id: account_id.clone(),
+            verified_activities,
+            checkpoint_id: None,
+            broken_link: Some(BrokenLink {
+                activity_id,
+                reason,
+            }),
+        }
+    }
+
+    pub fn is_intact(&self) -> bool {
+        self.broken_link.is_none()
+    }
+}
+
+/// Walks the activities of the account in the order they were persisted, recomputing every link,
+/// then checks that the chain ends at the kept head and contains the checkpointed one.
+/// Activities written before activities were chained are skipped.
+pub fn verify_chain(
+    account_id: &AccountId,
+    activities: &[ChainedActivity],
+    head: Option<&ChainHead>,
+    checkpointed_head: Option<&ChainHead>,
+) -> ChainVerification {
+    let mut expected_previous_hash = String::from(GENESIS_HASH);
+    let mut chained = false;
+    let mut verified_activities = 0;
+    let mut checkpoint_found = false;
+
+    for chained_activity in activities {
+        let activity_id = chained_activity.activity.id.clone();
+
+        let (previous_hash, hash) = match (&chained_activity.previous_hash, &chained_activity.hash)
+        {
+            (Some(previous_hash), Some(hash)) => (previous_hash, hash),
+            (None, None) if !chained => continue,
+            _ => {
+                return ChainVerification::broken(
+                    account_id,
+                    verified_activities,
+                    activity_id,
+                    ChainBreak::Unchained,
+                )
+            }
+        };
+        chained = true;
+
+        if *previous_hash != expected_previous_hash {
+            return ChainVerification::broken(
+                account_id,
+                verified_activities,
+                activity_id,
+                ChainBreak::PreviousHashMismatch {
+                    expected: expected_previous_hash,
+                    actual: previous_hash.clone(),
+                },
+            );
+        }
+
+        let expected_hash = hash_activity(previous_hash, &chained_activity.activity);
+        if *hash != expected_hash {
+            return ChainVerification::broken(
+                account_id,
+                verified_activities,
+                activity_id,
+                ChainBreak::HashMismatch {
+                    expected: expected_hash,
+                    actual: hash.clone(),
+                },
+            );
+        }
+
+        if let Some(checkpointed_head) = checkpointed_head {
+            if Some(&checkpointed_head.activity_id) == activity_id.as_ref() {
+                if *hash != checkpointed_head.hash {
+                    return ChainVerification::broken(
+                        account_id,
+                        verified_activities,
+                        activity_id,
+                        ChainBreak::CheckpointMismatch {
+                            expected: checkpointed_head.hash.clone(),
+                            actual: hash.clone(),
+                        },
+                    );
+                }
+                checkpoint_found = true;
+            }
+        }
+
+        expected_previous_hash = hash.clone();
+        verified_activities += 1;
+    }
+
+    let head_hash = head
+        .map(|head| head.hash.clone())
+        .unwrap_or_else(|| String::from(GENESIS_HASH));
+    if head_hash != expected_previous_hash {
+        return ChainVerification::broken(
+            account_id,
+            verified_activities,
+            None,
+            ChainBreak::HeadMismatch {
+                expected: head_hash,
+                actual: expected_previous_hash,
+            },
+        );
+    }
+
+    if let Some(checkpointed_head) = checkpointed_head {
+        if !checkpoint_found {
+            return ChainVerification::broken(
+                account_id,
+                verified_activities,
+                Some(checkpointed_head.activity_id.clone()),
+                ChainBreak::CheckpointMismatch {
+                    expected: checkpointed_head.hash.clone(),
+                    actual: String::new(),
+                },
+            );
+        }
+    }
+
+    ChainVerification {
+        account_id: account_id.clone(),
+        verified_activities,
+        checkpoint_id: None,
+        broken_link: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        hash_activity, verify_chain, ChainBreak, ChainHead, ChainedActivity, GENESIS_HASH,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::ActivityId;
+    use crate::domain::principal::Principal;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+
+    fn given_a_chain() -> Vec<ChainedActivity> {
+        let mut previous_hash = String::from(GENESIS_HASH);
+
+        (1..=3)
+            .map(|id| {
+                let activity = ActivityBuilder::default_activity()
+                    .with_activity_id(&ActivityId(id))
+                    .build();
+                let hash = hash_activity(&previous_hash, &activity);
+                let chained_activity =
+                    ChainedActivity::new(activity, Some(previous_hash.clone()), Some(hash.clone()));
+                previous_hash = hash;
+
+                chained_activity
+            })
+            .collect()
+    }
+
+    fn head_of(chain: &[ChainedActivity]) -> ChainHead {
+        let last = chain.last().unwrap();
+
+        ChainHead::new(
+            AccountId(42),
+            last.activity.id.clone().unwrap(),
+            last.hash.as_ref().unwrap(),
+        )
+    }
+
+    #[test]
+    fn intact_chain_verifies() {
+        let chain = given_a_chain();
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head_of(&chain)), None);
+
+        assert!(verification.is_intact());
+        assert_eq!(verification.verified_activities, 3);
+    }
+
+    #[test]
+    fn activities_before_the_chain_are_skipped() {
+        let mut chain = given_a_chain();
+        chain.insert(
+            0,
+            ChainedActivity::new(ActivityBuilder::default_activity().build(), None, None),
+        );
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head_of(&chain)), None);
+
+        assert!(verification.is_intact());
+    }
+
+    #[test]
+    fn activity_without_initiator_keeps_its_hash() {
+        let activity = ActivityBuilder::default_activity()
+            .with_activity_id(&ActivityId(1))
+            .with_timestamp(&DateTime::<Utc>::from_utc(
+                NaiveDate::from_ymd(2021, 5, 1).and_hms(12, 0, 0),
+                Utc,
+            ))
+            .build();
+
+        assert_eq!(
+            hash_activity(GENESIS_HASH, &activity),
+            "5d3240d4fa3dbb565ba5c379bff4e6697417579c39ef8cce2a13a019839b0f29"
+        );
+    }
+
+    #[test]
+    fn attributed_initiator_breaks_chain() {
+        let mut chain = given_a_chain();
+        let head = head_of(&chain);
+        chain[1].activity.initiator = Some(Principal::User(String::from("mallory")));
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head), None);
+
+        let broken_link = verification.broken_link.unwrap();
+        assert_eq!(broken_link.activity_id, Some(ActivityId(2)));
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::HashMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn edited_activity_breaks_chain() {
+        let mut chain = given_a_chain();
+        let head = head_of(&chain);
+        chain[1].activity.money = money!(1, "AUD");
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head), None);
+
+        let broken_link = verification.broken_link.unwrap();
+        assert_eq!(broken_link.activity_id, Some(ActivityId(2)));
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::HashMismatch { .. }
+        ));
+        assert_eq!(verification.verified_activities, 1);
+    }
+
+    #[test]
+    fn removed_activity_breaks_chain() {
+        let mut chain = given_a_chain();
+        let head = head_of(&chain);
+        chain.remove(1);
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head), None);
+
+        let broken_link = verification.broken_link.unwrap();
+        assert_eq!(broken_link.activity_id, Some(ActivityId(3)));
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::PreviousHashMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn removed_last_activity_breaks_chain_at_head() {
+        let mut chain = given_a_chain();
+        let head = head_of(&chain);
+        chain.pop();
+
+        let verification = verify_chain(&AccountId(42), &chain, Some(&head), None);
+
+        let broken_link = verification.broken_link.unwrap();
+        assert_eq!(broken_link.activity_id, None);
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::HeadMismatch { .. }
+        ));
+    }
+
+    #[test]
+    fn rewritten_chain_breaks_at_checkpoint() {
+        let chain = given_a_chain();
+        let checkpointed_head = ChainHead::new(AccountId(42), ActivityId(2), GENESIS_HASH);
+
+        let verification = verify_chain(
+            &AccountId(42),
+            &chain,
+            Some(&head_of(&chain)),
+            Some(&checkpointed_head),
+        );
+
+        let broken_link = verification.broken_link.unwrap();
+        assert_eq!(broken_link.activity_id, Some(ActivityId(2)));
+        assert!(matches!(
+            broken_link.reason,
+            ChainBreak::CheckpointMismatch { .. }
+        ));
+    }
+}
@@ -64,6 +64,17 @@ impl ActivityWindow {
         deposit_balance - withdrawal_balance
     }
 
+    /// The part of this window up to and including the given point in time.
+    pub fn up_to(&self, instant: &DateTime<Utc>) -> ActivityWindow {
+        ActivityWindow::new(
+            self.activities
+                .iter()
+                .filter(|activity| activity.timestamp <= *instant)
+                .cloned()
+                .collect(),
+        )
+    }
+
     pub fn add_activity(&mut self, activity: &Activity) {
         self.activities.push(activity.clone())
     }
@@ -0,0 +1,81 @@
+use crate::domain::principal::Principal;
+use chrono::{DateTime, Utc};
+
+/// Who invoked a use case and where the invocation came from, as far as the caller knows.
+#[derive(Debug, Eq, PartialEq, Clone, Default)]
+pub struct AuditContext {
+    pub actor: Option<Principal>,
+    pub source_ip: Option<String>,
+    /// Correlates the entry with the logs of the caller.
+    pub request_id: Option<String>,
+}
+
+impl AuditContext {
+    pub fn new(
+        actor: Option<Principal>,
+        source_ip: Option<String>,
+        request_id: Option<String>,
+    ) -> Self {
+        Self {
+            actor,
+            source_ip,
+            request_id,
+        }
+    }
+}
+
+/// Why an invocation failed, coarse enough to report on without leaking error messages.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum ErrorCategory {
+    /// The request was turned down by a business rule or failed validation.
+    Rejected,
+    /// The request clashed with another one, e.g. a reused idempotency key.
+    Conflict,
+    /// Something the request referred to doesn't exist.
+    NotFound,
+    /// The principal may not act on or read what the request referred to.
+    Forbidden,
+    /// The request couldn't be processed, e.g. because the database was unreachable.
+    Internal,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum AuditOutcome {
+    Succeeded,
+    Failed(ErrorCategory),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AuditEntryId(pub i32);
+
+/// A use case invocation, recorded once and never changed.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AuditEntry {
+    pub id: Option<AuditEntryId>,
+    pub occurred_at: DateTime<Utc>,
+    /// The use case that was invoked, e.g. `send_money`.
+    pub action: String,
+    /// What the use case was invoked with, e.g. the accounts and the amount of a transfer.
+    pub details: String,
+    pub context: AuditContext,
+    pub outcome: AuditOutcome,
+}
+
+impl AuditEntry {
+    pub fn new(
+        occurred_at: DateTime<Utc>,
+        action: &str,
+        details: &str,
+        context: &AuditContext,
+        outcome: AuditOutcome,
+    ) -> Self {
+        Self {
+            id: None,
+            occurred_at,
+            action: String::from(action),
+            details: String::from(details),
+            context: context.clone(),
+            outcome,
+        }
+    }
+}
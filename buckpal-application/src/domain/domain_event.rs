@@ -0,0 +1,73 @@
+use crate::domain::account::AccountId;
+use crate::domain::transfer::TransferId;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+/// Something that happened to an aggregate, recorded by the aggregate itself and published once
+/// the change has been committed.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum DomainEvent {
+    /// Money left an account as a leg of a transfer.
+    MoneyWithdrawn {
+        account_id: AccountId,
+        target_account_id: AccountId,
+        transfer_id: TransferId,
+        money: Money,
+        timestamp: DateTime<Utc>,
+    },
+    /// Money arrived on an account as a leg of a transfer.
+    MoneyDeposited {
+        account_id: AccountId,
+        source_account_id: AccountId,
+        transfer_id: TransferId,
+        money: Money,
+        timestamp: DateTime<Utc>,
+    },
+    /// Both legs of a transfer have been persisted.
+    TransferCompleted {
+        transfer_id: TransferId,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        timestamp: DateTime<Utc>,
+    },
+}
+
+impl DomainEvent {
+    /// The name of the kind of event, e.g. `MoneyWithdrawn`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::MoneyWithdrawn { .. } => "MoneyWithdrawn",
+            Self::MoneyDeposited { .. } => "MoneyDeposited",
+            Self::TransferCompleted { .. } => "TransferCompleted",
+        }
+    }
+
+    /// Identifies the event, which is recorded at most once for an account and a transfer.
+    pub fn id(&self) -> String {
+        match self {
+            Self::MoneyWithdrawn {
+                account_id,
+                transfer_id,
+                ..
+            }
+            | Self::MoneyDeposited {
+                account_id,
+                transfer_id,
+                ..
+            } => format!("{}-{}-{}", self.name(), transfer_id.0, account_id.0),
+            Self::TransferCompleted { transfer_id, .. } => {
+                format!("{}-{}", self.name(), transfer_id.0)
+            }
+        }
+    }
+
+    /// The point in time the event happened at.
+    pub fn timestamp(&self) -> &DateTime<Utc> {
+        match self {
+            Self::MoneyWithdrawn { timestamp, .. }
+            | Self::MoneyDeposited { timestamp, .. }
+            | Self::TransferCompleted { timestamp, .. } => timestamp,
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use crate::domain::account::AccountId;
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ExternalAccountError {
+    #[error("BSB must be six digits, optionally written as `XXX-XXX`, got `{0}`")]
+    InvalidBsb(String),
+    #[error("Account number must be 1 to 9 digits and not all zeros, got `{0}`")]
+    InvalidAccountNumber(String),
+    #[error("Account name must not be empty")]
+    MissingAccountName,
+}
+
+/// A Bank-State-Branch number identifying the branch of an Australian bank, kept in its usual
+/// `XXX-XXX` form.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Bsb(String);
+
+impl Bsb {
+    pub fn parse(value: &str) -> Result<Self, ExternalAccountError> {
+        let digits: String = value.chars().filter(|c| *c != '-').collect();
+
+        let well_formed = digits.len() == 6
+            && digits.chars().all(|c| c.is_ascii_digit())
+            && (value.len() == 6 || (value.len() == 7 && value.find('-') == Some(3)));
+        if !well_formed {
+            return Err(ExternalAccountError::InvalidBsb(String::from(value)));
+        }
+
+        Ok(Self(format!("{}-{}", &digits[..3], &digits[3..])))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Bsb {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// The number of an account within its branch, as it fits the 9 characters direct entry
+/// allows for it.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct AccountNumber(String);
+
+impl AccountNumber {
+    pub fn parse(value: &str) -> Result<Self, ExternalAccountError> {
+        let well_formed = !value.is_empty()
+            && value.len() <= 9
+            && value.chars().all(|c| c.is_ascii_digit())
+            && value.chars().any(|c| c != '0');
+        if !well_formed {
+            return Err(ExternalAccountError::InvalidAccountNumber(String::from(
+                value,
+            )));
+        }
+
+        Ok(Self(String::from(value)))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for AccountNumber {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// An account held at another bank. The ledger account standing in for it collects the money
+/// sent to it, which is then paid out to the bank by direct entry.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalAccount {
+    /// The ledger account standing in for the external account.
+    pub account_id: AccountId,
+    pub bsb: Bsb,
+    pub account_number: AccountNumber,
+    /// The name the account is held in at the other bank.
+    pub account_name: String,
+}
+
+impl ExternalAccount {
+    pub fn new(
+        account_id: AccountId,
+        bsb: &str,
+        account_number: &str,
+        account_name: &str,
+    ) -> Result<Self, ExternalAccountError> {
+        let account_name = account_name.trim();
+        if account_name.is_empty() {
+            return Err(ExternalAccountError::MissingAccountName);
+        }
+
+        Ok(Self {
+            account_id,
+            bsb: Bsb::parse(bsb)?,
+            account_number: AccountNumber::parse(account_number)?,
+            account_name: String::from(account_name),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AccountNumber, Bsb, ExternalAccountError};
+
+    #[test]
+    fn normalises_bsb() {
+        assert_eq!(Bsb::parse("062000").unwrap().as_str(), "062-000");
+        assert_eq!(Bsb::parse("062-000").unwrap().as_str(), "062-000");
+    }
+
+    #[test]
+    fn rejects_malformed_bsb() {
+        for value in &["06200", "0620000", "06-2000", "062 000", "06a-000", ""] {
+            assert_eq!(
+                Bsb::parse(value),
+                Err(ExternalAccountError::InvalidBsb(String::from(*value)))
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_malformed_account_number() {
+        assert!(AccountNumber::parse("12345678").is_ok());
+        for value in &["", "1234567890", "1234-5678", "000000"] {
+            assert_eq!(
+                AccountNumber::parse(value),
+                Err(ExternalAccountError::InvalidAccountNumber(String::from(
+                    *value
+                )))
+            );
+        }
+    }
+}
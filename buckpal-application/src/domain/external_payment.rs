@@ -0,0 +1,45 @@
+use crate::domain::account::AccountId;
+use crate::domain::external_account::ExternalAccount;
+use crate::domain::transfer::TransferId;
+use chrono::{DateTime, NaiveDate, Utc};
+use rusty_money::Money;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalPaymentBatchId(pub i32);
+
+/// A completed transfer to an external account, to be paid out to the other bank.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalPayment {
+    pub transfer_id: TransferId,
+    /// The debited account.
+    pub source_account_id: AccountId,
+    /// The account the money is paid out to.
+    pub external_account: ExternalAccount,
+    /// The money to pay out, always positive.
+    pub money: Money,
+    /// The reference of the transfer.
+    pub reference: Option<String>,
+    /// The timestamp of the transfer.
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The audit record of a direct entry file handed to the bank. Every payment is part of at most
+/// one batch, which is how it is known to have been paid out already.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ExternalPaymentBatch {
+    pub id: Option<ExternalPaymentBatchId>,
+    /// The timestamp the file was generated at.
+    pub created_at: DateTime<Utc>,
+    /// The day the bank is asked to process the file on.
+    pub processing_date: NaiveDate,
+    /// The transfers paid out by the file.
+    pub transfer_ids: Vec<TransferId>,
+    /// The number of detail records in the file, including a balancing record if there is one.
+    pub record_count: i64,
+    /// The sum of all credit records in cents.
+    pub credit_total: i64,
+    /// The sum of all debit records in cents.
+    pub debit_total: i64,
+    /// The file exactly as it was generated.
+    pub contents: String,
+}
@@ -0,0 +1,159 @@
+use crate::domain::account::AccountId;
+use crate::domain::transfer::TransferId;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct HoldId(pub i32);
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum HoldStatus {
+    /// The money is reserved and can be captured or voided.
+    Active,
+    /// The money was settled by a transfer to the target account.
+    Captured,
+    /// The reservation was cancelled before it was captured.
+    Voided,
+    /// The reservation ran out before it was captured.
+    Expired,
+}
+
+/// A reservation of money on an account for a later transfer to a target account. Active holds
+/// reduce the available balance of the account until they are captured, voided or expire.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Hold {
+    pub id: Option<HoldId>,
+    /// The account the money is reserved on.
+    pub account_id: AccountId,
+    /// The account that receives the money once the hold is captured.
+    pub target_account_id: AccountId,
+    /// The reserved money.
+    pub money: Money,
+    /// The timestamp the hold was authorized at.
+    pub created_at: DateTime<Utc>,
+    /// The timestamp after which the hold no longer reserves any money.
+    pub expires_at: DateTime<Utc>,
+    /// The current status of the hold.
+    pub status: HoldStatus,
+    /// The transfer that settled the hold once it is captured.
+    pub transfer_id: Option<TransferId>,
+}
+
+impl Hold {
+    /// Creates an active Hold entity without an ID. Use to create a new entity that is not yet
+    /// persisted.
+    pub fn new_without_id(
+        account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: None,
+            account_id,
+            target_account_id,
+            money,
+            created_at,
+            expires_at,
+            status: HoldStatus::Active,
+            transfer_id: None,
+        }
+    }
+
+    /// Creates a Hold entity with an ID. Use to reconstitute a persisted entity.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_id(
+        hold_id: HoldId,
+        account_id: AccountId,
+        target_account_id: AccountId,
+        money: Money,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+        status: HoldStatus,
+        transfer_id: Option<TransferId>,
+    ) -> Self {
+        Self {
+            id: Some(hold_id),
+            account_id,
+            target_account_id,
+            money,
+            created_at,
+            expires_at,
+            status,
+            transfer_id,
+        }
+    }
+
+    /// Whether the hold still reserves money at the given point in time.
+    pub fn is_active(&self, now: &DateTime<Utc>) -> bool {
+        self.status == HoldStatus::Active && self.expires_at > *now
+    }
+}
+
+pub mod hold_test_data {
+    use super::{AccountId, Hold, HoldId, HoldStatus};
+    use chrono::{DateTime, Duration, Utc};
+    use rusty_money::{money, Money};
+
+    pub struct HoldBuilder {
+        hold: Hold,
+    }
+
+    impl HoldBuilder {
+        pub fn default_hold() -> Self {
+            let hold = Hold::new_with_id(
+                HoldId(3),
+                AccountId(42),
+                AccountId(41),
+                money!(100, "AUD"),
+                Utc::now(),
+                Utc::now() + Duration::days(7),
+                HoldStatus::Active,
+                None,
+            );
+
+            Self { hold }
+        }
+
+        pub fn with_hold_id(&mut self, hold_id: &HoldId) -> &mut Self {
+            let mut hold = self.hold.clone();
+            hold.id = Some(hold_id.clone());
+
+            let mut new = self;
+            new.hold = hold;
+            new
+        }
+
+        pub fn with_account(&mut self, account_id: &AccountId) -> &mut Self {
+            let mut hold = self.hold.clone();
+            hold.account_id = account_id.clone();
+
+            let mut new = self;
+            new.hold = hold;
+            new
+        }
+
+        pub fn with_money(&mut self, money: &Money) -> &mut Self {
+            let mut hold = self.hold.clone();
+            hold.money = money.clone();
+
+            let mut new = self;
+            new.hold = hold;
+            new
+        }
+
+        pub fn with_expires_at(&mut self, expires_at: &DateTime<Utc>) -> &mut Self {
+            let mut hold = self.hold.clone();
+            hold.expires_at = *expires_at;
+
+            let mut new = self;
+            new.hold = hold;
+            new
+        }
+
+        pub fn build(&self) -> Hold {
+            self.hold.clone()
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::domain::account::AccountId;
+use crate::domain::transfer::TransferId;
+use chrono::{DateTime, Utc};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct IdempotencyKey(pub String);
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum IdempotencyStatus {
+    /// The request has been accepted and its outcome is not known yet.
+    InProgress,
+    /// The request completed and created a transfer.
+    Succeeded,
+    /// The request was rejected.
+    Failed,
+}
+
+/// The outcome of a request made with an idempotency key. A repeated request with the same key
+/// is answered from this record instead of being executed again, until the record expires.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct IdempotencyRecord {
+    pub key: IdempotencyKey,
+    /// The account the request sends money from. Keys are chosen by clients, the same key used
+    /// for two different source accounts names two different requests.
+    pub source_account_id: AccountId,
+    /// Identifies the request the key was first used with, so that reusing the key for a
+    /// different request can be detected.
+    pub fingerprint: String,
+    /// The current status of the request.
+    pub status: IdempotencyStatus,
+    /// The transfer the request created once it succeeded.
+    pub transfer_id: Option<TransferId>,
+    /// The reason the request was rejected once it failed.
+    pub error_message: Option<String>,
+    /// The timestamp the key was first used at.
+    pub created_at: DateTime<Utc>,
+    /// The timestamp after which the key can be used again.
+    pub expires_at: DateTime<Utc>,
+}
+
+impl IdempotencyRecord {
+    /// Creates a record for a request that is about to be executed.
+    pub fn new_in_progress(
+        key: IdempotencyKey,
+        source_account_id: AccountId,
+        fingerprint: String,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            key,
+            source_account_id,
+            fingerprint,
+            status: IdempotencyStatus::InProgress,
+            transfer_id: None,
+            error_message: None,
+            created_at,
+            expires_at,
+        }
+    }
+
+    /// Creates a record with the given outcome. Use to reconstitute a persisted record.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_outcome(
+        key: IdempotencyKey,
+        source_account_id: AccountId,
+        fingerprint: String,
+        status: IdempotencyStatus,
+        transfer_id: Option<TransferId>,
+        error_message: Option<String>,
+        created_at: DateTime<Utc>,
+        expires_at: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            key,
+            source_account_id,
+            fingerprint,
+            status,
+            transfer_id,
+            error_message,
+            created_at,
+            expires_at,
+        }
+    }
+
+    /// Records that the request created the given transfer.
+    pub fn succeed(&mut self, transfer_id: TransferId) {
+        self.status = IdempotencyStatus::Succeeded;
+        self.transfer_id = Some(transfer_id);
+    }
+
+    /// Records that the request was rejected for the given reason.
+    pub fn fail(&mut self, error_message: String) {
+        self.status = IdempotencyStatus::Failed;
+        self.error_message = Some(error_message);
+    }
+}
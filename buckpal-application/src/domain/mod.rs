@@ -1,3 +1,20 @@
+pub mod aba;
 pub mod account;
+pub mod account_balance;
+pub mod account_identifier;
 pub mod activity;
+pub mod activity_chain;
 pub mod activity_window;
+pub mod audit;
+pub mod domain_event;
+pub mod external_account;
+pub mod external_payment;
+pub mod hold;
+pub mod idempotency_record;
+pub mod payment_initiation;
+pub mod principal;
+pub mod reconciliation;
+pub mod statement;
+pub mod transfer;
+pub mod transfer_receipt;
+pub mod webhook;
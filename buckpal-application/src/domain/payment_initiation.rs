@@ -0,0 +1,175 @@
+use crate::domain::transfer::TransferId;
+use rust_decimal::Decimal;
+
+/// A customer credit transfer initiation (ISO 20022 `pain.001`) as submitted by a customer.
+/// Values are kept as written in the file, they are only validated when the initiation gets
+/// imported.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PaymentInitiation {
+    /// Identifies the file, a message id can only ever be imported once.
+    pub message_id: String,
+    /// The number of transactions in the file, as declared in its header.
+    pub number_of_transactions: String,
+    /// The sum of all amounts in the file, as declared in its header.
+    pub control_sum: Option<Decimal>,
+    pub payments: Vec<PaymentInstruction>,
+}
+
+/// A group of credit transfers debiting the same account.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PaymentInstruction {
+    pub payment_information_id: String,
+    pub debtor_account: String,
+    pub transactions: Vec<CreditTransferTransaction>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct CreditTransferTransaction {
+    pub instruction_id: Option<String>,
+    /// Identifies the transaction from end to end, unique within a file.
+    pub end_to_end_id: String,
+    pub creditor_account: String,
+    pub amount: Decimal,
+    pub currency: String,
+    /// Unstructured remittance information, used as the reference of the transfer.
+    pub remittance_information: Option<String>,
+}
+
+impl PaymentInitiation {
+    pub fn transactions(&self) -> impl Iterator<Item = &CreditTransferTransaction> {
+        self.payments
+            .iter()
+            .flat_map(|payment| payment.transactions.iter())
+    }
+}
+
+/// The outcome of a payment initiation or a transaction within it, named after the ISO 20022
+/// status codes they are reported with.
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum PaymentStatus {
+    /// Every transaction was executed (`ACSC`).
+    AcceptedSettlementCompleted,
+    /// Some transactions were executed, others were rejected (`PART`).
+    PartiallyAccepted,
+    /// Nothing was executed (`RJCT`).
+    Rejected,
+}
+
+/// Why a payment initiation or one of its transactions was rejected, as an ISO 20022 external
+/// status reason code with an explanation.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct StatusReason {
+    pub code: &'static str,
+    pub additional_information: String,
+}
+
+impl StatusReason {
+    pub fn new(code: &'static str, additional_information: impl Into<String>) -> Self {
+        Self {
+            code,
+            additional_information: additional_information.into(),
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TransactionStatus {
+    pub instruction_id: Option<String>,
+    pub end_to_end_id: String,
+    pub status: PaymentStatus,
+    pub reason: Option<StatusReason>,
+    /// The transfer that executed the transaction.
+    pub transfer_id: Option<TransferId>,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PaymentInstructionStatus {
+    pub payment_information_id: String,
+    pub transactions: Vec<TransactionStatus>,
+}
+
+/// The status of every transaction of a payment initiation (ISO 20022 `pain.002`).
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct PaymentStatusReport {
+    pub original_message_id: String,
+    pub original_number_of_transactions: String,
+    pub status: PaymentStatus,
+    /// Why the initiation as a whole was rejected.
+    pub reason: Option<StatusReason>,
+    pub payments: Vec<PaymentInstructionStatus>,
+}
+
+impl PaymentStatusReport {
+    /// Rejects the whole initiation without executing anything, transactions with a reason of
+    /// their own are rejected with it.
+    pub fn rejected(
+        initiation: &PaymentInitiation,
+        reason: StatusReason,
+        transaction_reasons: &[(String, StatusReason)],
+    ) -> Self {
+        let payments = initiation
+            .payments
+            .iter()
+            .map(|payment| PaymentInstructionStatus {
+                payment_information_id: payment.payment_information_id.clone(),
+                transactions: payment
+                    .transactions
+                    .iter()
+                    .map(|transaction| TransactionStatus {
+                        instruction_id: transaction.instruction_id.clone(),
+                        end_to_end_id: transaction.end_to_end_id.clone(),
+                        status: PaymentStatus::Rejected,
+                        reason: transaction_reasons
+                            .iter()
+                            .find(|(end_to_end_id, _)| *end_to_end_id == transaction.end_to_end_id)
+                            .map(|(_, reason)| reason.clone()),
+                        transfer_id: None,
+                    })
+                    .collect(),
+            })
+            .collect();
+
+        Self {
+            original_message_id: initiation.message_id.clone(),
+            original_number_of_transactions: initiation.number_of_transactions.clone(),
+            status: PaymentStatus::Rejected,
+            reason: Some(reason),
+            payments,
+        }
+    }
+
+    /// Reports the outcome of executed transactions, the status of the initiation follows from
+    /// theirs.
+    pub fn executed(
+        initiation: &PaymentInitiation,
+        payments: Vec<PaymentInstructionStatus>,
+    ) -> Self {
+        let statuses: Vec<PaymentStatus> = payments
+            .iter()
+            .flat_map(|payment| payment.transactions.iter())
+            .map(|transaction| transaction.status)
+            .collect();
+
+        let status = if statuses
+            .iter()
+            .all(|status| *status == PaymentStatus::AcceptedSettlementCompleted)
+        {
+            PaymentStatus::AcceptedSettlementCompleted
+        } else if statuses
+            .iter()
+            .all(|status| *status == PaymentStatus::Rejected)
+        {
+            PaymentStatus::Rejected
+        } else {
+            PaymentStatus::PartiallyAccepted
+        };
+
+        Self {
+            original_message_id: initiation.message_id.clone(),
+            original_number_of_transactions: initiation.number_of_transactions.clone(),
+            status,
+            reason: None,
+            payments,
+        }
+    }
+}
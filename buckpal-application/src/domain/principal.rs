@@ -0,0 +1,90 @@
+use std::fmt;
+use thiserror::Error;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum PrincipalError {
+    #[error(
+        "Principal must be `user:`, `service:` or `system_job:` followed by a name, got `{0}`"
+    )]
+    Malformed(String),
+}
+
+/// Who initiated a command or query.
+#[derive(Debug, Eq, PartialEq, Clone, Hash)]
+pub enum Principal {
+    /// A person, e.g. a customer or an operator.
+    User(String),
+    /// Another system calling the API on its own behalf.
+    Service(String),
+    /// A job run by buckpal itself, e.g. the reconciliation.
+    SystemJob(String),
+}
+
+impl Principal {
+    /// Parses the form written by `Display`, e.g. `user:alice`.
+    pub fn parse(value: &str) -> Result<Self, PrincipalError> {
+        let (kind, name) = match value.find(':') {
+            Some(index) => (&value[..index], &value[index + 1..]),
+            None => return Err(PrincipalError::Malformed(String::from(value))),
+        };
+
+        if name.is_empty() {
+            return Err(PrincipalError::Malformed(String::from(value)));
+        }
+
+        match kind {
+            "user" => Ok(Self::User(String::from(name))),
+            "service" => Ok(Self::Service(String::from(name))),
+            "system_job" => Ok(Self::SystemJob(String::from(name))),
+            _ => Err(PrincipalError::Malformed(String::from(value))),
+        }
+    }
+}
+
+impl fmt::Display for Principal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::User(name) => write!(f, "user:{}", name),
+            Self::Service(name) => write!(f, "service:{}", name),
+            Self::SystemJob(name) => write!(f, "system_job:{}", name),
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod principal_test_data {
+    use super::Principal;
+
+    pub fn default_principal() -> Principal {
+        Principal::User(String::from("alice"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Principal, PrincipalError};
+
+    #[test]
+    fn parses_what_it_displays() {
+        for principal in &[
+            Principal::User(String::from("alice")),
+            Principal::Service(String::from("payroll")),
+            Principal::SystemJob(String::from("reconciliation")),
+        ] {
+            assert_eq!(
+                Principal::parse(&principal.to_string()).as_ref(),
+                Ok(principal)
+            );
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_kind_or_missing_name() {
+        for value in &["robot:r2d2", "user:", "alice"] {
+            assert_eq!(
+                Principal::parse(value),
+                Err(PrincipalError::Malformed(String::from(*value)))
+            );
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use crate::domain::account::AccountId;
+use crate::domain::activity::Activity;
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+/// What the deposits and the withdrawals of all accounts add up to. Every transfer deposits what
+/// it withdraws, so the two are the same on a consistent ledger.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct LedgerTotals {
+    pub deposited: Money,
+    pub withdrawn: Money,
+}
+
+impl LedgerTotals {
+    pub fn new(deposited: Money, withdrawn: Money) -> Self {
+        Self {
+            deposited,
+            withdrawn,
+        }
+    }
+
+    pub fn is_conserved(&self) -> bool {
+        self.deposited == self.withdrawn
+    }
+}
+
+/// An invariant of the ledger that doesn't hold.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ReconciliationViolation {
+    /// A withdrawal without the deposit mirroring it on the target account, or a deposit without
+    /// the withdrawal on the source account. The mirror has the same accounts, amount, timestamp
+    /// and transfer.
+    UnmatchedActivity(Activity),
+    /// Money was created or destroyed, the deposits and withdrawals across all accounts differ.
+    MoneyNotConserved(LedgerTotals),
+    /// The activities of the account add up to less than zero. Accounts have no overdraft, so
+    /// none of them may ever go below zero.
+    NegativeBalance {
+        account_id: AccountId,
+        balance: Money,
+    },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct ReconciliationReport {
+    pub reconciled_at: DateTime<Utc>,
+    pub violations: Vec<ReconciliationViolation>,
+}
+
+impl ReconciliationReport {
+    pub fn new(reconciled_at: DateTime<Utc>, violations: Vec<ReconciliationViolation>) -> Self {
+        Self {
+            reconciled_at,
+            violations,
+        }
+    }
+
+    pub fn is_clean(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
@@ -0,0 +1,202 @@
+use crate::domain::account::{Account, AccountId};
+use crate::domain::activity::{ActivityId, ActivityKind};
+use crate::domain::transfer::TransferId;
+use chrono::{DateTime, NaiveDate, Utc};
+use rusty_money::{money, Money};
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct StatementId(pub i32);
+
+/// A single activity on a statement.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct StatementLine {
+    /// The activity this line was generated from.
+    pub activity_id: Option<ActivityId>,
+    /// The timestamp of the activity.
+    pub timestamp: DateTime<Utc>,
+    /// Whether the account was credited or debited.
+    pub kind: ActivityKind,
+    /// The account on the other side of the activity.
+    pub counterparty_account_id: AccountId,
+    /// The money that was transferred, always positive.
+    pub money: Money,
+    /// The balance of the account right after the activity.
+    pub running_balance: Money,
+    /// The transfer the activity is a leg of.
+    pub transfer_id: Option<TransferId>,
+    /// The reference of that transfer, filled in by the caller since an account does not know
+    /// its transfers.
+    pub reference: Option<String>,
+}
+
+/// The activities of an account over a period of days (UTC), together with the balances at the
+/// start and the end of the period. Statements of closed periods never change once generated.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Statement {
+    pub id: Option<StatementId>,
+    pub account_id: AccountId,
+    /// The first day of the period.
+    pub from: NaiveDate,
+    /// The last day of the period, inclusive.
+    pub to: NaiveDate,
+    /// The balance at the start of the first day.
+    pub opening_balance: Money,
+    /// The sum of all deposits within the period.
+    pub total_in: Money,
+    /// The sum of all withdrawals within the period.
+    pub total_out: Money,
+    /// The balance at the end of the last day.
+    pub closing_balance: Money,
+    /// The activities of the period in chronological order.
+    pub lines: Vec<StatementLine>,
+    /// The timestamp the statement was generated at.
+    pub generated_at: DateTime<Utc>,
+}
+
+impl Statement {
+    /// Generates the statement of the given period from an account whose baseline balance is
+    /// the balance at the start of the period, i.e. an account loaded with the start of `from`
+    /// as its baseline date.
+    pub fn generate(
+        account_id: &AccountId,
+        account: &Account,
+        from: NaiveDate,
+        to: NaiveDate,
+        generated_at: DateTime<Utc>,
+    ) -> Self {
+        let period_end = Self::start_of_day(&to.succ());
+
+        let mut activities: Vec<_> = account
+            .activity_window
+            .activities
+            .iter()
+            .filter(|activity| activity.timestamp < period_end)
+            .collect();
+        activities.sort_by_key(|activity| {
+            (
+                activity.timestamp,
+                activity.id.clone().map(|id| id.0).unwrap_or(0),
+            )
+        });
+
+        let opening_balance = account.baseline_balance.clone();
+        let mut running_balance = opening_balance.clone();
+        let mut total_in = money!(0, "AUD");
+        let mut total_out = money!(0, "AUD");
+        let mut lines = vec![];
+
+        for activity in activities {
+            let kind = activity.kind();
+
+            match kind {
+                ActivityKind::Deposit => {
+                    total_in += activity.money.clone();
+                    running_balance += activity.money.clone();
+                }
+                ActivityKind::Withdrawal => {
+                    total_out += activity.money.clone();
+                    running_balance -= activity.money.clone();
+                }
+            }
+
+            lines.push(StatementLine {
+                activity_id: activity.id.clone(),
+                timestamp: activity.timestamp,
+                kind,
+                counterparty_account_id: activity.counterparty_account_id().clone(),
+                money: activity.money.clone(),
+                running_balance: running_balance.clone(),
+                transfer_id: activity.transfer_id.clone(),
+                reference: None,
+            });
+        }
+
+        Self {
+            id: None,
+            account_id: account_id.clone(),
+            from,
+            to,
+            opening_balance,
+            total_in,
+            total_out,
+            closing_balance: running_balance,
+            lines,
+            generated_at,
+        }
+    }
+
+    /// Whether the period is over at the given point in time, after which its statement can no
+    /// longer change.
+    pub fn is_closed(&self, now: &DateTime<Utc>) -> bool {
+        Self::start_of_day(&self.to.succ()) <= *now
+    }
+
+    /// The first instant of the given day (UTC).
+    pub fn start_of_day(date: &NaiveDate) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(date.and_hms(0, 0, 0), Utc)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Statement;
+    use crate::domain::account::account_test_data::AccountBuilder;
+    use crate::domain::account::AccountId;
+    use crate::domain::activity::activity_test_data::ActivityBuilder;
+    use crate::domain::activity::ActivityKind;
+    use crate::domain::activity_window::ActivityWindow;
+    use chrono::{DateTime, NaiveDate, Utc};
+    use rusty_money::{money, Money};
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        DateTime::<Utc>::from_utc(NaiveDate::from_ymd(2021, 2, day).and_hms(hour, 0, 0), Utc)
+    }
+
+    #[test]
+    fn generates_running_balances_and_totals() {
+        let account_id = AccountId(42);
+        let activity_window = ActivityWindow::new(vec![
+            ActivityBuilder::default_activity()
+                .with_source_account(&account_id)
+                .with_target_account(&AccountId(41))
+                .with_timestamp(&at(10, 12))
+                .with_money(&money!(30, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_source_account(&AccountId(41))
+                .with_target_account(&account_id)
+                .with_timestamp(&at(2, 9))
+                .with_money(&money!(50, "AUD"))
+                .build(),
+            ActivityBuilder::default_activity()
+                .with_source_account(&AccountId(41))
+                .with_target_account(&account_id)
+                .with_timestamp(&at(1, 0) + chrono::Duration::days(28))
+                .with_money(&money!(999, "AUD"))
+                .build(),
+        ]);
+        let account = AccountBuilder::default_account()
+            .with_account_id(&account_id)
+            .with_baseline_balance(&money!(100, "AUD"))
+            .with_activity_window(&activity_window)
+            .build();
+
+        let statement = Statement::generate(
+            &account_id,
+            &account,
+            NaiveDate::from_ymd(2021, 2, 1),
+            NaiveDate::from_ymd(2021, 2, 28),
+            Utc::now(),
+        );
+
+        assert_eq!(statement.opening_balance, money!(100, "AUD"));
+        assert_eq!(statement.total_in, money!(50, "AUD"));
+        assert_eq!(statement.total_out, money!(30, "AUD"));
+        assert_eq!(statement.closing_balance, money!(120, "AUD"));
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(statement.lines[0].kind, ActivityKind::Deposit);
+        assert_eq!(statement.lines[0].running_balance, money!(150, "AUD"));
+        assert_eq!(statement.lines[1].counterparty_account_id, AccountId(41));
+        assert_eq!(statement.lines[1].running_balance, money!(120, "AUD"));
+    }
+}
@@ -0,0 +1,412 @@
+use crate::domain::account::AccountId;
+use crate::domain::domain_event::DomainEvent;
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use rusty_money::{money, Money};
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum TransferError {
+    #[error("Transfer can't be reversed while it is `{0:?}`")]
+    NotReversible(TransferStatus),
+    #[error("Transfer is itself a reversal of transfer `{0}` and can't be reversed")]
+    ReversalOfReversal(i32),
+    #[error("Reversal amount must be positive, got `{0}`")]
+    InvalidReversalAmount(i64),
+    #[error("Reversal of `{requested}` exceeds the remaining reversible amount of `{remaining}`")]
+    ReversalExceedsRemaining { requested: i64, remaining: i64 },
+    #[error("Transfer has no receipt while it is `{0:?}`")]
+    NotCompleted(TransferStatus),
+    #[error("Transfer id is invalid, can't `{0}`")]
+    InvalidTransferId(String),
+    #[error("Transfer `{0}` does not exist")]
+    TransferNotFound(i32),
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TransferId(pub i32);
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum TransferStatus {
+    /// The transfer was registered but its activities have not been persisted yet.
+    Pending,
+    /// Both legs of the transfer have been persisted.
+    Completed,
+    /// The transfer was rejected or its legs could not be persisted.
+    Failed,
+    /// Part of the transferred money has been sent back by one or more reversals.
+    PartiallyReversed,
+    /// All of the transferred money has been sent back by one or more reversals.
+    Reversed,
+}
+
+/// A money transfer between two Accounts. Each transfer is recorded as two mirrored activities,
+/// one owned by the source account and one owned by the target account.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct Transfer {
+    pub id: Option<TransferId>,
+    /// The debited account.
+    pub source_account_id: AccountId,
+    /// The credited account.
+    pub target_account_id: AccountId,
+    /// The timestamp of the transfer.
+    pub timestamp: DateTime<Utc>,
+    /// The money that was transferred between the accounts.
+    pub money: Money,
+    /// The current status of the transfer.
+    pub status: TransferStatus,
+    /// The original transfer if this transfer is a (partial) reversal of it.
+    pub reversed_transfer_id: Option<TransferId>,
+    /// A free-form reference supplied by the client, e.g. an invoice number.
+    pub reference: Option<String>,
+    /// The events recorded since they were last taken, never persisted.
+    pub events: Vec<DomainEvent>,
+}
+
+impl Transfer {
+    /// Creates a pending Transfer entity without an ID. Use to create a new entity that is not
+    /// yet persisted.
+    pub fn new_without_id(
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        timestamp: DateTime<Utc>,
+        money: Money,
+    ) -> Self {
+        Self {
+            id: None,
+            source_account_id,
+            target_account_id,
+            timestamp,
+            money,
+            status: TransferStatus::Pending,
+            reversed_transfer_id: None,
+            reference: None,
+            events: vec![],
+        }
+    }
+
+    /// Creates a Transfer entity with an ID. Use to reconstitute a persisted entity.
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_with_id(
+        transfer_id: TransferId,
+        source_account_id: AccountId,
+        target_account_id: AccountId,
+        timestamp: DateTime<Utc>,
+        money: Money,
+        status: TransferStatus,
+        reversed_transfer_id: Option<TransferId>,
+        reference: Option<String>,
+    ) -> Self {
+        Self {
+            id: Some(transfer_id),
+            source_account_id,
+            target_account_id,
+            timestamp,
+            money,
+            status,
+            reversed_transfer_id,
+            reference,
+            events: vec![],
+        }
+    }
+
+    /// Attaches the reference supplied by the client to a new transfer.
+    pub fn with_reference(mut self, reference: Option<String>) -> Self {
+        self.reference = reference;
+        self
+    }
+
+    /// Marks the transfer as completed once both of its legs have been persisted.
+    pub fn complete(&mut self) {
+        self.status = TransferStatus::Completed;
+
+        if let Some(transfer_id) = self.id.clone() {
+            self.events.push(DomainEvent::TransferCompleted {
+                transfer_id,
+                source_account_id: self.source_account_id.clone(),
+                target_account_id: self.target_account_id.clone(),
+                money: self.money.clone(),
+                timestamp: Utc::now(),
+            });
+        }
+    }
+
+    /// Hands out the events recorded so far, leaving none behind.
+    pub fn take_events(&mut self) -> Vec<DomainEvent> {
+        std::mem::take(&mut self.events)
+    }
+
+    /// Marks the transfer as failed.
+    pub fn fail(&mut self) {
+        self.status = TransferStatus::Failed;
+    }
+
+    /// Calculates how much of this transfer has not been sent back yet. Failed reversals are
+    /// ignored, pending ones are counted so that the same amount can't be reversed twice.
+    pub fn reversible_amount(&self, reversals: &[Transfer]) -> Money {
+        let reversed = reversals
+            .iter()
+            .filter(|reversal| reversal.status != TransferStatus::Failed)
+            .map(|reversal| reversal.money.clone())
+            .fold(money!(0, "AUD"), |acc, x| acc + x);
+
+        self.money.clone() - reversed
+    }
+
+    /// Creates a pending reversal sending the given money back from the target to the source
+    /// account, given the reversals already made for this transfer.
+    pub fn reverse(
+        &self,
+        money: &Money,
+        reversals: &[Transfer],
+        timestamp: DateTime<Utc>,
+    ) -> Result<Transfer> {
+        use rust_decimal::prelude::*;
+
+        let id = match self.id.clone() {
+            Some(id) => id,
+            None => {
+                return Err(anyhow!(TransferError::InvalidTransferId(String::from(
+                    "reverse"
+                ))))
+            }
+        };
+
+        if let Some(reversed_transfer_id) = self.reversed_transfer_id.clone() {
+            return Err(anyhow!(TransferError::ReversalOfReversal(
+                reversed_transfer_id.0
+            )));
+        }
+
+        match self.status {
+            TransferStatus::Completed | TransferStatus::PartiallyReversed => {}
+            status => return Err(anyhow!(TransferError::NotReversible(status))),
+        }
+
+        if !money.is_positive() {
+            return Err(anyhow!(TransferError::InvalidReversalAmount(
+                money.amount().to_i64().unwrap()
+            )));
+        }
+
+        let remaining = self.reversible_amount(reversals);
+        if *money > remaining {
+            return Err(anyhow!(TransferError::ReversalExceedsRemaining {
+                requested: money.amount().to_i64().unwrap(),
+                remaining: remaining.amount().to_i64().unwrap(),
+            }));
+        }
+
+        let mut reversal = Transfer::new_without_id(
+            self.target_account_id.clone(),
+            self.source_account_id.clone(),
+            timestamp,
+            money.clone(),
+        );
+        reversal.reversed_transfer_id = Some(id);
+
+        Ok(reversal)
+    }
+
+    /// Updates the status of this transfer after one of its reversals completed.
+    pub fn record_reversals(&mut self, reversals: &[Transfer]) {
+        if self.reversible_amount(reversals).is_zero() {
+            self.status = TransferStatus::Reversed;
+        } else {
+            self.status = TransferStatus::PartiallyReversed;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::transfer_test_data::TransferBuilder;
+    use super::{AccountId, TransferId, TransferStatus};
+    use crate::domain::domain_event::DomainEvent;
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+
+    #[test]
+    fn completion_records_event() {
+        let mut transfer = TransferBuilder::default_transfer()
+            .with_transfer_id(&TransferId(7))
+            .with_source_account(&AccountId(1))
+            .with_target_account(&AccountId(2))
+            .with_money(&money!(500, "AUD"))
+            .build();
+
+        transfer.complete();
+        let events = transfer.take_events();
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(
+            &events[0],
+            DomainEvent::TransferCompleted { transfer_id, source_account_id, target_account_id, money, .. }
+                if *transfer_id == TransferId(7)
+                    && *source_account_id == AccountId(1)
+                    && *target_account_id == AccountId(2)
+                    && *money == money!(500, "AUD")
+        ));
+        assert!(transfer.take_events().is_empty());
+    }
+
+    #[test]
+    fn reverses_transfer_back_to_source() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_transfer_id(&TransferId(7))
+            .with_source_account(&AccountId(1))
+            .with_target_account(&AccountId(2))
+            .with_money(&money!(500, "AUD"))
+            .build();
+
+        let reversal = transfer
+            .reverse(&money!(200, "AUD"), &[], Utc::now())
+            .unwrap();
+
+        assert_eq!(reversal.id, None);
+        assert_eq!(reversal.source_account_id, AccountId(2));
+        assert_eq!(reversal.target_account_id, AccountId(1));
+        assert_eq!(reversal.money, money!(200, "AUD"));
+        assert_eq!(reversal.status, TransferStatus::Pending);
+        assert_eq!(reversal.reversed_transfer_id, Some(TransferId(7)));
+    }
+
+    #[test]
+    fn reversal_exceeding_remaining_amount_fails() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_money(&money!(500, "AUD"))
+            .build();
+        let previous_reversal = TransferBuilder::default_transfer()
+            .with_money(&money!(300, "AUD"))
+            .build();
+
+        let success = transfer
+            .reverse(&money!(201, "AUD"), &[previous_reversal], Utc::now())
+            .is_ok();
+
+        assert_eq!(success, false);
+    }
+
+    #[test]
+    fn failed_reversals_do_not_count_towards_reversed_amount() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_money(&money!(500, "AUD"))
+            .build();
+        let failed_reversal = TransferBuilder::default_transfer()
+            .with_money(&money!(500, "AUD"))
+            .with_status(TransferStatus::Failed)
+            .build();
+
+        assert_eq!(
+            transfer.reversible_amount(&[failed_reversal]),
+            money!(500, "AUD")
+        );
+    }
+
+    #[test]
+    fn fully_reversed_transfer_can_not_be_reversed_again() {
+        let mut transfer = TransferBuilder::default_transfer()
+            .with_money(&money!(500, "AUD"))
+            .build();
+        let reversal = transfer
+            .reverse(&money!(500, "AUD"), &[], Utc::now())
+            .unwrap();
+
+        transfer.record_reversals(&[reversal.clone()]);
+
+        assert_eq!(transfer.status, TransferStatus::Reversed);
+        assert_eq!(
+            transfer
+                .reverse(&money!(1, "AUD"), &[reversal], Utc::now())
+                .is_ok(),
+            false
+        );
+    }
+
+    #[test]
+    fn pending_transfer_can_not_be_reversed() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_status(TransferStatus::Pending)
+            .build();
+
+        let success = transfer.reverse(&money!(1, "AUD"), &[], Utc::now()).is_ok();
+
+        assert_eq!(success, false);
+    }
+}
+
+pub mod transfer_test_data {
+    use super::{AccountId, Transfer, TransferId, TransferStatus};
+    use chrono::Utc;
+    use rusty_money::{money, Money};
+
+    pub struct TransferBuilder {
+        transfer: Transfer,
+    }
+
+    impl TransferBuilder {
+        pub fn default_transfer() -> Self {
+            let transfer = Transfer::new_with_id(
+                TransferId(7),
+                AccountId(42),
+                AccountId(41),
+                Utc::now(),
+                money!(999, "AUD"),
+                TransferStatus::Completed,
+                None,
+                None,
+            );
+
+            Self { transfer }
+        }
+
+        pub fn with_transfer_id(&mut self, transfer_id: &TransferId) -> &mut Self {
+            let mut transfer = self.transfer.clone();
+            transfer.id = Some(transfer_id.clone());
+
+            let mut new = self;
+            new.transfer = transfer;
+            new
+        }
+
+        pub fn with_source_account(&mut self, source_account_id: &AccountId) -> &mut Self {
+            let mut transfer = self.transfer.clone();
+            transfer.source_account_id = source_account_id.clone();
+
+            let mut new = self;
+            new.transfer = transfer;
+            new
+        }
+
+        pub fn with_target_account(&mut self, target_account_id: &AccountId) -> &mut Self {
+            let mut transfer = self.transfer.clone();
+            transfer.target_account_id = target_account_id.clone();
+
+            let mut new = self;
+            new.transfer = transfer;
+            new
+        }
+
+        pub fn with_money(&mut self, money: &Money) -> &mut Self {
+            let mut transfer = self.transfer.clone();
+            transfer.money = money.clone();
+
+            let mut new = self;
+            new.transfer = transfer;
+            new
+        }
+
+        pub fn with_status(&mut self, status: TransferStatus) -> &mut Self {
+            let mut transfer = self.transfer.clone();
+            transfer.status = status;
+
+            let mut new = self;
+            new.transfer = transfer;
+            new
+        }
+
+        pub fn build(&self) -> Transfer {
+            self.transfer.clone()
+        }
+    }
+}
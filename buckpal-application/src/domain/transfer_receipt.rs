@@ -0,0 +1,91 @@
+use crate::domain::account::AccountId;
+use crate::domain::transfer::{Transfer, TransferError, TransferId, TransferStatus};
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, Utc};
+use rusty_money::Money;
+
+/// Proof that a transfer was made, signed so that it can be checked without asking buckpal.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct TransferReceipt {
+    pub transfer_id: TransferId,
+    pub source_account_id: AccountId,
+    pub target_account_id: AccountId,
+    pub money: Money,
+    pub timestamp: DateTime<Utc>,
+    /// The signature over all of the above, empty until the receipt is signed.
+    pub signature: String,
+}
+
+impl TransferReceipt {
+    /// Creates the unsigned receipt of a transfer. Only completed transfers get one: the status
+    /// isn't part of the signature, so a receipt of a reversed transfer would read as if it still
+    /// stood.
+    pub fn for_transfer(transfer: &Transfer) -> Result<Self> {
+        let transfer_id = match transfer.id.clone() {
+            Some(id) => id,
+            None => {
+                return Err(anyhow!(TransferError::InvalidTransferId(String::from(
+                    "issue receipt"
+                ))))
+            }
+        };
+
+        if transfer.status != TransferStatus::Completed {
+            return Err(anyhow!(TransferError::NotCompleted(transfer.status)));
+        }
+
+        Ok(Self {
+            transfer_id,
+            source_account_id: transfer.source_account_id.clone(),
+            target_account_id: transfer.target_account_id.clone(),
+            money: transfer.money.clone(),
+            timestamp: transfer.timestamp,
+            signature: String::new(),
+        })
+    }
+
+    pub fn with_signature(mut self, signature: &str) -> Self {
+        self.signature = String::from(signature);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TransferReceipt;
+    use crate::domain::transfer::transfer_test_data::TransferBuilder;
+    use crate::domain::transfer::{TransferError, TransferId, TransferStatus};
+
+    #[test]
+    fn completed_transfer_has_receipt() {
+        let transfer = TransferBuilder::default_transfer()
+            .with_status(TransferStatus::Completed)
+            .build();
+
+        let receipt = TransferReceipt::for_transfer(&transfer).unwrap();
+
+        assert_eq!(receipt.transfer_id, TransferId(7));
+        assert_eq!(receipt.money, transfer.money);
+        assert_eq!(receipt.signature, "");
+    }
+
+    #[test]
+    fn reversed_or_failed_transfer_has_no_receipt() {
+        for status in &[
+            TransferStatus::PartiallyReversed,
+            TransferStatus::Reversed,
+            TransferStatus::Failed,
+        ] {
+            let transfer = TransferBuilder::default_transfer()
+                .with_status(*status)
+                .build();
+
+            let err = TransferReceipt::for_transfer(&transfer).unwrap_err();
+
+            assert!(matches!(
+                err.downcast_ref::<TransferError>(),
+                Some(TransferError::NotCompleted(_))
+            ));
+        }
+    }
+}
@@ -0,0 +1,328 @@
+use crate::domain::domain_event::DomainEvent;
+use chrono::{DateTime, Duration, Utc};
+use thiserror::Error;
+
+/// The kinds of domain events webhooks can be subscribed to.
+pub const WEBHOOK_EVENT_TYPES: [&str; 3] =
+    ["MoneyWithdrawn", "MoneyDeposited", "TransferCompleted"];
+
+/// The shortest secret accepted for signing payloads.
+const MINIMUM_SECRET_LENGTH: usize = 16;
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum WebhookError {
+    #[error("Webhook URL must be an absolute http or https URL, got `{0}`")]
+    InvalidUrl(String),
+    #[error("Webhook secret must be at least {0} characters long")]
+    SecretTooShort(usize),
+    #[error("Webhook must be subscribed to at least one event type")]
+    MissingEventTypes,
+    #[error("Unknown event type `{0}`, expected one of MoneyWithdrawn, MoneyDeposited or TransferCompleted")]
+    UnknownEventType(String),
+    #[error("Delivery is still pending and can't be redelivered")]
+    DeliveryPending,
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookSubscriptionId(pub i32);
+
+/// An endpoint of a downstream system told about the events it is subscribed to.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookSubscription {
+    pub id: Option<WebhookSubscriptionId>,
+    /// The URL payloads are posted to.
+    pub url: String,
+    /// The secret payloads are signed with, shared with the downstream system only.
+    pub secret: String,
+    /// The names of the events delivered to the endpoint, e.g. `MoneyDeposited`.
+    pub event_types: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WebhookSubscription {
+    pub fn new(
+        url: &str,
+        secret: &str,
+        event_types: &[String],
+        created_at: DateTime<Utc>,
+    ) -> Result<Self, WebhookError> {
+        let url = url.trim();
+        let host = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"));
+        if host.map_or(true, |host| host.is_empty() || host.starts_with('/')) {
+            return Err(WebhookError::InvalidUrl(String::from(url)));
+        }
+
+        if secret.chars().count() < MINIMUM_SECRET_LENGTH {
+            return Err(WebhookError::SecretTooShort(MINIMUM_SECRET_LENGTH));
+        }
+
+        if event_types.is_empty() {
+            return Err(WebhookError::MissingEventTypes);
+        }
+
+        let mut unique_event_types: Vec<String> = vec![];
+        for event_type in event_types {
+            if !WEBHOOK_EVENT_TYPES.contains(&event_type.as_str()) {
+                return Err(WebhookError::UnknownEventType(event_type.clone()));
+            }
+            if !unique_event_types.contains(event_type) {
+                unique_event_types.push(event_type.clone());
+            }
+        }
+
+        Ok(Self {
+            id: None,
+            url: String::from(url),
+            secret: String::from(secret),
+            event_types: unique_event_types,
+            created_at,
+        })
+    }
+
+    pub fn is_subscribed_to(&self, event: &DomainEvent) -> bool {
+        self.event_types
+            .iter()
+            .any(|event_type| event_type == event.name())
+    }
+}
+
+/// How often and how quickly failed deliveries are retried before they are dead-lettered.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct RetryPolicy {
+    /// The number of attempts after which a delivery is given up on.
+    pub max_attempts: i32,
+    /// The wait after the first failed attempt, doubling with every further one.
+    pub initial_backoff: Duration,
+    pub maximum_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 8,
+            initial_backoff: Duration::seconds(30),
+            maximum_backoff: Duration::hours(6),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// The wait before the next attempt, given the number of attempts made so far.
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let doublings = (attempts - 1).max(0).min(30) as u32;
+        let seconds = self
+            .initial_backoff
+            .num_seconds()
+            .saturating_mul(2_i64.pow(doublings));
+
+        Duration::seconds(seconds).min(self.maximum_backoff)
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookDeliveryId(pub i32);
+
+#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+pub enum WebhookDeliveryStatus {
+    /// The event is waiting for its next attempt.
+    Pending,
+    /// The endpoint acknowledged the event.
+    Delivered,
+    /// Every attempt failed, the event is only sent again when redelivered.
+    DeadLettered,
+}
+
+/// An event on its way to a subscribed endpoint.
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct WebhookDelivery {
+    pub id: Option<WebhookDeliveryId>,
+    pub subscription_id: WebhookSubscriptionId,
+    pub event: DomainEvent,
+    pub status: WebhookDeliveryStatus,
+    /// The number of attempts made since the delivery was created or last redelivered.
+    pub attempts: i32,
+    /// The point in time the delivery is due to be attempted again if it is pending.
+    pub next_attempt_at: DateTime<Utc>,
+    /// Why the last attempt failed.
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl WebhookDelivery {
+    /// Creates a delivery that is due right away.
+    pub fn new(
+        subscription_id: WebhookSubscriptionId,
+        event: DomainEvent,
+        now: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: None,
+            subscription_id,
+            event,
+            status: WebhookDeliveryStatus::Pending,
+            attempts: 0,
+            next_attempt_at: now,
+            last_error: None,
+            created_at: now,
+            delivered_at: None,
+        }
+    }
+
+    pub fn succeed(&mut self, now: DateTime<Utc>) {
+        self.attempts += 1;
+        self.status = WebhookDeliveryStatus::Delivered;
+        self.last_error = None;
+        self.delivered_at = Some(now);
+    }
+
+    /// Schedules the next attempt after a failed one, or dead-letters the delivery once the
+    /// policy gives up on it.
+    pub fn fail(&mut self, error: &str, now: DateTime<Utc>, retry_policy: &RetryPolicy) {
+        self.attempts += 1;
+        self.last_error = Some(String::from(error));
+
+        if self.attempts >= retry_policy.max_attempts {
+            self.status = WebhookDeliveryStatus::DeadLettered;
+        } else {
+            self.next_attempt_at = now + retry_policy.backoff(self.attempts);
+        }
+    }
+
+    /// Sends a delivered or dead-lettered event again, starting over with the attempts.
+    pub fn redeliver(&mut self, now: DateTime<Utc>) -> Result<(), WebhookError> {
+        if self.status == WebhookDeliveryStatus::Pending {
+            return Err(WebhookError::DeliveryPending);
+        }
+
+        self.status = WebhookDeliveryStatus::Pending;
+        self.attempts = 0;
+        self.next_attempt_at = now;
+        self.delivered_at = None;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        RetryPolicy, WebhookDelivery, WebhookDeliveryStatus, WebhookError, WebhookSubscription,
+        WebhookSubscriptionId,
+    };
+    use crate::domain::account::AccountId;
+    use crate::domain::domain_event::DomainEvent;
+    use crate::domain::transfer::TransferId;
+    use chrono::{Duration, Utc};
+    use rusty_money::{money, Money};
+
+    fn given_a_delivery() -> WebhookDelivery {
+        WebhookDelivery::new(
+            WebhookSubscriptionId(1),
+            DomainEvent::MoneyDeposited {
+                account_id: AccountId(2),
+                source_account_id: AccountId(1),
+                transfer_id: TransferId(7),
+                money: money!(500, "AUD"),
+                timestamp: Utc::now(),
+            },
+            Utc::now(),
+        )
+    }
+
+    #[test]
+    fn validates_subscription() {
+        let event_types = vec![String::from("MoneyDeposited")];
+
+        assert!(WebhookSubscription::new(
+            "https://example.com/hooks",
+            "0123456789abcdef",
+            &event_types,
+            Utc::now()
+        )
+        .is_ok());
+        assert_eq!(
+            WebhookSubscription::new(
+                "ftp://example.com",
+                "0123456789abcdef",
+                &event_types,
+                Utc::now()
+            ),
+            Err(WebhookError::InvalidUrl(String::from("ftp://example.com")))
+        );
+        assert_eq!(
+            WebhookSubscription::new("https://example.com", "short", &event_types, Utc::now()),
+            Err(WebhookError::SecretTooShort(16))
+        );
+        assert_eq!(
+            WebhookSubscription::new(
+                "https://example.com",
+                "0123456789abcdef",
+                &[String::from("AccountOpened")],
+                Utc::now()
+            ),
+            Err(WebhookError::UnknownEventType(String::from(
+                "AccountOpened"
+            )))
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_maximum() {
+        let retry_policy = RetryPolicy::default();
+
+        assert_eq!(retry_policy.backoff(1), Duration::seconds(30));
+        assert_eq!(retry_policy.backoff(2), Duration::seconds(60));
+        assert_eq!(retry_policy.backoff(4), Duration::seconds(240));
+        assert_eq!(retry_policy.backoff(20), Duration::hours(6));
+    }
+
+    #[test]
+    fn failed_delivery_is_retried_until_dead_lettered() {
+        let retry_policy = RetryPolicy {
+            max_attempts: 2,
+            ..RetryPolicy::default()
+        };
+        let mut delivery = given_a_delivery();
+        let now = Utc::now();
+
+        delivery.fail("503 Service Unavailable", now, &retry_policy);
+
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.next_attempt_at, now + Duration::seconds(30));
+
+        delivery.fail("503 Service Unavailable", now, &retry_policy);
+
+        assert_eq!(delivery.status, WebhookDeliveryStatus::DeadLettered);
+        assert_eq!(delivery.attempts, 2);
+        assert_eq!(
+            delivery.last_error,
+            Some(String::from("503 Service Unavailable"))
+        );
+    }
+
+    #[test]
+    fn only_finished_delivery_can_be_redelivered() {
+        let mut delivery = given_a_delivery();
+
+        assert_eq!(
+            delivery.redeliver(Utc::now()),
+            Err(WebhookError::DeliveryPending)
+        );
+
+        delivery.fail(
+            "timed out",
+            Utc::now(),
+            &RetryPolicy {
+                max_attempts: 1,
+                ..RetryPolicy::default()
+            },
+        );
+        delivery.redeliver(Utc::now()).unwrap();
+
+        assert_eq!(delivery.status, WebhookDeliveryStatus::Pending);
+        assert_eq!(delivery.attempts, 0);
+    }
+}
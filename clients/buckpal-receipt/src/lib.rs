@@ -0,0 +1,169 @@
+//! Offline verification of the transfer receipts issued by buckpal.
+//!
+//! A receipt is the `data` of `GET /v1/transfers/:transferId/receipt`. It is signed with Ed25519
+//! over the canonical message of its content, so it can be checked with nothing but the public
+//! key of the issuing buckpal.
+
+use ed25519_dalek::{PublicKey, Signature, Verifier};
+use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
+use thiserror::Error;
+
+/// Versions the canonical message so that its layout can change without old receipts turning
+/// into valid signatures over something else.
+const MESSAGE_PREFIX: &str = "buckpal-transfer-receipt:v1";
+
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum ReceiptError {
+    #[error("Public key is invalid: {0}")]
+    InvalidPublicKey(String),
+    #[error("Signature is malformed: {0}")]
+    MalformedSignature(String),
+    #[error("Signature doesn't match the receipt")]
+    SignatureMismatch,
+}
+
+/// What a receipt attests to.
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReceiptContent {
+    pub transfer_id: i32,
+    pub source_account_id: i32,
+    pub target_account_id: i32,
+    pub amount: i64,
+    /// The ISO 4217 code of the currency of the amount.
+    pub currency: String,
+    /// When the transfer was made, RFC 3339 in UTC with microseconds.
+    pub timestamp: String,
+}
+
+impl ReceiptContent {
+    /// The bytes that get signed, one field per line in a fixed order.
+    pub fn message(&self) -> Vec<u8> {
+        format!(
+            "{}\n{}\n{}\n{}\n{}\n{}\n{}\n",
+            MESSAGE_PREFIX,
+            self.transfer_id,
+            self.source_account_id,
+            self.target_account_id,
+            self.amount,
+            self.currency,
+            self.timestamp
+        )
+        .into_bytes()
+    }
+}
+
+#[derive(Debug, Eq, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferReceipt {
+    #[serde(flatten)]
+    pub content: ReceiptContent,
+    /// The hex encoded Ed25519 signature of the message of the content.
+    pub signature: String,
+}
+
+/// Checks that the receipt was signed with the key of `public_key`, hex encoded.
+pub fn verify_receipt(receipt: &TransferReceipt, public_key: &str) -> Result<(), ReceiptError> {
+    let public_key = hex::decode(public_key)
+        .map_err(|err| ReceiptError::InvalidPublicKey(err.to_string()))
+        .and_then(|bytes| {
+            PublicKey::from_bytes(&bytes)
+                .map_err(|err| ReceiptError::InvalidPublicKey(err.to_string()))
+        })?;
+
+    let signature = hex::decode(&receipt.signature)
+        .map_err(|err| ReceiptError::MalformedSignature(err.to_string()))
+        .and_then(|bytes| {
+            Signature::try_from(&bytes[..])
+                .map_err(|err| ReceiptError::MalformedSignature(err.to_string()))
+        })?;
+
+    public_key
+        .verify(&receipt.content.message(), &signature)
+        .map_err(|_| ReceiptError::SignatureMismatch)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::{ExpandedSecretKey, SecretKey};
+
+    fn signed_receipt(secret_key: &SecretKey, content: ReceiptContent) -> TransferReceipt {
+        let public_key = PublicKey::from(secret_key);
+        let signature = ExpandedSecretKey::from(secret_key).sign(&content.message(), &public_key);
+
+        TransferReceipt {
+            content,
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    fn content() -> ReceiptContent {
+        ReceiptContent {
+            transfer_id: 7,
+            source_account_id: 42,
+            target_account_id: 41,
+            amount: 999,
+            currency: String::from("AUD"),
+            timestamp: String::from("2021-05-16T09:30:00.000000Z"),
+        }
+    }
+
+    #[test]
+    fn verifies_receipt_signed_with_the_key() {
+        let secret_key = SecretKey::from_bytes(&[7; 32]).unwrap();
+        let public_key = hex::encode(PublicKey::from(&secret_key).to_bytes());
+
+        let receipt = signed_receipt(&secret_key, content());
+
+        assert_eq!(verify_receipt(&receipt, &public_key), Ok(()));
+    }
+
+    #[test]
+    fn rejects_tampered_receipt() {
+        let secret_key = SecretKey::from_bytes(&[7; 32]).unwrap();
+        let public_key = hex::encode(PublicKey::from(&secret_key).to_bytes());
+
+        let mut receipt = signed_receipt(&secret_key, content());
+        receipt.content.amount = 9999;
+
+        assert_eq!(
+            verify_receipt(&receipt, &public_key),
+            Err(ReceiptError::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn rejects_receipt_signed_with_another_key() {
+        let secret_key = SecretKey::from_bytes(&[7; 32]).unwrap();
+        let other_public_key =
+            hex::encode(PublicKey::from(&SecretKey::from_bytes(&[8; 32]).unwrap()).to_bytes());
+
+        let receipt = signed_receipt(&secret_key, content());
+
+        assert_eq!(
+            verify_receipt(&receipt, &other_public_key),
+            Err(ReceiptError::SignatureMismatch)
+        );
+    }
+
+    #[test]
+    fn reads_receipt_from_api_response() {
+        let receipt: TransferReceipt = serde_json::from_str(
+            r#"{
+                "transferId": 7,
+                "sourceAccountId": 42,
+                "targetAccountId": 41,
+                "amount": 999,
+                "currency": "AUD",
+                "timestamp": "2021-05-16T09:30:00.000000Z",
+                "signature": "00"
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(receipt.content, content());
+        assert_eq!(receipt.signature, "00");
+    }
+}
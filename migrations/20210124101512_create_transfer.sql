@@ -0,0 +1,10 @@
+CREATE TABLE IF NOT EXISTS transfer (
+    id                  SERIAL PRIMARY KEY,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    source_account_id   INT NOT NULL,
+    target_account_id   INT NOT NULL,
+    amount              BIGINT NOT NULL,
+    status              TEXT NOT NULL
+);
+
+ALTER TABLE activity ADD COLUMN IF NOT EXISTS transfer_id INT REFERENCES transfer (id);
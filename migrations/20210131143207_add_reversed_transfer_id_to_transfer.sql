@@ -0,0 +1,3 @@
+ALTER TABLE transfer ADD COLUMN IF NOT EXISTS reversed_transfer_id INT REFERENCES transfer (id);
+
+CREATE INDEX IF NOT EXISTS transfer_reversed_transfer_id_idx ON transfer (reversed_transfer_id);
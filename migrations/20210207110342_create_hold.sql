@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS hold (
+    id                  SERIAL PRIMARY KEY,
+    account_id          INT NOT NULL,
+    target_account_id   INT NOT NULL,
+    amount              BIGINT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL,
+    expires_at          TIMESTAMPTZ NOT NULL,
+    status              TEXT NOT NULL,
+    transfer_id         INT REFERENCES transfer (id)
+);
+
+CREATE INDEX IF NOT EXISTS hold_account_id_status_idx ON hold (account_id, status);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS idempotency_key (
+    key                 TEXT PRIMARY KEY,
+    fingerprint         TEXT NOT NULL,
+    status              TEXT NOT NULL,
+    transfer_id         INT REFERENCES transfer (id),
+    error_message       TEXT,
+    created_at          TIMESTAMPTZ NOT NULL,
+    expires_at          TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idempotency_key_expires_at_idx ON idempotency_key (expires_at);
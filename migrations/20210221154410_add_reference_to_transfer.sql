@@ -0,0 +1 @@
+ALTER TABLE transfer ADD COLUMN IF NOT EXISTS reference TEXT;
@@ -0,0 +1 @@
+CREATE INDEX IF NOT EXISTS activity_owner_account_id_timestamp_idx ON activity (owner_account_id, timestamp, id);
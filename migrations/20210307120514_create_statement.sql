@@ -0,0 +1,40 @@
+CREATE TABLE IF NOT EXISTS statement (
+    id                  SERIAL PRIMARY KEY,
+    account_id          INT NOT NULL,
+    period_from         DATE NOT NULL,
+    period_to           DATE NOT NULL,
+    opening_balance     BIGINT NOT NULL,
+    total_in            BIGINT NOT NULL,
+    total_out           BIGINT NOT NULL,
+    closing_balance     BIGINT NOT NULL,
+    generated_at        TIMESTAMPTZ NOT NULL,
+    UNIQUE (account_id, period_from, period_to)
+);
+
+CREATE TABLE IF NOT EXISTS statement_line (
+    id                      SERIAL PRIMARY KEY,
+    statement_id            INT NOT NULL REFERENCES statement (id),
+    position                INT NOT NULL,
+    activity_id             INT REFERENCES activity (id),
+    timestamp               TIMESTAMPTZ NOT NULL,
+    kind                    TEXT NOT NULL,
+    counterparty_account_id INT NOT NULL,
+    amount                  BIGINT NOT NULL,
+    running_balance         BIGINT NOT NULL,
+    UNIQUE (statement_id, position)
+);
+
+-- issued statements must never change
+CREATE OR REPLACE FUNCTION reject_statement_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'statements are immutable, % on % is not allowed', TG_OP, TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS statement_immutable ON statement;
+CREATE TRIGGER statement_immutable BEFORE UPDATE OR DELETE ON statement
+    FOR EACH ROW EXECUTE PROCEDURE reject_statement_modification();
+
+DROP TRIGGER IF EXISTS statement_line_immutable ON statement_line;
+CREATE TRIGGER statement_line_immutable BEFORE UPDATE OR DELETE ON statement_line
+    FOR EACH ROW EXECUTE PROCEDURE reject_statement_modification();
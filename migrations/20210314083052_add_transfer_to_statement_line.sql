@@ -0,0 +1,2 @@
+ALTER TABLE statement_line ADD COLUMN IF NOT EXISTS transfer_id INT REFERENCES transfer (id);
+ALTER TABLE statement_line ADD COLUMN IF NOT EXISTS reference TEXT;
@@ -0,0 +1,4 @@
+CREATE TABLE IF NOT EXISTS payment_initiation (
+    message_id          TEXT PRIMARY KEY,
+    received_at         TIMESTAMPTZ NOT NULL
+);
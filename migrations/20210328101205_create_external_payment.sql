@@ -0,0 +1,37 @@
+CREATE TABLE IF NOT EXISTS external_account (
+    account_id          INT PRIMARY KEY REFERENCES account (id),
+    bsb                 TEXT NOT NULL,
+    account_number      TEXT NOT NULL,
+    account_name        TEXT NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS external_payment_batch (
+    id                  SERIAL PRIMARY KEY,
+    created_at          TIMESTAMPTZ NOT NULL,
+    processing_date     DATE NOT NULL,
+    record_count        BIGINT NOT NULL,
+    credit_total        BIGINT NOT NULL,
+    debit_total         BIGINT NOT NULL,
+    contents            TEXT NOT NULL
+);
+
+-- a transfer is paid out by at most one batch
+CREATE TABLE IF NOT EXISTS external_payment (
+    transfer_id         INT PRIMARY KEY REFERENCES transfer (id),
+    batch_id            INT NOT NULL REFERENCES external_payment_batch (id)
+);
+
+-- files handed to the bank are kept exactly as they were generated
+CREATE OR REPLACE FUNCTION reject_external_payment_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'external payment batches are immutable, % on % is not allowed', TG_OP, TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS external_payment_batch_immutable ON external_payment_batch;
+CREATE TRIGGER external_payment_batch_immutable BEFORE UPDATE OR DELETE ON external_payment_batch
+    FOR EACH ROW EXECUTE PROCEDURE reject_external_payment_modification();
+
+DROP TRIGGER IF EXISTS external_payment_immutable ON external_payment;
+CREATE TRIGGER external_payment_immutable BEFORE UPDATE OR DELETE ON external_payment
+    FOR EACH ROW EXECUTE PROCEDURE reject_external_payment_modification();
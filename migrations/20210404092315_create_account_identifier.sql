@@ -0,0 +1,9 @@
+-- external identifiers accounts can be addressed by, e.g. a BSB and account number or an IBAN
+CREATE TABLE IF NOT EXISTS account_identifier (
+    scheme              TEXT NOT NULL,
+    value               TEXT NOT NULL,
+    account_id          INT NOT NULL REFERENCES account (id),
+    PRIMARY KEY (scheme, value)
+);
+
+CREATE INDEX IF NOT EXISTS account_identifier_account_id_idx ON account_identifier (account_id);
@@ -0,0 +1,18 @@
+-- events are written in the same transaction as the change they describe and relayed from here,
+-- so none is lost if the process stops before delivering them
+CREATE TABLE IF NOT EXISTS outbox (
+    id                  BIGSERIAL PRIMARY KEY,
+    -- the account whose events are delivered in the order they were written in
+    account_id          INT NOT NULL,
+    event_type          TEXT NOT NULL,
+    transfer_id         INT NOT NULL,
+    source_account_id   INT NOT NULL,
+    target_account_id   INT NOT NULL,
+    amount              BIGINT NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    attempts            INT NOT NULL DEFAULT 0,
+    last_error          TEXT,
+    processed_at        TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS outbox_pending_idx ON outbox (id) WHERE processed_at IS NULL;
@@ -0,0 +1,31 @@
+CREATE TABLE IF NOT EXISTS webhook_subscription (
+    id              SERIAL PRIMARY KEY,
+    url             TEXT NOT NULL,
+    secret          TEXT NOT NULL,
+    -- comma separated names of the events delivered to the endpoint
+    event_types     TEXT NOT NULL,
+    created_at      TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS webhook_delivery (
+    id                  SERIAL PRIMARY KEY,
+    subscription_id     INT NOT NULL REFERENCES webhook_subscription (id) ON DELETE CASCADE,
+    -- identifies the event across redeliveries of the outbox, so it is queued only once
+    event_id            TEXT NOT NULL,
+    account_id          INT NOT NULL,
+    event_type          TEXT NOT NULL,
+    transfer_id         INT NOT NULL,
+    source_account_id   INT NOT NULL,
+    target_account_id   INT NOT NULL,
+    amount              BIGINT NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    status              TEXT NOT NULL,
+    attempts            INT NOT NULL DEFAULT 0,
+    next_attempt_at     TIMESTAMPTZ NOT NULL,
+    last_error          TEXT,
+    created_at          TIMESTAMPTZ NOT NULL,
+    delivered_at        TIMESTAMPTZ,
+    UNIQUE (subscription_id, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS webhook_delivery_due_idx ON webhook_delivery (next_attempt_at) WHERE status = 'PENDING';
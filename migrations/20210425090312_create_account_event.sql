@@ -0,0 +1,36 @@
+-- the event-sourced ledger keeps one stream of events per account, versioned from 1
+CREATE TABLE IF NOT EXISTS account_event (
+    account_id          INT NOT NULL,
+    version             INT NOT NULL,
+    event_type          TEXT NOT NULL,
+    transfer_id         INT NOT NULL,
+    source_account_id   INT NOT NULL,
+    target_account_id   INT NOT NULL,
+    amount              BIGINT NOT NULL,
+    timestamp           TIMESTAMPTZ NOT NULL,
+    -- the row the activity projection wrote for the event
+    activity_id         INT,
+    recorded_at         TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, version)
+);
+
+-- the balance of a stream up to and including a version, so that it isn't replayed from the start
+CREATE TABLE IF NOT EXISTS account_snapshot (
+    account_id          INT NOT NULL,
+    version             INT NOT NULL,
+    balance             BIGINT NOT NULL,
+    -- the timestamp of the event at the version
+    last_event_at       TIMESTAMPTZ NOT NULL,
+    taken_at            TIMESTAMPTZ NOT NULL DEFAULT now(),
+    PRIMARY KEY (account_id, version)
+);
+
+-- the balance projection, written together with every append so it is always at the head of
+-- the stream
+CREATE TABLE IF NOT EXISTS account_ledger_balance (
+    account_id          INT PRIMARY KEY,
+    version             INT NOT NULL,
+    balance             BIGINT NOT NULL,
+    -- the timestamp of the event at the version
+    last_event_at       TIMESTAMPTZ NOT NULL
+);
@@ -0,0 +1,20 @@
+-- the booked balance of every account, updated in the same transaction as its activities
+CREATE TABLE IF NOT EXISTS account_balance (
+    account_id          INT PRIMARY KEY,
+    balance             BIGINT NOT NULL,
+    last_activity_id    INT NOT NULL,
+    updated_at          TIMESTAMPTZ NOT NULL
+);
+
+INSERT INTO account_balance (account_id, balance, last_activity_id, updated_at)
+SELECT
+        owner_account_id,
+        (SUM(CASE WHEN target_account_id = owner_account_id THEN amount ELSE 0 END)
+            - SUM(CASE WHEN source_account_id = owner_account_id THEN amount ELSE 0 END))::BIGINT,
+        MAX(id),
+        now()
+FROM
+        activity
+GROUP BY
+        owner_account_id
+ON CONFLICT (account_id) DO NOTHING;
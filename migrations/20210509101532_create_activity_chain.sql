@@ -0,0 +1,44 @@
+-- every activity is chained onto the one before it of the same owner account, hashing its content
+-- together with the hash of that one; activities written before were left unchained
+ALTER TABLE activity ADD COLUMN IF NOT EXISTS previous_hash TEXT;
+ALTER TABLE activity ADD COLUMN IF NOT EXISTS hash TEXT;
+
+-- the latest activity of every chain, locked while an activity is chained onto it
+CREATE TABLE IF NOT EXISTS activity_chain_head (
+    account_id          INT PRIMARY KEY,
+    -- none only while the first activity of the chain is being written
+    activity_id         INT,
+    hash                TEXT NOT NULL,
+    updated_at          TIMESTAMPTZ NOT NULL
+);
+
+-- the heads of all chains at a point in time, signed with a key kept outside of the database
+CREATE TABLE IF NOT EXISTS activity_chain_checkpoint (
+    id                  SERIAL PRIMARY KEY,
+    digest              TEXT NOT NULL,
+    signature           TEXT NOT NULL,
+    created_at          TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS activity_chain_checkpoint_head (
+    checkpoint_id       INT NOT NULL REFERENCES activity_chain_checkpoint (id),
+    account_id          INT NOT NULL,
+    activity_id         INT NOT NULL,
+    hash                TEXT NOT NULL,
+    PRIMARY KEY (checkpoint_id, account_id)
+);
+
+-- signed checkpoints must never change
+CREATE OR REPLACE FUNCTION reject_checkpoint_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'checkpoints are immutable, % on % is not allowed', TG_OP, TG_TABLE_NAME;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS activity_chain_checkpoint_immutable ON activity_chain_checkpoint;
+CREATE TRIGGER activity_chain_checkpoint_immutable BEFORE UPDATE OR DELETE ON activity_chain_checkpoint
+    FOR EACH ROW EXECUTE PROCEDURE reject_checkpoint_modification();
+
+DROP TRIGGER IF EXISTS activity_chain_checkpoint_head_immutable ON activity_chain_checkpoint_head;
+CREATE TRIGGER activity_chain_checkpoint_head_immutable BEFORE UPDATE OR DELETE ON activity_chain_checkpoint_head
+    FOR EACH ROW EXECUTE PROCEDURE reject_checkpoint_modification();
@@ -0,0 +1,29 @@
+CREATE TABLE IF NOT EXISTS audit_log (
+    id                  SERIAL PRIMARY KEY,
+    occurred_at         TIMESTAMPTZ NOT NULL,
+    action              TEXT NOT NULL,
+    details             TEXT NOT NULL,
+    actor               TEXT,
+    source_ip           TEXT,
+    request_id          TEXT,
+    outcome             TEXT NOT NULL,
+    error_category      TEXT
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_actor_idx ON audit_log (actor, id);
+CREATE INDEX IF NOT EXISTS audit_log_action_idx ON audit_log (action, id);
+
+-- the audit log is append-only
+CREATE OR REPLACE FUNCTION reject_audit_log_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_log is append-only, % is not allowed', TG_OP;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_log_append_only ON audit_log;
+CREATE TRIGGER audit_log_append_only BEFORE UPDATE OR DELETE ON audit_log
+    FOR EACH ROW EXECUTE PROCEDURE reject_audit_log_modification();
+
+DROP TRIGGER IF EXISTS audit_log_no_truncate ON audit_log;
+CREATE TRIGGER audit_log_no_truncate BEFORE TRUNCATE ON audit_log
+    FOR EACH STATEMENT EXECUTE PROCEDURE reject_audit_log_modification();
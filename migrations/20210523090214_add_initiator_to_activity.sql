@@ -0,0 +1,3 @@
+-- the principal that initiated the activity, e.g. `user:alice`, empty for activities recorded
+-- before principals were
+ALTER TABLE activity ADD COLUMN IF NOT EXISTS initiator TEXT;
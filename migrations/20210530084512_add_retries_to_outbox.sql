@@ -0,0 +1,7 @@
+-- a failed entry is retried with backoff and dead-lettered once the relay gives up on it, after
+-- which it no longer holds back the later events of its account
+ALTER TABLE outbox ADD COLUMN IF NOT EXISTS next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT now();
+ALTER TABLE outbox ADD COLUMN IF NOT EXISTS dead_lettered_at TIMESTAMPTZ;
+
+DROP INDEX IF EXISTS outbox_pending_idx;
+CREATE INDEX IF NOT EXISTS outbox_pending_idx ON outbox (account_id, id) WHERE processed_at IS NULL AND dead_lettered_at IS NULL;
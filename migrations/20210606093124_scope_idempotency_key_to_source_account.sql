@@ -0,0 +1,8 @@
+-- keys are chosen by clients, the same key used for two different source accounts names two
+-- different requests
+ALTER TABLE idempotency_key ADD COLUMN IF NOT EXISTS source_account_id INT;
+UPDATE idempotency_key SET source_account_id = split_part(fingerprint, ':', 2)::INT WHERE source_account_id IS NULL;
+ALTER TABLE idempotency_key ALTER COLUMN source_account_id SET NOT NULL;
+
+ALTER TABLE idempotency_key DROP CONSTRAINT IF EXISTS idempotency_key_pkey;
+ALTER TABLE idempotency_key ADD PRIMARY KEY (source_account_id, key);
@@ -0,0 +1,2 @@
+COMMENT ON COLUMN activity.initiator IS
+    'The principal that initiated the activity, e.g. `user:alice`, empty for activities recorded before principals were introduced';
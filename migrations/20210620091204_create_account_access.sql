@@ -0,0 +1,7 @@
+-- the principals that may act on an account and read it, e.g. `user:alice`, system jobs may act
+-- on every account without being listed
+CREATE TABLE IF NOT EXISTS account_access (
+    account_id          INT NOT NULL REFERENCES account (id),
+    principal           TEXT NOT NULL,
+    PRIMARY KEY (account_id, principal)
+);